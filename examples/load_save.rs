@@ -1,14 +1,8 @@
-use image::{open, GrayImage};
-use nanocv::{ImgBuf, ImgSize};
+use nanocv::io::png;
 
 fn main() {
-    // Load image using piston image
-    let buf = open("examples/raster.png").unwrap().into_luma();
-    // Convert into ImgBuf
-    let size = ImgSize::new(buf.width() as usize, buf.height() as usize);
-    let img = ImgBuf::from_vec(size, buf.into_vec());
-    // Convert back to piston gray image
-    let result = GrayImage::from_vec(size.x as u32, size.y as u32, img.into_vec()).unwrap();
+    // Load image using native PNG decoder and convert it to grayscale
+    let img = png::read("examples/raster.png").unwrap().into_gray8();
     // Save result into target directory
-    result.save("target/load_save.png").unwrap();
-}
\ No newline at end of file
+    png::write("target/load_save.png", &img).unwrap();
+}
@@ -0,0 +1,54 @@
+use std::time::Instant;
+use nanocv::{Img, ImgBuf, ImgSize, Vec2d, io::png, filter::{remap, rotate_shear_new}};
+
+fn main() {
+    // Load image using native PNG decoder and convert it to grayscale
+    let img = png::read("examples/raster.png").unwrap().into_gray8();
+    let angle = 0.3f64;
+    let repeats = 20;
+
+    // Rotation by three shears
+    let start = Instant::now();
+    let mut sheared = rotate_shear_new(&img, angle);
+
+    for _ in 1..repeats {
+        sheared = rotate_shear_new(&img, angle);
+    }
+
+    let shear_time = start.elapsed()/repeats;
+
+    // The same rotation by generic warp (inverse mapping with bilinear 
+    // interpolation) into the image of the same size, including map creation
+    let start = Instant::now();
+    let mut warped = warp_rotate(&img, angle, sheared.size());
+
+    for _ in 1..repeats {
+        warped = warp_rotate(&img, angle, sheared.size());
+    }
+
+    let warp_time = start.elapsed()/repeats;
+    println!("three shears: {:?}, generic warp: {:?}", shear_time, warp_time);
+
+    // Save results into target directory
+    for (image, name) in [(sheared, "rotate_shear"), (warped, "rotate_warp")] {
+        png::write(format!("target/{}.png", name), &image).unwrap();
+    }
+}
+
+/// Rotation by remapping, rotates content counter-clockwise as displayed
+fn warp_rotate(img: &ImgBuf<u8>, angle: f64, output: ImgSize) -> ImgBuf<u8> {
+    let size = img.size();
+    let center = |size: ImgSize| Vec2d::new((size.x as f64 - 1.0)/2.0, (size.y as f64 - 1.0)/2.0);
+    let (out_center, in_center) = (center(output), center(size));
+    let (sin, cos) = angle.sin_cos();
+    let mut map = ImgBuf::<Vec2d<f32>>::new(output);
+
+    for y in 0..output.y {
+        for x in 0..output.x {
+            let p = Vec2d::new(x as f64, y as f64) - out_center;
+            map[(x, y)] = (Vec2d::new(p.x*cos - p.y*sin, p.x*sin + p.y*cos) + in_center).cast::<f32>();
+        }
+    }
+
+    remap(img, &map)
+}
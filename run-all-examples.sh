@@ -2,4 +2,6 @@
 cargo run --release --example load_save
 cargo run --release --example negative_image
 cargo run --release --example vertical_convolution
-cargo run --release --example horizontal_convolution
\ No newline at end of file
+cargo run --release --example horizontal_convolution
+cargo run --release --example rotate_shear
+cargo run --release --example png_io
\ No newline at end of file
@@ -0,0 +1,117 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive};
+
+/// Calibrates `light` frame using optional master calibration frames
+/// 
+/// Computes `(light - bias - dark) / normalized_flat` for every pixel,
+/// where `normalized_flat` is the `flat` frame divided by its mean value,
+/// so the calibration preserves the overall signal level. Negative
+/// results are clamped to zero.
+/// 
+/// When both `bias` and `dark` are given, `dark` is expected not to contain
+/// the bias signal. The `flat` frame is expected to be already bias and
+/// dark subtracted. Flat pixels that are not positive are treated as
+/// neutral (no flat correction applied).
+/// 
+/// Panics if size of any calibration frame differs from `light` size
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, calibrate::apply};
+/// let size = ImgSize::new(2, 1);
+/// let light = ImgBuf::<u16>::from_vec(size, vec![110, 60]);
+/// let dark = ImgBuf::<f32>::new_init(size, 10.0);
+/// let flat = ImgBuf::<f32>::from_vec(size, vec![2.0, 1.0]);
+/// let result = apply(&light, Some(&dark), Some(&flat), None);
+/// assert_eq!(result.line_ref(0), &[75.0, 75.0]);
+/// ```
+pub fn apply<T: AsPrimitive<f32>>(
+    light: &dyn Img<T>,
+    dark: Option<&dyn Img<f32>>,
+    flat: Option<&dyn Img<f32>>,
+    bias: Option<&dyn Img<f32>>,
+) -> ImgBuf<f32> {
+    let size = light.size();
+
+    for frame in [dark, flat, bias].iter().flatten() {
+        assert_eq!(frame.size(), size, "Calibration frame size differs from light frame");
+    }
+
+    let flat_mean = flat.map(positive_mean).unwrap_or(1.0);
+    let mut output = ImgBuf::<f32>::new(size);
+
+    for line in 0..size.y {
+        let src = light.line_ref(line);
+        let dst = output.line_mut(line);
+
+        for x in 0..dst.len() {
+            dst[x] = src[x].as_();
+        }
+
+        for frame in [bias, dark].iter().flatten() {
+            let offset = frame.line_ref(line);
+
+            for x in 0..dst.len() {
+                dst[x] -= offset[x];
+            }
+        }
+
+        if let Some(flat) = flat {
+            let gain = flat.line_ref(line);
+
+            for x in 0..dst.len() {
+                if gain[x] > 0.0 {
+                    dst[x] *= flat_mean/gain[x];
+                }
+            }
+        }
+
+        for x in 0..dst.len() {
+            dst[x] = dst[x].max(0.0);
+        }
+    }
+
+    output
+}
+
+/// Mean of positive pixel values, 1.0 if there are none
+fn positive_mean(image: &dyn Img<f32>) -> f32 {
+    let (sum, count) = image.lines()
+        .flat_map(|line| line.iter())
+        .filter(|&&value| value > 0.0)
+        .fold((0.0f64, 0usize), |(sum, count), &value| (sum + value as f64, count + 1));
+
+    if count == 0 { 1.0 } else { (sum/count as f64) as f32 }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn without_calibration_frames_only_converts() {
+        let light = ImgBuf::<u8>::from_vec(ImgSize::new(3, 1), vec![0, 5, 255]);
+        assert_eq!(apply(&light, None, None, None).line_ref(0), &[0.0, 5.0, 255.0]);
+    }
+
+    #[test]
+    fn negative_values_are_clamped() {
+        let size = ImgSize::new(2, 1);
+        let light = ImgBuf::<u16>::from_vec(size, vec![5, 20]);
+        let bias = ImgBuf::<f32>::new_init(size, 8.0);
+        let dark = ImgBuf::<f32>::new_init(size, 2.0);
+        let result = apply(&light, Some(&dark), None, Some(&bias));
+        assert_eq!(result.line_ref(0), &[0.0, 10.0]);
+    }
+
+    #[test]
+    fn zero_flat_pixels_are_neutral() {
+        let size = ImgSize::new(3, 1);
+        let light = ImgBuf::<f32>::from_vec(size, vec![10.0, 10.0, 10.0]);
+        let flat = ImgBuf::<f32>::from_vec(size, vec![0.0, 4.0, 2.0]);
+        let result = apply(&light, None, Some(&flat), None);
+        assert_eq!(result.line_ref(0), &[10.0, 7.5, 15.0]);
+    }
+}
@@ -0,0 +1,17 @@
+use crate::{Img, ImgBuf, AsPrimitive, stack::mean_as_f32};
+
+/// Builds master calibration frame as per-pixel average of `frames`
+/// 
+/// Panics if `frames` is empty or frame sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, calibrate::master_frame};
+/// let first = ImgBuf::<u16>::new_init(ImgSize::new(2, 2), 100);
+/// let second = ImgBuf::<u16>::new_init(ImgSize::new(2, 2), 102);
+/// let master = master_frame(&[&first, &second]);
+/// assert_eq!(master[(0, 0)], 101.0);
+/// ```
+pub fn master_frame<T: AsPrimitive<f32>>(frames: &[&dyn Img<T>]) -> ImgBuf<f32> {
+    mean_as_f32(frames)
+}
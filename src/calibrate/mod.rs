@@ -0,0 +1,11 @@
+//! Calibration of scientific images using bias, dark and flat field frames
+//! 
+//! Typical workflow is to build master calibration frames by averaging
+//! several calibration exposures using [master_frame](fn.master_frame.html)
+//! and to apply them to every light frame using [apply](fn.apply.html).
+
+mod apply;
+mod master;
+
+pub use apply::apply;
+pub use master::master_frame;
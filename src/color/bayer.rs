@@ -0,0 +1,157 @@
+use crate::{Img, ImgMut, ImgBuf};
+use super::Rgb;
+
+/// Layout of the Bayer color filter array, variants are named by colors 
+/// of the top left 2x2 pixel block in row-major order
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum BayerPattern {
+    Rggb,
+    Bggr,
+    Grbg,
+    Gbrg,
+}
+
+/// Interpolation of missing color components
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DebayerMethod {
+    /// Missing components are copied from the nearest pixel of the
+    /// respective color, fast, but produces jagged edges
+    Nearest,
+    /// Missing components are averaged from all neighboring pixels 
+    /// of the respective color
+    Bilinear,
+}
+
+/// Color channel index, 0 for red, 1 for green and 2 for blue
+type Channel = usize;
+
+impl BayerPattern {
+    /// Color channel of pixel at position `(x, y)`
+    fn channel(self, x: usize, y: usize) -> Channel {
+        let block = match self {
+            BayerPattern::Rggb => [[0, 1], [1, 2]],
+            BayerPattern::Bggr => [[2, 1], [1, 0]],
+            BayerPattern::Grbg => [[1, 0], [2, 1]],
+            BayerPattern::Gbrg => [[1, 2], [0, 1]],
+        };
+
+        block[y % 2][x % 2]
+    }
+}
+
+/// Neighbors in the order of increasing distance used to find
+/// the nearest pixel of a given color
+const NEIGHBORS: [(isize, isize); 8] = [
+    (1, 0), (0, 1), (-1, 0), (0, -1), (1, 1), (-1, 1), (1, -1), (-1, -1)
+];
+
+/// Reconstructs color image from raw sensor data captured through 
+/// a Bayer color filter array of given `pattern`
+/// 
+/// Pixel component of the color captured by the pixel is kept,
+/// the other components are interpolated from the 3x3 neighborhood 
+/// using `method`. Components that cannot be interpolated (images 
+/// smaller than 2x2 pixels) are set to zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::{debayer, BayerPattern, DebayerMethod, Rgb}};
+/// let raw = ImgBuf::<u16>::from_vec(ImgSize::new(2, 2), vec![
+///     100, 50,
+///     60, 20,
+/// ]);
+/// let rgb = debayer(&raw, BayerPattern::Rggb, DebayerMethod::Bilinear);
+/// assert_eq!(rgb[(0, 0)], Rgb::new(100, 55, 20));
+/// assert_eq!(rgb[(1, 0)], Rgb::new(100, 50, 20));
+/// ```
+pub fn debayer(raw: &dyn Img<u16>, pattern: BayerPattern, method: DebayerMethod) -> ImgBuf<Rgb<u16>> {
+    let size = raw.size();
+    let mut output = ImgBuf::<Rgb<u16>>::new(size);
+
+    for y in 0..size.y {
+        let dst = output.line_mut(y);
+
+        for x in 0..size.x {
+            let mut sums = [0u32; 3];
+            let mut counts = [0u32; 3];
+            let own = pattern.channel(x, y);
+            sums[own] = raw.line_ref(y)[x] as u32;
+            counts[own] = 1;
+
+            for &(dx, dy) in NEIGHBORS.iter() {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+
+                if nx < 0 || ny < 0 || nx >= size.x as isize || ny >= size.y as isize {
+                    continue;
+                }
+
+                let (nx, ny) = (nx as usize, ny as usize);
+                let channel = pattern.channel(nx, ny);
+
+                if channel == own || (method == DebayerMethod::Nearest && counts[channel] > 0) {
+                    continue;
+                }
+
+                sums[channel] += raw.line_ref(ny)[nx] as u32;
+                counts[channel] += 1;
+            }
+
+            let component = |channel: usize| match counts[channel] {
+                0 => 0,
+                count => ((sums[channel] + count/2)/count) as u16,
+            };
+
+            dst[x] = Rgb::new(component(0), component(1), component(2));
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgLinesMut;
+    use crate::ImgSize;
+
+    /// Raw image of uniformly colored scene
+    fn uniform_raw(pattern: BayerPattern, color: [u16; 3]) -> ImgBuf<u16> {
+        let mut raw = ImgBuf::<u16>::new(ImgSize::new(5, 4));
+
+        for (position, pixel) in raw.enumerate_pixels_mut() {
+            *pixel = color[pattern.channel(position.x, position.y)];
+        }
+
+        raw
+    }
+
+    #[test]
+    fn uniform_color_is_reconstructed_for_all_patterns() {
+        let patterns = [BayerPattern::Rggb, BayerPattern::Bggr, BayerPattern::Grbg, BayerPattern::Gbrg];
+        let methods = [DebayerMethod::Nearest, DebayerMethod::Bilinear];
+
+        for &pattern in patterns.iter() {
+            for &method in methods.iter() {
+                let rgb = debayer(&uniform_raw(pattern, [10, 20, 30]), pattern, method);
+                assert!(rgb.lines().all(|line| line.iter().all(|&p| p == Rgb::new(10, 20, 30))));
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_copies_single_neighbor() {
+        let raw = ImgBuf::<u16>::from_vec(ImgSize::new(2, 2), vec![100, 50, 60, 20]);
+        let rgb = debayer(&raw, BayerPattern::Rggb, DebayerMethod::Nearest);
+        assert_eq!(rgb[(0, 0)], Rgb::new(100, 50, 20));
+        assert_eq!(rgb[(0, 1)], Rgb::new(100, 60, 20));
+    }
+
+    #[test]
+    fn single_pixel_has_missing_components() {
+        let raw = ImgBuf::<u16>::new_init(ImgSize::new(1, 1), 7);
+        let rgb = debayer(&raw, BayerPattern::Gbrg, DebayerMethod::Bilinear);
+        assert_eq!(rgb[(0, 0)], Rgb::new(0, 7, 0));
+    }
+}
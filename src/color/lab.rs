@@ -0,0 +1,53 @@
+use super::Rgb;
+
+/// Reference white of the D65 illuminant in CIE XYZ
+const WHITE: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Color in CIE L\*a\*b\* space (D65 reference white), `l` is lightness 
+/// in `0..=100` range, `a` and `b` are green-red and blue-yellow opponent
+/// components
+/// 
+/// # Example
+/// ```
+/// use nanocv::color::{Lab, Rgb};
+/// let white = Lab::from_srgb(Rgb::new(1.0, 1.0, 1.0));
+/// assert!((white.l - 100.0).abs() < 1e-3 && white.a.abs() < 1e-3 && white.b.abs() < 1e-3);
+/// 
+/// let red = Lab::from_srgb(Rgb::new(1.0, 0.0, 0.0));
+/// assert!((red.l - 53.24).abs() < 0.01 && (red.a - 80.09).abs() < 0.01 && (red.b - 67.20).abs() < 0.01);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Lab {
+    /// Create a new color from its components
+    pub fn new(l: f64, a: f64, b: f64) -> Self {
+        Self { l, a, b }
+    }
+
+    /// Converts gamma encoded sRGB color with components in `0..=1` range
+    pub fn from_srgb(color: Rgb<f64>) -> Self {
+        let linear = |value: f64| if value <= 0.040_45 { 
+            value/12.92 
+        } else { 
+            ((value + 0.055)/1.055).powf(2.4) 
+        };
+
+        let (r, g, b) = (linear(color.r), linear(color.g), linear(color.b));
+        let x = 0.412_456_4*r + 0.357_576_1*g + 0.180_437_5*b;
+        let y = 0.212_672_9*r + 0.715_152_2*g + 0.072_175_0*b;
+        let z = 0.019_333_9*r + 0.119_192_0*g + 0.950_304_1*b;
+
+        let f = |t: f64| {
+            const DELTA: f64 = 6.0/29.0;
+            if t > DELTA.powi(3) { t.cbrt() } else { t/(3.0*DELTA*DELTA) + 4.0/29.0 }
+        };
+
+        let (fx, fy, fz) = (f(x/WHITE[0]), f(y/WHITE[1]), f(z/WHITE[2]));
+        Self::new(116.0*fy - 16.0, 500.0*(fx - fy), 200.0*(fy - fz))
+    }
+}
@@ -0,0 +1,24 @@
+//! Color pixel types and color processing
+//! 
+//! Available functions:
+//!  * [debayer](fn.debayer.html) - demosaicing of raw sensor data 
+//!    captured through a Bayer color filter array
+//!  * [nv12_to_gray](fn.nv12_to_gray.html), [nv12_to_rgb](fn.nv12_to_rgb.html),
+//!    [i420_to_rgb](fn.i420_to_rgb.html) - conversion of YUV video frames 
+//!    to grayscale and RGB images
+//!  * [rgb_to_nv12](fn.rgb_to_nv12.html), [rgb_to_i420](fn.rgb_to_i420.html) - 
+//!    conversion of RGB images to YUV video frames
+
+mod rgb;
+mod rgba;
+mod bayer;
+mod lab;
+mod yuv;
+
+pub use rgb::Rgb;
+pub use rgba::Rgba;
+pub use lab::Lab;
+pub use bayer::{debayer, BayerPattern, DebayerMethod};
+pub use yuv::{
+    nv12_to_gray, nv12_to_rgb, i420_to_rgb, rgb_to_nv12, rgb_to_i420, YuvPlane, YuvMatrix
+};
\ No newline at end of file
@@ -0,0 +1,45 @@
+#[cfg(feature = "serde")]
+use serde_derive::{Serialize, Deserialize};
+
+/// Pixel with red, green and blue components
+/// 
+/// # Example
+/// ```
+/// use nanocv::color::Rgb;
+/// let pixel = Rgb::new(255u8, 128, 0);
+/// assert_eq!(pixel.g, 128);
+/// assert_eq!(Rgb::from([255, 128, 0]), pixel);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Rgb<T> {
+    pub r: T,
+    pub g: T,
+    pub b: T,
+}
+
+// Three components of the same type are laid out without padding
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Rgb<T> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Rgb<T> {}
+
+impl<T> Rgb<T> {
+    /// Create a new pixel from its components
+    pub fn new(r: T, g: T, b: T) -> Self {
+        Self { r, g, b }
+    }
+}
+
+impl<T> From<[T; 3]> for Rgb<T> {
+    fn from([r, g, b]: [T; 3]) -> Self {
+        Self { r, g, b }
+    }
+}
+
+impl<T> From<Rgb<T>> for [T; 3] {
+    fn from(pixel: Rgb<T>) -> Self {
+        [pixel.r, pixel.g, pixel.b]
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(feature = "serde")]
+use serde_derive::{Serialize, Deserialize};
+
+/// Pixel with red, green, blue and alpha components
+/// 
+/// # Example
+/// ```
+/// use nanocv::color::Rgba;
+/// let pixel = Rgba::new(255u8, 128, 0, 255);
+/// assert_eq!(pixel.a, 255);
+/// assert_eq!(Rgba::from([255, 128, 0, 255]), pixel);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Rgba<T> {
+    pub r: T,
+    pub g: T,
+    pub b: T,
+    pub a: T,
+}
+
+// Four components of the same type are laid out without padding
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for Rgba<T> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: bytemuck::Pod> bytemuck::Pod for Rgba<T> {}
+
+impl<T> Rgba<T> {
+    /// Create a new pixel from its components
+    pub fn new(r: T, g: T, b: T, a: T) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl<T> From<[T; 4]> for Rgba<T> {
+    fn from([r, g, b, a]: [T; 4]) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl<T> From<Rgba<T>> for [T; 4] {
+    fn from(pixel: Rgba<T>) -> Self {
+        [pixel.r, pixel.g, pixel.b, pixel.a]
+    }
+}
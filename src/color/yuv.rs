@@ -0,0 +1,285 @@
+use crate::{Img, ImgMut, ImgBuf, ImgSize};
+use super::Rgb;
+
+/// Plane of 8-bit samples stored in memory with line `stride` in bytes
+#[derive(Clone, Copy, Debug)]
+pub struct YuvPlane<'a> {
+    pub data: &'a [u8],
+    pub stride: usize,
+}
+
+impl<'a> YuvPlane<'a> {
+    /// Create plane from sample data and line stride
+    pub fn new(data: &'a [u8], stride: usize) -> Self {
+        Self { data, stride }
+    }
+
+    /// Panics if a plane of `width` bytes and `height` lines does not fit
+    fn check(&self, width: usize, height: usize, name: &str) {
+        assert!(self.stride >= width, "Stride of {} plane is smaller than its width", name);
+        let required = if height == 0 { 0 } else { (height - 1)*self.stride + width };
+        assert!(self.data.len() >= required, "Data of {} plane are too short", name);
+    }
+}
+
+/// Matrix converting between limited range YUV (Y'CbCr) and RGB
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum YuvMatrix {
+    /// ITU-R BT.601, standard definition video and most cameras
+    Bt601,
+    /// ITU-R BT.709, high definition video
+    Bt709,
+}
+
+impl YuvMatrix {
+    /// Luma weights of red and blue components
+    fn weights(self) -> (f32, f32) {
+        match self {
+            YuvMatrix::Bt601 => (0.299, 0.114),
+            YuvMatrix::Bt709 => (0.2126, 0.0722),
+        }
+    }
+
+    fn to_rgb(self, y: f32, u: f32, v: f32) -> Rgb<u8> {
+        let (kr, kb) = self.weights();
+        let kg = 1.0 - kr - kb;
+        let y = (y - 16.0)*255.0/219.0;
+        let (u, v) = ((u - 128.0)*255.0/224.0, (v - 128.0)*255.0/224.0);
+        let r = y + 2.0*(1.0 - kr)*v;
+        let b = y + 2.0*(1.0 - kb)*u;
+        let g = (y - kr*r - kb*b)/kg;
+        Rgb::new(clamp(r), clamp(g), clamp(b))
+    }
+
+    fn to_yuv(self, pixel: Rgb<u8>) -> (f32, f32, f32) {
+        let (kr, kb) = self.weights();
+        let (r, g, b) = (pixel.r as f32, pixel.g as f32, pixel.b as f32);
+        let y = kr*r + (1.0 - kr - kb)*g + kb*b;
+        let u = (b - y)/(2.0*(1.0 - kb));
+        let v = (r - y)/(2.0*(1.0 - kr));
+        (16.0 + y*219.0/255.0, 128.0 + u*224.0/255.0, 128.0 + v*224.0/255.0)
+    }
+}
+
+fn clamp(value: f32) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+/// Size of chroma planes subsampled by two in both directions
+fn chroma_size(size: ImgSize) -> ImgSize {
+    ImgSize::new(size.x.div_ceil(2), size.y.div_ceil(2))
+}
+
+/// Interpolates chroma plane with samples at `step` bytes apart to full
+/// image resolution, chroma samples are centered between luma samples
+fn upsample(plane: YuvPlane, step: usize, size: ImgSize) -> Vec<f32> {
+    let chroma = chroma_size(size);
+    let sample = |x: usize, y: usize| plane.data[y*plane.stride + x*step] as f32;
+
+    // Nearer chroma sample has weight 3/4, the other one 1/4
+    let neighbor = |position: usize, length: usize| match position % 2 {
+        0 => (position/2).saturating_sub(1),
+        _ => std::cmp::min(position/2 + 1, length - 1),
+    };
+
+    let mut result = Vec::with_capacity(size.product());
+
+    for y in 0..size.y {
+        let (near_y, far_y) = (y/2, neighbor(y, chroma.y));
+
+        for x in 0..size.x {
+            let (near_x, far_x) = (x/2, neighbor(x, chroma.x));
+            let near = 0.75*sample(near_x, near_y) + 0.25*sample(far_x, near_y);
+            let far = 0.75*sample(near_x, far_y) + 0.25*sample(far_x, far_y);
+            result.push(0.75*near + 0.25*far);
+        }
+    }
+
+    result
+}
+
+/// Converts luma and full resolution chroma into RGB image
+fn yuv_to_rgb(luma: YuvPlane, u: &[f32], v: &[f32], size: ImgSize, matrix: YuvMatrix) -> ImgBuf<Rgb<u8>> {
+    let mut image = ImgBuf::<Rgb<u8>>::new(size);
+
+    for y in 0..size.y {
+        let src = &luma.data[y*luma.stride..y*luma.stride + size.x];
+        let dst = image.line_mut(y);
+
+        for x in 0..size.x {
+            let index = y*size.x + x;
+            dst[x] = matrix.to_rgb(src[x] as f32, u[index], v[index]);
+        }
+    }
+
+    image
+}
+
+/// Converts luma plane of NV12 or I420 frame into full range grayscale image
+/// 
+/// Panics if the plane is too small for image `size`
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::{nv12_to_gray, YuvPlane}};
+/// let luma = [16, 235, 126, 0, 0, 0];
+/// let gray = nv12_to_gray(YuvPlane::new(&luma, 3), ImgSize::new(3, 1));
+/// assert_eq!(gray.line_ref(0), &[0, 255, 128]);
+/// ```
+pub fn nv12_to_gray(luma: YuvPlane, size: ImgSize) -> ImgBuf<u8> {
+    luma.check(size.x, size.y, "luma");
+    let mut image = ImgBuf::<u8>::new(size);
+
+    for y in 0..size.y {
+        let src = &luma.data[y*luma.stride..y*luma.stride + size.x];
+        let dst = image.line_mut(y);
+
+        for x in 0..size.x {
+            dst[x] = clamp((src[x] as f32 - 16.0)*255.0/219.0);
+        }
+    }
+
+    image
+}
+
+/// Converts NV12 frame (luma plane followed by plane of interleaved U and V
+/// samples subsampled by two in both directions) into RGB image
+/// 
+/// Chroma is interpolated bilinearly to full resolution, chroma samples
+/// are expected in the centers of 2x2 luma blocks
+/// 
+/// Panics if the planes are too small for image `size`
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::{nv12_to_rgb, YuvPlane, YuvMatrix, Rgb}};
+/// let luma = [81, 81, 81, 81];
+/// let chroma = [90, 240];
+/// let rgb = nv12_to_rgb(YuvPlane::new(&luma, 2), YuvPlane::new(&chroma, 2), ImgSize::new(2, 2), YuvMatrix::Bt601);
+/// let Rgb { r, g, b } = rgb[(1, 1)];
+/// assert!(r > 250 && g < 5 && b < 5);
+/// ```
+pub fn nv12_to_rgb(luma: YuvPlane, chroma: YuvPlane, size: ImgSize, matrix: YuvMatrix) -> ImgBuf<Rgb<u8>> {
+    let half = chroma_size(size);
+    luma.check(size.x, size.y, "luma");
+    chroma.check(2*half.x, half.y, "chroma");
+
+    let u = upsample(chroma, 2, size);
+    let v = upsample(YuvPlane::new(chroma.data.get(1..).unwrap_or(&[]), chroma.stride), 2, size);
+    yuv_to_rgb(luma, &u, &v, size, matrix)
+}
+
+/// Converts I420 frame (luma plane followed by separate U and V planes
+/// subsampled by two in both directions) into RGB image, see 
+/// [nv12_to_rgb](fn.nv12_to_rgb.html) for chroma interpolation
+/// 
+/// Panics if the planes are too small for image `size`
+pub fn i420_to_rgb(luma: YuvPlane, u: YuvPlane, v: YuvPlane, size: ImgSize, matrix: YuvMatrix) -> ImgBuf<Rgb<u8>> {
+    let half = chroma_size(size);
+    luma.check(size.x, size.y, "luma");
+    u.check(half.x, half.y, "U");
+    v.check(half.x, half.y, "V");
+    yuv_to_rgb(luma, &upsample(u, 1, size), &upsample(v, 1, size), size, matrix)
+}
+
+/// Converts RGB image into luma and subsampled chroma planes, chroma
+/// is averaged over 2x2 pixel blocks, returns `Y`, `U` and `V` planes 
+/// without line padding
+fn rgb_to_planes(image: &dyn Img<Rgb<u8>>, matrix: YuvMatrix) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let size = image.size();
+    let half = chroma_size(size);
+    let mut luma = Vec::with_capacity(size.product());
+    let mut u = vec![0.0f32; half.product()];
+    let mut v = vec![0.0f32; half.product()];
+    let mut counts = vec![0.0f32; half.product()];
+
+    for y in 0..size.y {
+        for (x, &pixel) in image.line_ref(y).iter().enumerate() {
+            let (luma_value, u_value, v_value) = matrix.to_yuv(pixel);
+            let index = (y/2)*half.x + x/2;
+            luma.push(clamp(luma_value));
+            u[index] += u_value;
+            v[index] += v_value;
+            counts[index] += 1.0;
+        }
+    }
+
+    let average = |values: Vec<f32>| values.iter().zip(&counts).map(|(value, count)| clamp(value/count)).collect();
+    (luma, average(u), average(v))
+}
+
+/// Converts RGB image into NV12 frame, returns luma plane with stride equal
+/// to image width and interleaved chroma plane with stride `2*ceil(width/2)`
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::{rgb_to_nv12, nv12_to_rgb, YuvPlane, YuvMatrix, Rgb}};
+/// let image = ImgBuf::new_init(ImgSize::new(3, 3), Rgb::new(20u8, 200, 120));
+/// let (luma, chroma) = rgb_to_nv12(&image, YuvMatrix::Bt709);
+/// assert_eq!((luma.len(), chroma.len()), (9, 8));
+/// let back = nv12_to_rgb(YuvPlane::new(&luma, 3), YuvPlane::new(&chroma, 4), image.size(), YuvMatrix::Bt709);
+/// assert!(back.lines().flatten().all(|p| (p.g as i32 - 200).abs() <= 1));
+/// ```
+pub fn rgb_to_nv12(image: &dyn Img<Rgb<u8>>, matrix: YuvMatrix) -> (Vec<u8>, Vec<u8>) {
+    let (luma, u, v) = rgb_to_planes(image, matrix);
+    let chroma = u.iter().zip(&v).flat_map(|(&u, &v)| [u, v]).collect();
+    (luma, chroma)
+}
+
+/// Converts RGB image into I420 frame, returns luma plane with stride equal
+/// to image width and `U` and `V` planes with stride `ceil(width/2)`
+pub fn rgb_to_i420(image: &dyn Img<Rgb<u8>>, matrix: YuvMatrix) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    rgb_to_planes(image, matrix)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgLinesMut;
+
+    fn test_image(size: ImgSize) -> ImgBuf<Rgb<u8>> {
+        let mut image = ImgBuf::new(size);
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgb::new((position.x*8) as u8, 128, (200 - position.y*8) as u8);
+        }
+
+        image
+    }
+
+    #[test]
+    fn i420_round_trip_preserves_smooth_image() {
+        let size = ImgSize::new(9, 7);
+        let image = test_image(size);
+        let (luma, u, v) = rgb_to_i420(&image, YuvMatrix::Bt601);
+        let back = i420_to_rgb(YuvPlane::new(&luma, 9), YuvPlane::new(&u, 5), YuvPlane::new(&v, 5), size, YuvMatrix::Bt601);
+
+        for (a, b) in image.lines().flatten().zip(back.lines().flatten()) {
+            let error = [a.r as i32 - b.r as i32, a.g as i32 - b.g as i32, a.b as i32 - b.b as i32];
+            assert!(error.iter().all(|e| e.abs() <= 8), "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn chroma_is_interpolated_between_samples() {
+        // Two chroma columns, edge in U between them is smoothed
+        let luma = [126; 8];
+        let chroma = [128, 128, 228, 128];
+        let u = upsample(YuvPlane::new(&chroma, 4), 2, ImgSize::new(4, 2));
+        assert_eq!(&u[..4], &[128.0, 153.0, 203.0, 228.0]);
+
+        let (y_nv12, uv) = rgb_to_nv12(&test_image(ImgSize::new(4, 2)), YuvMatrix::Bt601);
+        let (y_i420, u, v) = rgb_to_i420(&test_image(ImgSize::new(4, 2)), YuvMatrix::Bt601);
+        assert_eq!(y_nv12, y_i420);
+        assert_eq!(uv, vec![u[0], v[0], u[1], v[1]]);
+        assert_eq!(nv12_to_gray(YuvPlane::new(&luma, 4), ImgSize::new(4, 2)).line_ref(1), &[128; 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_planes_panic() {
+        nv12_to_rgb(YuvPlane::new(&[0; 4], 2), YuvPlane::new(&[0; 1], 2), ImgSize::new(2, 2), YuvMatrix::Bt601);
+    }
+}
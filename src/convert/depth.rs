@@ -0,0 +1,97 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive, geometry::round_as};
+
+/// Converts 8-bit image to 16-bit image, mapping `255` to `65535`
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, convert::u8_to_u16};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 1), vec![0, 1, 255]);
+/// assert_eq!(u8_to_u16(&image).line_ref(0), &[0, 257, 65535]);
+/// ```
+pub fn u8_to_u16(image: &dyn Img<u8>) -> ImgBuf<u16> {
+    convert(image, |value| value as u16*257)
+}
+
+/// Converts 16-bit image to 8-bit image, mapping `65535` to `255` and
+/// rounding to the nearest value
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, convert::u16_to_u8};
+/// let image = ImgBuf::<u16>::from_vec(ImgSize::new(4, 1), vec![0, 128, 129, 65535]);
+/// assert_eq!(u16_to_u8(&image).line_ref(0), &[0, 0, 1, 255]);
+/// ```
+pub fn u16_to_u8(image: &dyn Img<u16>) -> ImgBuf<u8> {
+    convert(image, |value| ((value as u32 + 128)/257) as u8)
+}
+
+/// Converts image to floating point image, mapping `max` value to `1.0`
+/// 
+/// Value of `max` is typically the maximal value of the pixel type, or
+/// the maximal value given by bit depth, e.g. `4095` for 12-bit sensor data
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, convert::to_f32};
+/// let image = ImgBuf::<u16>::from_vec(ImgSize::new(3, 1), vec![0, 1024, 4096]);
+/// assert_eq!(to_f32(&image, 4096).line_ref(0), &[0.0, 0.25, 1.0]);
+/// ```
+pub fn to_f32<T: AsPrimitive<f32>>(image: &dyn Img<T>, max: T) -> ImgBuf<f32> {
+    let scale = 1.0/max.as_();
+    convert(image, |value| value.as_()*scale)
+}
+
+/// Converts floating point image to pixel type `T`, mapping `1.0` to `max`
+/// 
+/// Values are rounded for integer types, when `clip` is set, values 
+/// are limited to `[0, max]` range, otherwise values outside pixel type
+/// range saturate.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, convert::from_f32};
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(4, 1), vec![-0.5, 0.5, 1.0, 2.0]);
+/// assert_eq!(from_f32::<u16>(&image, 4095, true).line_ref(0), &[0, 2048, 4095, 4095]);
+/// assert_eq!(from_f32::<u16>(&image, 4095, false).line_ref(0), &[0, 2048, 4095, 8190]);
+/// assert_eq!(from_f32::<f32>(&image, 1.0, false).line_ref(0), &[-0.5, 0.5, 1.0, 2.0]);
+/// ```
+pub fn from_f32<T>(image: &dyn Img<f32>, max: T, clip: bool) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let max = max.as_();
+
+    convert(image, |value| {
+        let scaled = value as f64*max;
+        round_as(if clip { scaled.clamp(0.0, max) } else { scaled })
+    })
+}
+
+/// Converts image pixel by pixel using `function`
+fn convert<S: Copy, D: Copy + Default, F: Fn(S) -> D>(image: &dyn Img<S>, function: F) -> ImgBuf<D> {
+    let mut output = ImgBuf::<D>::new(image.size());
+
+    for y in 0..image.height() {
+        let src = image.line_ref(y);
+        let dst = output.line_mut(y);
+
+        for x in 0..dst.len() {
+            dst[x] = function(src[x]);
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn u8_round_trips_through_u16_and_f32() {
+        let image = ImgBuf::<u8>::from_vec(ImgSize::new(256, 1), (0..=255).collect());
+        assert_eq!(u16_to_u8(&u8_to_u16(&image)), image);
+        assert_eq!(from_f32::<u8>(&to_f32(&image, 255), 255, true), image);
+    }
+}
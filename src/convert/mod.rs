@@ -0,0 +1,14 @@
+//! Conversions of images between pixel types
+//! 
+//! Unlike plain casts, conversions scale pixel values so the full range
+//! of the source type maps to the full range of the target type.
+//! 
+//! Available functions:
+//!  * [u8_to_u16](fn.u8_to_u16.html), [u16_to_u8](fn.u16_to_u8.html) - 
+//!    scaling between 8-bit and 16-bit integer images
+//!  * [to_f32](fn.to_f32.html), [from_f32](fn.from_f32.html) - conversion
+//!    to and from floating point images normalized to `[0, 1]` range
+
+mod depth;
+
+pub use depth::{u8_to_u16, u16_to_u8, to_f32, from_f32};
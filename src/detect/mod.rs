@@ -0,0 +1,11 @@
+//! Object detection scaffolding
+//! 
+//! Available functions:
+//!  * [sliding_window](fn.sliding_window.html) - scanning of windows
+//!    across a scale pyramid with a user supplied scoring function
+//!  * [non_max_suppression](fn.non_max_suppression.html) - suppression 
+//!    of overlapping detections
+
+mod window;
+
+pub use window::{sliding_window, non_max_suppression, Detection};
@@ -0,0 +1,198 @@
+use crate::{Img, ImgRange, ImgSize, Range2d, filter::resize_nearest_new};
+
+/// Scored detection window found by [sliding_window](fn.sliding_window.html)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Detection {
+    /// Window position in the original image coordinates
+    pub range: ImgRange,
+    /// Score assigned by the scoring function
+    pub score: f64,
+    /// Scale at which the window was detected
+    pub scale: f64,
+}
+
+/// Scans windows of `window_size` across `image` at several scales, scores
+/// them by `score` and suppresses overlapping detections
+/// 
+/// For each scale `s` of `scales` the image is resized (nearest neighbor)
+/// by the factor `1/s`, so larger scales detect larger objects, and 
+/// windows placed every `stride` pixels of the resized image are passed
+/// to `score`. Scoring function returns `None` for rejected windows. 
+/// Accepted windows are mapped back to the original image coordinates 
+/// and filtered by [non_max_suppression](fn.non_max_suppression.html) 
+/// with `iou_threshold`. Detections are sorted by decreasing score.
+/// 
+/// Any window classifier can be plugged in, e.g. 
+/// [hog](../feature/fn.hog.html) descriptor with a linear SVM or
+/// [haar_value](../feature/fn.haar_value.html) cascades.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, detect::sliding_window};
+/// // bright 8x8 square on dark background
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(32, 32));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = if (10..18).contains(&position.x) && (4..12).contains(&position.y) { 255 } else { 0 };
+/// }
+/// 
+/// let detections = sliding_window(&image, ImgSize::new(8, 8), 2, &[1.0], 0.3, |window| {
+///     let sum: u32 = window.lines().flat_map(|line| line.iter()).map(|&v| v as u32).sum();
+///     if sum > 255*32 { Some(sum as f64) } else { None }
+/// });
+/// 
+/// assert_eq!(detections.len(), 1);
+/// assert_eq!(detections[0].range, Range2d::new(10..18, 4..12));
+/// ```
+pub fn sliding_window<T, F>(
+    image: &dyn Img<T>, 
+    window_size: ImgSize, 
+    stride: usize, 
+    scales: &[f64], 
+    iou_threshold: f64,
+    mut score: F,
+) -> Vec<Detection> 
+where T: Copy + Default, F: FnMut(&dyn Img<T>) -> Option<f64> {
+    let size = image.size();
+    let stride = stride.max(1);
+    let mut detections = Vec::new();
+
+    if window_size.x == 0 || window_size.y == 0 {
+        return detections;
+    }
+
+    for &scale in scales.iter().filter(|&&scale| scale > 0.0) {
+        let scaled_size = ImgSize::new(
+            (size.x as f64/scale).round() as usize, 
+            (size.y as f64/scale).round() as usize
+        );
+
+        if scaled_size.x < window_size.x || scaled_size.y < window_size.y {
+            continue;
+        }
+
+        let resized = if scaled_size == size { None } else { Some(resize_nearest_new(image, scaled_size)) };
+        let scaled: &dyn Img<T> = match &resized {
+            Some(resized) => resized,
+            None => image,
+        };
+
+        let ratio = (size.x as f64/scaled_size.x as f64, size.y as f64/scaled_size.y as f64);
+        let original = |value: usize, ratio: f64| (value as f64*ratio).round() as isize;
+
+        for y in (0..=(scaled_size.y - window_size.y)).step_by(stride) {
+            for x in (0..=(scaled_size.x - window_size.x)).step_by(stride) {
+                let range = Range2d::new(
+                    (x as isize)..((x + window_size.x) as isize),
+                    (y as isize)..((y + window_size.y) as isize)
+                );
+
+                if let Some(value) = score(&WindowView { image: scaled, range }) {
+                    detections.push(Detection {
+                        range: Range2d::new(
+                            original(x, ratio.0)..original(x + window_size.x, ratio.0),
+                            original(y, ratio.1)..original(y + window_size.y, ratio.1)
+                        ),
+                        score: value,
+                        scale,
+                    });
+                }
+            }
+        }
+    }
+
+    non_max_suppression(detections, iou_threshold)
+}
+
+/// Greedy non-maximum suppression of detections
+/// 
+/// Detections are processed in order of decreasing score, a detection 
+/// is kept when its intersection over union with every already kept 
+/// detection does not exceed `iou_threshold`.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{Range2d, detect::{non_max_suppression, Detection}};
+/// let detection = |x: isize, score: f64| Detection { 
+///     range: Range2d::new(x..(x + 10), 0..10), score, scale: 1.0 
+/// };
+/// let kept = non_max_suppression(vec![detection(0, 1.0), detection(2, 3.0), detection(20, 0.5)], 0.5);
+/// assert_eq!(kept, vec![detection(2, 3.0), detection(20, 0.5)]);
+/// ```
+pub fn non_max_suppression(mut detections: Vec<Detection>, iou_threshold: f64) -> Vec<Detection> {
+    detections.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut kept: Vec<Detection> = Vec::with_capacity(detections.len());
+
+    for detection in detections {
+        if kept.iter().all(|other| iou(detection.range, other.range) <= iou_threshold) {
+            kept.push(detection);
+        }
+    }
+
+    kept
+}
+
+/// Intersection over union of two ranges
+fn iou(a: ImgRange, b: ImgRange) -> f64 {
+    let intersection = a.intersect(b);
+    let common = if intersection.is_empty() { 0 } else { intersection.area() };
+    let union = a.area() + b.area() - common;
+
+    if union > 0 { common as f64/union as f64 } else { 0.0 }
+}
+
+/// Read-only view of a window of another image
+struct WindowView<'a, T> {
+    image: &'a dyn Img<T>,
+    range: ImgRange,
+}
+
+impl<T> Img<T> for WindowView<'_, T> {
+    fn size(&self) -> ImgSize {
+        ImgSize::new(self.range.width() as usize, self.range.height() as usize)
+    }
+
+    fn line_ref(&self, line: usize) -> &[T] {
+        assert!(line < self.size().y, "Line {} outside of window", line);
+        let source = self.image.line_ref(self.range.y.start as usize + line);
+        &source[self.range.x.start as usize..self.range.x.end as usize]
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgBuf, ImgLinesMut};
+    use super::*;
+
+    #[test]
+    fn larger_scale_finds_larger_object() {
+        let mut image = ImgBuf::<u8>::new(ImgSize::new(64, 64));
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            *pixel = if (20..36).contains(&position.x) && (8..24).contains(&position.y) { 200 } else { 0 };
+        }
+
+        // object must fill the window except for a dark one pixel border
+        let detections = sliding_window(&image, ImgSize::new(10, 10), 1, &[1.0, 2.0], 0.2, |window| {
+            let inner = (1..9).all(|y| window.line_ref(y)[1..9].iter().all(|&v| v > 0));
+            let outer = window.lines().enumerate().all(|(y, line)| {
+                line.iter().enumerate().all(|(x, &v)| v == 0 || !(x == 0 || y == 0 || x == 9 || y == 9))
+            });
+            if inner && outer { Some(1.0) } else { None }
+        });
+
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].scale, 2.0);
+        assert_eq!(detections[0].range, Range2d::new(18..38, 6..26));
+    }
+
+    #[test]
+    fn window_view_lines() {
+        let image = ImgBuf::<u8>::from_vec(ImgSize::new(4, 3), (0..12).collect());
+        let view = WindowView { image: &image, range: Range2d::new(1..3, 1..3) };
+        assert_eq!(view.size(), ImgSize::new(2, 2));
+        assert_eq!(view.line_ref(1), &[9, 10]);
+    }
+}
@@ -0,0 +1,158 @@
+//! Error type returned by non-panicking variants of crate functions
+
+use std::fmt::{Display, Formatter};
+use crate::{ImgSize, ImgRange, Range, LayoutError, io::png::PngError};
+
+/// Errors reported by `try_` variants of functions that would 
+/// otherwise panic on invalid arguments and by decoders of `io` module
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Error {
+    /// Sizes of images or ranges that must be equal differ
+    SizeMismatch { 
+        /// Size required by the operation
+        expected: ImgSize, 
+        /// Size actually provided
+        actual: ImgSize 
+    },
+    /// Kernel is empty or its anchor lies outside the kernel
+    InvalidKernel { 
+        /// Number of kernel elements
+        length: usize, 
+        /// Index of kernel anchor element
+        anchor: usize 
+    },
+    /// Range is inverted (its start lies after its end)
+    InvalidRange(ImgRange),
+    /// Lengths of image lines or line ranges that must be equal differ
+    LengthMismatch {
+        /// Length required by the operation
+        expected: usize,
+        /// Length actually provided
+        actual: usize
+    },
+    /// Range within image line is inverted (its start lies after its end)
+    InvalidLineRange(Range<usize>),
+    /// Pixel data cannot be used with requested memory layout
+    Layout(LayoutError),
+    /// Number of bits per pixel is not supported by the operation
+    UnsupportedBitDepth(u32),
+    /// Arithmetic overflow while computing sizes
+    Overflow,
+    /// PNG data cannot be decoded or image cannot be encoded
+    Png(PngError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Error::SizeMismatch { expected, actual } => write!(
+                f, "Size mismatch, expected {:?}, got {:?}.", expected, actual
+            ),
+            Error::InvalidKernel { length, anchor } => write!(
+                f, "Invalid kernel of size {} with anchor {}.", length, anchor
+            ),
+            Error::InvalidRange(range) => write!(f, "Invalid range {:?}.", range),
+            Error::LengthMismatch { expected, actual } => write!(
+                f, "Length mismatch, expected {}, got {}.", expected, actual
+            ),
+            Error::InvalidLineRange(range) => write!(f, "Invalid line range {:?}.", range),
+            Error::Layout(error) => error.fmt(f),
+            Error::UnsupportedBitDepth(depth) => write!(f, "Unsupported bit depth {}.", depth),
+            Error::Overflow => write!(f, "Arithmetic overflow."),
+            Error::Png(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Layout(error) => Some(error),
+            Error::Png(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<LayoutError> for Error {
+    fn from(error: LayoutError) -> Self {
+        Error::Layout(error)
+    }
+}
+
+impl From<PngError> for Error {
+    fn from(error: PngError) -> Self {
+        Error::Png(error)
+    }
+}
+
+/// Checks that `range` is not inverted
+pub(crate) fn check_range(range: ImgRange) -> Result<(), Error> {
+    if range.x.start > range.x.end || range.y.start > range.y.end {
+        Err(Error::InvalidRange(range))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that both ranges are valid and have the same size
+pub(crate) fn check_same_size(expected: ImgRange, actual: ImgRange) -> Result<(), Error> {
+    check_range(expected)?;
+    check_range(actual)?;
+
+    let (expected, actual) = (range_size(expected)?, range_size(actual)?);
+
+    if expected != actual {
+        Err(Error::SizeMismatch { expected, actual })
+    } else {
+        Ok(())
+    }
+}
+
+/// Size of a valid (not inverted) range, fails if it does not fit into `isize`
+fn range_size(range: ImgRange) -> Result<ImgSize, Error> {
+    let width = range.x.end.checked_sub(range.x.start).ok_or(Error::Overflow)?;
+    let height = range.y.end.checked_sub(range.y.start).ok_or(Error::Overflow)?;
+    Ok(ImgSize::new(width as usize, height as usize))
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Range;
+
+    #[test]
+    fn inverted_range_is_invalid() {
+        let range = ImgRange { x: Range { start: 2, end: 1 }, y: Range::new(0..1) };
+        assert_eq!(check_range(range), Err(Error::InvalidRange(range)));
+    }
+
+    #[test]
+    fn ranges_of_different_size_do_not_match() {
+        assert_eq!(
+            check_same_size(ImgRange::new(0..2, 0..2), ImgRange::new(1..4, 1..3)),
+            Err(Error::SizeMismatch { 
+                expected: ImgSize::new(2, 2), 
+                actual: ImgSize::new(3, 2) 
+            })
+        );
+    }
+
+    #[test]
+    fn png_error_converts_into_error() {
+        let decode = || -> Result<(), Error> {
+            crate::io::png::decode(&[0; 8])?;
+            Ok(())
+        };
+
+        assert_eq!(decode(), Err(Error::Png(PngError::InvalidSignature)));
+    }
+
+    #[test]
+    fn range_size_overflow_is_error() {
+        let huge = ImgRange { x: Range { start: isize::MIN, end: isize::MAX }, y: Range::new(0..1) };
+        assert_eq!(check_same_size(huge, huge), Err(Error::Overflow));
+    }
+}
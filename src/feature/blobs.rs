@@ -0,0 +1,156 @@
+use crate::{
+    Img, ImgRange, Range2d, Vec2d, 
+    segmentation::{label_components, region_perimeter, Connectivity},
+    stats::compute_moments,
+};
+
+/// Parameters of [detect_blobs](fn.detect_blobs.html), blobs outside
+/// any of the limits are rejected
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlobParams {
+    /// Minimal number of blob pixels
+    pub min_area: usize,
+    /// Maximal number of blob pixels
+    pub max_area: usize,
+    /// Minimal circularity `4π*area/perimeter²`, `1` for a disk, 
+    /// lower for elongated or ragged shapes
+    pub min_circularity: f64,
+    /// Minimal ratio of minor and major second order moments, `1` for 
+    /// shapes symmetric in all directions, approaching `0` for lines
+    pub min_inertia_ratio: f64,
+}
+
+impl Default for BlobParams {
+    /// Parameters accepting blobs of at least 4 pixels with any shape
+    fn default() -> Self {
+        Self { min_area: 4, max_area: usize::MAX, min_circularity: 0.0, min_inertia_ratio: 0.0 }
+    }
+}
+
+/// Blob found by [detect_blobs](fn.detect_blobs.html)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Blob {
+    /// Centroid of blob pixels
+    pub center: Vec2d<f64>,
+    /// Radius of disk with the same area
+    pub radius: f64,
+    /// Number of blob pixels
+    pub area: usize,
+    /// Circularity `4π*area/perimeter²`, limited to `1`
+    pub circularity: f64,
+    /// Ratio of minor and major second order central moments
+    pub inertia_ratio: f64,
+    /// Bounding range of blob pixels
+    pub range: ImgRange,
+}
+
+/// Detects blobs (8-connected components of `true` pixels) in `mask`
+/// and filters them by area and shape
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::{detect_blobs, BlobParams}};
+/// let mut mask = ImgBuf::<bool>::new(ImgSize::new(20, 10));
+/// 
+/// // a disk and a thin line
+/// for (position, pixel) in mask.enumerate_pixels_mut() {
+///     let offset = position.cast::<f64>() - Vec2d::new(5.0, 5.0);
+///     *pixel = offset.length() <= 3.0 || (position.y == 2 && position.x > 10);
+/// }
+/// 
+/// let params = BlobParams { min_inertia_ratio: 0.5, ..BlobParams::default() };
+/// let blobs = detect_blobs(&mask, &params);
+/// assert_eq!(blobs.len(), 1);
+/// assert_eq!(blobs[0].center, Vec2d::new(5.0, 5.0));
+/// assert!(blobs[0].circularity > 0.7);
+/// ```
+pub fn detect_blobs(mask: &dyn Img<bool>, params: &BlobParams) -> Vec<Blob> {
+    let (labels, count) = label_components(mask, Connectivity::Eight);
+    let empty = Range2d::new(0..0, 0..0);
+    let mut ranges = vec![empty; count as usize];
+    let mut areas = vec![0usize; count as usize];
+
+    for (position, &label) in labels.enumerate_pixels() {
+        if label > 0 {
+            let index = label as usize - 1;
+            let position = position.cast::<isize>();
+            let pixel = Range2d::new(position.x..(position.x + 1), position.y..(position.y + 1));
+            ranges[index] = if areas[index] == 0 { pixel } else { ranges[index].union(pixel) };
+            areas[index] += 1;
+        }
+    }
+
+    let size = labels.size().cast::<isize>();
+
+    (0..count as usize)
+        .filter(|&index| areas[index] >= params.min_area && areas[index] <= params.max_area)
+        .filter_map(|index| {
+            let label = index as u32 + 1;
+            let range = ranges[index];
+            let area = areas[index];
+
+            let inside = |x: isize, y: isize| {
+                x >= 0 && y >= 0 && x < size.x && y < size.y && labels[(x as usize, y as usize)] == label
+            };
+
+            let moments = compute_moments(range, |x, y| if labels[(x, y)] == label { 1.0 } else { 0.0 });
+            let perimeter = region_perimeter(inside, range);
+
+            let circularity = if perimeter > 0.0 {
+                (4.0*std::f64::consts::PI*area as f64/(perimeter*perimeter)).min(1.0)
+            } else {
+                1.0
+            };
+
+            let spread = (((moments.mu20 - moments.mu02)/2.0).powi(2) + moments.mu11*moments.mu11).sqrt();
+            let major = (moments.mu20 + moments.mu02)/2.0 + spread;
+            let minor = (moments.mu20 + moments.mu02)/2.0 - spread;
+            let inertia_ratio = if major > 0.0 { minor.max(0.0)/major } else { 1.0 };
+
+            if circularity < params.min_circularity || inertia_ratio < params.min_inertia_ratio {
+                return None;
+            }
+
+            Some(Blob {
+                center: moments.centroid().unwrap_or_default(),
+                radius: (area as f64/std::f64::consts::PI).sqrt(),
+                area,
+                circularity,
+                inertia_ratio,
+                range,
+            })
+        })
+        .collect()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImgBuf, ImgLinesMut, ImgSize};
+
+    #[test]
+    fn blobs_are_filtered_by_area_and_circularity() {
+        let mut mask = ImgBuf::<bool>::new(ImgSize::new(30, 30));
+
+        for (position, pixel) in mask.enumerate_pixels_mut() {
+            let (x, y) = (position.x as isize, position.y as isize);
+            let disk = (x - 8).pow(2) + (y - 8).pow(2) <= 25;
+            let small = x == 28 && y == 2;
+            // ring shaped blob has low circularity
+            let distance = (x - 20).pow(2) + (y - 20).pow(2);
+            let ring = (16..=36).contains(&distance);
+            *pixel = disk || small || ring;
+        }
+
+        let all = detect_blobs(&mask, &BlobParams { min_area: 1, ..BlobParams::default() });
+        assert_eq!(all.len(), 3);
+
+        let params = BlobParams { min_circularity: 0.7, ..BlobParams::default() };
+        let round = detect_blobs(&mask, &params);
+        assert_eq!(round.len(), 1);
+        assert_eq!(round[0].range, Range2d::new(3..14, 3..14));
+        assert!((round[0].inertia_ratio - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Img, ImgRange, Range2d, Vec2d, AsPrimitive, stats::median};
+
+/// Maximal shift (in pixels) between iterations, at which 
+/// iterative re-centering is considered converged
+const CONVERGENCE: f64 = 1e-3;
+
+/// Computes intensity weighted center of mass of pixels within `range`
+/// 
+/// Range is clipped to image bounds, pixel `(x, y)` is considered to be
+/// located at coordinates `(x, y)`. If the range is empty or the sum 
+/// of pixel values is not positive, the center of the range is returned.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::centroid};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![
+///     0, 1, 3,
+///     0, 0, 0,
+/// ]);
+/// let center = centroid(&image, image.range());
+/// assert_eq!(center, Vec2d::new(1.75, 0.0));
+/// ```
+pub fn centroid<T: AsPrimitive<f64>>(image: &dyn Img<T>, range: ImgRange) -> Vec2d<f64> {
+    weighted_center(image, range, 0.0)
+}
+
+/// Computes intensity weighted center of mass with optional background
+/// subtraction and iterative re-centering
+/// 
+/// When `subtract_background` is set, median of pixels on the border of
+/// the window is subtracted from the window pixels and negative values
+/// are ignored. After computing centroid, the window (of the same size as
+/// `range`) is centered on the centroid and the computation is repeated, 
+/// at most `iterations` times, or until the centroid stops moving. Zero 
+/// iterations compute the centroid just once within the original range.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::centroid_refined};
+/// let mut image = ImgBuf::<u16>::new_init(ImgSize::new(16, 16), 100);
+/// image[(9, 9)] = 300;
+/// image[(10, 9)] = 300;
+/// 
+/// // window initially placed off center
+/// let center = centroid_refined(&image, Range2d::new(4..11, 4..11), true, 5);
+/// assert_eq!(center, Vec2d::new(9.5, 9.0));
+/// ```
+pub fn centroid_refined<T: AsPrimitive<f64>>(
+    image: &dyn Img<T>, 
+    range: ImgRange, 
+    subtract_background: bool, 
+    iterations: usize
+) -> Vec2d<f64> {
+    let mut window = range;
+    let mut center = Vec2d::new(0.0, 0.0);
+
+    for iteration in 0..(iterations + 1) {
+        let background = if subtract_background { border_median(image, window) } else { 0.0 };
+        let next = weighted_center(image, window, background);
+
+        if iteration > 0 && (next - center).length() < CONVERGENCE {
+            return next;
+        }
+
+        center = next;
+
+        // Shift the window so its center gets nearest to the centroid
+        let half = Vec2d::new(range.width() as f64 - 1.0, range.height() as f64 - 1.0)*0.5;
+        let start = center - half;
+        let shift = Vec2d::new(start.x.round() as isize, start.y.round() as isize) - range.start();
+        window = range + shift;
+    }
+
+    center
+}
+
+/// Centroid of pixel values above `background` within `range`
+fn weighted_center<T: AsPrimitive<f64>>(
+    image: &dyn Img<T>, 
+    range: ImgRange, 
+    background: f64
+) -> Vec2d<f64> {
+    let clipped = range.clamp_to(image.range());
+    let (mut sum, mut weighted) = (0.0, Vec2d::new(0.0, 0.0));
+
+    for y in clipped.y.start..clipped.y.end {
+        let line = image.line_ref(y as usize);
+
+        for x in clipped.x.start..clipped.x.end {
+            let weight = line[x as usize].as_() - background;
+
+            if weight > 0.0 {
+                sum += weight;
+                weighted = weighted + Vec2d::new(x as f64, y as f64)*weight;
+            }
+        }
+    }
+
+    if sum > 0.0 {
+        weighted*(1.0/sum)
+    } else {
+        Vec2d::new(
+            (range.x.start + range.x.end - 1) as f64/2.0,
+            (range.y.start + range.y.end - 1) as f64/2.0,
+        )
+    }
+}
+
+/// Median value of pixels on the border of `range` clipped to image
+pub(crate) fn border_median<T: AsPrimitive<f64>>(image: &dyn Img<T>, range: ImgRange) -> f64 {
+    let clipped = range.clamp_to(image.range());
+
+    if clipped.is_empty() {
+        return 0.0;
+    }
+
+    let inner = Range2d::new(
+        (clipped.x.start + 1)..(clipped.x.end - 1), 
+        (clipped.y.start + 1)..(clipped.y.end - 1)
+    );
+
+    let mut values: Vec<f32> = clipped.iter()
+        .filter(|&position| !inner.contains(position))
+        .map(|position| image.line_ref(position.y as usize)[position.x as usize].as_() as f32)
+        .collect();
+
+    median(&mut values).unwrap_or(0.0) as f64
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImgBuf, ImgSize};
+
+    #[test]
+    fn zero_image_returns_range_center() {
+        let image = ImgBuf::<f32>::new(ImgSize::new(8, 8));
+        assert_eq!(centroid(&image, Range2d::new(2..5, 1..7)), Vec2d::new(3.0, 3.5));
+    }
+
+    #[test]
+    fn background_is_subtracted() {
+        let mut image = ImgBuf::<f32>::new_init(ImgSize::new(5, 5), 10.0);
+        image[(3, 2)] = 20.0;
+        assert_eq!(centroid_refined(&image, image.range(), true, 0), Vec2d::new(3.0, 2.0));
+        assert!(centroid(&image, image.range()).x < 2.1);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{AsPrimitive, Img, ImgBuf, geometry::Point};
+use super::gradient::gradients;
+
+/// Half size of the window over which the structure tensor is summed
+const BLOCK_RADIUS: usize = 1;
+
+/// Detects strong corners by the Shi-Tomasi "good features to track" 
+/// criterion
+/// 
+/// Corner response is the smaller eigenvalue of the structure tensor 
+/// (gradient covariance summed over a 3x3 window), which is large only 
+/// when the image varies in both directions. Local maxima of the response 
+/// above `quality_level` times the strongest response are accepted in 
+/// order of decreasing response, corners closer than `min_distance` to an 
+/// already accepted corner are dropped. At most `max_corners` corners 
+/// are returned, strongest first, positions are integer pixel coordinates
+/// suitable for [refine_corners_subpix](fn.refine_corners_subpix.html).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, feature::good_features_to_track};
+/// // bright square has four corners, its edges are rejected
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(30, 30));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     let inside = (10..20).contains(&position.x) && (10..20).contains(&position.y);
+///     *pixel = if inside { 200 } else { 0 };
+/// }
+/// 
+/// let corners = good_features_to_track(&image, 10, 0.1, 5.0);
+/// assert_eq!(corners.len(), 4);
+/// assert!(corners.iter().all(|c| c.distance(Point::new(14.5, 14.5)) > 6.0));
+/// ```
+pub fn good_features_to_track<T: AsPrimitive<f32>>(
+    image: &dyn Img<T>, 
+    max_corners: usize, 
+    quality_level: f32, 
+    min_distance: f64,
+) -> Vec<Point> {
+    let response = min_eigenvalues(image);
+    let size = response.size();
+    let maximum = response.lines().flat_map(|line| line.iter()).fold(0.0f32, |a, &b| a.max(b));
+
+    if maximum <= 0.0 {
+        return Vec::new();
+    }
+
+    let threshold = quality_level*maximum;
+    let mut candidates = Vec::new();
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let value = response[(x, y)];
+
+            if value <= 0.0 || value < threshold {
+                continue;
+            }
+
+            let mut is_maximum = true;
+
+            for ny in y.saturating_sub(1)..(y + 2).min(size.y) {
+                for nx in x.saturating_sub(1)..(x + 2).min(size.x) {
+                    is_maximum &= response[(nx, ny)] <= value;
+                }
+            }
+
+            if is_maximum {
+                candidates.push((value, Point::new(x as f64, y as f64)));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let mut corners: Vec<Point> = Vec::new();
+
+    for (_, candidate) in candidates {
+        if corners.len() >= max_corners {
+            break;
+        }
+
+        if corners.iter().all(|corner| corner.distance(candidate) >= min_distance) {
+            corners.push(candidate);
+        }
+    }
+
+    corners
+}
+
+/// Smaller eigenvalue of the structure tensor at each pixel
+fn min_eigenvalues<T: AsPrimitive<f32>>(image: &dyn Img<T>) -> ImgBuf<f32> {
+    let (gx, gy) = gradients(image);
+    let size = image.size();
+    let mut output = ImgBuf::<f32>::new(size);
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let (mut a, mut b, mut c) = (0.0f32, 0.0f32, 0.0f32);
+
+            for ny in y.saturating_sub(BLOCK_RADIUS)..(y + BLOCK_RADIUS + 1).min(size.y) {
+                for nx in x.saturating_sub(BLOCK_RADIUS)..(x + BLOCK_RADIUS + 1).min(size.x) {
+                    let (dx, dy) = (gx[(nx, ny)], gy[(nx, ny)]);
+                    a += dx*dx;
+                    b += dx*dy;
+                    c += dy*dy;
+                }
+            }
+
+            let half_trace = (a + c)/2.0;
+            let spread = (((a - c)/2.0).powi(2) + b*b).sqrt();
+            output[(x, y)] = half_trace - spread;
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgLinesMut, ImgSize};
+    use super::*;
+
+    fn dots(size: ImgSize) -> ImgBuf<f32> {
+        let mut image = ImgBuf::<f32>::new(size);
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            if position.x % 6 == 3 && position.y % 6 == 3 {
+                *pixel = 100.0;
+            }
+        }
+
+        image
+    }
+
+    #[test]
+    fn corners_are_limited_and_separated() {
+        let image = dots(ImgSize::new(60, 60));
+        assert_eq!(good_features_to_track(&image, 1000, 0.01, 1.0).len(), 100);
+
+        let corners = good_features_to_track(&image, 1000, 0.01, 10.0);
+        assert!(corners.len() < 100 && corners.len() >= 25);
+
+        for (index, a) in corners.iter().enumerate() {
+            assert!(corners[(index + 1)..].iter().all(|b| a.distance(*b) >= 10.0));
+        }
+
+        assert_eq!(good_features_to_track(&image, 7, 0.01, 1.0).len(), 7);
+    }
+
+    #[test]
+    fn flat_image_has_no_corners() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(10, 10), 9);
+        assert!(good_features_to_track(&image, 10, 0.01, 1.0).is_empty());
+    }
+}
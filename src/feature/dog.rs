@@ -0,0 +1,293 @@
+use crate::{Img, ImgLinesMut, ImgBuf, ImgSize, AsPrimitive, geometry::Point, filter::gaussian_blur_new};
+
+/// Scale of the first level of every octave
+const BASE_SIGMA: f32 = 1.6;
+
+/// Blur already present in the input image
+const INPUT_SIGMA: f32 = 0.5;
+
+/// Maximal number of sub-pixel refinement steps
+const REFINEMENT_STEPS: usize = 5;
+
+/// Keypoints with principal curvature ratio above this value (edges) are rejected
+const EDGE_RATIO: f32 = 10.0;
+
+/// Keypoint found in difference-of-Gaussians scale space
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScaleKeypoint {
+    /// Sub-pixel position in input image coordinates
+    pub position: Point,
+    /// Standard deviation of the Gaussian the keypoint was detected at,
+    /// in input image pixels (blob radius is about `sqrt(2)*scale`)
+    pub scale: f64,
+    /// Interpolated difference-of-Gaussians response, positive for bright
+    /// blobs and negative for dark blobs
+    pub response: f32,
+}
+
+/// Detects scale-invariant keypoints as extrema of difference-of-Gaussians
+/// 
+/// Builds Gaussian scale space with `octaves` octaves, each halving 
+/// resolution of the previous one and containing `scales` intervals,
+/// differences of successive Gaussian levels approximate scale normalized
+/// Laplacian. Pixels greater or lower than all 26 neighbors in space and 
+/// scale are refined to sub-pixel and sub-scale precision by fitting a 
+/// 3D quadratic, extrema with absolute response below `threshold` (in
+/// input pixel value units) and extrema lying on edges are rejected.
+/// 
+/// Keypoints are returned octave by octave, typically they are described 
+/// by [orb](fn.orb.html) descriptors with patch size adapted to their scale.
+/// 
+/// # Panics
+/// 
+/// Panics when `scales` is zero
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::dog_keypoints};
+/// let mut image = ImgBuf::<f32>::new(ImgSize::new(64, 64));
+/// 
+/// // bright Gaussian blob with sigma 4 pixels
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     let offset = position.cast::<f64>() - Vec2d::new(30.0, 34.0);
+///     *pixel = 200.0*(-offset.length_squared()/32.0).exp() as f32;
+/// }
+/// 
+/// let keypoints = dog_keypoints(&image, 3, 3, 1.0);
+/// assert_eq!(keypoints.len(), 1);
+/// assert!((keypoints[0].position - Vec2d::new(30.0, 34.0)).length() < 0.5);
+/// assert!((keypoints[0].scale - 4.0).abs() < 1.0);
+/// ```
+pub fn dog_keypoints<T>(image: &dyn Img<T>, octaves: usize, scales: usize, threshold: f32) -> Vec<ScaleKeypoint>
+where T: AsPrimitive<f32> {
+    assert!(scales > 0, "Number of scales must not be zero");
+
+    let factor = 2f32.powf(1.0/scales as f32);
+    let mut base = gaussian_blur_new(image, (BASE_SIGMA*BASE_SIGMA - INPUT_SIGMA*INPUT_SIGMA).sqrt());
+    let mut keypoints = Vec::new();
+
+    for octave in 0..octaves {
+        let size = base.size();
+
+        if size.x < 3 || size.y < 3 {
+            break;
+        }
+
+        // Gaussian levels with scales BASE_SIGMA*factor^i, blurred incrementally
+        let mut levels = vec![base];
+
+        for level in 1..scales + 3 {
+            let previous = BASE_SIGMA*factor.powi(level as i32 - 1);
+            let current = previous*factor;
+            let blurred = gaussian_blur_new(&levels[level - 1], (current*current - previous*previous).sqrt());
+            levels.push(blurred);
+        }
+
+        let dog: Vec<ImgBuf<f32>> = levels.windows(2).map(|pair| difference(&pair[0], &pair[1])).collect();
+        let spacing = (1usize << octave) as f64;
+
+        for s in 1..=scales {
+            for y in 1..size.y - 1 {
+                for x in 1..size.x - 1 {
+                    let value = dog[s][(x, y)];
+
+                    // Cheap prefilter before the full comparison
+                    if value.abs() < 0.5*threshold || !is_extremum(&dog, x, y, s) {
+                        continue;
+                    }
+
+                    if let Some((offset, response)) = refine(&dog, x, y, s, threshold) {
+                        keypoints.push(ScaleKeypoint {
+                            position: Point::new(offset[0] as f64, offset[1] as f64)*spacing,
+                            scale: (BASE_SIGMA as f64)*(factor as f64).powf(offset[2] as f64)*spacing,
+                            response,
+                        });
+                    }
+                }
+            }
+        }
+
+        base = downsample(&levels[scales]);
+    }
+
+    keypoints
+}
+
+fn difference(a: &ImgBuf<f32>, b: &ImgBuf<f32>) -> ImgBuf<f32> {
+    let mut result = a.clone();
+
+    for (dst, src) in result.lines_mut().zip(b.lines()) {
+        for x in 0..src.len() {
+            dst[x] -= src[x];
+        }
+    }
+
+    result
+}
+
+/// Every second pixel of `image` in both directions
+fn downsample(image: &ImgBuf<f32>) -> ImgBuf<f32> {
+    let size = image.size();
+    let mut result = ImgBuf::<f32>::new(ImgSize::new(size.x.div_ceil(2), size.y.div_ceil(2)));
+
+    for y in 0..result.size().y {
+        for x in 0..result.size().x {
+            result[(x, y)] = image[(2*x, 2*y)];
+        }
+    }
+
+    result
+}
+
+fn is_extremum(dog: &[ImgBuf<f32>], x: usize, y: usize, s: usize) -> bool {
+    let value = dog[s][(x, y)];
+    let (mut maximum, mut minimum) = (true, true);
+
+    for level in &dog[s - 1..=s + 1] {
+        for ny in y - 1..=y + 1 {
+            for nx in x - 1..=x + 1 {
+                let other = level[(nx, ny)];
+
+                if std::ptr::eq(level, &dog[s]) && nx == x && ny == y {
+                    continue;
+                }
+
+                maximum &= value > other;
+                minimum &= value < other;
+            }
+        }
+    }
+
+    maximum || minimum
+}
+
+/// Sub-pixel refinement by quadratic fit, returns refined `(x, y, s)`
+/// coordinates within the octave and interpolated response, or `None`
+/// for weak, unstable or edge-like extrema
+fn refine(dog: &[ImgBuf<f32>], x: usize, y: usize, s: usize, threshold: f32) -> Option<([f32; 3], f32)> {
+    let size = dog[0].size();
+    let (mut x, mut y, mut s) = (x, y, s);
+
+    for _ in 0..REFINEMENT_STEPS {
+        let at = |dx: isize, dy: isize, ds: isize| {
+            dog[(s as isize + ds) as usize][((x as isize + dx) as usize, (y as isize + dy) as usize)]
+        };
+
+        let center = at(0, 0, 0);
+        let gradient = [
+            (at(1, 0, 0) - at(-1, 0, 0))/2.0,
+            (at(0, 1, 0) - at(0, -1, 0))/2.0,
+            (at(0, 0, 1) - at(0, 0, -1))/2.0,
+        ];
+
+        let dxx = at(1, 0, 0) + at(-1, 0, 0) - 2.0*center;
+        let dyy = at(0, 1, 0) + at(0, -1, 0) - 2.0*center;
+        let dss = at(0, 0, 1) + at(0, 0, -1) - 2.0*center;
+        let dxy = (at(1, 1, 0) - at(-1, 1, 0) - at(1, -1, 0) + at(-1, -1, 0))/4.0;
+        let dxs = (at(1, 0, 1) - at(-1, 0, 1) - at(1, 0, -1) + at(-1, 0, -1))/4.0;
+        let dys = (at(0, 1, 1) - at(0, -1, 1) - at(0, 1, -1) + at(0, -1, -1))/4.0;
+        let hessian = [[dxx, dxy, dxs], [dxy, dyy, dys], [dxs, dys, dss]];
+        let step = solve3(hessian, gradient)?;
+        let offset = [-step[0], -step[1], -step[2]];
+
+        // Slightly above half a sample to avoid oscillation between two
+        // samples when the extremum lies right in between them
+        if offset.iter().all(|value| value.abs() <= 0.6) {
+            let response = center + 0.5*(gradient[0]*offset[0] + gradient[1]*offset[1] + gradient[2]*offset[2]);
+            let trace = dxx + dyy;
+            let determinant = dxx*dyy - dxy*dxy;
+            let edge_limit = (EDGE_RATIO + 1.0)*(EDGE_RATIO + 1.0)/EDGE_RATIO;
+
+            if response.abs() < threshold || determinant <= 0.0 || trace*trace/determinant >= edge_limit {
+                return None;
+            }
+
+            return Some(([x as f32 + offset[0], y as f32 + offset[1], s as f32 + offset[2]], response));
+        }
+
+        // Extremum lies closer to a neighboring sample, continue from there
+        let moved = |value: usize, delta: f32, max: usize| -> Option<usize> {
+            let next = value as isize + delta.round() as isize;
+            if next >= 1 && next < max as isize - 1 { Some(next as usize) } else { None }
+        };
+
+        x = moved(x, offset[0], size.x)?;
+        y = moved(y, offset[1], size.y)?;
+        s = moved(s, offset[2], dog.len())?;
+    }
+
+    None
+}
+
+/// Solves 3x3 linear system by Cramer's rule
+fn solve3(a: [[f32; 3]; 3], b: [f32; 3]) -> Option<[f32; 3]> {
+    let det = |m: [[f32; 3]; 3]| {
+        m[0][0]*(m[1][1]*m[2][2] - m[1][2]*m[2][1])
+            - m[0][1]*(m[1][0]*m[2][2] - m[1][2]*m[2][0])
+            + m[0][2]*(m[1][0]*m[2][1] - m[1][1]*m[2][0])
+    };
+
+    let determinant = det(a);
+
+    if determinant.abs() < 1e-12 {
+        return None;
+    }
+
+    let mut result = [0.0; 3];
+
+    for column in 0..3 {
+        let mut replaced = a;
+
+        for row in 0..3 {
+            replaced[row][column] = b[row];
+        }
+
+        result[column] = det(replaced)/determinant;
+    }
+
+    Some(result)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::Vec2d;
+    use super::*;
+
+    fn blob(image: &mut ImgBuf<f32>, center: Vec2d<f64>, sigma: f64, amplitude: f32) {
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            let offset = position.cast::<f64>() - center;
+            *pixel += amplitude*(-offset.length_squared()/(2.0*sigma*sigma)).exp() as f32;
+        }
+    }
+
+    #[test]
+    fn blobs_of_different_sizes_are_found_at_their_scales() {
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(128, 96));
+        blob(&mut image, Vec2d::new(30.0, 40.0), 2.5, 150.0);
+        blob(&mut image, Vec2d::new(85.3, 50.6), 8.0, -150.0);
+
+        let keypoints = dog_keypoints(&image, 4, 3, 1.0);
+        assert_eq!(keypoints.len(), 2, "{:?}", keypoints);
+
+        let small = keypoints.iter().find(|k| k.response > 0.0).unwrap();
+        let large = keypoints.iter().find(|k| k.response < 0.0).unwrap();
+        assert!((small.position - Vec2d::new(30.0, 40.0)).length() < 0.5);
+        assert!((large.position - Vec2d::new(85.3, 50.6)).length() < 1.0);
+        assert!((small.scale - 2.5).abs() < 0.7, "{}", small.scale);
+        assert!((large.scale - 8.0).abs() < 2.0, "{}", large.scale);
+    }
+
+    #[test]
+    fn flat_image_has_no_keypoints() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(40, 40), 90);
+        assert!(dog_keypoints(&image, 3, 3, 0.1).is_empty());
+    }
+
+    #[test]
+    fn cramer_rule_solves_system() {
+        let result = solve3([[2.0, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 3.0]], [3.0, 2.0, 4.0]).unwrap();
+        assert!(result.iter().zip([1.0, 2.0, 1.0].iter()).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+}
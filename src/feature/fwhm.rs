@@ -0,0 +1,164 @@
+use crate::{Img, ImgRange, Vec2d, AsPrimitive};
+use super::centroid::border_median;
+
+/// Shape of a star (or other peak) measured by [measure_fwhm](fn.measure_fwhm.html)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fwhm {
+    /// Full width at half maximum along the `x` axis
+    pub x: f64,
+    /// Full width at half maximum along the `y` axis
+    pub y: f64,
+    /// Eccentricity of the peak, `0` for circular peaks and approaching `1`
+    /// for elongated ones
+    pub eccentricity: f64,
+}
+
+impl Fwhm {
+    /// Mean of full widths at half maximum along both axes
+    pub fn mean(&self) -> f64 {
+        (self.x + self.y)/2.0
+    }
+}
+
+/// Measures full width at half maximum of a peak located at `center`
+/// 
+/// Measurement is done within a window of `2*radius + 1` pixels centered
+/// at `center`, the background level is estimated as a median of pixels
+/// on the window border. Widths along `x` and `y` axes are measured on
+/// the pixel line and column passing through the center, by linear
+/// interpolation of the positions where intensity falls to half of the
+/// peak value. Eccentricity is computed from the second order central
+/// moments of background subtracted pixels within the window.
+/// 
+/// Returns `None` if the center lies outside image, peak is not above
+/// background or the intensity does not fall to half maximum within the window
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::measure_fwhm};
+/// let mut image = ImgBuf::<f32>::new(ImgSize::new(15, 15));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     let distance = (position.cast::<f64>() - Vec2d::new(7.0, 7.0)).length_squared();
+///     *pixel = 10.0 + 100.0*(-distance/(2.0*2.0*2.0)).exp() as f32;
+/// }
+/// 
+/// let fwhm = measure_fwhm(&image, Vec2d::new(7.0, 7.0), 7).unwrap();
+/// // FWHM of gaussian is 2.3548 times its sigma
+/// assert!((fwhm.x - 4.71).abs() < 0.15);
+/// assert!((fwhm.y - 4.71).abs() < 0.15);
+/// assert!(fwhm.eccentricity < 0.01);
+/// ```
+pub fn measure_fwhm<T: AsPrimitive<f64>>(
+    image: &dyn Img<T>, 
+    center: Vec2d<f64>, 
+    radius: usize
+) -> Option<Fwhm> {
+    let peak_position = Vec2d::new(center.x.round() as isize, center.y.round() as isize);
+
+    if !image.range().contains(peak_position) {
+        return None;
+    }
+
+    let window = ImgRange::window(peak_position, radius).clamp_to(image.range());
+    let background = border_median(image, window);
+    let value = |x: isize, y: isize| image.line_ref(y as usize)[x as usize].as_() - background;
+    let peak = value(peak_position.x, peak_position.y);
+
+    if peak <= 0.0 {
+        return None;
+    }
+
+    let row = |x: isize| value(x, peak_position.y);
+    let column = |y: isize| value(peak_position.x, y);
+
+    let x = half_width(&row, peak_position.x, window.x.start..window.x.end, peak/2.0)?;
+    let y = half_width(&column, peak_position.y, window.y.start..window.y.end, peak/2.0)?;
+
+    Some(Fwhm { x, y, eccentricity: eccentricity(&value, window) })
+}
+
+/// Distance between positions left and right of `peak` where `profile` 
+/// falls below `half` maximum
+fn half_width<F: Fn(isize) -> f64>(
+    profile: &F, 
+    peak: isize, 
+    range: std::ops::Range<isize>, 
+    half: f64
+) -> Option<f64> {
+    let left = (range.start..peak).rev().find(|&position| profile(position) < half)?;
+    let right = (peak + 1..range.end).find(|&position| profile(position) < half)?;
+
+    Some(crossing(profile, right, right - 1, half) - crossing(profile, left, left + 1, half))
+}
+
+/// Linearly interpolated position between `outside` (below half maximum) 
+/// and `inside` (above half maximum) positions, where `profile` equals `half`
+fn crossing<F: Fn(isize) -> f64>(profile: &F, outside: isize, inside: isize, half: f64) -> f64 {
+    let (low, high) = (profile(outside), profile(inside));
+    outside as f64 + (inside - outside) as f64*(half - low)/(high - low)
+}
+
+/// Eccentricity of positive values within `window` computed from 
+/// second order central moments
+fn eccentricity<F: Fn(isize, isize) -> f64>(value: &F, window: ImgRange) -> f64 {
+    let (mut sum, mut mean) = (0.0, Vec2d::new(0.0, 0.0));
+
+    for position in window.iter() {
+        let weight = value(position.x, position.y).max(0.0);
+        sum += weight;
+        mean = mean + position.cast::<f64>()*weight;
+    }
+
+    if sum <= 0.0 {
+        return 0.0;
+    }
+
+    mean = mean*(1.0/sum);
+    let (mut xx, mut yy, mut xy) = (0.0, 0.0, 0.0);
+
+    for position in window.iter() {
+        let weight = value(position.x, position.y).max(0.0);
+        let offset = position.cast::<f64>() - mean;
+        xx += weight*offset.x*offset.x;
+        yy += weight*offset.y*offset.y;
+        xy += weight*offset.x*offset.y;
+    }
+
+    let (xx, yy, xy) = (xx/sum, yy/sum, xy/sum);
+    let spread = (((xx - yy)/2.0).powi(2) + xy*xy).sqrt();
+    let major = (xx + yy)/2.0 + spread;
+    let minor = (xx + yy)/2.0 - spread;
+
+    if major <= 0.0 { 0.0 } else { (1.0 - minor/major).max(0.0).sqrt() }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImgBuf, ImgSize, ImgLinesMut};
+
+    #[test]
+    fn elongated_peak_has_high_eccentricity() {
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(21, 21));
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            let offset = position.cast::<f64>() - Vec2d::new(10.0, 10.0);
+            let exponent = offset.x*offset.x/(2.0*9.0) + offset.y*offset.y/(2.0*1.0);
+            *pixel = 100.0*(-exponent).exp() as f32;
+        }
+
+        let fwhm = measure_fwhm(&image, Vec2d::new(10.0, 10.0), 10).unwrap();
+        assert!(fwhm.x > 2.5*fwhm.y);
+        assert!(fwhm.eccentricity > 0.9);
+    }
+
+    #[test]
+    fn flat_image_has_no_fwhm() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(9, 9), 5);
+        assert_eq!(measure_fwhm(&image, Vec2d::new(4.0, 4.0), 3), None);
+        assert_eq!(measure_fwhm(&image, Vec2d::new(-4.0, 4.0), 3), None);
+    }
+}
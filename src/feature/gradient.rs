@@ -0,0 +1,28 @@
+use crate::{Img, ImgBuf, AsPrimitive};
+
+/// Horizontal and vertical image derivatives by central differences,
+/// one-sided differences are used on image borders
+pub(crate) fn gradients<T: AsPrimitive<f32>>(image: &dyn Img<T>) -> (ImgBuf<f32>, ImgBuf<f32>) {
+    let size = image.size();
+    let mut gx = ImgBuf::<f32>::new(size);
+    let mut gy = ImgBuf::<f32>::new(size);
+
+    for y in 0..size.y {
+        let (up, down) = (y.saturating_sub(1), (y + 1).min(size.y - 1));
+        let (line, above, below) = (image.line_ref(y), image.line_ref(up), image.line_ref(down));
+
+        for x in 0..size.x {
+            let (left, right) = (x.saturating_sub(1), (x + 1).min(size.x - 1));
+
+            if right > left {
+                gx[(x, y)] = (line[right].as_() - line[left].as_())/(right - left) as f32;
+            }
+
+            if down > up {
+                gy[(x, y)] = (below[x].as_() - above[x].as_())/(down - up) as f32;
+            }
+        }
+    }
+
+    (gx, gy)
+}
@@ -0,0 +1,119 @@
+use crate::{Img, ImgRange, Range2d, filter::integral_sum};
+
+/// Haar-like feature layouts evaluated by [haar_value](fn.haar_value.html)
+/// 
+/// Each feature splits the evaluated window into equally sized rectangles
+/// (as far as the window size allows), which are either positive or 
+/// negative. Orientation describes the direction of the intensity change
+/// the feature responds to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum HaarFeature {
+    /// Two rectangles side by side, right half minus left half
+    EdgeX,
+    /// Two rectangles above each other, bottom half minus top half
+    EdgeY,
+    /// Three vertical stripes, middle stripe minus outer stripes
+    LineX,
+    /// Three horizontal stripes, middle stripe minus outer stripes
+    LineY,
+    /// Four quadrants, top-left and bottom-right minus the other two
+    Diagonal,
+}
+
+/// Evaluates Haar-like `feature` over the window `range` in constant time
+/// 
+/// Feature value is the difference between mean pixel values of positive
+/// and negative rectangles, so uniform windows give zero regardless of 
+/// the window size and rectangles of unequal size (for window sizes not
+/// divisible by the number of rectangles) are balanced. `integral` is the 
+/// integral image created by 
+/// [integral_image](../filter/fn.integral_image.html), windows are 
+/// clipped to the image bounds, rectangles outside the image count 
+/// as zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::integral_image, feature::{haar_value, HaarFeature}};
+/// // vertical edge, dark left half, bright right half
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(8, 8));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = if position.x >= 4 { 100 } else { 20 };
+/// }
+/// 
+/// let integral = integral_image(&image);
+/// let window = Range2d::new(0..8, 0..8);
+/// assert_eq!(haar_value(&integral, HaarFeature::EdgeX, window), 80.0);
+/// assert_eq!(haar_value(&integral, HaarFeature::EdgeY, window), 0.0);
+/// assert_eq!(haar_value(&integral, HaarFeature::EdgeX, Range2d::new(4..8, 0..8)), 0.0);
+/// ```
+pub fn haar_value(integral: &dyn Img<f64>, feature: HaarFeature, range: ImgRange) -> f64 {
+    let (x, y) = (range.x.start, range.y.start);
+    let (width, height) = (range.width(), range.height());
+    let rect = |x0: isize, x1: isize, y0: isize, y1: isize| Range2d::new(x0..x1, y0..y1);
+    let (half_x, half_y) = (x + width/2, y + height/2);
+    let (x_end, y_end) = (range.x.end, range.y.end);
+
+    let (positive, negative): (Vec<ImgRange>, Vec<ImgRange>) = match feature {
+        HaarFeature::EdgeX => (vec![rect(half_x, x_end, y, y_end)], vec![rect(x, half_x, y, y_end)]),
+        HaarFeature::EdgeY => (vec![rect(x, x_end, half_y, y_end)], vec![rect(x, x_end, y, half_y)]),
+        HaarFeature::LineX => {
+            let (a, b) = (x + width/3, x + 2*width/3);
+            (vec![rect(a, b, y, y_end)], vec![rect(x, a, y, y_end), rect(b, x_end, y, y_end)])
+        },
+        HaarFeature::LineY => {
+            let (a, b) = (y + height/3, y + 2*height/3);
+            (vec![rect(x, x_end, a, b)], vec![rect(x, x_end, y, a), rect(x, x_end, b, y_end)])
+        },
+        HaarFeature::Diagonal => (
+            vec![rect(x, half_x, y, half_y), rect(half_x, x_end, half_y, y_end)],
+            vec![rect(half_x, x_end, y, half_y), rect(x, half_x, half_y, y_end)],
+        ),
+    };
+
+    mean(integral, &positive) - mean(integral, &negative)
+}
+
+/// Mean pixel value over a set of rectangles, zero for empty rectangles
+fn mean(integral: &dyn Img<f64>, ranges: &[ImgRange]) -> f64 {
+    let area: isize = ranges.iter().map(|range| range.width().max(0)*range.height().max(0)).sum();
+
+    if area == 0 {
+        return 0.0;
+    }
+
+    ranges.iter().map(|&range| integral_sum(integral, range)).sum::<f64>()/area as f64
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgBuf, ImgLinesMut, ImgSize, filter::integral_image};
+    use super::*;
+
+    #[test]
+    fn uniform_window_gives_zero() {
+        let integral = integral_image(&ImgBuf::<u8>::new_init(ImgSize::new(10, 10), 40));
+        let window = Range2d::new(1..8, 2..9);
+
+        for &feature in &[HaarFeature::EdgeX, HaarFeature::EdgeY, HaarFeature::LineX, HaarFeature::LineY, HaarFeature::Diagonal] {
+            assert!(haar_value(&integral, feature, window).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn line_and_diagonal_features() {
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(9, 6));
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            let stripe = (3..6).contains(&position.x);
+            let quadrant = (position.x < 4) == (position.y < 3);
+            *pixel = if stripe { 10.0 } else { 0.0 } + if quadrant { 1.0 } else { 0.0 };
+        }
+
+        let integral = integral_image(&image);
+        assert!((haar_value(&integral, HaarFeature::LineX, Range2d::new(0..9, 0..6)) - 10.0).abs() < 1.0);
+        assert!((haar_value(&integral, HaarFeature::Diagonal, Range2d::new(0..8, 0..6)) - 1.0).abs() < 1e-12);
+    }
+}
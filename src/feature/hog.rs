@@ -0,0 +1,231 @@
+use std::f32::consts::PI;
+use crate::{Img, ImgBuf, ImgSize, AsPrimitive, geometry::{LineSegment, Point}};
+
+/// Histogram bins are clipped to this value during L2-Hys normalization
+const HYS_CLIP: f32 = 0.2;
+
+/// Computes histogram of oriented gradients (HOG) descriptor of `image`
+/// 
+/// Image is divided into square cells of `cell_size` pixels (incomplete 
+/// cells at right and bottom borders are ignored), every cell accumulates
+/// gradient magnitudes into `bins` unsigned orientation bins covering 
+/// `0..180` degrees, each gradient votes for the two nearest bins with 
+/// linear weights. Cells are grouped into overlapping blocks of 
+/// `block_size x block_size` cells with one cell stride, each block is 
+/// normalized using L2-Hys scheme (L2 normalization, clipping to `0.2` 
+/// and renormalization).
+/// 
+/// Returns concatenated block histograms, block by block in row-major 
+/// order and cells within a block in row-major order, the vector has
+/// `blocks_x*blocks_y*block_size²*bins` elements. Image too small to 
+/// contain a single block produces an empty vector.
+/// 
+/// # Panics
+/// 
+/// Panics when `cell_size`, `block_size` or `bins` is zero
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::hog};
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(32, 16));
+/// 
+/// // vertical edge produces horizontal gradients
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = if position.x >= 12 { 200 } else { 0 };
+/// }
+/// 
+/// let features = hog(&image, 8, 2, 9);
+/// assert_eq!(features.len(), 3*1*2*2*9);
+/// // the first bin (around 0 degrees) dominates in the cell with the edge
+/// let cell = &features[9..18];
+/// assert!(cell.iter().all(|&value| value <= cell[0]));
+/// ```
+pub fn hog<T>(image: &dyn Img<T>, cell_size: usize, block_size: usize, bins: usize) -> Vec<f32>
+where T: AsPrimitive<f32> {
+    assert!(block_size > 0, "Block size must not be zero");
+
+    let (cells, histograms) = cell_histograms(image, cell_size, bins);
+
+    if cells.x < block_size || cells.y < block_size {
+        return Vec::new();
+    }
+
+    let blocks = ImgSize::new(cells.x - block_size + 1, cells.y - block_size + 1);
+    let block_length = block_size*block_size*bins;
+    let mut features = Vec::with_capacity(blocks.product()*block_length);
+
+    for by in 0..blocks.y {
+        for bx in 0..blocks.x {
+            let start = features.len();
+
+            for cy in by..by + block_size {
+                for cx in bx..bx + block_size {
+                    let offset = (cy*cells.x + cx)*bins;
+                    features.extend_from_slice(&histograms[offset..offset + bins]);
+                }
+            }
+
+            let block = &mut features[start..];
+            normalize(block);
+
+            for value in block.iter_mut() {
+                *value = value.min(HYS_CLIP);
+            }
+
+            normalize(block);
+        }
+    }
+
+    features
+}
+
+/// Renders cell orientation histograms of `image` for visual inspection
+/// 
+/// Every cell of `cell_size` pixels is drawn as a star of line segments 
+/// through the cell center, one segment per orientation bin, oriented 
+/// along the edge direction (perpendicular to the gradient) with 
+/// brightness proportional to the bin value. Values are scaled so the
+/// largest bin of the image has brightness `1`.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::hog_visualization};
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(16, 16));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = if position.x >= 8 { 200 } else { 0 };
+/// }
+/// 
+/// let rendering = hog_visualization(&image, 8, 9);
+/// assert_eq!(rendering.size(), ImgSize::new(16, 16));
+/// // vertical edge is drawn as a vertical line
+/// assert_eq!(rendering[(4, 1)], 1.0);
+/// assert_eq!(rendering[(1, 4)], 0.0);
+/// ```
+pub fn hog_visualization<T>(image: &dyn Img<T>, cell_size: usize, bins: usize) -> ImgBuf<f32>
+where T: AsPrimitive<f32> {
+    let (cells, histograms) = cell_histograms(image, cell_size, bins);
+    let mut rendering = ImgBuf::<f32>::new(ImgSize::new(cells.x*cell_size, cells.y*cell_size));
+    let largest = histograms.iter().fold(0.0f32, |max, &value| max.max(value));
+
+    if largest <= 0.0 {
+        return rendering;
+    }
+
+    let half = cell_size as f64/2.0;
+
+    for cy in 0..cells.y {
+        for cx in 0..cells.x {
+            let center = Point::new(cx as f64*cell_size as f64 + half - 0.5, cy as f64*cell_size as f64 + half - 0.5);
+
+            for bin in 0..bins {
+                let intensity = histograms[(cy*cells.x + cx)*bins + bin]/largest;
+
+                if intensity <= 0.0 {
+                    continue;
+                }
+
+                // Edges are perpendicular to gradients
+                let angle = (bin as f64 + 0.5)*std::f64::consts::PI/bins as f64 + std::f64::consts::FRAC_PI_2;
+                let direction = Point::new(angle.cos(), angle.sin())*(half - 0.5);
+                let segment = LineSegment::new(center - direction, center + direction);
+                let steps = (2.0*segment.length()).ceil() as usize + 1;
+
+                for step in 0..=steps {
+                    let point = segment.point_at(step as f64/steps as f64);
+                    let (x, y) = (point.x.round() as usize, point.y.round() as usize);
+                    let pixel = &mut rendering[(x, y)];
+                    *pixel = pixel.max(intensity);
+                }
+            }
+        }
+    }
+
+    rendering
+}
+
+/// Number of cells and flattened histograms of all cells in row-major order
+fn cell_histograms<T>(image: &dyn Img<T>, cell_size: usize, bins: usize) -> (ImgSize, Vec<f32>)
+where T: AsPrimitive<f32> {
+    assert!(cell_size > 0, "Cell size must not be zero");
+    assert!(bins > 0, "Number of bins must not be zero");
+
+    let size = image.size();
+    let cells = ImgSize::new(size.x/cell_size, size.y/cell_size);
+    let mut histograms = vec![0.0f32; cells.product()*bins];
+    let bin_width = PI/bins as f32;
+
+    for y in 0..cells.y*cell_size {
+        let up = image.line_ref(y.saturating_sub(1));
+        let line = image.line_ref(y);
+        let down = image.line_ref((y + 1).min(size.y - 1));
+
+        for x in 0..cells.x*cell_size {
+            let gx = line[(x + 1).min(size.x - 1)].as_() - line[x.saturating_sub(1)].as_();
+            let gy = down[x].as_() - up[x].as_();
+            let magnitude = (gx*gx + gy*gy).sqrt();
+
+            if magnitude == 0.0 {
+                continue;
+            }
+
+            // Unsigned orientation in [0, PI), split between the two nearest bins
+            let angle = gy.atan2(gx).rem_euclid(PI);
+            let position = angle/bin_width - 0.5;
+            let lower = position.floor();
+            let fraction = position - lower;
+            let first = (lower as isize).rem_euclid(bins as isize) as usize;
+            let second = (first + 1)%bins;
+            let offset = ((y/cell_size)*cells.x + x/cell_size)*bins;
+            histograms[offset + first] += magnitude*(1.0 - fraction);
+            histograms[offset + second] += magnitude*fraction;
+        }
+    }
+
+    (cells, histograms)
+}
+
+fn normalize(values: &mut [f32]) {
+    let norm = (values.iter().map(|value| value*value).sum::<f32>() + 1e-6).sqrt();
+
+    for value in values.iter_mut() {
+        *value /= norm;
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_are_normalized() {
+        let size = ImgSize::new(24, 24);
+        let image = ImgBuf::<f32>::from_vec(size, (0..size.product()).map(|v| ((v*37)%101) as f32).collect());
+        let features = hog(&image, 6, 2, 8);
+        assert_eq!(features.len(), 3*3*2*2*8);
+
+        for block in features.chunks(2*2*8) {
+            let norm: f32 = block.iter().map(|v| v*v).sum::<f32>().sqrt();
+            assert!((norm - 1.0).abs() < 1e-3);
+            assert!(block.iter().all(|&v| v >= 0.0));
+        }
+    }
+
+    #[test]
+    fn gradient_votes_are_split_between_bins() {
+        // horizontal gradient (0 degrees) lies between the first and the 
+        // last bin centered at 10 and 170 degrees
+        let image = ImgBuf::<f32>::from_vec(ImgSize::new(4, 1), vec![0.0, 3.0, 6.0, 9.0]);
+        let (cells, histograms) = cell_histograms(&image, 1, 9);
+        assert_eq!(cells, ImgSize::new(4, 1));
+        assert_eq!(&histograms[9..18], &[3.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 3.0]);
+    }
+
+    #[test]
+    fn small_image_has_no_blocks() {
+        let image = ImgBuf::<u8>::new(ImgSize::new(10, 30));
+        assert!(hog(&image, 8, 2, 9).is_empty());
+    }
+}
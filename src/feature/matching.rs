@@ -0,0 +1,197 @@
+/// Match of descriptor `a[first]` to descriptor `b[second]`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct DescriptorMatch {
+    /// Index into the first descriptor set
+    pub first: usize,
+    /// Index into the second descriptor set
+    pub second: usize,
+    /// Hamming distance of the matched descriptors
+    pub distance: u32,
+}
+
+/// Hamming distance of two 256-bit binary descriptors
+/// 
+/// Descriptors are compared as four 64-bit words, so the distance reduces
+/// to four xor and popcount instructions on CPUs supporting them.
+/// 
+/// # Example
+/// ```
+/// use nanocv::feature::hamming_distance;
+/// let mut b = [0u8; 32];
+/// b[0] = 0b1011;
+/// b[31] = 0x80;
+/// assert_eq!(hamming_distance(&[0; 32], &b), 4);
+/// ```
+pub fn hamming_distance(a: &[u8; 32], b: &[u8; 32]) -> u32 {
+    let mut distance = 0;
+
+    for word in 0..4 {
+        let mut x = [0u8; 8];
+        let mut y = [0u8; 8];
+        x.copy_from_slice(&a[word*8..word*8 + 8]);
+        y.copy_from_slice(&b[word*8..word*8 + 8]);
+        distance += (u64::from_ne_bytes(x) ^ u64::from_ne_bytes(y)).count_ones();
+    }
+
+    distance
+}
+
+/// Brute-force matching of binary descriptors by Hamming distance
+/// 
+/// Every descriptor in `a` is matched to its nearest descriptor in `b`,
+/// matches with distance above `max_hamming` are dropped. With 
+/// `cross_check` enabled, only mutual nearest neighbors are kept (`a[i]`
+/// is the nearest to `b[j]` as well). Matches are returned in the order
+/// of descriptors in `a`, ties are resolved by the lower index.
+/// 
+/// # Example
+/// ```
+/// use nanocv::feature::match_descriptors;
+/// let a = [[0u8; 32], [0xff; 32]];
+/// let mut near = [0xff; 32];
+/// near[3] = 0x0f;
+/// let b = [near, [0x01; 32]];
+/// 
+/// let matches = match_descriptors(&a, &b, 40, true);
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!((matches[0].first, matches[0].second, matches[0].distance), (0, 1, 32));
+/// assert_eq!((matches[1].first, matches[1].second, matches[1].distance), (1, 0, 4));
+/// ```
+pub fn match_descriptors(
+    a: &[[u8; 32]], 
+    b: &[[u8; 32]], 
+    max_hamming: u32, 
+    cross_check: bool
+) -> Vec<DescriptorMatch> {
+    matches(a, b, max_hamming, cross_check, None)
+}
+
+/// Brute-force matching of binary descriptors with Lowe's ratio test
+/// 
+/// Same as [match_descriptors](fn.match_descriptors.html), but a match is 
+/// kept only when its distance is lower than `ratio` times the distance
+/// to the second nearest descriptor in `b`, which rejects ambiguous 
+/// matches in repetitive structures. A sole descriptor in `b` always 
+/// passes the test.
+/// 
+/// # Example
+/// ```
+/// use nanocv::feature::match_descriptors_ratio;
+/// let a = [[0u8; 32]];
+/// let mut first = [0u8; 32];
+/// first[0] = 0x03;
+/// let mut second = [0u8; 32];
+/// second[0] = 0x07;
+/// 
+/// // distances 2 and 3 are too similar to distinguish
+/// assert!(match_descriptors_ratio(&a, &[first, second], 10, false, 0.6).is_empty());
+/// assert_eq!(match_descriptors_ratio(&a, &[first, [0xff; 32]], 10, false, 0.6).len(), 1);
+/// ```
+pub fn match_descriptors_ratio(
+    a: &[[u8; 32]], 
+    b: &[[u8; 32]], 
+    max_hamming: u32, 
+    cross_check: bool,
+    ratio: f32
+) -> Vec<DescriptorMatch> {
+    matches(a, b, max_hamming, cross_check, Some(ratio))
+}
+
+fn matches(
+    a: &[[u8; 32]], 
+    b: &[[u8; 32]], 
+    max_hamming: u32, 
+    cross_check: bool,
+    ratio: Option<f32>
+) -> Vec<DescriptorMatch> {
+    let backward: Vec<Option<usize>> = if cross_check {
+        b.iter().map(|descriptor| nearest(descriptor, a).map(|(index, _, _)| index)).collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut result = Vec::new();
+
+    for (first, descriptor) in a.iter().enumerate() {
+        let (second, distance, runner_up) = match nearest(descriptor, b) {
+            Some(nearest) => nearest,
+            None => continue,
+        };
+
+        if distance > max_hamming {
+            continue;
+        }
+
+        if cross_check && backward[second] != Some(first) {
+            continue;
+        }
+
+        if let (Some(ratio), Some(runner_up)) = (ratio, runner_up) {
+            if distance as f32 >= ratio*runner_up as f32 {
+                continue;
+            }
+        }
+
+        result.push(DescriptorMatch { first, second, distance });
+    }
+
+    result
+}
+
+/// Index and distance of the nearest descriptor in `set` and distance of
+/// the second nearest one (if any)
+fn nearest(descriptor: &[u8; 32], set: &[[u8; 32]]) -> Option<(usize, u32, Option<u32>)> {
+    let mut best: Option<(usize, u32)> = None;
+    let mut runner_up: Option<u32> = None;
+
+    for (index, other) in set.iter().enumerate() {
+        let distance = hamming_distance(descriptor, other);
+
+        match best {
+            Some((_, best_distance)) if distance >= best_distance => {
+                if runner_up.map_or(true, |runner_up| distance < runner_up) {
+                    runner_up = Some(distance);
+                }
+            },
+            _ => {
+                runner_up = best.map(|(_, best_distance)| best_distance);
+                best = Some((index, distance));
+            }
+        }
+    }
+
+    best.map(|(index, distance)| (index, distance, runner_up))
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cross_check_rejects_one_sided_matches() {
+        let a = [[0u8; 32], [0x01; 32]];
+        let b = [[0u8; 32]];
+
+        // both descriptors in `a` prefer the only one in `b`
+        assert_eq!(match_descriptors(&a, &b, 256, false).len(), 2);
+        let matches = match_descriptors(&a, &b, 256, true);
+        assert_eq!(matches, vec![DescriptorMatch { first: 0, second: 0, distance: 0 }]);
+    }
+
+    #[test]
+    fn empty_sets_produce_no_matches() {
+        assert!(match_descriptors(&[], &[[0; 32]], 10, true).is_empty());
+        assert!(match_descriptors_ratio(&[[0; 32]], &[], 10, false, 0.8).is_empty());
+    }
+
+    #[test]
+    fn runner_up_distance_is_tracked() {
+        let mut set = [[0u8; 32]; 3];
+        set[0][0] = 0x0f;
+        set[1][0] = 0x01;
+        set[2][0] = 0x03;
+        assert_eq!(nearest(&[0; 32], &set), Some((1, 1, Some(2))));
+    }
+}
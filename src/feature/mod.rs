@@ -0,0 +1,51 @@
+//! Detection and measurement of image features
+//! 
+//! Available functions:
+//!  * [detect_stars](fn.detect_stars.html) - detection of point sources
+//!    on a smooth background
+//!  * [centroid](fn.centroid.html), [centroid_refined](fn.centroid_refined.html) -
+//!    sub-pixel intensity weighted center of mass
+//!  * [measure_fwhm](fn.measure_fwhm.html) - full width at half maximum 
+//!    and eccentricity of a peak
+//!  * [detect_blobs](fn.detect_blobs.html) - detection of connected
+//!    regions in binary masks filtered by size and shape
+//!  * [orb](fn.orb.html) - oriented BRIEF binary descriptors of keypoints
+//!  * [match_descriptors](fn.match_descriptors.html), 
+//!    [match_descriptors_ratio](fn.match_descriptors_ratio.html) - 
+//!    brute-force matching of binary descriptors
+//!  * [hog](fn.hog.html), [hog_visualization](fn.hog_visualization.html) -
+//!    histogram of oriented gradients descriptor
+//!  * [dog_keypoints](fn.dog_keypoints.html) - scale-invariant keypoints
+//!    detected in difference-of-Gaussians scale space
+//!  * [refine_corners_subpix](fn.refine_corners_subpix.html) - sub-pixel
+//!    refinement of corner positions
+//!  * [good_features_to_track](fn.good_features_to_track.html) - Shi-Tomasi
+//!    corner detection
+//!  * [haar_value](fn.haar_value.html) - Haar-like features evaluated
+//!    over integral images
+
+mod stars;
+mod centroid;
+mod fwhm;
+mod blobs;
+mod orb;
+mod matching;
+mod hog;
+mod dog;
+mod gradient;
+mod subpix;
+mod corners;
+mod haar;
+
+pub use stars::{detect_stars, Star};
+pub use centroid::{centroid, centroid_refined};
+pub use fwhm::{measure_fwhm, Fwhm};
+pub use blobs::{detect_blobs, Blob, BlobParams};
+pub use orb::orb;
+pub use matching::{match_descriptors, match_descriptors_ratio, hamming_distance, DescriptorMatch};
+pub use hog::{hog, hog_visualization};
+pub use dog::{dog_keypoints, ScaleKeypoint};
+pub use subpix::{refine_corners_subpix, TermCriteria};
+pub use corners::good_features_to_track;
+pub use haar::{haar_value, HaarFeature};
+pub(crate) use gradient::gradients;
\ No newline at end of file
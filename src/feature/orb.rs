@@ -0,0 +1,164 @@
+use crate::{Img, ImgBuf, AsPrimitive, geometry::Point, filter::gaussian_blur_new, random::Random};
+
+/// Radius of the circular patch used for orientation and sampling
+const PATCH_RADIUS: isize = 15;
+
+/// Standard deviation of Gaussian smoothing applied before sampling
+const SMOOTHING: f32 = 2.0;
+
+/// Seed of the fixed random sampling pattern
+const PATTERN_SEED: u64 = 0x5EED_0B5E;
+
+/// Computes oriented BRIEF (ORB) binary descriptors of `keypoints`
+/// 
+/// Orientation of each keypoint is estimated from the intensity centroid
+/// of a circular patch with radius 15 pixels, the fixed pattern of 256
+/// point pairs is rotated accordingly and compared on Gaussian smoothed 
+/// image, bit `i` (bit `i % 8` of byte `i / 8`) is set when the first 
+/// point of pair `i` is darker than the second one. Descriptors are thus
+/// invariant to image rotation and monotonic intensity changes.
+/// 
+/// Keypoints are typically provided by a corner detector, pixels outside
+/// of the image are replicated from the nearest border so every keypoint
+/// gets a descriptor, although descriptors of keypoints close to image 
+/// borders are less distinctive.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::orb};
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(40, 40));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = ((position.x*7 + position.y*3) % 50) as u8;
+/// }
+/// 
+/// let descriptors = orb(&image, &[Vec2d::new(20.0, 20.0), Vec2d::new(25.0, 15.0)]);
+/// assert_eq!(descriptors.len(), 2);
+/// assert_ne!(descriptors[0], descriptors[1]);
+/// ```
+pub fn orb<T>(image: &dyn Img<T>, keypoints: &[Point]) -> Vec<[u8; 32]>
+where T: AsPrimitive<f32> {
+    let smooth = gaussian_blur_new(image, SMOOTHING);
+    let pattern = sampling_pattern();
+
+    keypoints.iter().map(|&keypoint| {
+        let (cx, cy) = (keypoint.x.round() as isize, keypoint.y.round() as isize);
+        let angle = orientation(&smooth, cx, cy);
+        let (sin, cos) = angle.sin_cos();
+        let mut descriptor = [0u8; 32];
+
+        let sample = |(dx, dy): (f64, f64)| {
+            let x = (cos*dx - sin*dy).round() as isize;
+            let y = (sin*dx + cos*dy).round() as isize;
+            pixel(&smooth, cx + x, cy + y)
+        };
+
+        for (bit, &(first, second)) in pattern.iter().enumerate() {
+            if sample(first) < sample(second) {
+                descriptor[bit/8] |= 1 << (bit%8);
+            }
+        }
+
+        descriptor
+    }).collect()
+}
+
+/// Angle of the vector from patch center to its intensity centroid
+fn orientation(image: &ImgBuf<f32>, cx: isize, cy: isize) -> f64 {
+    let (mut m10, mut m01) = (0.0f64, 0.0f64);
+
+    for dy in -PATCH_RADIUS..=PATCH_RADIUS {
+        for dx in -PATCH_RADIUS..=PATCH_RADIUS {
+            if dx*dx + dy*dy <= PATCH_RADIUS*PATCH_RADIUS {
+                let value = pixel(image, cx + dx, cy + dy) as f64;
+                m10 += dx as f64*value;
+                m01 += dy as f64*value;
+            }
+        }
+    }
+
+    m01.atan2(m10)
+}
+
+/// 256 point pairs drawn from isotropic Gaussian distribution with standard
+/// deviation of one fifth of the patch size, limited to the patch radius
+/// minus one pixel so rotated points stay within the patch
+fn sampling_pattern() -> Vec<((f64, f64), (f64, f64))> {
+    let mut random = Random::new(PATTERN_SEED);
+    let sigma = (2*PATCH_RADIUS + 1) as f64/5.0;
+    let limit = (PATCH_RADIUS - 1) as f64;
+
+    let mut point = || loop {
+        let (x, y) = (random.normal()*sigma, random.normal()*sigma);
+
+        if x*x + y*y <= limit*limit {
+            return (x, y);
+        }
+    };
+
+    (0..256).map(|_| (point(), point())).collect()
+}
+
+fn pixel(image: &ImgBuf<f32>, x: isize, y: isize) -> f32 {
+    let size = image.size();
+    let x = x.max(0).min(size.x as isize - 1) as usize;
+    let y = y.max(0).min(size.y as isize - 1) as usize;
+    image[(x, y)]
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgLinesMut, ImgSize, Vec2d};
+    use super::*;
+
+    fn hamming(a: &[u8; 32], b: &[u8; 32]) -> u32 {
+        a.iter().zip(b.iter()).map(|(a, b)| (a ^ b).count_ones()).sum()
+    }
+
+    fn texture(size: usize) -> ImgBuf<f32> {
+        let mut random = Random::new(3);
+        let noise = ImgBuf::<f32>::from_vec(
+            ImgSize::new(size, size), 
+            (0..size*size).map(|_| 255.0*random.uniform() as f32).collect()
+        );
+        gaussian_blur_new(&noise, 1.5)
+    }
+
+    #[test]
+    fn descriptor_is_rotation_invariant() {
+        let n = 61;
+        let image = texture(n);
+        let mut rotated = ImgBuf::<f32>::new(ImgSize::new(n, n));
+
+        // rotation by 90 degrees, (x, y) of rotated comes from (y, n - 1 - x)
+        for y in 0..n {
+            for x in 0..n {
+                rotated[(x, y)] = image[(y, n - 1 - x)];
+            }
+        }
+
+        let (cx, cy) = (27.0, 33.0);
+        let original = orb(&image, &[Vec2d::new(cx, cy), Vec2d::new(35.0, 24.0)]);
+        let turned = orb(&rotated, &[Vec2d::new((n - 1) as f64 - cy, cx)]);
+
+        assert!(hamming(&original[0], &turned[0]) < 20);
+        assert!(hamming(&original[1], &turned[0]) > 60);
+    }
+
+    #[test]
+    fn descriptor_is_invariant_to_brightness_changes() {
+        let image = texture(40);
+        let mut brighter = image.clone();
+
+        for line in brighter.lines_mut() {
+            for value in line.iter_mut() {
+                *value = 2.0*(*value) + 30.0;
+            }
+        }
+
+        let keypoints = [Vec2d::new(20.0, 20.0)];
+        assert_eq!(orb(&image, &keypoints), orb(&brighter, &keypoints));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    Img, ImgBuf, ImgSize, ImgRange, Vec2d, AsPrimitive, Range2d,
+    filter::{estimate_background, subtract_background}, 
+    segmentation::{label_components, Connectivity},
+    stats::mad_std_dev,
+};
+
+/// Tile size used for background estimation during star detection
+const BACKGROUND_TILE: usize = 64;
+
+/// Star candidate found by [detect_stars](fn.detect_stars.html)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Star {
+    /// Intensity weighted center of star pixels (background subtracted)
+    pub position: Vec2d<f64>,
+    /// Sum of background subtracted star pixel values
+    pub flux: f64,
+    /// Maximal background subtracted pixel value
+    pub peak: f32,
+    /// Number of star pixels
+    pub area: usize,
+    /// Bounding range of star pixels
+    pub range: ImgRange,
+}
+
+/// Detects stars (point sources) in an image
+/// 
+/// Smooth background is estimated and subtracted from image, noise level
+/// is estimated robustly from the residual image, and pixels brighter than
+/// `threshold_sigma` times the noise level are grouped into 8-connected 
+/// components. Components having at least `min_area` pixels are returned 
+/// as star candidates, sorted by flux in descending order.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::detect_stars};
+/// let mut image = ImgBuf::<u16>::new_init(ImgSize::new(32, 32), 100);
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel += (position.x + 3*position.y) as u16 % 5;
+/// }
+/// 
+/// image[(10, 20)] = 1000;
+/// image[(11, 20)] = 1000;
+/// 
+/// let stars = detect_stars(&image, 5.0, 2);
+/// assert_eq!(stars.len(), 1);
+/// assert!((stars[0].position.x - 10.5).abs() < 0.01);
+/// assert!((stars[0].position.y - 20.0).abs() < 0.01);
+/// assert_eq!(stars[0].area, 2);
+/// ```
+pub fn detect_stars<T: AsPrimitive<f32>>(
+    image: &dyn Img<T>, 
+    threshold_sigma: f32, 
+    min_area: usize
+) -> Vec<Star> {
+    let tile = ImgSize::new(BACKGROUND_TILE, BACKGROUND_TILE);
+    let residual = subtract_background(image, &estimate_background(image, tile));
+
+    let mut values: Vec<f32> = residual.lines().flat_map(|line| line.iter().copied()).collect();
+    let threshold = threshold_sigma*mad_std_dev(&mut values);
+    let size = residual.size();
+    let mut mask = ImgBuf::<bool>::new(size);
+
+    for (position, value) in residual.enumerate_pixels() {
+        mask[(position.x, position.y)] = *value > threshold;
+    }
+
+    let (labels, count) = label_components(&mask, Connectivity::Eight);
+    let mut accumulators = vec![Accumulator::default(); count as usize];
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let label = labels[(x, y)];
+
+            if label > 0 {
+                accumulators[label as usize - 1].add(x, y, residual[(x, y)]);
+            }
+        }
+    }
+
+    let mut stars: Vec<Star> = accumulators.into_iter()
+        .filter(|accumulator| accumulator.area >= std::cmp::max(min_area, 1))
+        .map(Accumulator::star)
+        .collect();
+
+    stars.sort_by(|a, b| b.flux.total_cmp(&a.flux));
+    stars
+}
+
+/// Running sums of star pixel values
+#[derive(Clone, Default)]
+struct Accumulator {
+    flux: f64,
+    weighted: Vec2d<f64>,
+    peak: f32,
+    area: usize,
+    min: Vec2d<usize>,
+    max: Vec2d<usize>,
+}
+
+impl Accumulator {
+    fn add(&mut self, x: usize, y: usize, value: f32) {
+        if self.area == 0 {
+            self.min = Vec2d::new(x, y);
+            self.max = Vec2d::new(x, y);
+            self.peak = value;
+        }
+
+        let weight = value as f64;
+        self.flux += weight;
+        self.weighted = self.weighted + Vec2d::new(x as f64, y as f64)*weight;
+        self.peak = self.peak.max(value);
+        self.area += 1;
+        self.min = Vec2d::new(std::cmp::min(self.min.x, x), std::cmp::min(self.min.y, y));
+        self.max = Vec2d::new(std::cmp::max(self.max.x, x), std::cmp::max(self.max.y, y));
+    }
+
+    fn star(self) -> Star {
+        Star {
+            position: self.weighted*(1.0/self.flux),
+            flux: self.flux,
+            peak: self.peak,
+            area: self.area,
+            range: Range2d::new(
+                self.min.x as isize..self.max.x as isize + 1, 
+                self.min.y as isize..self.max.y as isize + 1,
+            ),
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgLinesMut;
+
+    #[test]
+    fn stars_are_sorted_by_flux_and_filtered_by_area() {
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(40, 30));
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            *pixel = 10.0 + ((position.x*7 + position.y*13) % 3) as f32;
+        }
+
+        for &(x, y) in &[(5, 5), (6, 5), (5, 6), (6, 6)] {
+            image[(x, y)] = 100.0;
+        }
+
+        for &(x, y) in &[(30, 20), (31, 20), (30, 21)] {
+            image[(x, y)] = 200.0;
+        }
+
+        image[(20, 10)] = 500.0;
+
+        let stars = detect_stars(&image, 5.0, 2);
+        assert_eq!(stars.len(), 2);
+        assert_eq!(stars[0].area, 3);
+        assert_eq!(stars[0].range, Range2d::new(30..32, 20..22));
+        assert_eq!(stars[1].area, 4);
+        assert!((stars[1].position - Vec2d::new(5.5, 5.5)).length() < 0.05);
+    }
+}
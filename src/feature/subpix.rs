@@ -0,0 +1,155 @@
+use crate::{AsPrimitive, Img, Vec2d, filter::bilinear, geometry::Point};
+use super::gradient::gradients;
+
+/// Termination criteria of iterative refinement, iterations stop after
+/// `max_iterations` or when the position moves by less than `epsilon`
+/// pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TermCriteria {
+    /// Maximal number of iterations
+    pub max_iterations: usize,
+    /// Minimal position change (in pixels) to continue iterating
+    pub epsilon: f64,
+}
+
+impl Default for TermCriteria {
+    /// At most 30 iterations with 0.01 pixel precision
+    fn default() -> Self {
+        Self { max_iterations: 30, epsilon: 0.01 }
+    }
+}
+
+/// Refines corner positions to sub-pixel precision
+/// 
+/// For the exact corner position `c`, image gradient at each point `q`
+/// near the corner is orthogonal to the vector `q - c` (gradient is zero
+/// in flat regions, perpendicular to the edge on edges). Corner position 
+/// is therefore found iteratively as the least squares solution weighted
+/// by the local gradient covariance within a window of `2*window + 1` 
+/// pixels centered at the current estimate, with gradients interpolated 
+/// bilinearly and weighted by a Gaussian. Corners whose refined position 
+/// would move out of the original window (or whose window contains no 
+/// structure) keep their initial positions.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, feature::{refine_corners_subpix, TermCriteria}};
+/// // checkerboard corner located between pixels
+/// let mut image = ImgBuf::<f32>::new(ImgSize::new(20, 20));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     let (x, y) = (position.x as f32 - 9.5, position.y as f32 - 9.5);
+///     *pixel = if (x > 0.0) == (y > 0.0) { 1.0 } else { 0.0 };
+/// }
+/// 
+/// let corners = refine_corners_subpix(&image, &[Point::new(9.0, 11.0)], 4, TermCriteria::default());
+/// assert!((corners[0] - Point::new(9.5, 9.5)).length() < 0.05);
+/// ```
+pub fn refine_corners_subpix<T: AsPrimitive<f32>>(
+    image: &dyn Img<T>, 
+    corners: &[Point], 
+    window: usize, 
+    criteria: TermCriteria,
+) -> Vec<Point> {
+    let (gx, gy) = gradients(image);
+    let radius = window as f64;
+    let sigma2 = (radius*radius).max(1.0);
+
+    corners.iter().map(|&initial| {
+        let mut corner = initial;
+
+        for _ in 0..criteria.max_iterations.max(1) {
+            let (mut a, mut b, mut c) = (0.0, 0.0, 0.0);
+            let mut rhs = Vec2d::new(0.0, 0.0);
+
+            for dy in -(window as isize)..=(window as isize) {
+                for dx in -(window as isize)..=(window as isize) {
+                    let (dx, dy) = (dx as f64, dy as f64);
+                    let position = corner + Vec2d::new(dx, dy);
+                    let sample = position.cast::<f32>();
+
+                    let (x, y) = match (bilinear(&gx, sample), bilinear(&gy, sample)) {
+                        (Some(x), Some(y)) => (x, y),
+                        _ => continue,
+                    };
+
+                    let weight = (-(dx*dx + dy*dy)/sigma2).exp();
+                    let (xx, xy, yy) = (weight*x*x, weight*x*y, weight*y*y);
+                    a += xx;
+                    b += xy;
+                    c += yy;
+                    rhs = rhs + Vec2d::new(xx*position.x + xy*position.y, xy*position.x + yy*position.y);
+                }
+            }
+
+            let determinant = a*c - b*b;
+
+            if determinant.abs() <= 1e-12*(a*c).abs().max(f64::MIN_POSITIVE) {
+                return initial;
+            }
+
+            let next = Vec2d::new(c*rhs.x - b*rhs.y, a*rhs.y - b*rhs.x)/determinant;
+            let change = (next - corner).length();
+            corner = next;
+
+            if change < criteria.epsilon {
+                break;
+            }
+        }
+
+        let offset = corner - initial;
+
+        if offset.x.abs() > radius || offset.y.abs() > radius {
+            initial
+        } else {
+            corner
+        }
+    }).collect()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgBuf, ImgLinesMut, ImgSize};
+    use super::*;
+
+    /// Checkerboard corner at `center` rendered with 8x8 supersampling
+    fn checkerboard(size: ImgSize, center: Point) -> ImgBuf<f32> {
+        let mut image = ImgBuf::<f32>::new(size);
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            let mut sum = 0.0;
+
+            for sy in 0..8 {
+                for sx in 0..8 {
+                    let x = position.x as f64 + (sx as f64 + 0.5)/8.0 - 0.5 - center.x;
+                    let y = position.y as f64 + (sy as f64 + 0.5)/8.0 - 0.5 - center.y;
+                    sum += if (x > 0.0) == (y > 0.0) { 1.0 } else { 0.0 };
+                }
+            }
+
+            *pixel = 200.0*sum/64.0;
+        }
+
+        image
+    }
+
+    #[test]
+    fn checkerboard_corner_is_refined() {
+        let center = Point::new(20.3, 15.7);
+        let image = checkerboard(ImgSize::new(40, 30), center);
+        let corners = refine_corners_subpix(&image, &[Point::new(21.0, 15.0), center], 5, TermCriteria::default());
+
+        for corner in corners {
+            assert!((corner - center).length() < 0.05, "{:?}", corner);
+        }
+    }
+
+    #[test]
+    fn flat_region_keeps_position() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(20, 20), 7);
+        let corners = refine_corners_subpix(&image, &[Point::new(10.0, 10.0)], 3, TermCriteria::default());
+        assert_eq!(corners, vec![Point::new(10.0, 10.0)]);
+    }
+}
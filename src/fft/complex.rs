@@ -0,0 +1,90 @@
+use std::ops::{Add, Sub, Mul};
+#[cfg(feature = "serde")]
+use serde_derive::{Serialize, Deserialize};
+
+/// Complex number used as pixel type of image spectra
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    /// Create complex number from real and imaginary parts
+    pub fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    /// Complex number with unit magnitude and given `phase` in radians
+    /// ```
+    /// use nanocv::fft::Complex;
+    /// let value = Complex::from_phase(std::f64::consts::FRAC_PI_2);
+    /// assert!((value - Complex::new(0.0, 1.0)).norm() < 1e-12);
+    /// ```
+    pub fn from_phase(phase: f64) -> Self {
+        Self { re: phase.cos(), im: phase.sin() }
+    }
+
+    /// Complex conjugate
+    pub fn conj(self) -> Self {
+        Self { re: self.re, im: -self.im }
+    }
+
+    /// Magnitude (absolute value)
+    /// ```
+    /// use nanocv::fft::Complex;
+    /// assert_eq!(Complex::new(3.0, 4.0).norm(), 5.0);
+    /// ```
+    pub fn norm(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Phase (argument) in radians
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Multiplication by real `factor`
+    pub fn scale(self, factor: f64) -> Self {
+        Self { re: self.re*factor, im: self.im*factor }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self { re, im: 0.0 }
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self { re: self.re + other.re, im: self.im + other.im }
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self { re: self.re - other.re, im: self.im - other.im }
+    }
+}
+
+/// Complex multiplication
+/// ```
+/// use nanocv::fft::Complex;
+/// assert_eq!(Complex::new(1.0, 2.0)*Complex::new(3.0, -1.0), Complex::new(5.0, 5.0));
+/// ```
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            re: self.re*other.re - self.im*other.im,
+            im: self.re*other.im + self.im*other.re,
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Img, ImgMut, ImgLinesMut, ImgBuf, AsPrimitive};
+use super::{forward_2d, inverse_2d};
+
+/// Filters image in frequency domain
+/// 
+/// Image is transformed by 2D Fourier transform, every frequency 
+/// component is multiplied by `gain(fx, fy)`, where `fx` and `fy` are 
+/// frequencies in cycles per pixel in `[-0.5, 0.5)` range, and the result
+/// is transformed back. Image is considered periodic, so content at 
+/// opposite borders influences each other.
+/// 
+/// # Example
+/// Remove periodic horizontal stripes with frequency of 0.25 cycles per pixel
+/// ```
+/// use nanocv::{*, fft::filter_frequency};
+/// let mut image = ImgBuf::<f32>::new_init(ImgSize::new(16, 8), 10.0);
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel += [0.0, 1.0, 0.0, -1.0][position.y % 4];
+/// }
+/// 
+/// let filtered = filter_frequency(&image, |_, fy| if fy.abs() == 0.25 { 0.0 } else { 1.0 });
+/// assert!(filtered.lines().all(|line| line.iter().all(|v| (v - 10.0).abs() < 1e-4)));
+/// ```
+pub fn filter_frequency<T, F>(image: &dyn Img<T>, gain: F) -> ImgBuf<f32>
+where T: AsPrimitive<f64>, F: Fn(f64, f64) -> f64 {
+    let mut spectrum = forward_2d(image);
+    let size = spectrum.size();
+    let fx: Vec<f64> = (0..size.x).map(|k| frequency(k, size.x)).collect();
+
+    for y in 0..size.y {
+        let fy = frequency(y, size.y);
+        let line = spectrum.line_mut(y);
+
+        for x in 0..line.len() {
+            line[x] = line[x].scale(gain(fx[x], fy));
+        }
+    }
+
+    let restored = inverse_2d(&spectrum);
+    let mut output = ImgBuf::<f32>::new(size);
+
+    for (dst, src) in output.lines_mut().zip(restored.lines()) {
+        for x in 0..dst.len() {
+            dst[x] = src[x].re as f32;
+        }
+    }
+
+    output
+}
+
+/// Ideal low-pass gain, passing frequencies up to `cutoff` (in cycles
+/// per pixel) and removing higher frequencies
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, fft::{filter_frequency, ideal_lowpass}};
+/// let image = ImgBuf::<u8>::new_init(ImgSize::new(8, 8), 5);
+/// let filtered = filter_frequency(&image, ideal_lowpass(0.1));
+/// assert!((filtered[(3, 3)] - 5.0).abs() < 1e-5);
+/// ```
+pub fn ideal_lowpass(cutoff: f64) -> impl Fn(f64, f64) -> f64 {
+    move |fx, fy| if fx.hypot(fy) <= cutoff { 1.0 } else { 0.0 }
+}
+
+/// Ideal high-pass gain, complement of [ideal_lowpass](fn.ideal_lowpass.html)
+pub fn ideal_highpass(cutoff: f64) -> impl Fn(f64, f64) -> f64 {
+    let lowpass = ideal_lowpass(cutoff);
+    move |fx, fy| 1.0 - lowpass(fx, fy)
+}
+
+/// Butterworth low-pass gain `1/(1 + (f/cutoff)^(2*order))`, with smooth
+/// transition getting sharper with increasing `order`
+pub fn butterworth_lowpass(cutoff: f64, order: u32) -> impl Fn(f64, f64) -> f64 {
+    move |fx, fy| 1.0/(1.0 + (fx.hypot(fy)/cutoff).powi(2*order as i32))
+}
+
+/// Butterworth high-pass gain, complement of 
+/// [butterworth_lowpass](fn.butterworth_lowpass.html)
+pub fn butterworth_highpass(cutoff: f64, order: u32) -> impl Fn(f64, f64) -> f64 {
+    let lowpass = butterworth_lowpass(cutoff, order);
+    move |fx, fy| 1.0 - lowpass(fx, fy)
+}
+
+/// Gaussian low-pass gain `exp(-f²/(2*cutoff²))`, frequency domain 
+/// equivalent of Gaussian blur with spatial standard deviation 
+/// `1/(2π*cutoff)`
+pub fn gaussian_lowpass(cutoff: f64) -> impl Fn(f64, f64) -> f64 {
+    move |fx, fy| (-(fx*fx + fy*fy)/(2.0*cutoff*cutoff)).exp()
+}
+
+/// Gaussian high-pass gain, complement of 
+/// [gaussian_lowpass](fn.gaussian_lowpass.html)
+pub fn gaussian_highpass(cutoff: f64) -> impl Fn(f64, f64) -> f64 {
+    let lowpass = gaussian_lowpass(cutoff);
+    move |fx, fy| 1.0 - lowpass(fx, fy)
+}
+
+/// Frequency in cycles per pixel of spectrum index `k` of `n` elements
+fn frequency(k: usize, n: usize) -> f64 {
+    if 2*k < n { k as f64/n as f64 } else { k as f64/n as f64 - 1.0 }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn highpass_removes_mean() {
+        let image = ImgBuf::<f32>::from_vec(ImgSize::new(5, 3), (0..15).map(|v| v as f32).collect());
+        let filtered = filter_frequency(&image, gaussian_highpass(0.01));
+        let mean: f32 = filtered.lines().flat_map(|line| line.iter()).sum::<f32>()/15.0;
+        assert!(mean.abs() < 1e-4);
+    }
+
+    #[test]
+    fn frequencies_cover_half_open_range() {
+        let values: Vec<f64> = (0..4).map(|k| frequency(k, 4)).collect();
+        assert_eq!(values, vec![0.0, 0.25, -0.5, -0.25]);
+        assert_eq!(frequency(2, 5), 0.4);
+    }
+
+    #[test]
+    fn butterworth_gain_is_half_at_cutoff() {
+        assert!((butterworth_lowpass(0.2, 3)(0.2, 0.0) - 0.5).abs() < 1e-12);
+        assert!((butterworth_highpass(0.2, 3)(0.0, 0.2) - 0.5).abs() < 1e-12);
+        assert_eq!(ideal_highpass(0.1)(0.0, 0.0), 0.0);
+    }
+}
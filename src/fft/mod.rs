@@ -0,0 +1,32 @@
+//! Fast Fourier transform of images and frequency domain filtering
+//! 
+//! Transforms work for arbitrary sizes, power of two sizes are computed
+//! by radix-2 algorithm, other sizes use Bluestein's algorithm, which is
+//! several times slower. Spectrum uses the usual (unshifted) layout, 
+//! zero frequency is at index `0`, frequencies in cycles per pixel 
+//! are `k/n` for `k < n/2` and `k/n - 1` for the rest.
+//! 
+//! Available functions:
+//!  * [forward](fn.forward.html), [inverse](fn.inverse.html) - 1D 
+//!    in-place transforms
+//!  * [forward_2d](fn.forward_2d.html), [inverse_2d](fn.inverse_2d.html) - 
+//!    2D image transforms
+//!  * [filter_frequency](fn.filter_frequency.html) - filtering of image 
+//!    by gain function of frequency
+//!  * gain functions for the frequency filter: 
+//!    [ideal_lowpass](fn.ideal_lowpass.html), [ideal_highpass](fn.ideal_highpass.html),
+//!    [butterworth_lowpass](fn.butterworth_lowpass.html), 
+//!    [butterworth_highpass](fn.butterworth_highpass.html),
+//!    [gaussian_lowpass](fn.gaussian_lowpass.html), 
+//!    [gaussian_highpass](fn.gaussian_highpass.html)
+
+mod complex;
+mod transform;
+mod filter;
+
+pub use complex::Complex;
+pub use transform::{forward, inverse, forward_2d, inverse_2d};
+pub use filter::{
+    filter_frequency, ideal_lowpass, ideal_highpass, butterworth_lowpass, 
+    butterworth_highpass, gaussian_lowpass, gaussian_highpass,
+};
@@ -0,0 +1,235 @@
+use std::f64::consts::PI;
+use crate::{Img, ImgLinesMut, ImgBuf, AsPrimitive};
+use super::Complex;
+
+/// Computes forward discrete Fourier transform of `data` in place,
+/// `X[k] = sum(x[n]*exp(-2πi*k*n/N))`
+/// 
+/// # Example
+/// ```
+/// use nanocv::fft::{Complex, forward};
+/// let mut data = vec![Complex::from(1.0); 3];
+/// forward(&mut data);
+/// assert!((data[0] - Complex::new(3.0, 0.0)).norm() < 1e-12);
+/// assert!(data[1].norm() < 1e-12 && data[2].norm() < 1e-12);
+/// ```
+pub fn forward(data: &mut [Complex]) {
+    if data.len().is_power_of_two() {
+        radix2(data);
+    } else if data.len() > 1 {
+        bluestein(data);
+    }
+}
+
+/// Computes inverse discrete Fourier transform of `data` in place,
+/// normalized so `inverse` of `forward` restores the original data
+/// 
+/// # Example
+/// ```
+/// use nanocv::fft::{Complex, forward, inverse};
+/// let original: Vec<Complex> = (0..6).map(|v| Complex::new(v as f64, 1.0)).collect();
+/// let mut data = original.clone();
+/// forward(&mut data);
+/// inverse(&mut data);
+/// assert!(data.iter().zip(&original).all(|(&a, &b)| (a - b).norm() < 1e-12));
+/// ```
+pub fn inverse(data: &mut [Complex]) {
+    let scale = 1.0/data.len() as f64;
+
+    for value in data.iter_mut() {
+        *value = value.conj();
+    }
+
+    forward(data);
+
+    for value in data.iter_mut() {
+        *value = value.conj().scale(scale);
+    }
+}
+
+/// Computes 2D discrete Fourier transform of image
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, fft::forward_2d};
+/// let image = ImgBuf::<u8>::new_init(ImgSize::new(4, 3), 2);
+/// let spectrum = forward_2d(&image);
+/// assert!((spectrum[(0, 0)].re - 24.0).abs() < 1e-12);
+/// assert!(spectrum[(1, 2)].norm() < 1e-12);
+/// ```
+pub fn forward_2d<T: AsPrimitive<f64>>(image: &dyn Img<T>) -> ImgBuf<Complex> {
+    let mut spectrum = ImgBuf::<Complex>::new(image.size());
+
+    for (dst, src) in spectrum.lines_mut().zip(image.lines()) {
+        for x in 0..dst.len() {
+            dst[x] = Complex::from(src[x].as_());
+        }
+    }
+
+    transform_2d(&mut spectrum, forward);
+    spectrum
+}
+
+/// Computes inverse 2D discrete Fourier transform of spectrum 
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, fft::{forward_2d, inverse_2d}};
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(3, 2), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+/// let restored = inverse_2d(&forward_2d(&image));
+/// assert!((restored[(2, 1)].re - 6.0).abs() < 1e-12);
+/// ```
+pub fn inverse_2d(spectrum: &dyn Img<Complex>) -> ImgBuf<Complex> {
+    let mut image = ImgBuf::<Complex>::new(spectrum.size());
+
+    for (dst, src) in image.lines_mut().zip(spectrum.lines()) {
+        dst.copy_from_slice(src);
+    }
+
+    transform_2d(&mut image, inverse);
+    image
+}
+
+/// Applies 1D `transform` to all lines and then to all columns of `image`
+fn transform_2d<F: Fn(&mut [Complex])>(image: &mut ImgBuf<Complex>, transform: F) {
+    for line in image.lines_mut() {
+        transform(line);
+    }
+
+    let size = image.size();
+    let mut column = vec![Complex::default(); size.y];
+
+    for x in 0..size.x {
+        for y in 0..size.y {
+            column[y] = image[(x, y)];
+        }
+
+        transform(&mut column);
+
+        for y in 0..size.y {
+            image[(x, y)] = column[y];
+        }
+    }
+}
+
+/// Iterative in-place radix-2 transform, length must be a power of two
+fn radix2(data: &mut [Complex]) {
+    let n = data.len();
+
+    if n <= 1 {
+        return;
+    }
+
+    let bits = n.trailing_zeros();
+
+    for index in 0..n {
+        let reversed = index.reverse_bits() >> (usize::BITS - bits);
+
+        if reversed > index {
+            data.swap(index, reversed);
+        }
+    }
+
+    let mut length = 2;
+
+    while length <= n {
+        let half = length/2;
+        let twiddles: Vec<Complex> = (0..half)
+            .map(|k| Complex::from_phase(-2.0*PI*k as f64/length as f64))
+            .collect();
+
+        for start in (0..n).step_by(length) {
+            for k in 0..half {
+                let even = data[start + k];
+                let odd = data[start + k + half]*twiddles[k];
+                data[start + k] = even + odd;
+                data[start + k + half] = even - odd;
+            }
+        }
+
+        length *= 2;
+    }
+}
+
+/// Bluestein's algorithm expressing transform of arbitrary length 
+/// as a convolution computed by power of two transforms
+fn bluestein(data: &mut [Complex]) {
+    let n = data.len();
+    let m = (2*n - 1).next_power_of_two();
+
+    // chirp exp(-πi*k²/n), k² is reduced modulo 2n to keep precision
+    let chirp: Vec<Complex> = (0..n)
+        .map(|k| Complex::from_phase(-PI*((k*k) % (2*n)) as f64/n as f64))
+        .collect();
+
+    let mut a = vec![Complex::default(); m];
+    let mut b = vec![Complex::default(); m];
+
+    for k in 0..n {
+        a[k] = data[k]*chirp[k];
+    }
+
+    b[0] = chirp[0].conj();
+
+    for k in 1..n {
+        b[k] = chirp[k].conj();
+        b[m - k] = chirp[k].conj();
+    }
+
+    radix2(&mut a);
+    radix2(&mut b);
+
+    for k in 0..m {
+        a[k] = (a[k]*b[k]).conj();
+    }
+
+    // inverse transform via conjugation
+    radix2(&mut a);
+    let scale = 1.0/m as f64;
+
+    for k in 0..n {
+        data[k] = a[k].conj().scale(scale)*chirp[k];
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Direct evaluation of discrete Fourier transform
+    fn naive(data: &[Complex]) -> Vec<Complex> {
+        let n = data.len();
+
+        (0..n)
+            .map(|k| (0..n).fold(Complex::default(), |sum, j| {
+                sum + data[j]*Complex::from_phase(-2.0*PI*(j*k) as f64/n as f64)
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn transform_matches_naive_dft_for_all_lengths() {
+        for n in 1..20 {
+            let data: Vec<Complex> = (0..n)
+                .map(|v| Complex::new(((v*7) % 5) as f64, ((v*3) % 4) as f64 - 1.5))
+                .collect();
+
+            let mut result = data.clone();
+            forward(&mut result);
+
+            for (a, b) in result.iter().zip(naive(&data)) {
+                assert!((*a - b).norm() < 1e-9, "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_data_is_unchanged() {
+        let mut data: Vec<Complex> = Vec::new();
+        forward(&mut data);
+        inverse(&mut data);
+        assert!(data.is_empty());
+    }
+}
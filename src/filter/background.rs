@@ -0,0 +1,183 @@
+use crate::{Img, ImgMut, ImgBuf, ImgSize, AsPrimitive, stats::{median, sigma_clip}};
+
+/// Rejection threshold in standard deviations used for tile statistics
+const KAPPA: f32 = 3.0;
+/// Maximal number of rejection iterations used for tile statistics
+const ITERATIONS: usize = 5;
+
+/// Estimates smooth image background
+/// 
+/// Image is divided into tiles of `tile_size`, a sigma-clipped median
+/// (values further than 3 standard deviations from median are rejected
+/// in up to 5 iterations) is computed for every tile, and background 
+/// is bilinearly interpolated between tile centers.
+/// 
+/// Tile size should be considerably larger than objects in the image
+/// (e.g. stars), so their pixels get rejected as outliers
+/// 
+/// Panics if tile width or height is zero
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::estimate_background};
+/// let mut image = ImgBuf::<u16>::new_init(ImgSize::new(16, 16), 100);
+/// image[(5, 5)] = 60000;
+/// let background = estimate_background(&image, ImgSize::new(8, 8));
+/// assert_eq!(background[(5, 5)], 100.0);
+/// ```
+pub fn estimate_background<T: AsPrimitive<f32>>(
+    image: &dyn Img<T>, 
+    tile_size: ImgSize
+) -> ImgBuf<f32> {
+    assert!(tile_size.x > 0 && tile_size.y > 0, "Tile size must not be zero");
+
+    let size = image.size();
+    let tiles = ImgSize::new(size.x.div_ceil(tile_size.x), size.y.div_ceil(tile_size.y));
+    let mut values = Vec::with_capacity(tile_size.product());
+    let mut grid = ImgBuf::<f32>::new(tiles);
+
+    for ty in 0..tiles.y {
+        for tx in 0..tiles.x {
+            values.clear();
+
+            for line in tile_bounds(ty, tile_size.y, size.y) {
+                let pixels = &image.line_ref(line)[tile_bounds(tx, tile_size.x, size.x)];
+                values.extend(pixels.iter().map(|value| value.as_()));
+            }
+
+            let count = sigma_clip(&mut values, KAPPA, ITERATIONS);
+            grid[(tx, ty)] = median(&mut values[..count]).unwrap_or(0.0);
+        }
+    }
+
+    interpolate_grid(&grid, tile_size, size)
+}
+
+/// Subtracts `background` from `image`, producing `f32` image with 
+/// the same size as `image`
+/// 
+/// Panics if image and background sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::subtract_background};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(2, 1), vec![10, 20]);
+/// let background = ImgBuf::<f32>::new_init(ImgSize::new(2, 1), 15.0);
+/// let result = subtract_background(&image, &background);
+/// assert_eq!(result.line_ref(0), &[-5.0, 5.0]);
+/// ```
+pub fn subtract_background<T: AsPrimitive<f32>>(
+    image: &dyn Img<T>, 
+    background: &dyn Img<f32>
+) -> ImgBuf<f32> {
+    assert_eq!(image.size(), background.size(), "Image and background sizes differ");
+    let mut output = ImgBuf::new(image.size());
+
+    for line in 0..image.height() {
+        let src = image.line_ref(line);
+        let bg = background.line_ref(line);
+        let dst = output.line_mut(line);
+
+        for x in 0..dst.len() {
+            dst[x] = src[x].as_() - bg[x];
+        }
+    }
+
+    output
+}
+
+/// Pixel range of tile with `index` of size `tile` in image of size `length`
+fn tile_bounds(index: usize, tile: usize, length: usize) -> std::ops::Range<usize> {
+    (index*tile)..std::cmp::min(length, (index + 1)*tile)
+}
+
+/// For every pixel of a line of `length` pixels, computes the index of
+/// nearest tile center to the left and interpolation weight of the next one
+fn interpolation_table(tile: usize, length: usize) -> Vec<(usize, f32)> {
+    let count = length.div_ceil(tile);
+
+    let centers: Vec<f32> = (0..count)
+        .map(|index| tile_bounds(index, tile, length))
+        .map(|range| (range.start + range.end) as f32/2.0)
+        .collect();
+
+    (0..length)
+        .map(|pixel| {
+            let position = pixel as f32 + 0.5;
+            let next = centers.partition_point(|&center| center <= position);
+
+            if next == 0 {
+                (0, 0.0)
+            } else if next == count {
+                (count - 1, 0.0)
+            } else {
+                let (left, right) = (centers[next - 1], centers[next]);
+                (next - 1, (position - left)/(right - left))
+            }
+        })
+        .collect()
+}
+
+/// Bilinear interpolation of tile values into image of `size`
+fn interpolate_grid(grid: &ImgBuf<f32>, tile_size: ImgSize, size: ImgSize) -> ImgBuf<f32> {
+    let mut output = ImgBuf::new(size);
+
+    if size.product() == 0 {
+        return output;
+    }
+
+    let columns = interpolation_table(tile_size.x, size.x);
+    let lines = interpolation_table(tile_size.y, size.y);
+    let last = grid.size() - 1;
+
+    for y in 0..size.y {
+        let (ty, wy) = lines[y];
+        let top = grid.line_ref(ty);
+        let bottom = grid.line_ref(std::cmp::min(ty + 1, last.y));
+        let dst = output.line_mut(y);
+
+        for x in 0..size.x {
+            let (tx, wx) = columns[x];
+            let next = std::cmp::min(tx + 1, last.x);
+            let upper = top[tx] + (top[next] - top[tx])*wx;
+            let lower = bottom[tx] + (bottom[next] - bottom[tx])*wx;
+            dst[x] = upper + (lower - upper)*wy;
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgLinesMut;
+
+    #[test]
+    fn linear_gradient_is_preserved_between_tile_centers() {
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(16, 4));
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            *pixel = position.x as f32;
+        }
+
+        let background = estimate_background(&image, ImgSize::new(4, 4));
+
+        for x in 2..14 {
+            assert!((background[(x, 1)] - x as f32).abs() < 1e-4, "x = {}", x);
+        }
+    }
+
+    #[test]
+    fn background_of_empty_image() {
+        let image = ImgBuf::<u8>::new(ImgSize::new(0, 0));
+        assert_eq!(estimate_background(&image, ImgSize::new(4, 4)).size(), ImgSize::new(0, 0));
+    }
+
+    #[test]
+    fn interpolation_table_of_single_tile() {
+        assert_eq!(interpolation_table(4, 3), vec![(0, 0.0), (0, 0.0), (0, 0.0)]);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Img, ImgLinesMut, ImgBuf, pyramid};
+
+/// Multi-band blending of two images using Laplacian pyramids
+/// 
+/// Both images are decomposed into Laplacian pyramids with at most 
+/// `levels` levels, each band is blended using the respective level of
+/// the Gaussian pyramid of `mask` (weight of image `a`, `1 - mask` is the 
+/// weight of image `b`) and the result is collapsed back. Low frequencies 
+/// are thus blended over wide transitions and fine details over narrow 
+/// ones, which avoids both visible seams and ghosting.
+/// 
+/// # Panics
+/// 
+/// Panics when image or mask sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::pyramid_blend};
+/// let a = ImgBuf::<f32>::new_init(ImgSize::new(32, 8), 100.0);
+/// let b = ImgBuf::<f32>::new_init(ImgSize::new(32, 8), 20.0);
+/// let mut mask = ImgBuf::<f32>::new(ImgSize::new(32, 8));
+/// 
+/// // left half from `a`, right half from `b`
+/// for (position, pixel) in mask.enumerate_pixels_mut() {
+///     *pixel = if position.x < 16 { 1.0 } else { 0.0 };
+/// }
+/// 
+/// let blended = pyramid_blend(&a, &b, &mask, 3);
+/// assert!((blended[(0, 4)] - 100.0).abs() < 1.0);
+/// assert!((blended[(31, 4)] - 20.0).abs() < 1.0);
+/// // smooth transition around the mask edge
+/// assert!(blended[(15, 4)] < 100.0 && blended[(16, 4)] > 20.0);
+/// ```
+pub fn pyramid_blend(
+    a: &dyn Img<f32>, 
+    b: &dyn Img<f32>, 
+    mask: &dyn Img<f32>, 
+    levels: usize
+) -> ImgBuf<f32> {
+    assert_eq!(a.size(), b.size(), "Blended image sizes differ");
+    assert_eq!(mask.size(), a.size(), "Mask size differs from blended images");
+
+    let mut bands = pyramid::laplacian(a, levels);
+    let other = pyramid::laplacian(b, levels);
+    let weights = pyramid::gaussian(mask, levels);
+
+    for ((band, other), weights) in bands.iter_mut().zip(other.iter()).zip(weights.iter()) {
+        for ((dst, src), weight) in band.lines_mut().zip(other.lines()).zip(weights.lines()) {
+            for x in 0..dst.len() {
+                dst[x] = weight[x]*dst[x] + (1.0 - weight[x])*src[x];
+            }
+        }
+    }
+
+    pyramid::collapse(&bands)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn constant_mask_selects_single_image() {
+        let size = ImgSize::new(13, 9);
+        let a = ImgBuf::<f32>::from_vec(size, (0..size.product()).map(|v| (v%7) as f32).collect());
+        let b = ImgBuf::<f32>::new_init(size, 50.0);
+
+        let only_a = pyramid_blend(&a, &b, &ImgBuf::new_init(size, 1.0), 3);
+        let only_b = pyramid_blend(&a, &b, &ImgBuf::new_init(size, 0.0), 3);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                assert!((only_a[(x, y)] - a[(x, y)]).abs() < 1e-4);
+                assert!((only_b[(x, y)] - 50.0).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{Img, ImgMut, ImgBuf, Vec2d};
+
+/// Maximal number of solver iterations
+const MAX_ITERATIONS: usize = 5000;
+
+/// Solver stops when the largest pixel update drops below this value
+const TOLERANCE: f32 = 1e-4;
+
+/// Successive over-relaxation factor
+const OVERRELAXATION: f32 = 1.8;
+
+/// Poisson (seamless) cloning of masked `src` pixels into `dst`
+/// 
+/// Pixels of `src` where `mask` is `true` are pasted into `dst` with `src`
+/// top left corner placed at `position`. Instead of copying values, the
+/// Poisson equation is solved over the masked region so the result keeps
+/// gradients of `src` inside the region while matching `dst` values on its
+/// boundary, which hides the seams between the two images. Solved by 
+/// Gauss-Seidel iterations with successive over-relaxation.
+/// 
+/// Masked pixels falling outside of `dst` are ignored, no guidance 
+/// gradient is used across `src` borders.
+/// 
+/// # Panics
+/// 
+/// Panics when mask size differs from `src` size
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::seamless_clone};
+/// let src = ImgBuf::<f32>::new_init(ImgSize::new(5, 5), 200.0);
+/// let mut dst = ImgBuf::<f32>::new_init(ImgSize::new(9, 9), 10.0);
+/// let mut mask = ImgBuf::<bool>::new(ImgSize::new(5, 5));
+/// 
+/// for (position, pixel) in mask.enumerate_pixels_mut() {
+///     *pixel = (1..4).contains(&position.x) && (1..4).contains(&position.y);
+/// }
+/// 
+/// // flat source adapts to the surrounding destination level
+/// seamless_clone(&src, &mut dst, &mask, Vec2d::new(2, 2));
+/// assert!((dst[(4, 4)] - 10.0).abs() < 0.01);
+/// ```
+pub fn seamless_clone(
+    src: &dyn Img<f32>, 
+    dst: &mut dyn ImgMut<f32>, 
+    mask: &dyn Img<bool>, 
+    position: Vec2d<isize>
+) {
+    assert_eq!(mask.size(), src.size(), "Mask size differs from source image");
+
+    let (src_size, dst_size) = (src.size(), dst.size());
+    let mut indices = ImgBuf::<usize>::new_init(dst_size, usize::MAX);
+    let mut unknowns = Vec::new();
+
+    for y in 0..src_size.y {
+        let line = mask.line_ref(y);
+
+        for x in 0..src_size.x {
+            let (dx, dy) = (x as isize + position.x, y as isize + position.y);
+
+            if line[x] && dx >= 0 && dy >= 0 && (dx as usize) < dst_size.x && (dy as usize) < dst_size.y {
+                indices[(dx as usize, dy as usize)] = unknowns.len();
+                unknowns.push(Unknown { 
+                    dst: (dx as usize, dy as usize), 
+                    src: (x, y), 
+                    rhs: 0.0, 
+                    count: 0,
+                    neighbors: Vec::with_capacity(4)
+                });
+            }
+        }
+    }
+
+    // Discrete Poisson equation |N|*f(p) - sum f(q) = sum (g(p) - g(q)), 
+    // known destination values of boundary neighbors move to the right side
+    for unknown in unknowns.iter_mut() {
+        let (x, y) = unknown.dst;
+        let (sx, sy) = unknown.src;
+        let guide = src.line_ref(sy)[sx];
+
+        for &(ox, oy) in [(-1isize, 0isize), (1, 0), (0, -1), (0, 1)].iter() {
+            let (nx, ny) = (x as isize + ox, y as isize + oy);
+
+            if nx < 0 || ny < 0 || nx as usize >= dst_size.x || ny as usize >= dst_size.y {
+                continue;
+            }
+
+            let (nx, ny) = (nx as usize, ny as usize);
+            let (snx, sny) = (sx as isize + ox, sy as isize + oy);
+
+            if snx >= 0 && sny >= 0 && (snx as usize) < src_size.x && (sny as usize) < src_size.y {
+                unknown.rhs += guide - src.line_ref(sny as usize)[snx as usize];
+            }
+
+            match indices[(nx, ny)] {
+                usize::MAX => unknown.rhs += dst.line_ref(ny)[nx],
+                index => unknown.neighbors.push(index),
+            }
+
+            unknown.count += 1;
+        }
+    }
+
+    let mut values: Vec<f32> = unknowns.iter().map(|unknown| src.line_ref(unknown.src.1)[unknown.src.0]).collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut largest = 0.0f32;
+
+        for (index, unknown) in unknowns.iter().enumerate() {
+            if unknown.count == 0 {
+                continue;
+            }
+
+            let sum: f32 = unknown.neighbors.iter().map(|&neighbor| values[neighbor]).sum();
+            let target = (unknown.rhs + sum)/unknown.count as f32;
+            let update = OVERRELAXATION*(target - values[index]);
+            values[index] += update;
+            largest = largest.max(update.abs());
+        }
+
+        if largest < TOLERANCE {
+            break;
+        }
+    }
+
+    for (unknown, value) in unknowns.iter().zip(values.iter()) {
+        dst.line_mut(unknown.dst.1)[unknown.dst.0] = *value;
+    }
+}
+
+/// Masked pixel with unknown resulting value
+struct Unknown {
+    dst: (usize, usize),
+    src: (usize, usize),
+    rhs: f32,
+    count: usize,
+    neighbors: Vec<usize>,
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn source_gradients_are_kept_with_destination_level() {
+        let size = ImgSize::new(12, 10);
+        let mut src = ImgBuf::<f32>::new(size);
+        let mut dst = ImgBuf::<f32>::new(size);
+        let mut mask = ImgBuf::<bool>::new(size);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                // both images share the same ramp, source is offset by a constant
+                dst[(x, y)] = 3.0*x as f32 + y as f32;
+                src[(x, y)] = dst[(x, y)] + 100.0;
+                mask[(x, y)] = (3..9).contains(&x) && (2..8).contains(&y);
+            }
+        }
+
+        let expected = dst.clone();
+        seamless_clone(&src, &mut dst, &mask, Vec2d::new(0, 0));
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                assert!((dst[(x, y)] - expected[(x, y)]).abs() < 0.01, "pixel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn pixels_outside_destination_are_ignored() {
+        let src = ImgBuf::<f32>::new_init(ImgSize::new(4, 4), 50.0);
+        let mask = ImgBuf::<bool>::new_init(ImgSize::new(4, 4), true);
+        let mut dst = ImgBuf::<f32>::new_init(ImgSize::new(3, 3), 5.0);
+        seamless_clone(&src, &mut dst, &mask, Vec2d::new(-2, 1));
+        assert_eq!(dst.line_ref(0), &[5.0, 5.0, 5.0]);
+        assert!((dst[(0, 1)] - 5.0).abs() < 0.01);
+        assert!((dst[(2, 1)] - 5.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive};
+
+/// Filters `input` image by a non-separable 2D `kernel` into `output`
+/// 
+/// Output pixel is the sum of products of kernel elements and input pixels
+/// covered by the kernel, with kernel center (or the element left/above of
+/// center for even sizes) aligned with the output pixel, the same 
+/// alignment as used by [horizontal_filter](fn.horizontal_filter.html). 
+/// Pixels outside of input image are replicated from the nearest border 
+/// pixels, previous content of `output` is overwritten.
+/// 
+/// Separable kernels are much faster applied by horizontal and vertical
+/// filters, this function is meant for kernels that can not be separated,
+/// such as rotated Gabor kernels.
+/// 
+/// Panics if input and output sizes differ or kernel is empty
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::filter_2d};
+/// let input = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![1, 2, 3, 4, 5, 6]);
+/// // difference of the pixel bottom right and top left neighbors
+/// let kernel = ImgBuf::<f32>::from_vec(ImgSize::new(3, 3), vec![
+///     -1.0, 0.0, 0.0, 
+///     0.0, 0.0, 0.0, 
+///     0.0, 0.0, 1.0,
+/// ]);
+/// let mut output = ImgBuf::<f32>::new(input.size());
+/// filter_2d(&input, &mut output, &kernel);
+/// assert_eq!(output.line_ref(0), &[4.0, 5.0, 4.0]);
+/// assert_eq!(output.line_ref(1), &[4.0, 5.0, 4.0]);
+/// ```
+pub fn filter_2d<T: AsPrimitive<f32>>(input: &dyn Img<T>, output: &mut dyn ImgMut<f32>, kernel: &dyn Img<f32>) {
+    let size = input.size();
+    let kernel_size = kernel.size();
+    assert_eq!(output.size(), size, "Input and output image sizes differ");
+    assert!(kernel_size.x > 0 && kernel_size.y > 0, "Kernel must not be empty");
+
+    if size.x == 0 || size.y == 0 {
+        return;
+    }
+
+    let anchor = ((kernel_size.x - 1)/2, (kernel_size.y - 1)/2);
+    let mut padded = vec![0.0f32; size.x + kernel_size.x - 1];
+
+    for y in 0..size.y {
+        let dst = output.line_mut(y);
+
+        for value in dst.iter_mut() {
+            *value = 0.0;
+        }
+
+        for ky in 0..kernel_size.y {
+            let line = (y + ky).saturating_sub(anchor.1).min(size.y - 1);
+            let src = input.line_ref(line);
+
+            // Input line extended by replicated border pixels
+            for (index, value) in padded.iter_mut().enumerate() {
+                *value = src[index.saturating_sub(anchor.0).min(size.x - 1)].as_();
+            }
+
+            let weights = kernel.line_ref(ky);
+
+            for kx in 0..kernel_size.x {
+                let weight = weights[kx];
+
+                if weight == 0.0 {
+                    continue;
+                }
+
+                let shifted = &padded[kx..kx + size.x];
+
+                for x in 0..size.x {
+                    dst[x] += weight*shifted[x];
+                }
+            }
+        }
+    }
+}
+
+/// Filters `input` image by a non-separable 2D `kernel` into a new image,
+/// see [filter_2d](fn.filter_2d.html)
+pub fn filter_2d_new<T: AsPrimitive<f32>>(input: &dyn Img<T>, kernel: &dyn Img<f32>) -> ImgBuf<f32> {
+    let mut output = ImgBuf::<f32>::new(input.size());
+    filter_2d(input, &mut output, kernel);
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn even_kernel_is_anchored_left_above_center() {
+        let input = ImgBuf::<f32>::from_vec(ImgSize::new(3, 3), (1..=9).map(|v| v as f32).collect());
+        let kernel = ImgBuf::<f32>::from_vec(ImgSize::new(2, 2), vec![0.0, 0.0, 0.0, 1.0]);
+        let output = filter_2d_new(&input, &kernel);
+        assert_eq!(output.line_ref(0), &[5.0, 6.0, 6.0]);
+        assert_eq!(output.line_ref(2), &[8.0, 9.0, 9.0]);
+    }
+
+    #[test]
+    fn box_kernel_keeps_constant_image() {
+        let input = ImgBuf::<u8>::new_init(ImgSize::new(5, 4), 7);
+        let kernel = ImgBuf::<f32>::new_init(ImgSize::new(3, 5), 1.0/15.0);
+        let output = filter_2d_new(&input, &kernel);
+        assert!(output.lines().all(|line| line.iter().all(|&v| (v - 7.0).abs() < 1e-5)));
+    }
+}
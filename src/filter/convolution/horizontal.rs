@@ -1,6 +1,6 @@
-use crate::{ImgMut, Img, ImgRange, ImageMapping, Range};
+use crate::{ImgMut, Img, ImgRange, ImageMapping, Range, Error, error::check_same_size};
 use std::{cmp::min};
-use super::plan::{create_filter_plan, FilterIteration};
+use crate::filter::plan::{create_filter_plan, default_anchor, assert_kernel_valid, check_kernel};
 
 /// Horizontal image filter for whole image
 /// 
@@ -9,17 +9,20 @@ use super::plan::{create_filter_plan, FilterIteration};
 /// 
 /// # Arguments
 ///
-/// * `input` - input read-only image
-/// * `output` - output mutable image
-/// * `kernel` - filter kernel, must contain odd number of elements
+/// * `input` - input read-only image with pixels of type `T`
+/// * `output` - output mutable image with accumulator pixels of type `A`
+/// * `kernel` - filter kernel with elements of type `K`, must not be empty,
+///   the center element (or the one left of center for even sized kernels)
+///   is aligned with the output pixel
 /// * `operator` - operator between input, output and kernel, for convolution
-///   filter, use `convolution_operator` function
+///   filter, use `convolution_operator` function, operator may keep 
+///   its own state
 /// 
 /// # Example
 /// ```
 /// use nanocv::{*, filter::{horizontal_filter, convolution_operator}};
 /// 
-/// let input = ImgBuf::from_vec(
+/// let input = ImgBuf::<i8>::from_vec(
 ///     ImgSize::new(3, 2), 
 ///     vec![
 ///         1,  2,  0,
@@ -28,7 +31,7 @@ use super::plan::{create_filter_plan, FilterIteration};
 /// );
 /// 
 /// let mut output = ImgBuf::new_like(&input);
-/// let kernel = [0, 0, -1];
+/// let kernel = [0i8, 0, -1];
 /// horizontal_filter(&input, &mut output, &kernel, convolution_operator);
 /// 
 /// assert_eq!(
@@ -39,55 +42,83 @@ use super::plan::{create_filter_plan, FilterIteration};
 ///     ])
 /// );
 /// ```
-pub fn horizontal_filter<T: Copy, F>(
+/// 
+/// Filter `u8` image with `i16` kernel, accumulating results in `i16`
+/// ```
+/// use nanocv::{*, filter::{horizontal_filter, convolution_operator}};
+/// let input = ImgBuf::<u8>::from_vec(ImgSize::new(3, 1), vec![200, 250, 255]);
+/// let mut output = ImgBuf::<i16>::new(input.size());
+/// horizontal_filter(&input, &mut output, &[1i16, 0, -1], convolution_operator);
+/// assert_eq!(output.line_ref(0), &[50, 55, 5]);
+/// ```
+/// 
+/// Stateful operator counting multiply-accumulate operations
+/// ```
+/// use nanocv::{*, filter::{horizontal_filter, convolution_operator}};
+/// let input = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![1, 2, 3, 4, 5, 6]);
+/// let mut output = ImgBuf::<u16>::new(input.size());
+/// let mut operations = 0;
+/// 
+/// horizontal_filter(&input, &mut output, &[1u16, 1, 1], |src, dst, k| {
+///     operations += src.len();
+///     convolution_operator(src, dst, k)
+/// });
+/// 
+/// assert_eq!(operations, 3*2*3);
+/// ```
+pub fn horizontal_filter<T: Copy, K: Copy, A: Copy, F>(
     input: &dyn Img<T>,
-    output: &mut dyn ImgMut<T>, 
-    kernel: &[T], 
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
     operator: F
-) where F: Fn(&[T], &mut [T], T) {
+) where F: FnMut(&[T], &mut [A], K) {
     let output_range = output.range();
-    horizontal_filter_range(input, output, kernel, input.range(), output_range, operator)
+    let anchor = default_anchor(kernel.len());
+    horizontal_filter_range(input, output, kernel, anchor, input.range(), output_range, operator)
 }
 
 /// Horizontal image filter for specific range
 /// 
 /// # Arguments
 ///
-/// * `input` - input read-only image
-/// * `output` - output mutable image
-/// * `kernel` - filter kernel, must contain odd number of elements
+/// * `input` - input read-only image with pixels of type `T`
+/// * `output` - output mutable image with accumulator pixels of type `A`
+/// * `kernel` - filter kernel with elements of type `K`, must not be empty
+/// * `anchor` - index of kernel element aligned with the output pixel,
+///   `output[x] = sum(kernel[i]*input[x + anchor - i])` for convolution,
+///   anchor `0` makes the filter causal
 /// * `input_range` - input pixel range
 /// * `output_range` - output pixel range
 /// * `operator` - operator between input, output and kernel, for convolution
-///   filter, use `convolution_operator` function
-pub fn horizontal_filter_range<T: Copy, F>(
+///   filter, use `convolution_operator` function, operator may keep 
+///   its own state
+pub fn horizontal_filter_range<T: Copy, K: Copy, A: Copy, F>(
     input: &dyn Img<T>,
-    output: &mut dyn ImgMut<T>, 
-    kernel: &[T], 
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
+    anchor: usize,
     input_range: ImgRange, 
     output_range: ImgRange, 
-    operator: F
-) where F: Fn(&[T], &mut [T], T) {
-    if kernel.len() % 2 == 0 {
-        panic!("Only kernels with odd number of elements are supported");
-    }
+    mut operator: F
+) where F: FnMut(&[T], &mut [A], K) {
+    assert_kernel_valid(kernel.len(), anchor);
 
     let mapping = ImageMapping::new(input_range, output_range, input.range(), output.range());
-    let (l, r) = (mapping.src.x.start, mapping.src.x.end);
+    let (l, r) = (mapping.dst.x.start, mapping.dst.x.end);
 
     let plan = create_filter_plan(
-        input.width(), kernel.len(), 
+        input.width(), kernel.len(), anchor,
         Range::<isize>::from(mapping.src.x), 
         Range::<isize>::from(mapping.dst.x),
     );
 
     for line in mapping.src.y.to_range() {
-        let src = input.line_ref(line as usize);
+        let src = input.line_ref(line);
         let dst = output.line_mut((line as isize + mapping.shift.y) as usize);
 
         for index in 0..plan.len() {
-            let ref bound: &FilterIteration = &plan[index];
-            let value: T = kernel[bound.kernel_index];
+            let bound = &plan[index];
+            let value: K = kernel[bound.kernel_index];
 
             // Convolution with pixels outside image at the beginning
             for outside in 0..min(mapping.src.width(), bound.outside_start) {
@@ -97,7 +128,7 @@ pub fn horizontal_filter_range<T: Copy, F>(
             }
 
             // Convolution with pixels within image
-            {
+            if bound.src_range.length() > 0 {
                 let src = &src[bound.src_range.to_range()];
                 let dst = &mut dst[bound.dst_range.to_range()];
                 operator(src, dst, value);
@@ -114,6 +145,58 @@ pub fn horizontal_filter_range<T: Copy, F>(
     }
 }
 
+/// Horizontal image filter for whole image, returning error instead 
+/// of panicking on invalid arguments
+/// 
+/// Same as `horizontal_filter`, but fails if kernel is empty or 
+/// `input` and `output` sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::{try_horizontal_filter, convolution_operator}};
+/// let input = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+/// let mut output = ImgBuf::<u16>::new(ImgSize::new(2, 2));
+/// let kernel: [u16; 0] = [];
+/// 
+/// assert!(try_horizontal_filter(&input, &mut output, &[1u16], convolution_operator).is_ok());
+/// 
+/// assert_eq!(
+///     try_horizontal_filter(&input, &mut output, &kernel, convolution_operator),
+///     Err(Error::InvalidKernel { length: 0, anchor: 0 })
+/// );
+/// ```
+pub fn try_horizontal_filter<T: Copy, K: Copy, A: Copy, F>(
+    input: &dyn Img<T>,
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
+    operator: F
+) -> Result<(), Error> where F: FnMut(&[T], &mut [A], K) {
+    let output_range = output.range();
+    let anchor = default_anchor(kernel.len());
+    try_horizontal_filter_range(input, output, kernel, anchor, input.range(), output_range, operator)
+}
+
+/// Horizontal image filter for specific range, returning error instead 
+/// of panicking on invalid arguments
+/// 
+/// Same as `horizontal_filter_range`, but fails if kernel is empty, anchor
+/// lies outside kernel or `input_range` and `output_range` are inverted or 
+/// differ in size, parts of ranges outside images are ignored
+pub fn try_horizontal_filter_range<T: Copy, K: Copy, A: Copy, F>(
+    input: &dyn Img<T>,
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
+    anchor: usize,
+    input_range: ImgRange, 
+    output_range: ImgRange, 
+    operator: F
+) -> Result<(), Error> where F: FnMut(&[T], &mut [A], K) {
+    check_kernel(kernel.len(), anchor)?;
+    check_same_size(input_range, output_range)?;
+    horizontal_filter_range(input, output, kernel, anchor, input_range, output_range, operator);
+    Ok(())
+}
+
 // ================================== TESTS ==================================
 
 #[cfg(test)]
@@ -152,13 +235,17 @@ mod tests {
     // ```
     macro_rules! tst {
         ($name: ident, $img: expr, $kernel: expr, $expected: expr) => {
+            tst!($name, $img, $kernel, ($kernel.len() - 1)/2, $expected);
+        };
+        ($name: ident, $img: expr, $kernel: expr, $anchor: expr, $expected: expr) => {
             #[test]
             fn $name() {
                 let input = $img;
                 let mut output = ImgBuf::new_like(&input);
+                let kernel: &[i16] = &$kernel;
         
                 horizontal_filter_range(
-                    &input, &mut output, &$kernel, 
+                    &input, &mut output, kernel, $anchor,
                     input.range(), input.range(), convolution_operator
                 );
         
@@ -206,13 +293,43 @@ mod tests {
         )
     );
 
+    tst!(
+        conv_matrix_4x3_even_kernel_1_1, test_image_1(), [1, 1], 0,
+        ImgBuf::from_vec(
+            ImgSize::new(4, 3), 
+            vec![
+                 2,    3,    5,    7,
+                10,   11,   13,   15,
+                18,   19,   21,   23,
+            ]
+        )
+    );
+
+    tst!(
+        conv_matrix_4x3_causal_kernel_1_2_3, test_image_1(), [1, 2, 3], 0,
+        ImgBuf::from_vec(
+            ImgSize::new(4, 3), 
+            vec![
+                 6,    7,   10,   16,
+                30,   31,   34,   40,
+                54,   55,   58,   64,
+            ]
+        )
+    );
+
+    tst!(
+        conv_matrix_1x1_kernel_longer_than_image, 
+        ImgBuf::from_vec(ImgSize::new(1, 1), vec![2]), [1, 1, 1, 1, 1],
+        ImgBuf::from_vec(ImgSize::new(1, 1), vec![10])
+    );
+
     #[test]
     fn conv_identity_to_larger_image() {
         let input = test_image_1();
         let mut output = ImgBuf::new(ImgSize::new(5, 4));
 
         horizontal_filter_range(
-            &input, &mut output, &[1], 
+            &input, &mut output, &[1i16], 0,
             input.range(), input.range(), convolution_operator
         );
 
@@ -236,7 +353,7 @@ mod tests {
         let mut output = ImgBuf::new(ImgSize::new(5, 4));
 
         horizontal_filter_range(
-            &input, &mut output, &[1], 
+            &input, &mut output, &[1i16], 0,
             input.range(), 
             input.range() + Vec2d::new(0, 1), 
             convolution_operator
@@ -262,7 +379,7 @@ mod tests {
         let mut output = ImgBuf::new(ImgSize::new(5, 4));
 
         horizontal_filter_range(
-            &input, &mut output, &[1], 
+            &input, &mut output, &[1i16], 0,
             input.range(), 
             input.range() + Vec2d::new(0, 2), 
             convolution_operator
@@ -288,7 +405,7 @@ mod tests {
         let mut output = ImgBuf::new(ImgSize::new(5, 4));
 
         horizontal_filter_range(
-            &input, &mut output, &[1], 
+            &input, &mut output, &[1i16], 0,
             input.range(), 
             input.range() + Vec2d::new(0, -1), 
             convolution_operator
@@ -314,7 +431,7 @@ mod tests {
         let mut output = ImgBuf::new(ImgSize::new(5, 4));
 
         horizontal_filter_range(
-            &input, &mut output, &[1], 
+            &input, &mut output, &[1i16], 0,
             input.range(), 
             input.range() + Vec2d::new(1, 0), 
             convolution_operator
@@ -341,7 +458,7 @@ mod tests {
         let mut output = ImgBuf::new(ImgSize::new(5, 4));
 
         horizontal_filter_range(
-            &input, &mut output, &[1], 
+            &input, &mut output, &[1i16], 0,
             input.range(), 
             input.range() + Vec2d::new(2, 0), 
             convolution_operator
@@ -368,7 +485,7 @@ mod tests {
         let mut output = ImgBuf::new(ImgSize::new(5, 4));
 
         horizontal_filter_range(
-            &input, &mut output, &[1], 
+            &input, &mut output, &[1i16], 0,
             input.range(), 
             input.range() + Vec2d::new(-1, 0), 
             convolution_operator
@@ -387,4 +504,19 @@ mod tests {
             )            
         );
     }      
-}
\ No newline at end of file
+
+    #[test]
+    fn conv_replicated_border_output_moved_right() {
+        let input = ImgBuf::<i16>::from_vec(ImgSize::new(2, 1), vec![1, 2]);
+        let mut output = ImgBuf::new(ImgSize::new(3, 1));
+
+        horizontal_filter_range(
+            &input, &mut output, &[1i16, 1, 1], 1,
+            input.range(), 
+            input.range() + Vec2d::new(1, 0), 
+            convolution_operator
+        );
+
+        assert_eq!(output, ImgBuf::from_vec(ImgSize::new(3, 1), vec![0, 4, 5]));
+    }
+}
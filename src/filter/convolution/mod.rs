@@ -1,8 +1,17 @@
 mod horizontal;
 mod vertical;
-mod plan;
 mod operator;
+mod filter2d;
 
-pub use horizontal::{horizontal_filter_range, horizontal_filter};
-pub use vertical::{vertical_filter_range, vertical_filter};
-pub use operator::convolution_operator;
\ No newline at end of file
+pub use horizontal::{
+    horizontal_filter_range, horizontal_filter, 
+    try_horizontal_filter_range, try_horizontal_filter
+};
+
+pub use vertical::{
+    vertical_filter_range, vertical_filter, 
+    try_vertical_filter_range, try_vertical_filter
+};
+
+pub use operator::convolution_operator;
+pub use filter2d::{filter_2d, filter_2d_new};
\ No newline at end of file
@@ -2,12 +2,15 @@ use std::{cmp::min, ops::{Mul, Add}};
 
 /// Convolution operator function
 /// 
-/// For every index `i` in src, computes `dst[i] += kernel*src[i]`
+/// For every index `i` in src, computes `dst[i] += kernel*src[i]`,
+/// both kernel and source values are converted into the accumulator
+/// type of `dst` before multiplication, so for example `u8` pixels
+/// can be convolved with `i32` kernel into `i32` accumulator
 /// 
 /// # Arguments
 ///
 /// * `src` - source read-only slice
-/// * `dst` - destination mutable slice
+/// * `dst` - destination mutable slice (accumulator)
 /// * `kernel` - kernel value
 /// 
 /// # Example
@@ -19,18 +22,32 @@ use std::{cmp::min, ops::{Mul, Add}};
 /// convolution_operator(&input, &mut output, kernel);
 /// assert_eq!(output, [4 + 3*1, 5 + 3*2, 6 + 3*3]);
 /// ```
+/// 
+/// Accumulate `u8` pixels multiplied by `i32` kernel into `i32` values
+/// ```
+/// use nanocv::{*, filter::convolution_operator};
+/// let input: [u8; 3] = [200, 250, 255];
+/// let mut output: [i32; 3] = [0, 0, 0];
+/// convolution_operator(&input, &mut output, -2i32);
+/// assert_eq!(output, [-400, -500, -510]);
+/// ```
 #[inline(never)]
-pub fn convolution_operator<T>(
+pub fn convolution_operator<T, K, A>(
     src: &[T], 
-    dst: &mut [T],
-    kernel: T
+    dst: &mut [A],
+    kernel: K
 )
-where T: Add<T, Output=T> + Mul<T, Output=T> + Copy {
+where 
+    T: Into<A> + Copy, 
+    K: Into<A> + Copy,
+    A: Add<A, Output=A> + Mul<A, Output=A> + Copy 
+{
     let max = min(src.len(), dst.len());
     let src = &src[0..max];
     let dst = &mut dst[0..max];
+    let kernel: A = kernel.into();
 
     for index in 0..max {
-        dst[index] = dst[index] + kernel*src[index];
+        dst[index] = dst[index] + kernel*src[index].into();
     }
 }
\ No newline at end of file
@@ -1,54 +1,82 @@
-use crate::{ImgMut, Img, ImgRange, ImageMapping, Range};
+use crate::{ImgMut, Img, ImgRange, ImageMapping, Range, Error, error::check_same_size};
 use std::{cmp::min};
-use super::plan::{create_filter_plan, FilterIteration};
+use crate::filter::plan::{create_filter_plan, default_anchor, assert_kernel_valid, check_kernel};
 
-pub fn vertical_filter<T: Copy, F>(
+/// Vertical image filter for whole image
+/// 
+/// Input image is considered infinite, replicating values of 
+/// nearest existing pixels.
+/// 
+/// # Arguments
+///
+/// * `input` - input read-only image with pixels of type `T`
+/// * `output` - output mutable image with accumulator pixels of type `A`
+/// * `kernel` - filter kernel with elements of type `K`, must not be empty,
+///   the center element (or the one left of center for even sized kernels)
+///   is aligned with the output pixel
+/// * `operator` - operator between input, output and kernel, for convolution
+///   filter, use `convolution_operator` function, operator may keep 
+///   its own state
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::{vertical_filter, convolution_operator}};
+/// let input = ImgBuf::<u8>::from_vec(ImgSize::new(1, 3), vec![200, 250, 255]);
+/// let mut output = ImgBuf::<i16>::new(input.size());
+/// vertical_filter(&input, &mut output, &[1i16, 0, -1], convolution_operator);
+/// assert_eq!(output, ImgBuf::from_vec(input.size(), vec![50, 55, 5]));
+/// ```
+pub fn vertical_filter<T: Copy, K: Copy, A: Copy, F>(
     input: &dyn Img<T>,
-    output: &mut dyn ImgMut<T>, 
-    kernel: &[T], 
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
     operator: F
-) where F: Fn(&[T], &mut [T], T) {
+) where F: FnMut(&[T], &mut [A], K) {
     let output_range = output.range();
-    vertical_filter_range(input, output, kernel, input.range(), output_range, operator)
+    let anchor = default_anchor(kernel.len());
+    vertical_filter_range(input, output, kernel, anchor, input.range(), output_range, operator)
 }
 
 /// Vertical image filter for specific range
 /// 
 /// # Arguments
 ///
-/// * `input` - input read-only image
-/// * `output` - output mutable image
-/// * `kernel` - filter kernel, must contain odd number of elements
+/// * `input` - input read-only image with pixels of type `T`
+/// * `output` - output mutable image with accumulator pixels of type `A`
+/// * `kernel` - filter kernel with elements of type `K`, must not be empty
+/// * `anchor` - index of kernel element aligned with the output pixel,
+///   `output[y] = sum(kernel[i]*input[y + anchor - i])` for convolution,
+///   anchor `0` makes the filter causal
 /// * `input_range` - input pixel range
 /// * `output_range` - output pixel range
 /// * `operator` - operator between input, output and kernel, for convolution
-///   filter, use `convolution_operator` function
-pub fn vertical_filter_range<T: Copy, F>(
+///   filter, use `convolution_operator` function, operator may keep 
+///   its own state
+pub fn vertical_filter_range<T: Copy, K: Copy, A: Copy, F>(
     input: &dyn Img<T>,
-    output: &mut dyn ImgMut<T>, 
-    kernel: &[T], 
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
+    anchor: usize,
     input_range: ImgRange, 
     output_range: ImgRange, 
-    operator: F
-) where F: Fn(&[T], &mut [T], T) {
-    if kernel.len() % 2 == 0 {
-        panic!("Only kernels with odd number of elements are supported");
-    }
+    mut operator: F
+) where F: FnMut(&[T], &mut [A], K) {
+    assert_kernel_valid(kernel.len(), anchor);
 
     let mapping = ImageMapping::new(input_range, output_range, input.range(), output.range());
 
     let plan = create_filter_plan(
-        input.height(), kernel.len(), 
+        input.height(), kernel.len(), anchor,
         Range::<isize>::from(mapping.src.y), 
         Range::<isize>::from(mapping.dst.y),
     );
 
     let columns = mapping.src.x;
-    let (t, b) = (mapping.src.y.start, mapping.src.y.end);
+    let (t, b) = (mapping.dst.y.start, mapping.dst.y.end);
     
     for index in 0..plan.len() {
-        let ref bound: &FilterIteration = &plan[index];
-        let value: T = kernel[bound.kernel_index];
+        let bound = &plan[index];
+        let value: K = kernel[bound.kernel_index];
 
         // Convolution with pixels outside image at the beginning
         for extend in 0..min(mapping.src.height(), bound.outside_start) {
@@ -74,6 +102,58 @@ pub fn vertical_filter_range<T: Copy, F>(
     }
 }
 
+/// Vertical image filter for whole image, returning error instead 
+/// of panicking on invalid arguments
+/// 
+/// Same as `vertical_filter`, but fails if kernel is empty or 
+/// `input` and `output` sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::{try_vertical_filter, convolution_operator}};
+/// let input = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+/// let mut output = ImgBuf::<u16>::new(ImgSize::new(2, 2));
+/// let kernel: [u16; 0] = [];
+/// 
+/// assert!(try_vertical_filter(&input, &mut output, &[1u16], convolution_operator).is_ok());
+/// 
+/// assert_eq!(
+///     try_vertical_filter(&input, &mut output, &kernel, convolution_operator),
+///     Err(Error::InvalidKernel { length: 0, anchor: 0 })
+/// );
+/// ```
+pub fn try_vertical_filter<T: Copy, K: Copy, A: Copy, F>(
+    input: &dyn Img<T>,
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
+    operator: F
+) -> Result<(), Error> where F: FnMut(&[T], &mut [A], K) {
+    let output_range = output.range();
+    let anchor = default_anchor(kernel.len());
+    try_vertical_filter_range(input, output, kernel, anchor, input.range(), output_range, operator)
+}
+
+/// Vertical image filter for specific range, returning error instead 
+/// of panicking on invalid arguments
+/// 
+/// Same as `vertical_filter_range`, but fails if kernel is empty, anchor
+/// lies outside kernel or `input_range` and `output_range` are inverted or 
+/// differ in size, parts of ranges outside images are ignored
+pub fn try_vertical_filter_range<T: Copy, K: Copy, A: Copy, F>(
+    input: &dyn Img<T>,
+    output: &mut dyn ImgMut<A>, 
+    kernel: &[K], 
+    anchor: usize,
+    input_range: ImgRange, 
+    output_range: ImgRange, 
+    operator: F
+) -> Result<(), Error> where F: FnMut(&[T], &mut [A], K) {
+    check_kernel(kernel.len(), anchor)?;
+    check_same_size(input_range, output_range)?;
+    vertical_filter_range(input, output, kernel, anchor, input_range, output_range, operator);
+    Ok(())
+}
+
 // ================================== TESTS ==================================
 
 #[cfg(test)]
@@ -112,13 +192,17 @@ mod tests {
     // ```
     macro_rules! tst {
         ($name: ident, $img: expr, $kernel: expr, $expected: expr) => {
+            tst!($name, $img, $kernel, ($kernel.len() - 1)/2, $expected);
+        };
+        ($name: ident, $img: expr, $kernel: expr, $anchor: expr, $expected: expr) => {
             #[test]
             fn $name() {
                 let input = $img;
                 let mut output = ImgBuf::new_like(&input);
+                let kernel: &[i16] = &$kernel;
         
                 vertical_filter_range(
-                    &input, &mut output, &$kernel, 
+                    &input, &mut output, kernel, $anchor,
                     input.range(), input.range(), convolution_operator
                 );
         
@@ -165,4 +249,57 @@ mod tests {
             ],
         )    
     );    
-}
\ No newline at end of file
+
+    tst!(
+        conv_matrix_4x3_even_kernel_1_1, test_image_1(), [1, 1], 0,
+        ImgBuf::from_vec(
+            ImgSize::new(4, 3), 
+            vec![
+                 2,   4,   6,   8,
+                 6,   8,  10,  12,
+                14,  16,  18,  20,
+            ],
+        )    
+    );
+
+    tst!(
+        conv_matrix_4x3_causal_kernel_1_2_3, test_image_1(), [1, 2, 3], 0,
+        ImgBuf::from_vec(
+            ImgSize::new(4, 3), 
+            vec![
+                 6,  12,  18,  24,
+                10,  16,  22,  28,
+                22,  28,  34,  40,
+            ],
+        )    
+    );
+
+    #[test]
+    #[should_panic]
+    fn anchor_outside_kernel_panics() {
+        let input = test_image_1();
+        let mut output = ImgBuf::new_like(&input);
+
+        vertical_filter_range(
+            &input, &mut output, &[1i16, 1], 2,
+            input.range(), input.range(), convolution_operator
+        );
+    }
+
+    #[test]
+    fn stateful_operator_is_called_for_every_kernel_element() {
+        let input = test_image_1();
+        let mut output = ImgBuf::new_like(&input);
+        let mut kernel_values = Vec::new();
+
+        vertical_filter(&input, &mut output, &[1i16, 2, 3], |src, dst, k| {
+            kernel_values.push(k);
+            convolution_operator(src, dst, k)
+        });
+
+        kernel_values.sort();
+        kernel_values.dedup();
+        assert_eq!(kernel_values, vec![1, 2, 3]);
+        assert_eq!(output.line_ref(1), &[22, 28, 34, 40]);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive, geometry::round_as, stats::{median, mad_std_dev}};
+
+/// Detects hot and dead pixels
+/// 
+/// Every pixel is compared to the median of its 8 neighbors, pixel is
+/// flagged as defective when the difference is larger than `kappa` times
+/// the noise level, which is estimated robustly from the differences
+/// of all image pixels (using median absolute deviation). Returns a defect
+/// map with the same size as `image`, where defective pixels are `true`.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::detect_defective_pixels};
+/// let mut image = ImgBuf::<u16>::new_init(ImgSize::new(5, 5), 1000);
+/// image[(1, 3)] = 65535;
+/// image[(3, 2)] = 0;
+/// let defects = detect_defective_pixels(&image, 5.0);
+/// let flagged: Vec<_> = defects.enumerate_pixels()
+///     .filter(|(_, &defect)| defect)
+///     .map(|(position, _)| (position.x, position.y))
+///     .collect();
+/// assert_eq!(flagged, vec![(3, 2), (1, 3)]);
+/// ```
+pub fn detect_defective_pixels<T: AsPrimitive<f32>>(
+    image: &dyn Img<T>, 
+    kappa: f32
+) -> ImgBuf<bool> {
+    let size = image.size();
+    let mut residuals = ImgBuf::<f32>::new(size);
+    let mut neighbors = Vec::with_capacity(8);
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            neighbors.clear();
+
+            for_neighbors(image, x, y, |value| neighbors.push(value.as_()));
+            let center = image.line_ref(y)[x].as_();
+            residuals[(x, y)] = median(&mut neighbors).map(|m| center - m).unwrap_or(0.0);
+        }
+    }
+
+    let mut values: Vec<f32> = residuals.lines().flat_map(|line| line.iter().copied()).collect();
+    let threshold = kappa*mad_std_dev(&mut values);
+    let mut defects = ImgBuf::<bool>::new(size);
+
+    for y in 0..size.y {
+        let src = residuals.line_ref(y);
+        let dst = defects.line_mut(y);
+
+        for x in 0..dst.len() {
+            dst[x] = src[x].abs() > threshold;
+        }
+    }
+
+    defects
+}
+
+/// Replaces pixels flagged in `defects` by the mean value of their
+/// non-defective neighbors
+/// 
+/// Pixels without any non-defective neighbor are left untouched,
+/// integer pixel values are rounded to the nearest integer
+/// 
+/// Panics if image and defect map sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::correct_defective_pixels};
+/// let mut image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 1), vec![10, 255, 13]);
+/// let defects = ImgBuf::<bool>::from_vec(ImgSize::new(3, 1), vec![false, true, false]);
+/// correct_defective_pixels(&mut image, &defects);
+/// assert_eq!(image.line_ref(0), &[10, 12, 13]);
+/// ```
+pub fn correct_defective_pixels<T>(image: &mut dyn ImgMut<T>, defects: &dyn Img<bool>) 
+where T: AsPrimitive<f64>, f64: AsPrimitive<T> {
+    assert_eq!(image.size(), defects.size(), "Image and defect map sizes differ");
+    let size = image.size();
+    let mut corrections = Vec::new();
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            if !defects.line_ref(y)[x] {
+                continue;
+            }
+
+            let (mut sum, mut count) = (0.0, 0);
+
+            for ny in y.saturating_sub(1)..std::cmp::min(y + 2, size.y) {
+                for nx in x.saturating_sub(1)..std::cmp::min(x + 2, size.x) {
+                    if !defects.line_ref(ny)[nx] {
+                        sum += image.line_ref(ny)[nx].as_();
+                        count += 1;
+                    }
+                }
+            }
+
+            if count > 0 {
+                corrections.push((x, y, round_as(sum/count as f64)));
+            }
+        }
+    }
+
+    for (x, y, value) in corrections {
+        image.line_mut(y)[x] = value;
+    }
+}
+
+/// Calls `function` for all neighbors of pixel `(x, y)` inside the image
+fn for_neighbors<T: Copy, F: FnMut(T)>(image: &dyn Img<T>, x: usize, y: usize, mut function: F) {
+    let size = image.size();
+
+    for ny in y.saturating_sub(1)..std::cmp::min(y + 2, size.y) {
+        let line = image.line_ref(ny);
+
+        for nx in x.saturating_sub(1)..std::cmp::min(x + 2, size.x) {
+            if nx != x || ny != y {
+                function(line[nx]);
+            }
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn float_pixels_are_not_rounded() {
+        let mut image = ImgBuf::<f32>::from_vec(ImgSize::new(3, 1), vec![1.0, 9.0, 2.0]);
+        let defects = ImgBuf::<bool>::from_vec(ImgSize::new(3, 1), vec![false, true, false]);
+        correct_defective_pixels(&mut image, &defects);
+        assert_eq!(image.line_ref(0), &[1.0, 1.5, 2.0]);
+    }
+
+    #[test]
+    fn noise_is_not_flagged() {
+        let values = (0..64).map(|index| 100 + [0, 3, 1, 4, 2][index % 5]).collect();
+        let mut image = ImgBuf::<i32>::from_vec(ImgSize::new(8, 8), values);
+        image[(4, 4)] = 400;
+        let defects = detect_defective_pixels(&image, 6.0);
+        let count = defects.lines().flat_map(|line| line.iter()).filter(|&&d| d).count();
+        assert_eq!(count, 1);
+        assert!(defects[(4, 4)]);
+    }
+
+    #[test]
+    fn isolated_defects_are_not_corrected() {
+        let mut image = ImgBuf::<u8>::new_init(ImgSize::new(1, 1), 7);
+        let defects = ImgBuf::<bool>::new_init(ImgSize::new(1, 1), true);
+        correct_defective_pixels(&mut image, &defects);
+        assert_eq!(image[(0, 0)], 7);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{Img, ImgMut, ImgLinesMut, ImgBuf};
+
+/// Edge preserving smoothing by Perona-Malik anisotropic diffusion
+/// 
+/// In each of `iterations`, every pixel is updated by `lambda` times the
+/// sum of differences to its 4 neighbors, each weighted by conduction 
+/// coefficient `exp(-(difference/kappa)²)`, so smoothing is suppressed 
+/// across edges with contrast much larger than `kappa`. The `lambda` 
+/// should not exceed `0.25` for the diffusion to be stable. No flux 
+/// passes through image borders.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::anisotropic_diffusion};
+/// // step edge with small noise on both sides
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(6, 1), vec![
+///     1.0, 0.0, 1.0, 100.0, 101.0, 100.0
+/// ]);
+/// let smooth = anisotropic_diffusion(&image, 20, 5.0, 0.25);
+/// let line = smooth.line_ref(0);
+/// assert!((line[0] - line[2]).abs() < 0.1);
+/// assert!(line[3] - line[2] > 95.0);
+/// ```
+pub fn anisotropic_diffusion(
+    image: &dyn Img<f32>, 
+    iterations: usize, 
+    kappa: f32, 
+    lambda: f32
+) -> ImgBuf<f32> {
+    let size = image.size();
+    let mut current = ImgBuf::<f32>::new(size);
+    let mut next = ImgBuf::<f32>::new(size);
+
+    for (dst, src) in current.lines_mut().zip(image.lines()) {
+        dst.copy_from_slice(src);
+    }
+
+    let conduction = |difference: f32| (-(difference/kappa).powi(2)).exp();
+
+    for _ in 0..iterations {
+        for y in 0..size.y {
+            let up = current.line_ref(y.saturating_sub(1));
+            let line = current.line_ref(y);
+            let down = current.line_ref(std::cmp::min(y + 1, size.y - 1));
+            let dst = next.line_mut(y);
+
+            for x in 0..size.x {
+                let center = line[x];
+
+                let differences = [
+                    up[x] - center,
+                    down[x] - center,
+                    line[x.saturating_sub(1)] - center,
+                    line[std::cmp::min(x + 1, size.x - 1)] - center,
+                ];
+
+                let flux: f32 = differences.iter().map(|&d| conduction(d)*d).sum();
+                dst[x] = center + lambda*flux;
+            }
+        }
+
+        std::mem::swap(&mut current, &mut next);
+    }
+
+    current
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn diffusion_preserves_mean() {
+        let values = (0..30).map(|v| ((v*13) % 7) as f32).collect();
+        let image = ImgBuf::<f32>::from_vec(ImgSize::new(6, 5), values);
+        let smooth = anisotropic_diffusion(&image, 10, 3.0, 0.2);
+        let sum = |image: &ImgBuf<f32>| image.lines().flat_map(|line| line.iter()).sum::<f32>();
+        assert!((sum(&image) - sum(&smooth)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn zero_iterations_copy_image() {
+        let image = ImgBuf::<f32>::from_vec(ImgSize::new(2, 1), vec![1.0, 2.0]);
+        assert_eq!(anisotropic_diffusion(&image, 0, 1.0, 0.25), image);
+    }
+}
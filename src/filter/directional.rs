@@ -0,0 +1,119 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive, Vec2d};
+use super::bilinear;
+
+/// Convolves `input` image with 1D `kernel` oriented along a line at 
+/// `angle` (in radians, measured from the x axis towards the y axis) into 
+/// `output` image
+/// 
+/// Kernel element `i` is applied to the input sampled at distance 
+/// `i - kernel.len()/2` pixels from the output pixel along the line, 
+/// samples between pixels are interpolated bilinearly and positions 
+/// outside the image are clamped to the nearest border pixel. Angle `0` 
+/// gives the same result as [horizontal_filter](fn.horizontal_filter.html)
+/// with the same kernel. Averaging kernel simulates linear motion blur,
+/// derivative kernels enhance edges perpendicular to the line.
+/// 
+/// Panics if input and output sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::directional_filter};
+/// let mut input = ImgBuf::<u8>::new_init(ImgSize::new(9, 9), 0);
+/// input[(4, 4)] = 90;
+/// let mut output = ImgBuf::<f32>::new(input.size());
+/// // Diagonal motion blur smears the point along the diagonal only
+/// directional_filter(&input, &mut output, &[1.0/3.0; 3], std::f32::consts::FRAC_PI_4);
+/// assert!(output[(3, 3)] > 0.0 && output[(5, 5)] > 0.0);
+/// assert!(output[(5, 3)] == 0.0 && output[(3, 5)] == 0.0);
+/// ```
+pub fn directional_filter<T: AsPrimitive<f64>>(
+    input: &dyn Img<T>, 
+    output: &mut dyn ImgMut<f32>, 
+    kernel: &[f32], 
+    angle: f32
+) {
+    assert_eq!(input.size(), output.size(), "Input and output sizes differ");
+    let size = input.size();
+
+    if size.x == 0 || size.y == 0 {
+        return;
+    }
+
+    let anchor = (kernel.len()/2) as f32;
+    let (max_x, max_y) = ((size.x - 1) as f32, (size.y - 1) as f32);
+
+    // Offsets of the kernel taps relative to the output pixel
+    let taps: Vec<(Vec2d<f32>, f32)> = kernel.iter().enumerate()
+        .map(|(i, &k)| {
+            let distance = i as f32 - anchor;
+            (Vec2d::new(distance*angle.cos(), distance*angle.sin()), k)
+        })
+        .collect();
+
+    for y in 0..size.y {
+        let dst = output.line_mut(y);
+
+        for (x, value) in dst.iter_mut().enumerate() {
+            *value = taps.iter()
+                .map(|(offset, k)| {
+                    let position = Vec2d::new(
+                        (x as f32 + offset.x).clamp(0.0, max_x),
+                        (y as f32 + offset.y).clamp(0.0, max_y),
+                    );
+
+                    k*bilinear(input, position).unwrap_or(0.0) as f32
+                })
+                .sum();
+        }
+    }
+}
+
+/// Convolves `input` with oriented 1D `kernel` into a new `f32` image, 
+/// see [directional_filter](fn.directional_filter.html)
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::directional_filter_new};
+/// let input = ImgBuf::<u8>::from_vec(ImgSize::new(1, 3), vec![0, 10, 20]);
+/// // Vertical derivative
+/// let output = directional_filter_new(&input, &[-0.5, 0.0, 0.5], std::f32::consts::FRAC_PI_2);
+/// assert!((output[(0, 1)] - 10.0).abs() < 1e-4);
+/// ```
+pub fn directional_filter_new<T: AsPrimitive<f64>>(input: &dyn Img<T>, kernel: &[f32], angle: f32) -> ImgBuf<f32> {
+    let mut output = ImgBuf::<f32>::new(input.size());
+    directional_filter(input, &mut output, kernel, angle);
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+    use crate::filter::{horizontal_filter, convolution_operator};
+
+    #[test]
+    fn zero_angle_matches_horizontal_filter() {
+        let input = ImgBuf::<u8>::from_vec(ImgSize::new(5, 2), (0..10).map(|v| v*v).collect());
+        let kernel = [0.25, 0.5, 0.25];
+        let mut expected = ImgBuf::<f32>::new_init(input.size(), 0.0);
+        let floats = ImgBuf::<f32>::from_vec(input.size(), input.lines().flatten().map(|&v| v as f32).collect());
+        horizontal_filter(&floats, &mut expected, &kernel, convolution_operator);
+        let result = directional_filter_new(&input, &kernel, 0.0);
+
+        for (a, b) in result.lines().flatten().zip(expected.lines().flatten()) {
+            assert!((a - b).abs() < 1e-4, "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn fractional_taps_are_interpolated() {
+        let input = ImgBuf::<f32>::from_vec(ImgSize::new(3, 3), (0..9).map(|v| v as f32).collect());
+        // Single tap one pixel away at 30 degrees lands between pixels
+        let angle = 30f32.to_radians();
+        let output = directional_filter_new(&input, &[0.0, 0.0, 1.0], angle);
+        let expected = bilinear(&input, Vec2d::new(angle.cos(), 1.0 + angle.sin())).unwrap() as f32;
+        assert!((output[(0, 1)] - expected).abs() < 1e-4);
+    }
+}
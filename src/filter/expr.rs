@@ -0,0 +1,244 @@
+use std::cell::RefCell;
+use crate::{Img, ImgMut, ImgBuf, ImgSize, AsPrimitive};
+
+/// Lazy point-wise image expression evaluated line by line
+/// 
+/// Expressions are built by chaining operations starting from [expr](fn.expr.html),
+/// no pixels are processed until [eval_into](#method.eval_into) or
+/// [eval_new](#method.eval_new) is called. All chained operations are then
+/// fused into a single pass over the images, each line is evaluated in
+/// a small line buffer, so intermediate images are never stored.
+/// 
+/// Values are computed in `f32`, binary operations panic when operand
+/// sizes differ.
+pub trait Expr: Sized {
+    /// Size of the image produced by expression
+    fn size(&self) -> ImgSize;
+
+    /// Evaluates line `y` of the expression into `dst` of image width
+    fn eval_line(&self, y: usize, dst: &mut [f32]);
+
+    /// Maps each value by `operator`
+    fn map<F: Fn(f32) -> f32>(self, operator: F) -> ExprMap<Self, F> {
+        ExprMap { inner: self, operator }
+    }
+
+    /// Combines values with another expression or image by `operator`
+    fn zip<B: IntoExpr, F: Fn(f32, f32) -> f32>(self, other: B, operator: F) -> ExprZip<Self, B::Expr, F> {
+        let other = other.into_expr();
+        assert_eq!(self.size(), other.size(), "Expression sizes differ");
+        ExprZip { left: self, right: other, operator, buffer: RefCell::new(Vec::new()) }
+    }
+
+    /// Adds values of another expression or image
+    fn add<B: IntoExpr>(self, other: B) -> ExprZip<Self, B::Expr, impl Fn(f32, f32) -> f32> {
+        self.zip(other, |a, b| a + b)
+    }
+
+    /// Subtracts values of another expression or image
+    fn sub<B: IntoExpr>(self, other: B) -> ExprZip<Self, B::Expr, impl Fn(f32, f32) -> f32> {
+        self.zip(other, |a, b| a - b)
+    }
+
+    /// Multiplies by values of another expression or image
+    fn mul<B: IntoExpr>(self, other: B) -> ExprZip<Self, B::Expr, impl Fn(f32, f32) -> f32> {
+        self.zip(other, |a, b| a*b)
+    }
+
+    /// Multiplies values by constant `factor`
+    fn scale(self, factor: f32) -> ExprMap<Self, impl Fn(f32) -> f32> {
+        self.map(move |x| x*factor)
+    }
+
+    /// Adds constant `offset` to values
+    fn offset(self, offset: f32) -> ExprMap<Self, impl Fn(f32) -> f32> {
+        self.map(move |x| x + offset)
+    }
+
+    /// Limits values into `min..=max` interval
+    fn clamp(self, min: f32, max: f32) -> ExprMap<Self, impl Fn(f32) -> f32> {
+        self.map(move |x| x.max(min).min(max))
+    }
+
+    /// Absolute value
+    fn abs(self) -> ExprMap<Self, fn(f32) -> f32> {
+        self.map(f32::abs)
+    }
+
+    /// Evaluates expression into `output` image, values are converted 
+    /// into output pixel type by `as` casting
+    /// 
+    /// Panics if output size differs from expression size
+    fn eval_into<T: Copy + 'static>(&self, output: &mut dyn ImgMut<T>) where f32: AsPrimitive<T> {
+        let size = self.size();
+        assert_eq!(size, output.size(), "Expression and output sizes differ");
+        let mut line = vec![0.0f32; size.x];
+
+        for y in 0..size.y {
+            self.eval_line(y, &mut line);
+            let dst = output.line_mut(y);
+
+            for x in 0..size.x {
+                dst[x] = line[x].as_();
+            }
+        }
+    }
+
+    /// Evaluates expression into a new `f32` image buffer
+    fn eval_new(&self) -> ImgBuf<f32> {
+        let size = self.size();
+        let mut output = ImgBuf::<f32>::new(size);
+
+        for y in 0..size.y {
+            self.eval_line(y, output.line_mut(y));
+        }
+
+        output
+    }
+}
+
+/// Conversion of images and expressions into expression operands
+pub trait IntoExpr {
+    /// Resulting expression type
+    type Expr: Expr;
+
+    /// Converts value into expression
+    fn into_expr(self) -> Self::Expr;
+}
+
+/// Starts a lazy point-wise expression reading pixels of `image`
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::{expr, Expr}};
+/// let size = ImgSize::new(3, 1);
+/// let a = ImgBuf::<u8>::from_vec(size, vec![10, 200, 250]);
+/// let b = ImgBuf::<u8>::from_vec(size, vec![30, 100, 250]);
+/// let mut out = ImgBuf::<u8>::new(size);
+/// 
+/// expr(&a).add(&b).scale(0.5).offset(100.0).clamp(0.0, 255.0).eval_into(&mut out);
+/// assert_eq!(out.into_vec(), vec![120, 250, 255]);
+/// ```
+pub fn expr<T: AsPrimitive<f32>>(image: &dyn Img<T>) -> ExprSource<'_, T> {
+    ExprSource { image }
+}
+
+/// Expression reading pixels of an image, see [expr](fn.expr.html)
+pub struct ExprSource<'a, T> {
+    image: &'a dyn Img<T>,
+}
+
+/// Expression applying unary operator, see [Expr::map](trait.Expr.html#method.map)
+pub struct ExprMap<E, F> {
+    inner: E,
+    operator: F,
+}
+
+/// Expression combining two operands, see [Expr::zip](trait.Expr.html#method.zip)
+pub struct ExprZip<A, B, F> {
+    left: A,
+    right: B,
+    operator: F,
+    buffer: RefCell<Vec<f32>>,
+}
+
+impl<'a, T: AsPrimitive<f32>> Expr for ExprSource<'a, T> {
+    fn size(&self) -> ImgSize {
+        self.image.size()
+    }
+
+    fn eval_line(&self, y: usize, dst: &mut [f32]) {
+        let src = self.image.line_ref(y);
+
+        for x in 0..std::cmp::min(src.len(), dst.len()) {
+            dst[x] = src[x].as_();
+        }
+    }
+}
+
+impl<E: Expr, F: Fn(f32) -> f32> Expr for ExprMap<E, F> {
+    fn size(&self) -> ImgSize {
+        self.inner.size()
+    }
+
+    fn eval_line(&self, y: usize, dst: &mut [f32]) {
+        self.inner.eval_line(y, dst);
+
+        for value in dst.iter_mut() {
+            *value = (self.operator)(*value);
+        }
+    }
+}
+
+impl<A: Expr, B: Expr, F: Fn(f32, f32) -> f32> Expr for ExprZip<A, B, F> {
+    fn size(&self) -> ImgSize {
+        self.left.size()
+    }
+
+    fn eval_line(&self, y: usize, dst: &mut [f32]) {
+        let mut buffer = self.buffer.borrow_mut();
+        buffer.resize(dst.len(), 0.0);
+        self.left.eval_line(y, dst);
+        self.right.eval_line(y, &mut buffer);
+
+        for x in 0..dst.len() {
+            dst[x] = (self.operator)(dst[x], buffer[x]);
+        }
+    }
+}
+
+impl<E: Expr> IntoExpr for E {
+    type Expr = E;
+
+    fn into_expr(self) -> E {
+        self
+    }
+}
+
+impl<'a, T: AsPrimitive<f32>> IntoExpr for &'a dyn Img<T> {
+    type Expr = ExprSource<'a, T>;
+
+    fn into_expr(self) -> Self::Expr {
+        expr(self)
+    }
+}
+
+impl<'a, T: AsPrimitive<f32>> IntoExpr for &'a ImgBuf<T> {
+    type Expr = ExprSource<'a, T>;
+
+    fn into_expr(self) -> Self::Expr {
+        expr(self)
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::map_new;
+
+    #[test]
+    fn fused_expression_matches_chained_maps() {
+        let size = ImgSize::new(4, 3);
+        let a = ImgBuf::<u8>::from_vec(size, (0..12).map(|x| x*20).collect());
+        let b = ImgBuf::<i16>::from_vec(size, (0..12).map(|x| 50 - x*10).collect());
+        let result = expr(&a).sub(&b).abs().mul(expr(&a).scale(0.01)).eval_new();
+
+        let expected: Vec<f32> = a.lines().flatten().zip(b.lines().flatten())
+            .map(|(&a, &b)| (a as f32 - b as f32).abs()*(a as f32*0.01))
+            .collect();
+
+        assert_eq!(result.into_vec(), expected);
+        let doubled: ImgBuf<f32> = map_new(&a, |x: u8| 2.0*x as f32);
+        assert_eq!(expr(&a).add(&a).eval_new(), doubled);
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_sizes_panic() {
+        let a = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+        let b = ImgBuf::<u8>::new(ImgSize::new(3, 2));
+        expr(&a).add(&b);
+    }
+}
@@ -0,0 +1,116 @@
+use std::f32::consts::PI;
+use crate::{Img, ImgBuf, ImgSize, AsPrimitive};
+use super::filter_2d_new;
+
+/// Parameters of a single Gabor filter, see [gabor_kernel](fn.gabor_kernel.html)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GaborParams {
+    /// Standard deviation of the Gaussian envelope
+    pub sigma: f32,
+    /// Orientation of the filter normal (radians)
+    pub theta: f32,
+    /// Wavelength of the sinusoidal carrier (pixels)
+    pub lambda: f32,
+    /// Spatial aspect ratio of the envelope
+    pub gamma: f32,
+    /// Phase offset of the carrier (radians)
+    pub psi: f32,
+}
+
+/// Creates real Gabor filter kernel
+/// 
+/// Kernel element at offset `(x, y)` from the kernel center equals
+/// `exp(-(x'² + gamma²*y'²)/(2*sigma²))*cos(2*PI*x'/lambda + psi)`, where
+/// `x' = x*cos(theta) + y*sin(theta)` and `y' = -x*sin(theta) + y*cos(theta)`,
+/// so the filter responds to stripes with period `lambda` perpendicular to
+/// `theta` direction. Kernel is square with odd size covering three 
+/// standard deviations of the envelope in both directions.
+/// 
+/// # Example
+/// ```
+/// use nanocv::filter::gabor_kernel;
+/// let kernel = gabor_kernel(2.0, 0.0, 4.0, 1.0, 0.0);
+/// assert_eq!(nanocv::Img::size(&kernel), nanocv::ImgSize::new(13, 13));
+/// assert_eq!(kernel[(6, 6)], 1.0);
+/// // half a period from the center the carrier is negative
+/// assert!(kernel[(8, 6)] < 0.0);
+/// ```
+pub fn gabor_kernel(sigma: f32, theta: f32, lambda: f32, gamma: f32, psi: f32) -> ImgBuf<f32> {
+    let extent = 3.0*sigma/gamma.clamp(f32::EPSILON, 1.0);
+    let radius = extent.ceil().max(0.0) as usize;
+    let size = 2*radius + 1;
+    let mut kernel = ImgBuf::<f32>::new(ImgSize::new(size, size));
+    let (sin, cos) = theta.sin_cos();
+
+    for ky in 0..size {
+        for kx in 0..size {
+            let (x, y) = (kx as f32 - radius as f32, ky as f32 - radius as f32);
+            let rotated_x = x*cos + y*sin;
+            let rotated_y = -x*sin + y*cos;
+            let envelope = (-(rotated_x*rotated_x + gamma*gamma*rotated_y*rotated_y)/(2.0*sigma*sigma)).exp();
+            kernel[(kx, ky)] = envelope*(2.0*PI*rotated_x/lambda + psi).cos();
+        }
+    }
+
+    kernel
+}
+
+/// Filters `image` by a bank of Gabor filters
+/// 
+/// Returns one response image per element of `params` in the same order,
+/// computed by 2D filtering with [gabor_kernel](fn.gabor_kernel.html) 
+/// kernels and replicated image borders.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::{gabor_bank, GaborParams}};
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(32, 32));
+/// 
+/// // vertical stripes with period 8 pixels
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = if position.x % 8 < 4 { 100 } else { 0 };
+/// }
+/// 
+/// // odd (sine) filters have zero response to flat areas
+/// let vertical = GaborParams { sigma: 3.0, theta: 0.0, lambda: 8.0, gamma: 1.0, psi: std::f32::consts::FRAC_PI_2 };
+/// let horizontal = GaborParams { theta: std::f32::consts::FRAC_PI_2, ..vertical };
+/// let responses = gabor_bank(&image, &[vertical, horizontal]);
+/// 
+/// let energy = |response: &ImgBuf<f32>| response.line_ref(16)[8..24].iter().map(|v| v.abs()).sum::<f32>();
+/// assert!(energy(&responses[0]) > 10.0*energy(&responses[1]));
+/// ```
+pub fn gabor_bank<T: AsPrimitive<f32>>(image: &dyn Img<T>, params: &[GaborParams]) -> Vec<ImgBuf<f32>> {
+    params.iter()
+        .map(|p| filter_2d_new(image, &gabor_kernel(p.sigma, p.theta, p.lambda, p.gamma, p.psi)))
+        .collect()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotated_kernel_is_transposed() {
+        let kernel = gabor_kernel(1.5, 0.0, 5.0, 0.5, 0.3);
+        let rotated = gabor_kernel(1.5, PI/2.0, 5.0, 0.5, 0.3);
+        let size = kernel.size();
+        assert_eq!(rotated.size(), size);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                assert!((kernel[(x, y)] - rotated[(y, x)]).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn bank_returns_response_per_filter() {
+        let image = ImgBuf::<f32>::new_init(ImgSize::new(8, 6), 1.0);
+        let params = GaborParams { sigma: 1.0, theta: 0.0, lambda: 3.0, gamma: 1.0, psi: 0.0 };
+        let responses = gabor_bank(&image, &[params, params, params]);
+        assert_eq!(responses.len(), 3);
+        assert!(responses.iter().all(|response| response.size() == image.size()));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive};
+use super::{horizontal_filter, vertical_filter};
+
+/// Creates normalized Gaussian kernel with standard deviation `sigma`,
+/// kernel has `2*ceil(3*sigma) + 1` elements, non-positive `sigma`
+/// produces identity kernel `[1.0]`
+/// 
+/// # Example
+/// ```
+/// use nanocv::filter::gaussian_kernel;
+/// let kernel = gaussian_kernel(1.0);
+/// assert_eq!(kernel.len(), 7);
+/// assert!((kernel.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+/// assert_eq!(gaussian_kernel(0.0), vec![1.0]);
+/// ```
+pub fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    if sigma <= 0.0 {
+        return vec![1.0];
+    }
+
+    let radius = (3.0*sigma).ceil() as isize;
+
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|x| (-(x*x) as f32/(2.0*sigma*sigma)).exp())
+        .collect();
+
+    let sum: f32 = kernel.iter().sum();
+    kernel.iter().map(|value| value/sum).collect()
+}
+
+/// Blurs `input` image by Gaussian filter with standard deviation `sigma`
+/// into `output` image, pixels outside image are replicated from the
+/// nearest border pixels
+/// 
+/// Filter is separable and is computed as horizontal filter followed by
+/// vertical filter, previous content of `output` is overwritten
+/// 
+/// Panics if input and output sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::gaussian_blur};
+/// let mut input = ImgBuf::<u8>::new(ImgSize::new(9, 9));
+/// input[(4, 4)] = 100;
+/// let mut output = ImgBuf::<f32>::new(input.size());
+/// gaussian_blur(&input, &mut output, 1.0);
+/// let sum: f32 = output.lines().flat_map(|line| line.iter()).sum();
+/// assert!((sum - 100.0).abs() < 1e-3);
+/// assert!(output[(4, 4)] > output[(5, 4)]);
+/// ```
+pub fn gaussian_blur<T: AsPrimitive<f32>>(
+    input: &dyn Img<T>, 
+    output: &mut dyn ImgMut<f32>, 
+    sigma: f32
+) {
+    assert_eq!(input.size(), output.size(), "Input and output sizes differ");
+    let kernel = gaussian_kernel(sigma);
+    let mut horizontal = ImgBuf::<f32>::new(input.size());
+
+    horizontal_filter(input, &mut horizontal, &kernel, |src: &[T], dst: &mut [f32], k: f32| {
+        for i in 0..std::cmp::min(src.len(), dst.len()) {
+            dst[i] += k*src[i].as_();
+        }
+    });
+
+    for y in 0..output.height() {
+        for value in output.line_mut(y).iter_mut() {
+            *value = 0.0;
+        }
+    }
+
+    vertical_filter(&horizontal, output, &kernel, super::convolution_operator);
+}
+
+/// Blurs `input` by Gaussian filter into a new `f32` image, 
+/// see [gaussian_blur](fn.gaussian_blur.html)
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::gaussian_blur_new};
+/// let input = ImgBuf::<u16>::new_init(ImgSize::new(4, 3), 10);
+/// let output = gaussian_blur_new(&input, 2.0);
+/// assert!(output.lines().all(|line| line.iter().all(|v| (v - 10.0).abs() < 1e-4)));
+/// ```
+pub fn gaussian_blur_new<T: AsPrimitive<f32>>(input: &dyn Img<T>, sigma: f32) -> ImgBuf<f32> {
+    let mut output = ImgBuf::<f32>::new(input.size());
+    gaussian_blur(input, &mut output, sigma);
+    output
+}
@@ -0,0 +1,85 @@
+use crate::{AsPrimitive, Img, ImgBuf, ImgRange, ImgSize};
+
+/// Computes integral image (summed area table) of `image`
+/// 
+/// Output is one pixel wider and higher than the input, pixel `(x, y)` 
+/// contains the sum of all input pixels with coordinates lower than `x` 
+/// and `y`, so the sum over any rectangle is obtained from four values 
+/// by [integral_sum](fn.integral_sum.html).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::{integral_image, integral_sum}};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![
+///     1, 2, 3,
+///     4, 5, 6,
+/// ]);
+/// let integral = integral_image(&image);
+/// assert_eq!(integral.size(), ImgSize::new(4, 3));
+/// assert_eq!(integral.line_ref(2), &[0.0, 5.0, 12.0, 21.0]);
+/// assert_eq!(integral_sum(&integral, Range2d::new(1..3, 0..2)), 16.0);
+/// ```
+pub fn integral_image<T: AsPrimitive<f64>>(image: &dyn Img<T>) -> ImgBuf<f64> {
+    let size = image.size();
+    let mut integral = ImgBuf::<f64>::new(ImgSize::new(size.x + 1, size.y + 1));
+
+    for y in 0..size.y {
+        let line = image.line_ref(y);
+        let mut row = 0.0;
+
+        for x in 0..size.x {
+            row += line[x].as_();
+            integral[(x + 1, y + 1)] = integral[(x + 1, y)] + row;
+        }
+    }
+
+    integral
+}
+
+/// Sum of image pixels within `range` computed in constant time from 
+/// the integral image, range is clipped to the image bounds
+pub fn integral_sum(integral: &dyn Img<f64>, range: ImgRange) -> f64 {
+    let size = integral.size();
+
+    if size.x == 0 || size.y == 0 {
+        return 0.0;
+    }
+
+    let clamp = |value: isize, limit: usize| value.max(0).min(limit as isize - 1) as usize;
+    let (x0, x1) = (clamp(range.x.start, size.x), clamp(range.x.end, size.x));
+    let (y0, y1) = (clamp(range.y.start, size.y), clamp(range.y.end, size.y));
+
+    if x1 <= x0 || y1 <= y0 {
+        return 0.0;
+    }
+
+    let (top, bottom) = (integral.line_ref(y0), integral.line_ref(y1));
+    bottom[x1] - bottom[x0] - top[x1] + top[x0]
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::Range2d;
+    use super::*;
+
+    #[test]
+    fn sums_match_direct_summation() {
+        let size = ImgSize::new(7, 5);
+        let image = ImgBuf::<u16>::from_vec(size, (0..35).map(|v| (v*7 % 11) as u16).collect());
+        let integral = integral_image(&image);
+
+        for range in &[Range2d::new(0..7, 0..5), Range2d::new(2..5, 1..4), Range2d::new(-3..2, 3..9), Range2d::new(4..4, 0..5)] {
+            let mut expected = 0.0;
+
+            for y in range.y.start.max(0)..range.y.end.min(5) {
+                for x in range.x.start.max(0)..range.x.end.min(7) {
+                    expected += image[(x as usize, y as usize)] as f64;
+                }
+            }
+
+            assert_eq!(integral_sum(&integral, *range), expected);
+        }
+    }
+}
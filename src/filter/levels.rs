@@ -0,0 +1,187 @@
+use crate::{Img, ImgBuf, color::Rgb};
+
+/// How [auto_levels_rgb](fn.auto_levels_rgb.html) treats color channels
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum LevelsMode {
+    /// Every channel is stretched by its own black and white points, 
+    /// which also removes color casts
+    PerChannel,
+    /// Black and white points are found from luminance and the same 
+    /// mapping is applied to all channels, preserving color balance
+    Luminance,
+}
+
+/// Stretches image contrast by moving black and white points
+/// 
+/// Black point is the value below which `clip_low_pct` percent of pixels 
+/// lie, white point is the value above which `clip_high_pct` percent of 
+/// pixels lie, both found from the image histogram. Values between black
+/// and white points are linearly remapped to the full `0..=255` range, 
+/// values outside are clipped. Images with (nearly) constant values
+/// are returned unchanged.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::auto_levels};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(4, 1), vec![50, 100, 150, 250]);
+/// assert_eq!(auto_levels(&image, 0.0, 0.0).line_ref(0), &[0, 64, 128, 255]);
+/// // the brightest pixel (25 % of pixels) is clipped
+/// assert_eq!(auto_levels(&image, 0.0, 25.0).line_ref(0), &[0, 128, 255, 255]);
+/// ```
+pub fn auto_levels(image: &dyn Img<u8>, clip_low_pct: f32, clip_high_pct: f32) -> ImgBuf<u8> {
+    let mut histogram = [0usize; 256];
+
+    for line in image.lines() {
+        for &value in line {
+            histogram[value as usize] += 1;
+        }
+    }
+
+    let table = levels_table(&histogram, clip_low_pct, clip_high_pct);
+    let mut output = ImgBuf::<u8>::new(image.size());
+
+    for (y, line) in image.lines().enumerate() {
+        for (x, &value) in line.iter().enumerate() {
+            output[(x, y)] = table[value as usize];
+        }
+    }
+
+    output
+}
+
+/// Stretches contrast of a color image, see 
+/// [auto_levels](fn.auto_levels.html) for the meaning of clipping 
+/// percentages and [LevelsMode](enum.LevelsMode.html) for channel modes
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, filter::{auto_levels_rgb, LevelsMode}};
+/// let image = ImgBuf::from_vec(ImgSize::new(2, 1), vec![
+///     Rgb::new(20u8, 40, 60), Rgb::new(120, 140, 160),
+/// ]);
+/// let stretched = auto_levels_rgb(&image, 0.0, 0.0, LevelsMode::PerChannel);
+/// assert_eq!(stretched.line_ref(0), &[Rgb::new(0, 0, 0), Rgb::new(255, 255, 255)]);
+/// 
+/// let linked = auto_levels_rgb(&image, 0.0, 0.0, LevelsMode::Luminance);
+/// assert!(linked[(0, 0)].r < linked[(0, 0)].b && linked[(1, 0)].r < linked[(1, 0)].b);
+/// ```
+pub fn auto_levels_rgb(
+    image: &dyn Img<Rgb<u8>>, 
+    clip_low_pct: f32, 
+    clip_high_pct: f32, 
+    mode: LevelsMode
+) -> ImgBuf<Rgb<u8>> {
+    let mut histograms = [[0usize; 256]; 3];
+
+    for line in image.lines() {
+        for pixel in line {
+            match mode {
+                LevelsMode::PerChannel => {
+                    histograms[0][pixel.r as usize] += 1;
+                    histograms[1][pixel.g as usize] += 1;
+                    histograms[2][pixel.b as usize] += 1;
+                },
+                LevelsMode::Luminance => histograms[0][luminance(*pixel) as usize] += 1,
+            }
+        }
+    }
+
+    let tables = match mode {
+        LevelsMode::PerChannel => [0, 1, 2].map(|channel| levels_table(&histograms[channel], clip_low_pct, clip_high_pct)),
+        LevelsMode::Luminance => [levels_table(&histograms[0], clip_low_pct, clip_high_pct); 3],
+    };
+
+    let mut output = ImgBuf::<Rgb<u8>>::new(image.size());
+
+    for (y, line) in image.lines().enumerate() {
+        for (x, pixel) in line.iter().enumerate() {
+            output[(x, y)] = Rgb::new(
+                tables[0][pixel.r as usize], 
+                tables[1][pixel.g as usize], 
+                tables[2][pixel.b as usize]
+            );
+        }
+    }
+
+    output
+}
+
+/// Rec. 601 luma of a pixel
+fn luminance(pixel: Rgb<u8>) -> u8 {
+    (0.299*pixel.r as f32 + 0.587*pixel.g as f32 + 0.114*pixel.b as f32).round() as u8
+}
+
+/// Lookup table mapping black and white points found 
+/// in the histogram to the full range
+fn levels_table(histogram: &[usize; 256], clip_low_pct: f32, clip_high_pct: f32) -> [u8; 256] {
+    let total: usize = histogram.iter().sum();
+    let mut table = [0u8; 256];
+
+    for (index, value) in table.iter_mut().enumerate() {
+        *value = index as u8;
+    }
+
+    if total == 0 {
+        return table;
+    }
+
+    let limit = |pct: f32| (pct.max(0.0) as f64/100.0*total as f64) as usize;
+    let (low_limit, high_limit) = (limit(clip_low_pct), limit(clip_high_pct));
+
+    // Values whose cumulative counts from either end do not exceed 
+    // the limits are clipped
+    let mut black = 0;
+    let mut count = histogram[0];
+
+    while black < 255 && count <= low_limit {
+        black += 1;
+        count += histogram[black];
+    }
+
+    let mut white = 255;
+    let mut count = histogram[255];
+
+    while white > 0 && count <= high_limit {
+        white -= 1;
+        count += histogram[white];
+    }
+
+    if white <= black {
+        return table;
+    }
+
+    let scale = 255.0/(white - black) as f32;
+
+    for (index, value) in table.iter_mut().enumerate() {
+        *value = ((index as f32 - black as f32)*scale).round().clamp(0.0, 255.0) as u8;
+    }
+
+    table
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn constant_image_is_unchanged() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(3, 3), 77);
+        assert_eq!(auto_levels(&image, 1.0, 1.0), image);
+    }
+
+    #[test]
+    fn outliers_are_clipped() {
+        let mut values: Vec<u8> = (0..200).map(|v| 100 + (v % 50) as u8).collect();
+        values[0] = 0;
+        values[1] = 255;
+        let image = ImgBuf::from_vec(ImgSize::new(200, 1), values);
+        let stretched = auto_levels(&image, 1.0, 1.0);
+        assert_eq!(stretched[(0, 0)], 0);
+        assert_eq!(stretched[(1, 0)], 255);
+        assert_eq!(stretched[(50, 0)], 0);
+        assert_eq!(stretched[(49, 0)], 255);
+    }
+}
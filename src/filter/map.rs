@@ -1,5 +1,5 @@
 use std::cmp::min;
-use crate::{ImgMut, ImgBuf, Img, ImgRange, ImageMapping};
+use crate::{ImgMut, ImgBuf, Img, ImgRange, ImageMapping, Vec2d};
 
 /// Maps pixels from `input` at `input_range` into pixels
 /// in `output` image in `output_range`
@@ -13,8 +13,8 @@ use crate::{ImgMut, ImgBuf, Img, ImgRange, ImageMapping};
 /// * `input_range` - input pixel range
 /// * `output_range` - output pixel range
 /// * `operator` - either just mapping function from input to output `|x, _| -x`
-/// or combinator that uses both input and output values to produce new 
-/// output value `|i, o| i + o`
+///   or combinator that uses both input and output values to produce new 
+///   output value `|i, o| i + o`
 /// 
 /// # Example
 /// ```
@@ -73,8 +73,8 @@ pub fn map_range<TI: Copy, TO: Copy, F>(
 /// * `input` - input read-only image
 /// * `output` - output mutable image
 /// * `operator` - either just mapping function from input to output `|x, _| -x`
-/// or combinator that uses both input and output values to produce new 
-/// output value `|i, o| i + o`
+///   or combinator that uses both input and output values to produce new 
+///   output value `|i, o| i + o`
 /// 
 /// # Example
 /// 
@@ -104,6 +104,76 @@ pub fn map<TI: Copy, TO: Copy, F>(
     map_range(input, output, input.range(), output_range, operator);
 }
 
+/// Maps pixels from `input` at `input_range` into pixels in `output` 
+/// image in `output_range` using an operator that also receives position
+/// of the output pixel in `output` image
+/// 
+/// Pixels outside image ranges are ignored, see [map_range](fn.map_range.html)
+/// 
+/// # Example
+/// 
+/// Apply per-column gain
+/// ```
+/// use nanocv::{*, filter::map_range_indexed};
+/// let input = ImgBuf::<f32>::new_init(ImgSize::new(3, 2), 10.0);
+/// let gains = [1.0, 0.5, 2.0];
+/// let mut output = ImgBuf::<f32>::new(ImgSize::new(4, 2));
+/// 
+/// map_range_indexed(
+///     &input, 
+///     &mut output, 
+///     input.range(), 
+///     Range2d::new(1..4, 0..2),
+///     |position, x, _| x*gains[position.x - 1]
+/// );
+/// 
+/// assert_eq!(output.line_ref(1), &[0.0, 10.0, 5.0, 20.0]);
+/// ```
+pub fn map_range_indexed<TI: Copy, TO: Copy, F>(
+    input: &dyn Img<TI>,
+    output: &mut dyn ImgMut<TO>,
+    input_range: ImgRange,
+    output_range: ImgRange,
+    mut operator: F
+) where F: FnMut(Vec2d<usize>, TI, TO) -> TO { 
+    let mapping = ImageMapping::new(input_range, output_range, input.range(), output.range());
+
+    for line in 0..mapping.src.height() {
+        let y = mapping.dst.y.start + line;
+        let src = &input.line_ref(mapping.src.y.start + line)[mapping.src.x.to_range()];
+        let dst = &mut output.line_mut(y)[mapping.dst.x.to_range()];
+        let max = min(src.len(), dst.len());
+
+        for column in 0..max {
+            let position = Vec2d::new(mapping.dst.x.start + column, y);
+            dst[column] = operator(position, src[column], dst[column]);
+        }
+    }    
+}
+
+/// Maps pixels from `input` image onto `output` image using an operator
+/// that also receives pixel position, see 
+/// [map_range_indexed](fn.map_range_indexed.html)
+/// 
+/// # Example
+/// 
+/// Darken pixels with distance from image center
+/// ```
+/// use nanocv::{*, filter::map_indexed};
+/// let input = ImgBuf::<f32>::new_init(ImgSize::new(5, 1), 1.0);
+/// let mut output = ImgBuf::new(input.size());
+/// map_indexed(&input, &mut output, |p, x, _| x/(1.0 + (p.x as f32 - 2.0).abs()));
+/// assert_eq!(output.line_ref(0), &[1.0/3.0, 0.5, 1.0, 0.5, 1.0/3.0]);
+/// ```
+pub fn map_indexed<TI: Copy, TO: Copy, F>(
+    input: &dyn Img<TI>,
+    output: &mut dyn ImgMut<TO>,
+    operator: F
+) where F: FnMut(Vec2d<usize>, TI, TO) -> TO { 
+    let output_range = output.range();
+    map_range_indexed(input, output, input.range(), output_range, operator);
+}
+
 /// Maps pixels from `input` image into newly created `ImgBuf` image with same size as `input`
 /// 
 /// # Example
@@ -159,4 +229,27 @@ mod tests {
             ])
         )        
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn map_range_indexed_passes_output_positions() {
+        let input = ImgBuf::<u8>::new_init(ImgSize::new(2, 2), 1);
+        let mut output = ImgBuf::<usize>::new(ImgSize::new(3, 3));
+
+        map_range_indexed(
+            &input, 
+            &mut output, 
+            ImgRange::new(0..2, 0..2), 
+            ImgRange::new(1..3, 1..3), 
+            |position, x, _| 10*position.y + position.x + x as usize
+        );
+
+        assert_eq!(
+            output,
+            ImgBuf::<usize>::from_vec(ImgSize::new(3, 3), vec![
+                0,  0,  0,
+                0, 12, 13,
+                0, 22, 23,
+            ])
+        )
+    }
+}
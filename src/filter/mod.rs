@@ -14,28 +14,89 @@
 //!  * functions with `_new` suffix automatically create new output image
 //!    buffer of `ImgBuf` type and do not require output buffer to be specified,
 //!    however, no other buffer type constructors are supported
+//!  * functions with `try_` prefix validate their arguments and return
+//!    `Err(nanocv::Error)` instead of panicking
 //! 
 //! Notable function families are:
 //!  * `update` - update image pixels in place:
-//!    [update](fn.update.html), [update_range](fn.update_range.html)
+//!    [update](fn.update.html), [update_range](fn.update_range.html),
+//!    [update_indexed](fn.update_indexed.html), 
+//!    [update_range_indexed](fn.update_range_indexed.html)
 //!  * `map` - map pixels from one image to another:
 //!    [map](fn.map.html), [map_range](fn.map_range.html), 
-//!    [map_new](fn.map_new.html)
+//!    [map_new](fn.map_new.html), [map_indexed](fn.map_indexed.html),
+//!    [map_range_indexed](fn.map_range_indexed.html)
+//!  * `gaussian_blur` - separable Gaussian smoothing:
+//!    [gaussian_blur](fn.gaussian_blur.html), 
+//!    [gaussian_blur_new](fn.gaussian_blur_new.html)
+//!  * `expr` - lazy point-wise expressions fused into a single pass:
+//!    [expr](fn.expr.html), [Expr](trait.Expr.html)
+//! 
+//! Custom sliding-window filters with correct handling of image borders 
+//! can be built on iteration plans from the [plan](plan/index.html) module.
+
+pub mod plan;
 
 mod update;
 mod map;
 mod convolution;
 mod resize;
 mod mirror;
+mod background;
+mod defects;
+mod normalize;
+mod tonemap;
+mod gaussian;
+mod sharpen;
+mod diffusion;
+mod clone;
+mod blend;
+mod remap;
+mod undistort;
+mod gabor;
+mod integral;
+mod levels;
+mod vignetting;
+mod rotate;
+mod expr;
+mod threshold;
+mod retinex;
+mod directional;
 
-pub use update::{update, update_range};
-pub use map::{map, map_range, map_new};
-pub use resize::resize_nearest_new;
+pub use update::{update, update_range, update_indexed, update_range_indexed};
+pub use map::{map, map_range, map_new, map_indexed, map_range_indexed};
+pub use resize::{resize_nearest_new, resize_bilinear_new};
+#[cfg(feature = "gpu")]
+pub(crate) use resize::scale_index_table;
+pub use threshold::threshold_new;
 
 pub use convolution::{
     horizontal_filter_range, horizontal_filter, 
     vertical_filter_range, vertical_filter,
-    convolution_operator
+    try_horizontal_filter_range, try_horizontal_filter,
+    try_vertical_filter_range, try_vertical_filter,
+    convolution_operator,
+    filter_2d, filter_2d_new
 };
 
-pub use mirror::{mirror_horizontal_new, mirror_vertical_new};
\ No newline at end of file
+pub use mirror::{mirror_horizontal_new, mirror_vertical_new};
+pub use background::{estimate_background, subtract_background};
+pub use defects::{detect_defective_pixels, correct_defective_pixels};
+pub use normalize::normalize_percentile;
+pub use tonemap::{tonemap_reinhard, tonemap_reinhard_rgb, tonemap_log};
+pub use gaussian::{gaussian_kernel, gaussian_blur, gaussian_blur_new};
+pub use sharpen::unsharp_mask;
+pub use diffusion::anisotropic_diffusion;
+pub use clone::seamless_clone;
+pub use blend::pyramid_blend;
+pub use remap::remap;
+pub(crate) use remap::bilinear;
+pub use undistort::{undistort, undistort_map};
+pub use gabor::{gabor_kernel, gabor_bank, GaborParams};
+pub use integral::{integral_image, integral_sum};
+pub use levels::{auto_levels, auto_levels_rgb, LevelsMode};
+pub use vignetting::{correct_vignetting, estimate_vignetting, VignettingModel};
+pub use rotate::rotate_shear_new;
+pub use directional::{directional_filter, directional_filter_new};
+pub use retinex::{single_scale_retinex, multi_scale_retinex, multi_scale_retinex_rgb};
+pub use expr::{expr, Expr, IntoExpr, ExprSource, ExprMap, ExprZip};
\ No newline at end of file
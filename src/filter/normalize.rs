@@ -0,0 +1,69 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive, stats::quantile};
+
+/// Linearly maps pixel values between `low_pct` and `high_pct` percentiles
+/// to `[0, 1]` range, values outside the range are clipped
+/// 
+/// Percentiles are given in `[0, 100]` range, e.g. `0.5` and `99.5`, 
+/// which makes the normalization robust to hot pixels and other outliers,
+/// unlike scaling by minimal and maximal values. Use 
+/// [convert::from_f32](../convert/fn.from_f32.html) to get an integer 
+/// image, e.g. in `[0, 255]` range. If both percentiles have the same 
+/// value (constant image), all pixels are set to zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::normalize_percentile};
+/// let mut values: Vec<u16> = (0..100).collect();
+/// values[10] = 60000;
+/// let image = ImgBuf::<u16>::from_vec(ImgSize::new(100, 1), values);
+/// let normalized = normalize_percentile(&image, 1.0, 98.0);
+/// assert_eq!(normalized[(10, 0)], 1.0);
+/// assert_eq!(normalized[(0, 0)], 0.0);
+/// assert!((normalized[(50, 0)] - 0.5).abs() < 0.02);
+/// ```
+pub fn normalize_percentile<T: AsPrimitive<f32>>(
+    image: &dyn Img<T>, 
+    low_pct: f32, 
+    high_pct: f32
+) -> ImgBuf<f32> {
+    let mut values: Vec<f32> = image.lines()
+        .flat_map(|line| line.iter().map(|value| value.as_()))
+        .collect();
+
+    let low = quantile(&mut values, low_pct/100.0).unwrap_or(0.0);
+    let high = quantile(&mut values, high_pct/100.0).unwrap_or(0.0);
+    let scale = if high > low { 1.0/(high - low) } else { 0.0 };
+    let mut output = ImgBuf::<f32>::new(image.size());
+
+    for y in 0..image.height() {
+        let src = image.line_ref(y);
+        let dst = output.line_mut(y);
+
+        for x in 0..dst.len() {
+            dst[x] = ((src[x].as_() - low)*scale).clamp(0.0, 1.0);
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn constant_image_is_normalized_to_zero() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(3, 3), 7);
+        let normalized = normalize_percentile(&image, 1.0, 99.0);
+        assert!(normalized.lines().all(|line| line.iter().all(|&v| v == 0.0)));
+    }
+
+    #[test]
+    fn full_range_matches_min_max_scaling() {
+        let image = ImgBuf::<i16>::from_vec(ImgSize::new(3, 1), vec![-10, 0, 30]);
+        assert_eq!(normalize_percentile(&image, 0.0, 100.0).line_ref(0), &[0.0, 0.25, 1.0]);
+    }
+}
@@ -0,0 +1,388 @@
+//! Iteration plans for border-correct sliding-window filters
+//! 
+//! Sliding-window filter combining `kernel_size` neighboring pixels is
+//! decomposed into `kernel_size` iterations, each iteration applies one
+//! kernel element to a contiguous range of source pixels shifted
+//! against destination pixels. Pixels outside the image are replaced
+//! by the nearest border pixel, so each iteration additionally reports 
+//! how many destination pixels at the beginning and at the end use the 
+//! first or the last source pixel. The same plan is used for lines of
+//! horizontal filters and for columns of vertical filters, see
+//! [horizontal_filter](../fn.horizontal_filter.html).
+//! 
+//! # Example
+//! 
+//! Sliding maximum of three neighboring values
+//! ```
+//! use nanocv::filter::plan::FilterPlan;
+//! let line = [1, 5, 2, 0, 3];
+//! let mut output = [i32::MIN; 5];
+//! 
+//! for step in FilterPlan::new(line.len(), 3).build() {
+//!     for dst in output.iter_mut().take(step.outside_start) {
+//!         *dst = (*dst).max(line[0]);
+//!     }
+//! 
+//!     let src = &line[step.src_range.to_range()];
+//!     for (dst, src) in output[step.dst_range.to_range()].iter_mut().zip(src) {
+//!         *dst = (*dst).max(*src);
+//!     }
+//! 
+//!     for dst in output.iter_mut().rev().take(step.outside_end) {
+//!         *dst = (*dst).max(line[line.len() - 1]);
+//!     }
+//! }
+//! 
+//! assert_eq!(output, [5, 5, 5, 3, 3]);
+//! ```
+
+use std::cmp::{min, max};
+use crate::{geometry::Range, Error};
+
+/// A recipe for one iteration of a convolution filter
+/// 
+/// Used for both vertical and horizontal filter implementations
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FilterIteration {
+    /// Range in one line/column of source image
+    pub src_range: Range<usize>,
+    /// Range in one line/column of destination image
+    pub dst_range: Range<usize>,
+    /// Index into convolution kernel
+    pub kernel_index: usize,
+    /// Number of pixels outside image to be replaced by first pixel value
+    pub outside_start: usize,
+    /// Number of pixels outside image to be replaced by last pixel value
+    pub outside_end: usize
+}
+
+/// Builder of filter iteration plans, see [module level](index.html) 
+/// documentation for an example
+/// 
+/// By default the kernel is anchored at [default_anchor](fn.default_anchor.html)
+/// and both source and destination ranges cover the whole line
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FilterPlan {
+    length: usize,
+    kernel_size: usize,
+    anchor: usize,
+    src: Range<usize>,
+    dst: Range<usize>,
+}
+
+impl FilterPlan {
+    /// Create plan builder for image line (or column) of `length` pixels
+    /// and kernel of `kernel_size` elements
+    pub fn new(length: usize, kernel_size: usize) -> Self {
+        let whole = Range::new(0..length);
+        Self { length, kernel_size, anchor: default_anchor(kernel_size), src: whole, dst: whole }
+    }
+
+    /// Set index of kernel element aligned with the output pixel
+    pub fn anchor(self, anchor: usize) -> Self {
+        Self { anchor, ..self }
+    }
+
+    /// Set source pixel range in the line
+    pub fn src_range(self, src: Range<usize>) -> Self {
+        Self { src, ..self }
+    }
+
+    /// Set destination pixel range in the destination line, must have
+    /// the same length as the source range
+    pub fn dst_range(self, dst: Range<usize>) -> Self {
+        Self { dst, ..self }
+    }
+
+    /// Create iteration plan, returns error if the kernel is empty, 
+    /// the anchor lies outside the kernel, any range is inverted or
+    /// the ranges have different lengths
+    pub fn try_build(self) -> Result<Vec<FilterIteration>, Error> {
+        check_kernel(self.kernel_size, self.anchor)?;
+
+        for range in [self.src, self.dst] {
+            if range.start > range.end {
+                return Err(Error::InvalidLineRange(range));
+            }
+        }
+
+        if self.src.length() != self.dst.length() {
+            return Err(Error::LengthMismatch {
+                expected: self.src.length(),
+                actual: self.dst.length(),
+            });
+        }
+
+        Ok(create_filter_plan(
+            self.length, self.kernel_size, self.anchor, 
+            Range::from(self.src), Range::from(self.dst),
+        ))
+    }
+
+    /// Create iteration plan, panics on invalid arguments, 
+    /// see [try_build](#method.try_build)
+    pub fn build(self) -> Vec<FilterIteration> {
+        match self.try_build() {
+            Ok(plan) => plan,
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+/// Prepare iteration plan for a filter
+///
+/// # Arguments
+///
+/// * `length` - length of image line for horizontal filter,
+///   or image height for vertical filter
+/// * `kernel_size` - size of a kernel
+/// * `anchor` - index of kernel element aligned with the output pixel,
+///   `(kernel_size - 1)/2` for centered kernels, `0` for causal kernels
+/// * `src` - source pixel range in a line/column
+/// * `dst` - destination pixel range in a line/column
+pub fn create_filter_plan(
+    length: usize,
+    kernel_size: usize,
+    anchor: usize,
+    src: Range<isize>,
+    dst: Range<isize>,
+) -> Vec<FilterIteration> {
+    let anchor = anchor as isize;
+    let first = anchor - (kernel_size as isize - 1);
+    let last = anchor + 1;
+    let shift = dst.start - src.start;
+
+    (first..last)
+        .map(|position| iteration(position, shift, anchor, length as isize, src))
+        .collect()
+}
+
+/// Default anchor of a kernel, the center element for odd
+/// kernel sizes, or the element left to the center for even sizes
+pub fn default_anchor(kernel_size: usize) -> usize {
+    kernel_size.saturating_sub(1)/2
+}
+
+/// Checks that kernel is not empty and the anchor lies inside the kernel
+pub fn check_kernel(kernel_size: usize, anchor: usize) -> Result<(), Error> {
+    if kernel_size == 0 || anchor >= kernel_size {
+        Err(Error::InvalidKernel { length: kernel_size, anchor })
+    } else {
+        Ok(())
+    }
+}
+
+/// Panics if kernel is empty or the anchor lies outside the kernel
+pub fn assert_kernel_valid(kernel_size: usize, anchor: usize) {
+    if let Err(error) = check_kernel(kernel_size, anchor) {
+        panic!("{}", error)
+    }
+}
+
+fn iteration(
+    pos: isize, 
+    shift: isize,
+    anchor: isize, 
+    length: isize, 
+    src: Range<isize>,
+) -> FilterIteration {
+    // Source range is kept within the line and never gets inverted
+    let start = min(length, max(0, src.start + pos));
+    let src_range = Range::new(start..max(start, min(length, src.end + pos)));
+
+    FilterIteration { 
+        src_range: Range::from(src_range),
+        dst_range: Range::from(
+            Range::new((src_range.start - pos + shift)..(src_range.end - pos + shift))
+        ),
+        kernel_index: (anchor - pos) as usize,
+        outside_start: max(0, - (src.start + pos)) as usize,
+        outside_end: max(0, src.end - length + pos) as usize,
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernel_size_1_image_size_3_from_0_to_3() {
+        assert_eq!(
+            create_filter_plan(3, 1, 0, Range::new(0..3), Range::new(0..3)),
+            vec![
+                FilterIteration {
+                    src_range: Range::new(0..3),
+                    dst_range: Range::new(0..3),
+                    kernel_index: 0,
+                    outside_start: 0,
+                    outside_end: 0
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn kernel_size_1_image_size_3_from_1_to_2() {
+        assert_eq!(
+            create_filter_plan(3, 1, 0, Range::new(1..2), Range::new(1..2)),
+            vec![
+                FilterIteration {
+                    src_range: Range::new(1..2),
+                    dst_range: Range::new(1..2),
+                    kernel_index: 0,
+                    outside_start: 0,
+                    outside_end: 0
+                }
+            ]
+        )
+    } 
+
+    #[test]
+    fn kernel_size_3_image_size_3_from_0_to_3() {
+        assert_eq!(
+            create_filter_plan(3, 3, 1, Range::new(0..3), Range::new(0..3)),
+            vec![
+                FilterIteration {
+                    src_range: Range::new(0..2),
+                    dst_range: Range::new(1..3),
+                    kernel_index: 2,
+                    outside_start: 1,
+                    outside_end: 0
+                },
+                FilterIteration {
+                    src_range: Range::new(0..3),
+                    dst_range: Range::new(0..3),
+                    kernel_index: 1,
+                    outside_start: 0,
+                    outside_end: 0
+                },
+                FilterIteration {
+                    src_range: Range::new(1..3),
+                    dst_range: Range::new(0..2),
+                    kernel_index: 0,
+                    outside_start: 0,
+                    outside_end: 1
+                },                                
+            ]
+        )
+    }    
+
+    #[test]
+    fn kernel_size_3_image_size_3_from_1_to_2() {
+        assert_eq!(
+            create_filter_plan(3, 3, 1, Range::new(1..2), Range::new(1..2)),
+            vec![
+                FilterIteration {
+                    src_range: Range::new(0..1),
+                    dst_range: Range::new(1..2),
+                    kernel_index: 2,
+                    outside_start: 0,
+                    outside_end: 0
+                },
+                FilterIteration {
+                    src_range: Range::new(1..2),
+                    dst_range: Range::new(1..2),
+                    kernel_index: 1,
+                    outside_start: 0,
+                    outside_end: 0
+                },
+                FilterIteration {
+                    src_range: Range::new(2..3),
+                    dst_range: Range::new(1..2),
+                    kernel_index: 0,
+                    outside_start: 0,
+                    outside_end: 0
+                },                                
+            ]
+        )
+    }   
+    
+    #[test]
+    fn kernel_size_3_image_size_1_from_0_to_1() {
+        assert_eq!(
+            create_filter_plan(1, 3, 1, Range::new(0..1), Range::new(0..1)),
+            vec![
+                FilterIteration {
+                    src_range: Range::new(0..0),
+                    dst_range: Range::new(1..1),
+                    kernel_index: 2,
+                    outside_start: 1,
+                    outside_end: 0
+                },
+                FilterIteration {
+                    src_range: Range::new(0..1),
+                    dst_range: Range::new(0..1),
+                    kernel_index: 1,
+                    outside_start: 0,
+                    outside_end: 0
+                },
+                FilterIteration {
+                    src_range: Range::new(1..1),
+                    dst_range: Range::new(0..0),
+                    kernel_index: 0,
+                    outside_start: 0,
+                    outside_end: 1
+                },                                
+            ]
+        )
+    }    
+
+    #[test]
+    fn kernel_size_2_anchor_0_image_size_3_from_0_to_3() {
+        assert_eq!(
+            create_filter_plan(3, 2, 0, Range::new(0..3), Range::new(0..3)),
+            vec![
+                FilterIteration {
+                    src_range: Range::new(0..2),
+                    dst_range: Range::new(1..3),
+                    kernel_index: 1,
+                    outside_start: 1,
+                    outside_end: 0
+                },
+                FilterIteration {
+                    src_range: Range::new(0..3),
+                    dst_range: Range::new(0..3),
+                    kernel_index: 0,
+                    outside_start: 0,
+                    outside_end: 0
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn kernel_size_5_image_size_1_ranges_are_not_inverted() {
+        let plan = create_filter_plan(1, 5, 2, Range::new(0..1), Range::new(0..1));
+
+        for iteration in plan {
+            assert!(iteration.src_range.start <= iteration.src_range.end);
+            assert!(iteration.src_range.end <= 1);
+        }
+    }
+
+    #[test]
+    fn builder_matches_create_filter_plan() {
+        let plan = FilterPlan::new(7, 4).anchor(2).src_range(Range::new(1..5)).dst_range(Range::new(0..4)).build();
+        assert_eq!(plan, create_filter_plan(7, 4, 2, Range::new(1..5), Range::new(0..4)));
+        assert_eq!(FilterPlan::new(3, 3).build(), create_filter_plan(3, 3, 1, Range::new(0..3), Range::new(0..3)));
+    }
+
+    #[test]
+    fn builder_reports_invalid_arguments() {
+        assert_eq!(
+            FilterPlan::new(3, 3).anchor(3).try_build(), 
+            Err(Error::InvalidKernel { length: 3, anchor: 3 })
+        );
+        assert_eq!(
+            FilterPlan::new(3, 3).dst_range(Range::new(0..2)).try_build(), 
+            Err(Error::LengthMismatch { expected: 3, actual: 2 })
+        );
+        let inverted = Range { start: 2, end: 1 };
+        assert_eq!(
+            FilterPlan::new(3, 3).src_range(inverted).dst_range(inverted).try_build(), 
+            Err(Error::InvalidLineRange(inverted))
+        );
+    }
+}
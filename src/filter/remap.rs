@@ -0,0 +1,91 @@
+use crate::{Img, ImgBuf, Vec2d, AsPrimitive, geometry::round_as};
+
+/// Resamples `input` image by a coordinate map
+/// 
+/// Output image has the same size as `map`, output pixel `(x, y)` is
+/// interpolated bilinearly from `input` at position `map[(x, y)]`. Pixel
+/// centers are at integer coordinates, positions further than half a pixel
+/// outside of `input` produce default (zero) pixels. Integer pixel types
+/// are rounded to the nearest value.
+/// 
+/// Maps are usually precomputed once and reused for many images, see e.g.
+/// [undistort_map](fn.undistort_map.html).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::remap};
+/// let input = ImgBuf::<u8>::from_vec(ImgSize::new(2, 1), vec![10, 20]);
+/// let map = ImgBuf::<Vec2d<f32>>::from_vec(ImgSize::new(3, 1), vec![
+///     Vec2d::new(1.0, 0.0), Vec2d::new(0.5, 0.0), Vec2d::new(5.0, 0.0),
+/// ]);
+/// assert_eq!(remap(&input, &map).line_ref(0), &[20, 15, 0]);
+/// ```
+pub fn remap<T>(input: &dyn Img<T>, map: &dyn Img<Vec2d<f32>>) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let size = map.size();
+    let mut output = ImgBuf::<T>::new(size);
+
+    for y in 0..size.y {
+        let positions = map.line_ref(y);
+
+        for x in 0..size.x {
+            if let Some(value) = bilinear(input, positions[x]) {
+                output[(x, y)] = round_as(value);
+            }
+        }
+    }
+
+    output
+}
+
+/// Bilinear interpolation at `position`, or `None` outside of the image
+pub(crate) fn bilinear<T: AsPrimitive<f64>>(image: &dyn Img<T>, position: Vec2d<f32>) -> Option<f64> {
+    let size = image.size();
+    let (px, py) = (position.x as f64, position.y as f64);
+
+    let inside = px > -0.5 && py > -0.5 
+        && px < size.x as f64 - 0.5 && py < size.y as f64 - 0.5;
+
+    if !inside {
+        return None;
+    }
+
+    let x = px.max(0.0).min((size.x - 1) as f64);
+    let y = py.max(0.0).min((size.y - 1) as f64);
+    let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(size.x - 1), (y0 + 1).min(size.y - 1));
+    let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+    let (top, bottom) = (image.line_ref(y0), image.line_ref(y1));
+    let upper = top[x0].as_()*(1.0 - fx) + top[x1].as_()*fx;
+    let lower = bottom[x0].as_()*(1.0 - fx) + bottom[x1].as_()*fx;
+    Some(upper*(1.0 - fy) + lower*fy)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgLinesMut, ImgSize};
+    use super::*;
+
+    #[test]
+    fn identity_map_copies_image() {
+        let size = ImgSize::new(5, 4);
+        let input = ImgBuf::<f32>::from_vec(size, (0..20).map(|v| v as f32*1.5).collect());
+        let mut map = ImgBuf::<Vec2d<f32>>::new(size);
+
+        for (position, value) in map.enumerate_pixels_mut() {
+            *value = position.cast::<f32>();
+        }
+
+        assert_eq!(remap(&input, &map), input);
+    }
+
+    #[test]
+    fn bilinear_interpolates_both_directions() {
+        let image = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![0, 10, 20, 30]);
+        assert_eq!(bilinear(&image, Vec2d::new(0.5, 0.5)), Some(15.0));
+        assert_eq!(bilinear(&image, Vec2d::new(1.4, 0.0)), Some(10.0));
+        assert_eq!(bilinear(&image, Vec2d::new(-0.6, 0.0)), None);
+    }
+}
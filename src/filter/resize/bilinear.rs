@@ -0,0 +1,87 @@
+use crate::{ImgSize, ImgBuf, Img, ImgMut, Pixel, AsPrimitive, geometry::round_as};
+
+/// Scale image to different resolution by bilinear interpolation,
+/// each pixel component is interpolated separately
+/// 
+/// Pixel centers of input and output images are aligned, so that image
+/// corners match, pixels outside image are replicated from border
+///
+/// # Arguments
+/// * `image` - input image 
+/// * `size` - target image size
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, filter::resize_bilinear_new};
+/// let image = ImgBuf::from_vec(ImgSize::new(2, 1), vec![Rgb::new(0u8, 100, 0), Rgb::new(100, 100, 200)]);
+/// let result = resize_bilinear_new(&image, ImgSize::new(4, 1));
+/// assert_eq!(result.line_ref(0), &[
+///     Rgb::new(0, 100, 0), Rgb::new(25, 100, 50), Rgb::new(75, 100, 150), Rgb::new(100, 100, 200)
+/// ]);
+/// ```
+pub fn resize_bilinear_new<P>(image: &dyn Img<P>, size: ImgSize) -> ImgBuf<P> 
+where P: Pixel + Default, P::Component: AsPrimitive<f64>, f64: AsPrimitive<P::Component> {
+    let mut result = ImgBuf::<P>::new(size);
+
+    if image.width() == 0 || image.height() == 0 {
+        return result;
+    }
+
+    let columns = interpolation_table(image.width(), size.x);
+    let rows = interpolation_table(image.height(), size.y);
+
+    for y in 0..size.y {
+        let (top, bottom, wy) = rows[y];
+        let (top, bottom) = (image.line_ref(top), image.line_ref(bottom));
+        let dst = result.line_mut(y);
+
+        for x in 0..size.x {
+            let (left, right, wx) = columns[x];
+
+            dst[x] = P::from_components(|c| {
+                let value = |pixel: &P| pixel.component(c).as_();
+                let upper = value(&top[left])*(1.0 - wx) + value(&top[right])*wx;
+                let lower = value(&bottom[left])*(1.0 - wx) + value(&bottom[right])*wx;
+                round_as(upper*(1.0 - wy) + lower*wy)
+            });
+        }
+    }
+
+    result
+}
+
+/// Source indices of the two neighbors and weight of the second one
+/// for each target position
+fn interpolation_table(source_size: usize, target_size: usize) -> Vec<(usize, usize, f64)> {
+    let scale = source_size as f64/target_size as f64;
+    let last = source_size as f64 - 1.0;
+
+    (0..target_size)
+        .map(|x| {
+            let position = ((x as f64 + 0.5)*scale - 0.5).max(0.0).min(last);
+            let first = position.floor() as usize;
+            let second = std::cmp::min(first + 1, source_size - 1);
+            (first, second, position - first as f64)
+        })
+        .collect()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_image_stays_constant() {
+        let image = ImgBuf::<f32>::new_init(ImgSize::new(3, 5), 0.25);
+        let result = resize_bilinear_new(&image, ImgSize::new(7, 2));
+        assert!(result.lines().flatten().all(|&x| x == 0.25));
+    }
+
+    #[test]
+    fn downscale_averages_neighbors() {
+        let image = ImgBuf::<u8>::from_vec(ImgSize::new(4, 1), vec![0, 100, 200, 40]);
+        assert_eq!(resize_bilinear_new(&image, ImgSize::new(2, 1)).into_vec(), vec![50, 120]);
+    }
+}
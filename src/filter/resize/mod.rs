@@ -1,3 +1,7 @@
 mod nearest;
+mod bilinear;
 
-pub use nearest::resize_nearest_new;
\ No newline at end of file
+pub use nearest::resize_nearest_new;
+#[cfg(feature = "gpu")]
+pub(crate) use nearest::scale_index_table;
+pub use bilinear::resize_bilinear_new;
\ No newline at end of file
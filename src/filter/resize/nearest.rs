@@ -28,7 +28,7 @@ pub fn resize_nearest_new<T: Copy + Default>(
 
 /// Generates lookup table for scaling source vector info target vector
 /// of different size
-fn scale_index_table(source_size: usize, target_size: usize) -> Vec<usize> {
+pub(crate) fn scale_index_table(source_size: usize, target_size: usize) -> Vec<usize> {
     (0..target_size)
         .map(|x| x*source_size/target_size)
         .collect()
@@ -0,0 +1,190 @@
+use crate::{Img, ImgMut, ImgBuf, ImgSize, AsPrimitive, color::Rgb};
+use super::{gaussian_blur_new, normalize_percentile};
+
+/// Percentiles clipped when stretching color restored retinex output
+const CLIP_PCT: f32 = 1.0;
+
+/// Color restoration parameters from Jobson, Rahman and Woodell (1997)
+const ALPHA: f32 = 125.0;
+const BETA: f32 = 46.0;
+
+/// Single scale retinex, estimates illumination by Gaussian blur with 
+/// standard deviation `sigma` and returns reflectance in logarithmic domain
+/// `ln(1 + I) - ln(1 + G*I)`
+/// 
+/// Output values are centered around zero, positive where the pixel is 
+/// brighter than its surround, use 
+/// [normalize_percentile](fn.normalize_percentile.html) to map them to 
+/// a displayable range. Large `sigma` (e.g. `80`) gives good color 
+/// rendition, small `sigma` (e.g. `15`) stronger local contrast.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::single_scale_retinex};
+/// // Gradient of illumination is removed from uniform reflectance
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(64, 1));
+/// image.line_mut(0).iter_mut().enumerate().for_each(|(x, v)| *v = (20 + 3*x) as u8);
+/// let result = single_scale_retinex(&image, 4.0);
+/// assert!(result[(32, 0)].abs() < 0.01);
+/// ```
+pub fn single_scale_retinex<T: AsPrimitive<f32>>(image: &dyn Img<T>, sigma: f32) -> ImgBuf<f32> {
+    let surround = gaussian_blur_new(image, sigma);
+    let mut output = ImgBuf::<f32>::new(image.size());
+
+    for y in 0..image.height() {
+        let (src, blur, dst) = (image.line_ref(y), surround.line_ref(y), output.line_mut(y));
+
+        for x in 0..dst.len() {
+            dst[x] = src[x].as_().max(0.0).ln_1p() - blur[x].max(0.0).ln_1p();
+        }
+    }
+
+    output
+}
+
+/// Multi-scale retinex, average of 
+/// [single_scale_retinex](fn.single_scale_retinex.html) outputs for 
+/// all `sigmas`, combining local contrast and color rendition of small 
+/// and large scales, typically `[15.0, 80.0, 250.0]`
+/// 
+/// Panics if `sigmas` is empty
+pub fn multi_scale_retinex<T: AsPrimitive<f32>>(image: &dyn Img<T>, sigmas: &[f32]) -> ImgBuf<f32> {
+    assert!(!sigmas.is_empty(), "No retinex scales given");
+    let mut output = ImgBuf::<f32>::new_init(image.size(), 0.0);
+    let weight = 1.0/sigmas.len() as f32;
+
+    for &sigma in sigmas {
+        let scale = single_scale_retinex(image, sigma);
+
+        for y in 0..image.height() {
+            for (dst, src) in output.line_mut(y).iter_mut().zip(scale.line_ref(y)) {
+                *dst += weight*src;
+            }
+        }
+    }
+
+    output
+}
+
+/// Multi-scale retinex with color restoration (MSRCR) enhancing unevenly 
+/// lit color images, e.g. tunnels or backlit subjects
+/// 
+/// Each color channel is processed by 
+/// [multi_scale_retinex](fn.multi_scale_retinex.html) and multiplied by 
+/// color restoration factor `β*(ln(α*I) - ln(R + G + B))`, which brings 
+/// back saturation lost by processing the channels independently. Result 
+/// is stretched to `[0, 255]` range, 1% of the darkest and brightest 
+/// values are clipped.
+/// 
+/// Panics if `sigmas` is empty
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, filter::multi_scale_retinex_rgb};
+/// // Stripes under dark illumination on the left and bright on the right
+/// let mut image = ImgBuf::<Rgb<u8>>::new(ImgSize::new(80, 8));
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     let light = if position.x < 40 { 1 } else { 8 };
+///     let value = if position.x % 4 < 2 { 5*light } else { 15*light };
+///     *pixel = Rgb::new(2*value as u8, value as u8, value as u8);
+/// }
+/// let result = multi_scale_retinex_rgb(&image, &[4.0, 16.0]);
+/// let contrast = |x: usize| result[(x + 2, 4)].g as f32 - result[(x, 4)].g as f32;
+/// // Stripe contrast differs 8 times in the input, but not in the output
+/// assert!((contrast(8) - contrast(60)).abs() < 0.3*contrast(60));
+/// // Red tone is preserved
+/// assert!(result[(10, 4)].r > result[(10, 4)].g);
+/// ```
+pub fn multi_scale_retinex_rgb(image: &dyn Img<Rgb<u8>>, sigmas: &[f32]) -> ImgBuf<Rgb<u8>> {
+    let size = image.size();
+    let channels = [
+        channel(image, |pixel| pixel.r), 
+        channel(image, |pixel| pixel.g), 
+        channel(image, |pixel| pixel.b),
+    ];
+
+    let retinex: Vec<_> = channels.iter().map(|plane| multi_scale_retinex(plane, sigmas)).collect();
+
+    // Components of all channels are stored side by side, so they are 
+    // stretched by common percentiles and color balance is preserved
+    let mut restored = ImgBuf::<f32>::new(ImgSize::new(3*size.x, size.y));
+
+    for y in 0..size.y {
+        let dst = restored.line_mut(y);
+
+        for x in 0..size.x {
+            let sum: f32 = channels.iter().map(|plane| plane.line_ref(y)[x] as f32).sum();
+
+            for c in 0..3 {
+                let value = channels[c].line_ref(y)[x] as f32;
+                let restoration = BETA*((ALPHA*value).ln_1p() - sum.ln_1p());
+                dst[3*x + c] = retinex[c].line_ref(y)[x]*restoration;
+            }
+        }
+    }
+
+    let stretched = normalize_percentile(&restored, CLIP_PCT, 100.0 - CLIP_PCT);
+    let mut output = ImgBuf::<Rgb<u8>>::new(size);
+
+    for y in 0..size.y {
+        let (src, dst) = (stretched.line_ref(y), output.line_mut(y));
+
+        for x in 0..size.x {
+            let component = |c: usize| (src[3*x + c]*255.0).round() as u8;
+            dst[x] = Rgb::new(component(0), component(1), component(2));
+        }
+    }
+
+    output
+}
+
+/// Extracts single component of color image
+fn channel(image: &dyn Img<Rgb<u8>>, component: impl Fn(&Rgb<u8>) -> u8) -> ImgBuf<u8> {
+    let mut output = ImgBuf::<u8>::new(image.size());
+
+    for y in 0..image.height() {
+        for (dst, src) in output.line_mut(y).iter_mut().zip(image.line_ref(y)) {
+            *dst = component(src);
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_image_has_zero_reflectance() {
+        let image = ImgBuf::<u16>::new_init(ImgSize::new(7, 5), 1000);
+        let result = multi_scale_retinex(&image, &[1.0, 3.0]);
+        assert!(result.lines().flatten().all(|v| v.abs() < 1e-4));
+    }
+
+    #[test]
+    fn retinex_equalizes_unevenly_lit_edges() {
+        // The same edge under dark and bright illumination
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(40, 1));
+
+        for (x, value) in image.line_mut(0).iter_mut().enumerate() {
+            let reflectance = if x % 10 < 5 { 1.0 } else { 2.0 };
+            let illumination = if x < 20 { 10.0 } else { 100.0 };
+            *value = reflectance*illumination;
+        }
+
+        let result = single_scale_retinex(&image, 2.0);
+        let dark = result[(4, 0)] - result[(5, 0)];
+        let bright = result[(34, 0)] - result[(35, 0)];
+        assert!(dark < 0.0 && bright < 0.0);
+        assert!((dark - bright).abs() < 0.1, "{} {}", dark, bright);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_scales_panic() {
+        multi_scale_retinex(&ImgBuf::<u8>::new(ImgSize::new(2, 2)), &[]);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{Img, ImgMut, AsPrimitive, geometry::round_as};
+use super::gaussian_blur_new;
+
+/// Sharpens `input` image by unsharp masking into `output` image
+/// 
+/// Each pixel is computed as `input + amount*(input - blurred)`, where 
+/// `blurred` is the input blurred by Gaussian filter with standard deviation
+/// `sigma`. Pixels where the absolute difference from blurred image is
+/// below `threshold` are copied unchanged, which avoids amplification of 
+/// noise in flat areas. Results are rounded for integer pixel types and
+/// clamped to the pixel type range.
+/// 
+/// Panics if input and output sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::unsharp_mask};
+/// let input = ImgBuf::<u8>::from_vec(ImgSize::new(4, 1), vec![50, 50, 200, 200]);
+/// let mut output = ImgBuf::new_like(&input);
+/// unsharp_mask(&input, &mut output, 1.0, 1.0, 0.0);
+/// let line = output.line_ref(0);
+/// assert!(line[1] < 50 && line[2] > 200);
+/// ```
+pub fn unsharp_mask<T>(
+    input: &dyn Img<T>, 
+    output: &mut dyn ImgMut<T>, 
+    sigma: f32, 
+    amount: f32, 
+    threshold: f32
+) where T: AsPrimitive<f32> + AsPrimitive<f64>, f64: AsPrimitive<T> {
+    assert_eq!(input.size(), output.size(), "Input and output sizes differ");
+    let blurred = gaussian_blur_new(input, sigma);
+
+    for y in 0..input.height() {
+        let src = input.line_ref(y);
+        let smooth = blurred.line_ref(y);
+        let dst = output.line_mut(y);
+
+        for x in 0..dst.len() {
+            let value: f32 = src[x].as_();
+            let difference = value - smooth[x];
+
+            dst[x] = if difference.abs() < threshold {
+                src[x]
+            } else {
+                round_as((value + amount*difference) as f64)
+            };
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImgBuf, ImgSize};
+
+    #[test]
+    fn threshold_keeps_small_differences() {
+        let input = ImgBuf::<u8>::from_vec(ImgSize::new(4, 1), vec![100, 102, 100, 250]);
+        let mut output = ImgBuf::new_like(&input);
+        unsharp_mask(&input, &mut output, 0.8, 2.0, 10.0);
+        assert_eq!(&output.line_ref(0)[..2], &[100, 102]);
+        assert_eq!(output.line_ref(0)[3], 255);
+    }
+
+    #[test]
+    fn flat_float_image_is_unchanged() {
+        let input = ImgBuf::<f32>::new_init(ImgSize::new(5, 5), 0.5);
+        let mut output = ImgBuf::new_like(&input);
+        unsharp_mask(&input, &mut output, 2.0, 3.0, 0.0);
+        assert!(output.lines().all(|line| line.iter().all(|v| (v - 0.5).abs() < 1e-5)));
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{Img, ImgBuf, Pixel};
+use super::map_new;
+
+/// Binary threshold of each pixel component, components greater than
+/// `level` are set to `max_value`, other components to default value
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, filter::threshold_new};
+/// let image = ImgBuf::from_vec(ImgSize::new(2, 1), vec![Rgb::new(10u8, 200, 128), Rgb::new(0, 0, 255)]);
+/// let binary = threshold_new(&image, 128, 255);
+/// assert_eq!(binary.line_ref(0), &[Rgb::new(0, 255, 0), Rgb::new(0, 0, 255)]);
+/// ```
+pub fn threshold_new<P>(image: &dyn Img<P>, level: P::Component, max_value: P::Component) -> ImgBuf<P> 
+where P: Pixel + Default, P::Component: PartialOrd + Default {
+    map_new(image, |pixel: P| pixel.map_components(|x| {
+        if x > level { max_value } else { Default::default() }
+    }))
+}
@@ -0,0 +1,149 @@
+use crate::{Img, ImgMut, ImgBuf, color::Rgb};
+
+/// Small value avoiding logarithm of zero in log-average luminance
+const DELTA: f64 = 1e-6;
+
+/// Maps high dynamic range image to 8-bit image using global Reinhard 
+/// operator
+/// 
+/// Image is scaled so its log-average value maps to `key` (typically
+/// `0.18`), then compressed by `L*(1 + L/white_point²)/(1 + L)`, where
+/// `white_point` is the smallest scaled value mapped to pure white. 
+/// Output values are linear (no gamma encoding is applied).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::tonemap_reinhard};
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(3, 1), vec![0.0, 1.0, 1000.0]);
+/// let result = tonemap_reinhard(&image, 0.18, 2.0);
+/// assert_eq!(result.line_ref(0)[0], 0);
+/// assert_eq!(result.line_ref(0)[2], 255);
+/// ```
+pub fn tonemap_reinhard(image: &dyn Img<f32>, key: f32, white_point: f32) -> ImgBuf<u8> {
+    let scale = key as f64/log_average(image.lines().flat_map(|line| line.iter().copied()));
+    let operator = reinhard(scale, white_point as f64);
+    tonemap(image, |value| to_u8(operator(value as f64)))
+}
+
+/// Maps high dynamic range color image to 8-bit color image using global 
+/// Reinhard operator applied to pixel luminance
+/// 
+/// Luminance is computed using Rec. 709 coefficients, color components 
+/// are scaled by the ratio of mapped and original luminance, so hue and 
+/// saturation are preserved, see [tonemap_reinhard](fn.tonemap_reinhard.html)
+/// for description of the parameters.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, filter::tonemap_reinhard_rgb};
+/// let image = ImgBuf::<Rgb<f32>>::from_vec(ImgSize::new(2, 1), vec![
+///     Rgb::new(2.0, 1.0, 0.0), Rgb::new(0.5, 0.5, 0.5)
+/// ]);
+/// let result = tonemap_reinhard_rgb(&image, 0.18, 1.0);
+/// assert_eq!(result[(0, 0)], Rgb::new(122, 61, 0));
+/// ```
+pub fn tonemap_reinhard_rgb(image: &dyn Img<Rgb<f32>>, key: f32, white_point: f32) -> ImgBuf<Rgb<u8>> {
+    let lines = image.lines().flat_map(|line| line.iter().map(|&pixel| luminance(pixel)));
+    let operator = reinhard(key as f64/log_average(lines), white_point as f64);
+
+    tonemap(image, |pixel| {
+        let original = luminance(pixel) as f64;
+        let ratio = if original > 0.0 { operator(original)/original } else { 0.0 };
+        Rgb::new(
+            to_u8(pixel.r as f64*ratio), 
+            to_u8(pixel.g as f64*ratio), 
+            to_u8(pixel.b as f64*ratio),
+        )
+    })
+}
+
+/// Maps high dynamic range image to 8-bit image using logarithmic 
+/// operator `ln(1 + value)/ln(1 + max)`, where `max` is the maximal image value
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::tonemap_log};
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(3, 1), vec![0.0, 15.0, 255.0]);
+/// assert_eq!(tonemap_log(&image).line_ref(0), &[0, 128, 255]);
+/// ```
+pub fn tonemap_log(image: &dyn Img<f32>) -> ImgBuf<u8> {
+    let max = image.lines().flat_map(|line| line.iter().copied()).fold(0.0, f32::max) as f64;
+    let scale = if max > 0.0 { 1.0/max.ln_1p() } else { 0.0 };
+    tonemap(image, |value| to_u8((value.max(0.0) as f64).ln_1p()*scale))
+}
+
+/// Rec. 709 luminance of linear RGB pixel
+fn luminance(pixel: Rgb<f32>) -> f32 {
+    0.2126*pixel.r + 0.7152*pixel.g + 0.0722*pixel.b
+}
+
+/// Geometric mean of `values`, `1.0` if there are no values
+fn log_average<I: Iterator<Item=f32>>(values: I) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| {
+        (sum + (DELTA + value.max(0.0) as f64).ln(), count + 1)
+    });
+
+    if count == 0 { 1.0 } else { (sum/count as f64).exp() }
+}
+
+/// Reinhard operator applied to values multiplied by `scale`
+fn reinhard(scale: f64, white_point: f64) -> impl Fn(f64) -> f64 {
+    let white_squared = white_point*white_point;
+
+    move |value| {
+        let scaled = value.max(0.0)*scale;
+        scaled*(1.0 + scaled/white_squared)/(1.0 + scaled)
+    }
+}
+
+/// Converts value in `[0, 1]` range to rounded and clipped 8-bit value
+fn to_u8(value: f64) -> u8 {
+    (value*255.0).round().clamp(0.0, 255.0) as u8
+}
+
+/// Maps image pixels using `function` into a new image
+fn tonemap<S: Copy, D: Copy + Default, F: Fn(S) -> D>(image: &dyn Img<S>, function: F) -> ImgBuf<D> {
+    let mut output = ImgBuf::<D>::new(image.size());
+
+    for y in 0..image.height() {
+        let src = image.line_ref(y);
+        let dst = output.line_mut(y);
+
+        for x in 0..dst.len() {
+            dst[x] = function(src[x]);
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn reinhard_is_monotonic() {
+        let values: Vec<f32> = (0..100).map(|v| (v as f32).powi(3)).collect();
+        let image = ImgBuf::<f32>::from_vec(ImgSize::new(100, 1), values);
+        let result = tonemap_reinhard(&image, 0.18, 1e9);
+        assert!(result.line_ref(0).windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn gray_rgb_matches_grayscale_operator() {
+        let values = vec![0.5, 2.0, 8.0];
+        let gray = ImgBuf::<f32>::from_vec(ImgSize::new(3, 1), values.clone());
+        let rgb = ImgBuf::from_vec(ImgSize::new(3, 1), values.iter().map(|&v| Rgb::new(v, v, v)).collect());
+        let expected = tonemap_reinhard(&gray, 0.3, 2.0);
+        let result = tonemap_reinhard_rgb(&rgb, 0.3, 2.0);
+
+        for x in 0..3 {
+            let pixel = result[(x, 0)];
+            assert!((pixel.r as i32 - expected[(x, 0)] as i32).abs() <= 1);
+            assert_eq!((pixel.r, pixel.g), (pixel.b, pixel.b));
+        }
+    }
+}
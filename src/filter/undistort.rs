@@ -0,0 +1,104 @@
+use crate::{Img, ImgBuf, ImgSize, Vec2d, AsPrimitive};
+use super::remap;
+
+/// Computes coordinate map removing lens distortion from images of `size`
+/// 
+/// Uses Brown-Conrady model with the pinhole `camera_matrix` 
+/// `[[fx, s, cx], [0, fy, cy], [0, 0, 1]]` and `distortion` coefficients 
+/// ordered as `[k1, k2, p1, p2, k3]` (radial `k` and tangential `p`, the
+/// same order as used by OpenCV), missing coefficients are treated as 
+/// zeros. Every undistorted pixel is mapped to its position in the 
+/// distorted image, the map is meant to be computed once and applied to
+/// all frames of a video stream using [remap](fn.remap.html).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::undistort_map};
+/// let camera = [[100.0, 0.0, 50.0], [0.0, 100.0, 40.0], [0.0, 0.0, 1.0]];
+/// let map = undistort_map(ImgSize::new(100, 80), &camera, &[0.1]);
+/// // principal point stays in place, barrel distortion pulls corners inwards
+/// assert_eq!(map[(50, 40)], Vec2d::new(50.0, 40.0));
+/// assert!(map[(0, 0)].x < 0.0);
+/// ```
+pub fn undistort_map(size: ImgSize, camera_matrix: &[[f64; 3]; 3], distortion: &[f64]) -> ImgBuf<Vec2d<f32>> {
+    let coefficient = |index: usize| distortion.get(index).copied().unwrap_or(0.0);
+    let (k1, k2, p1, p2, k3) = (coefficient(0), coefficient(1), coefficient(2), coefficient(3), coefficient(4));
+    let [[fx, skew, cx], [_, fy, cy], _] = *camera_matrix;
+    let mut map = ImgBuf::<Vec2d<f32>>::new(size);
+
+    for v in 0..size.y {
+        for u in 0..size.x {
+            // Normalized camera coordinates of the undistorted pixel
+            let y = (v as f64 - cy)/fy;
+            let x = (u as f64 - cx - skew*y)/fx;
+            let r2 = x*x + y*y;
+            let radial = 1.0 + r2*(k1 + r2*(k2 + r2*k3));
+            let xd = x*radial + 2.0*p1*x*y + p2*(r2 + 2.0*x*x);
+            let yd = y*radial + p1*(r2 + 2.0*y*y) + 2.0*p2*x*y;
+            map[(u, v)] = Vec2d::new((fx*xd + skew*yd + cx) as f32, (fy*yd + cy) as f32);
+        }
+    }
+
+    map
+}
+
+/// Removes lens distortion from `image`
+/// 
+/// Computes the map by [undistort_map](fn.undistort_map.html) and applies
+/// it by [remap](fn.remap.html), pixels mapped outside of the distorted 
+/// image are set to zero. When processing many images taken by the same
+/// camera, compute the map once and use `remap` directly.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::undistort};
+/// let image = ImgBuf::<u8>::new_init(ImgSize::new(20, 10), 50);
+/// let camera = [[20.0, 0.0, 10.0], [0.0, 20.0, 5.0], [0.0, 0.0, 1.0]];
+/// // without distortion the image is unchanged
+/// assert_eq!(undistort(&image, &camera, &[]), image);
+/// ```
+pub fn undistort<T>(image: &dyn Img<T>, camera_matrix: &[[f64; 3]; 3], distortion: &[f64]) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    remap(image, &undistort_map(image.size(), camera_matrix, distortion))
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tangential_distortion_shifts_points() {
+        let camera = [[100.0, 0.0, 50.0], [0.0, 100.0, 50.0], [0.0, 0.0, 1.0]];
+        let map = undistort_map(ImgSize::new(101, 101), &camera, &[0.0, 0.0, 0.01, 0.0]);
+        // point at x = 0.5 (normalized) on the horizontal axis: yd = p1*r² = 0.0025
+        let position = map[(100, 50)];
+        assert!((position.x - 100.0).abs() < 1e-4);
+        assert!((position.y - 50.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn radial_distortion_is_removed() {
+        let camera = [[40.0, 0.0, 30.0], [0.0, 40.0, 30.0], [0.0, 0.0, 1.0]];
+        let distortion = [-0.2, 0.05];
+        let size = ImgSize::new(61, 61);
+        let map = undistort_map(size, &camera, &distortion);
+
+        // distorted image of a vertical straight line x = 45
+        let mut distorted = ImgBuf::<f32>::new(size);
+
+        for v in 0..size.y {
+            let position = map[(45, v)];
+            let (x, y) = (position.x.round() as usize, position.y.round() as usize);
+            distorted[(x, y)] = 1.0;
+        }
+
+        // after undistortion the line is straight again
+        let restored = undistort(&distorted, &camera, &distortion);
+
+        for v in 10..50 {
+            assert!(restored[(45, v)] > 0.2, "row {}", v);
+        }
+    }
+}
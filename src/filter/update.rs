@@ -17,7 +17,7 @@ use crate::{Range2d, ImgMut, ImgRange};
 pub fn update_range<T: Copy, F>(image: &mut dyn ImgMut<T>, range: ImgRange, operator: F) 
 where F : Fn(T) -> T {
     // Assure that range is within image
-    let range = Range2d::<usize>::from(range.intersect(image.range()));
+    let range = Range2d::<usize>::from(range.clamp_to(image.range()));
 
     for line in range.y.start..range.y.end {
         let dst = &mut image.line_mut(line)[range.x.start..range.x.end];
@@ -46,16 +46,83 @@ where F : Fn(T) -> T {
     update_range(image, range, operator)
 }
 
+/// Update specific range of the given image using an operator receiving
+/// pixel coordinates `x`, `y` in the image together with the pixel value
+/// 
+/// Out of range pixels are ignored.
+/// 
+/// # Examples
+///
+/// Add horizontal gradient to the bottom line of an image
+/// ```
+/// use nanocv::{*, filter::update_range_indexed};
+/// let mut img = ImgBuf::<u8>::new_init(ImgSize::new(3, 2), 10);
+/// update_range_indexed(&mut img, Range2d::new(0..3, 1..2), |x, _, v| v + x as u8);
+/// assert_eq!(img.line_ref(0), &[10, 10, 10]);
+/// assert_eq!(img.line_ref(1), &[10, 11, 12]);
+/// ```
+pub fn update_range_indexed<T: Copy, F>(image: &mut dyn ImgMut<T>, range: ImgRange, operator: F) 
+where F : Fn(usize, usize, T) -> T {
+    // Assure that range is within image
+    let range = Range2d::<usize>::from(range.clamp_to(image.range()));
+
+    for line in range.y.start..range.y.end {
+        let dst = &mut image.line_mut(line)[range.x.start..range.x.end];
+
+        for (offset, value) in dst.iter_mut().enumerate() {
+            *value = operator(range.x.start + offset, line, *value);
+        }
+    }    
+}
+
+/// Update the given image using an operator receiving pixel coordinates,
+/// see [update_range_indexed](fn.update_range_indexed.html)
+/// 
+/// # Examples
+///
+/// Generate radial mask
+/// ```
+/// use nanocv::{*, filter::update_indexed};
+/// let mut img = ImgBuf::<u8>::new(ImgSize::new(5, 5));
+/// update_indexed(&mut img, |x, y, _| {
+///     let (dx, dy) = (x as i32 - 2, y as i32 - 2);
+///     if dx*dx + dy*dy <= 1 { 255 } else { 0 }
+/// });
+/// assert_eq!(img.line_ref(1), &[0, 0, 255, 0, 0]);
+/// assert_eq!(img.line_ref(2), &[0, 255, 255, 255, 0]);
+/// ```
+pub fn update_indexed<T: Copy, F>(image: &mut dyn ImgMut<T>, operator: F) 
+where F : Fn(usize, usize, T) -> T {
+    let range = image.range();  
+    update_range_indexed(image, range, operator)
+}
+
 // ================================== TESTS ==================================
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ImgSize, ImgBuf};
+    use crate::{Img, ImgSize, ImgBuf};
 
     #[test]
     fn test_image_update_0x0_does_not_panic() {
         let mut image = ImgBuf::<u8>::new(ImgSize::new(0, 0));
         update(&mut image, |x| x);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_image_update_range_outside_image_does_not_panic() {
+        let mut image = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+        update_range(&mut image, ImgRange::new(3..5, 0..2), |x| x + 1);
+        assert_eq!(image, ImgBuf::new(ImgSize::new(2, 2)));
+    }
+
+    #[test]
+    fn test_image_update_range_indexed_passes_image_coordinates() {
+        let mut image = ImgBuf::<usize>::new_init(ImgSize::new(4, 3), 0);
+        update_range_indexed(&mut image, ImgRange::new(-1..2, 1..5), |x, y, _| 10*y + x);
+        assert_eq!(image.line_ref(0), &[0, 0, 0, 0]);
+        assert_eq!(image.line_ref(1), &[10, 11, 0, 0]);
+        assert_eq!(image.line_ref(2), &[20, 21, 0, 0]);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{AsPrimitive, Img, ImgBuf, geometry::{Point, solve}};
+
+/// Radial polynomial model of vignetting, relative brightness at distance
+/// `r` from the center is `1 + k1*r² + k2*r⁴ + k3*r⁶`, where `r` is 
+/// normalized by `radius`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VignettingModel {
+    /// Optical center of the vignetting, in pixels
+    pub center: Point,
+    /// Distance (in pixels) normalized to `r = 1`, usually half 
+    /// of the image diagonal
+    pub radius: f64,
+    /// Polynomial coefficients `[k1, k2, k3]`
+    pub coefficients: [f64; 3],
+}
+
+impl VignettingModel {
+    /// Relative brightness at position `(x, y)`, `1` at the center
+    pub fn gain(&self, x: f64, y: f64) -> f64 {
+        let r2 = (Point::new(x, y) - self.center).length_squared()/(self.radius*self.radius);
+        let [k1, k2, k3] = self.coefficients;
+        1.0 + r2*(k1 + r2*(k2 + r2*k3))
+    }
+}
+
+/// Removes vignetting described by `model` by dividing every pixel 
+/// by the model gain at its position
+/// 
+/// Positions where the model gain is not positive are set to zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, filter::{correct_vignetting, VignettingModel}};
+/// let model = VignettingModel { 
+///     center: Point::new(2.0, 0.0), radius: 2.0, coefficients: [-0.5, 0.0, 0.0] 
+/// };
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(5, 1), vec![50.0, 87.5, 100.0, 87.5, 50.0]);
+/// assert_eq!(correct_vignetting(&image, &model).line_ref(0), &[100.0; 5]);
+/// ```
+pub fn correct_vignetting<T: AsPrimitive<f32>>(image: &dyn Img<T>, model: &VignettingModel) -> ImgBuf<f32> {
+    let size = image.size();
+    let mut output = ImgBuf::<f32>::new(size);
+
+    for y in 0..size.y {
+        let line = image.line_ref(y);
+
+        for x in 0..size.x {
+            let gain = model.gain(x as f64, y as f64);
+
+            if gain > 0.0 {
+                output[(x, y)] = (line[x].as_() as f64/gain) as f32;
+            }
+        }
+    }
+
+    output
+}
+
+/// Fits vignetting model to a flat field frame
+/// 
+/// Model center is placed to the image center and the radius is half 
+/// of the image diagonal, brightness scale and polynomial coefficients 
+/// are fitted by least squares, so the flat frame does not need to be 
+/// normalized. Returns `None` for images too small to fit the model 
+/// or flat frames with zero brightness at the center.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, filter::{estimate_vignetting, correct_vignetting}};
+/// let mut flat = ImgBuf::<f32>::new(ImgSize::new(40, 30));
+/// 
+/// for (position, pixel) in flat.enumerate_pixels_mut() {
+///     let offset = position.cast::<f64>() - Vec2d::new(19.5, 14.5);
+///     // squared distance normalized by half of the diagonal
+///     let r2 = offset.length_squared()/(19.5*19.5 + 14.5*14.5);
+///     *pixel = (1000.0*(1.0 - 0.3*r2 + 0.05*r2*r2)) as f32;
+/// }
+/// 
+/// let model = estimate_vignetting(&flat).unwrap();
+/// assert!((model.coefficients[0] + 0.3).abs() < 1e-4);
+/// 
+/// let corrected = correct_vignetting(&flat, &model);
+/// assert!(corrected.lines().all(|line| line.iter().all(|v| (v - 1000.0).abs() < 0.1)));
+/// ```
+pub fn estimate_vignetting<T: AsPrimitive<f32>>(flat: &dyn Img<T>) -> Option<VignettingModel> {
+    let size = flat.size();
+
+    if size.x < 2 || size.y < 2 {
+        return None;
+    }
+
+    let center = Point::new((size.x - 1) as f64/2.0, (size.y - 1) as f64/2.0);
+    let radius = center.length();
+
+    // flat = a + a*k1*r² + a*k2*r⁴ + a*k3*r⁶ is linear in (a, a*k1, a*k2, a*k3)
+    let mut ata = vec![0.0; 16];
+    let mut atb = vec![0.0; 4];
+
+    for y in 0..size.y {
+        let line = flat.line_ref(y);
+
+        for x in 0..size.x {
+            let r2 = (Point::new(x as f64, y as f64) - center).length_squared()/(radius*radius);
+            let row = [1.0, r2, r2*r2, r2*r2*r2];
+            let value = line[x].as_() as f64;
+
+            for i in 0..4 {
+                atb[i] += row[i]*value;
+
+                for j in 0..4 {
+                    ata[i*4 + j] += row[i]*row[j];
+                }
+            }
+        }
+    }
+
+    let solution = solve(ata, atb)?;
+    let scale = solution[0];
+
+    if scale.abs() < f64::EPSILON {
+        return None;
+    }
+
+    Some(VignettingModel {
+        center,
+        radius,
+        coefficients: [solution[1]/scale, solution[2]/scale, solution[3]/scale],
+    })
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn uniform_flat_has_no_vignetting() {
+        let model = estimate_vignetting(&ImgBuf::<u16>::new_init(ImgSize::new(16, 9), 3000)).unwrap();
+        assert!(model.coefficients.iter().all(|k| k.abs() < 1e-9));
+        assert_eq!(model.center, Point::new(7.5, 4.0));
+    }
+
+    #[test]
+    fn degenerate_flats_are_rejected() {
+        assert_eq!(estimate_vignetting(&ImgBuf::<u8>::new_init(ImgSize::new(1, 10), 9)), None);
+        assert_eq!(estimate_vignetting(&ImgBuf::<u8>::new(ImgSize::new(10, 10))), None);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{Img, ImgBuf, ImgSize, Vec2d, AsPrimitive};
+
+/// Cost function comparing blocks of pixels
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum BlockMetric {
+    /// Sum of absolute differences
+    Sad,
+    /// Sum of squared differences
+    Ssd,
+}
+
+/// Estimates motion between two frames by exhaustive block matching
+/// 
+/// Frame `prev` is divided into blocks of `block_size` pixels (blocks
+/// at right and bottom borders may be smaller), for every block, all 
+/// displacements up to `search_radius` pixels in both directions keeping
+/// the block inside `next` frame are evaluated using `metric`, and the one 
+/// with the lowest cost is stored, so that `next[p + v] ≈ prev[p]`. When
+/// more displacements have equal cost, the shortest one is preferred.
+/// 
+/// Returns motion field with one vector per block. Panics if frame sizes
+/// differ or block size is zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, flow::{block_match, BlockMetric}};
+/// let pattern = |x: isize, y: isize| ((x*x*7 + y*y*3 + x*y) % 23) as u8;
+/// let mut prev = ImgBuf::<u8>::new(ImgSize::new(16, 16));
+/// let mut next = ImgBuf::<u8>::new(prev.size());
+/// 
+/// // the whole scene moves by 2 pixels to the right and 1 pixel up
+/// for y in 0..16 {
+///     for x in 0..16 {
+///         prev[(x, y)] = pattern(x as isize, y as isize);
+///         next[(x, y)] = pattern(x as isize - 2, y as isize + 1);
+///     }
+/// }
+/// 
+/// let field = block_match(&prev, &next, ImgSize::new(8, 8), 3, BlockMetric::Sad);
+/// assert_eq!(field.size(), ImgSize::new(2, 2));
+/// assert_eq!(field[(0, 1)], Vec2d::new(2, -1));
+/// ```
+pub fn block_match<T: AsPrimitive<f64>>(
+    prev: &dyn Img<T>, 
+    next: &dyn Img<T>, 
+    block_size: ImgSize, 
+    search_radius: usize, 
+    metric: BlockMetric
+) -> ImgBuf<Vec2d<i16>> {
+    assert_eq!(prev.size(), next.size(), "Frame sizes differ");
+    assert!(block_size.x > 0 && block_size.y > 0, "Block size must not be zero");
+
+    let size = prev.size();
+    let blocks = ImgSize::new(size.x.div_ceil(block_size.x), size.y.div_ceil(block_size.y));
+    let radius = search_radius as isize;
+    let mut field = ImgBuf::<Vec2d<i16>>::new(blocks);
+
+    for by in 0..blocks.y {
+        for bx in 0..blocks.x {
+            let start = Vec2d::new(bx*block_size.x, by*block_size.y);
+            let end = Vec2d::new(
+                std::cmp::min(start.x + block_size.x, size.x), 
+                std::cmp::min(start.y + block_size.y, size.y)
+            );
+
+            let mut best = (f64::INFINITY, 0, Vec2d::new(0, 0));
+
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let fits = start.x as isize + dx >= 0 && start.y as isize + dy >= 0
+                        && end.x as isize + dx <= size.x as isize 
+                        && end.y as isize + dy <= size.y as isize;
+
+                    if !fits {
+                        continue;
+                    }
+
+                    let cost = block_cost(prev, next, start, end, Vec2d::new(dx, dy), metric);
+                    let length = dx*dx + dy*dy;
+
+                    if cost < best.0 || (cost == best.0 && length < best.1) {
+                        best = (cost, length, Vec2d::new(dx, dy));
+                    }
+                }
+            }
+
+            field[(bx, by)] = best.2.cast::<i16>();
+        }
+    }
+
+    field
+}
+
+/// Cost of matching block between `start` and `end` in `prev` 
+/// with the block shifted by `shift` in `next`
+fn block_cost<T: AsPrimitive<f64>>(
+    prev: &dyn Img<T>, 
+    next: &dyn Img<T>, 
+    start: Vec2d<usize>, 
+    end: Vec2d<usize>, 
+    shift: Vec2d<isize>,
+    metric: BlockMetric
+) -> f64 {
+    let mut cost = 0.0;
+
+    for y in start.y..end.y {
+        let a = &prev.line_ref(y)[start.x..end.x];
+        let shifted = (start.x as isize + shift.x) as usize;
+        let b = &next.line_ref((y as isize + shift.y) as usize)[shifted..(shifted + a.len())];
+
+        for x in 0..a.len() {
+            let difference = a[x].as_() - b[x].as_();
+
+            cost += match metric {
+                BlockMetric::Sad => difference.abs(),
+                BlockMetric::Ssd => difference*difference,
+            };
+        }
+    }
+
+    cost
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_scene_has_zero_motion() {
+        let image = ImgBuf::<u16>::new_init(ImgSize::new(10, 7), 3);
+        let field = block_match(&image, &image, ImgSize::new(4, 4), 2, BlockMetric::Ssd);
+        assert_eq!(field.size(), ImgSize::new(3, 2));
+        assert!(field.lines().all(|line| line.iter().all(|&v| v == Vec2d::new(0, 0))));
+    }
+
+    #[test]
+    fn moving_spot_is_tracked() {
+        let mut prev = ImgBuf::<f32>::new(ImgSize::new(18, 18));
+        let mut next = prev.clone();
+        prev[(8, 8)] = 1.0;
+        next[(10, 7)] = 1.0;
+        let field = block_match(&prev, &next, ImgSize::new(6, 6), 2, BlockMetric::Ssd);
+        assert_eq!(field[(1, 1)], Vec2d::new(2, -1));
+        assert_eq!(field[(0, 0)], Vec2d::new(0, 0));
+    }
+}
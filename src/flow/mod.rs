@@ -0,0 +1,9 @@
+//! Motion estimation between successive frames
+//! 
+//! Available functions:
+//!  * [block_match](fn.block_match.html) - coarse motion vector field 
+//!    by exhaustive block matching
+
+mod block;
+
+pub use block::{block_match, BlockMetric};
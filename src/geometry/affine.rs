@@ -0,0 +1,263 @@
+use std::ops::Mul;
+use crate::{Vec2d, ImgRange};
+use super::linalg::least_squares;
+#[cfg(feature = "serde")]
+use serde_derive::{Serialize, Deserialize};
+
+/// Affine transformation of 2D points, represented by 2x3 matrix
+/// `[[a, b, tx], [c, d, ty]]` mapping point `(x, y)` into
+/// `(a*x + b*y + tx, c*x + d*y + ty)`
+///
+/// Transformations are composed by multiplication, `a*b` first applies
+/// `b` and then `a`, or equivalently `b.then(a)`
+///
+/// # Example
+/// ```
+/// use nanocv::{Vec2d, geometry::Affine2d};
+/// let transform = Affine2d::scale(2.0, 3.0).then(Affine2d::translation(Vec2d::new(1.0, 1.0)));
+/// assert_eq!(transform.transform_point(Vec2d::new(1.0, 1.0)), Vec2d::new(3.0, 4.0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Affine2d {
+    /// Rows of the transformation matrix
+    pub matrix: [[f64; 3]; 2],
+}
+
+impl Affine2d {
+    /// Create transformation from matrix rows
+    pub fn new(matrix: [[f64; 3]; 2]) -> Self {
+        Self { matrix }
+    }
+
+    /// Transformation that keeps all points in place
+    pub fn identity() -> Self {
+        Self::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]])
+    }
+
+    /// Transformation shifting points by `shift`
+    pub fn translation(shift: Vec2d<f64>) -> Self {
+        Self::new([[1.0, 0.0, shift.x], [0.0, 1.0, shift.y]])
+    }
+
+    /// Rotation around origin by `angle` (in radians), rotating `x` axis 
+    /// towards `y` axis
+    /// ```
+    /// use nanocv::{Vec2d, geometry::Affine2d};
+    /// let point = Affine2d::rotation(std::f64::consts::FRAC_PI_2).transform_point(Vec2d::new(1.0, 0.0));
+    /// assert!((point - Vec2d::new(0.0, 1.0)).length() < 1e-12);
+    /// ```
+    pub fn rotation(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new([[cos, -sin, 0.0], [sin, cos, 0.0]])
+    }
+
+    /// Rotation around `center` by `angle` (in radians)
+    /// ```
+    /// use nanocv::{Vec2d, geometry::Affine2d};
+    /// let center = Vec2d::new(2.0, 3.0);
+    /// assert_eq!(Affine2d::rotation_around(1.0, center).transform_point(center), center);
+    /// ```
+    pub fn rotation_around(angle: f64, center: Vec2d<f64>) -> Self {
+        Self::translation(-center)
+            .then(Self::rotation(angle))
+            .then(Self::translation(center))
+    }
+
+    /// Scaling relative to origin
+    pub fn scale(x: f64, y: f64) -> Self {
+        Self::new([[x, 0.0, 0.0], [0.0, y, 0.0]])
+    }
+
+    /// Shear transformation, mapping `(x, y)` into `(x + x_shear*y, y + y_shear*x)`
+    /// ```
+    /// use nanocv::{Vec2d, geometry::Affine2d};
+    /// let point = Affine2d::shear(0.5, 0.0).transform_point(Vec2d::new(1.0, 2.0));
+    /// assert_eq!(point, Vec2d::new(2.0, 2.0));
+    /// ```
+    pub fn shear(x_shear: f64, y_shear: f64) -> Self {
+        Self::new([[1.0, x_shear, 0.0], [y_shear, 1.0, 0.0]])
+    }
+
+    /// Affine transformation mapping `src` points onto `dst` points in 
+    /// pairs `(src, dst)`, in least squares sense when more than three 
+    /// pairs are provided
+    ///
+    /// Returns `None` for less than three pairs or collinear points
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::{Vec2d, geometry::Affine2d};
+    /// let truth = Affine2d::new([[2.0, 0.5, 1.0], [-0.3, 1.5, 4.0]]);
+    /// let pairs: Vec<_> = [(0.0, 0.0), (3.0, 1.0), (1.0, 5.0)].iter()
+    ///     .map(|&(x, y)| (Vec2d::new(x, y), truth.transform_point(Vec2d::new(x, y))))
+    ///     .collect();
+    /// let estimate = Affine2d::from_correspondences(&pairs).unwrap();
+    /// assert!(estimate.matrix.iter().flatten().zip(truth.matrix.iter().flatten())
+    ///     .all(|(a, b)| (a - b).abs() < 1e-9));
+    /// ```
+    pub fn from_correspondences(pairs: &[(Vec2d<f64>, Vec2d<f64>)]) -> Option<Self> {
+        if pairs.len() < 3 {
+            return None;
+        }
+
+        // Centering the source points improves conditioning of normal equations
+        let count = pairs.len() as f64;
+        let center = pairs.iter().fold(Vec2d::new(0.0, 0.0), |sum, pair| sum + pair.0)/count;
+        let mut a = Vec::with_capacity(pairs.len()*3);
+
+        for (src, _) in pairs {
+            a.extend_from_slice(&[src.x - center.x, src.y - center.y, 1.0]);
+        }
+
+        let x: Vec<f64> = pairs.iter().map(|pair| pair.1.x).collect();
+        let y: Vec<f64> = pairs.iter().map(|pair| pair.1.y).collect();
+        let first = least_squares(&a, &x, 3)?;
+        let second = least_squares(&a, &y, 3)?;
+
+        let centered = Self::new([
+            [first[0], first[1], first[2]], 
+            [second[0], second[1], second[2]]
+        ]);
+
+        Some(Self::translation(-center).then(centered))
+    }
+
+    /// Transformation applying `self` first and then `next`
+    pub fn then(&self, next: Affine2d) -> Self {
+        next*(*self)
+    }
+
+    /// Determinant of the linear part of the transformation
+    pub fn determinant(&self) -> f64 {
+        let [[a, b, _], [c, d, _]] = self.matrix;
+        a*d - b*c
+    }
+
+    /// Inverse transformation, or `None` if transformation is singular
+    /// ```
+    /// use nanocv::{Vec2d, geometry::Affine2d};
+    /// let transform = Affine2d::rotation(0.3).then(Affine2d::translation(Vec2d::new(1.0, 2.0)));
+    /// let point = Vec2d::new(5.0, -1.0);
+    /// let back = transform.inverse().unwrap().transform_point(transform.transform_point(point));
+    /// assert!((back - point).length() < 1e-12);
+    /// assert_eq!(Affine2d::scale(0.0, 1.0).inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let [[a, b, tx], [c, d, ty]] = self.matrix;
+        let (ia, ib, ic, id) = (d/det, -b/det, -c/det, a/det);
+
+        Some(Self::new([
+            [ia, ib, -(ia*tx + ib*ty)],
+            [ic, id, -(ic*tx + id*ty)],
+        ]))
+    }
+
+    /// Transform point position
+    pub fn transform_point(&self, point: Vec2d<f64>) -> Vec2d<f64> {
+        let [[a, b, tx], [c, d, ty]] = self.matrix;
+        Vec2d::new(a*point.x + b*point.y + tx, c*point.x + d*point.y + ty)
+    }
+
+    /// Transform vector (direction), ignoring translation
+    pub fn transform_vector(&self, vector: Vec2d<f64>) -> Vec2d<f64> {
+        let [[a, b, _], [c, d, _]] = self.matrix;
+        Vec2d::new(a*vector.x + b*vector.y, c*vector.x + d*vector.y)
+    }
+
+    /// Smallest range of whole pixels containing the area 
+    /// of `range` after transformation
+    /// ```
+    /// use nanocv::{ImgRange, Vec2d, geometry::Affine2d};
+    /// let range = ImgRange::new(0..2, 0..1);
+    /// assert_eq!(
+    ///     Affine2d::scale(1.5, -1.0).transform_range(range), 
+    ///     ImgRange::new(0..3, -1..0)
+    /// );
+    /// ```
+    pub fn transform_range(&self, range: ImgRange) -> ImgRange {
+        let (start, end) = (range.start().cast::<f64>(), range.end().cast::<f64>());
+
+        let corners = [
+            start, Vec2d::new(end.x, start.y), Vec2d::new(start.x, end.y), end
+        ].map(|corner| self.transform_point(corner));
+
+        let fold = |init: f64, select: fn(Vec2d<f64>) -> f64, pick: fn(f64, f64) -> f64| {
+            corners.iter().map(|corner| select(*corner)).fold(init, pick)
+        };
+
+        let min_x = fold(f64::INFINITY, |p| p.x, f64::min).floor() as isize;
+        let max_x = fold(f64::NEG_INFINITY, |p| p.x, f64::max).ceil() as isize;
+        let min_y = fold(f64::INFINITY, |p| p.y, f64::min).floor() as isize;
+        let max_y = fold(f64::NEG_INFINITY, |p| p.y, f64::max).ceil() as isize;
+
+        ImgRange::new(min_x..max_x, min_y..max_y)
+    }
+}
+
+impl Default for Affine2d {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul<Affine2d> for Affine2d {
+    type Output = Affine2d;
+
+    /// Composition of transformations, `rhs` is applied first
+    fn mul(self, rhs: Affine2d) -> Affine2d {
+        let [[a, b, tx], [c, d, ty]] = self.matrix;
+        let [[e, f, ux], [g, h, uy]] = rhs.matrix;
+
+        Affine2d::new([
+            [a*e + b*g, a*f + b*h, a*ux + b*uy + tx],
+            [c*e + d*g, c*f + d*h, c*ux + d*uy + ty],
+        ])
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Vec2d<f64>, b: Vec2d<f64>) {
+        assert!((a - b).length() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn composition_order() {
+        let shift = Affine2d::translation(Vec2d::new(1.0, 0.0));
+        let scale = Affine2d::scale(2.0, 2.0);
+        let point = Vec2d::new(1.0, 1.0);
+
+        assert_close((scale*shift).transform_point(point), Vec2d::new(4.0, 2.0));
+        assert_close((shift*scale).transform_point(point), Vec2d::new(3.0, 2.0));
+    }
+
+    #[test]
+    fn inverse_of_composition() {
+        let transform = Affine2d::shear(0.2, 0.1)
+            .then(Affine2d::rotation_around(0.7, Vec2d::new(3.0, 4.0)))
+            .then(Affine2d::scale(2.0, 0.5));
+
+        let identity = transform*transform.inverse().unwrap();
+
+        for point in [Vec2d::new(0.0, 0.0), Vec2d::new(10.0, -3.0)] {
+            assert_close(identity.transform_point(point), point);
+        }
+    }
+
+    #[test]
+    fn vector_ignores_translation() {
+        let transform = Affine2d::translation(Vec2d::new(5.0, 5.0));
+        assert_close(transform.transform_vector(Vec2d::new(1.0, 2.0)), Vec2d::new(1.0, 2.0));
+    }
+}
@@ -0,0 +1,63 @@
+/// Conversion between primitive numeric types with the semantics 
+/// of the `as` operator
+/// 
+/// Integer conversions wrap or truncate, float to integer conversions 
+/// round towards zero and saturate, see the Rust reference for details
+/// 
+/// # Example
+/// ```
+/// use nanocv::AsPrimitive;
+/// let value: u8 = 3.7f32.as_();
+/// assert_eq!(value, 3);
+/// ```
+pub trait AsPrimitive<T>: Copy {
+    /// Convert value into type `T` using `as` operator
+    fn as_(self) -> T;
+}
+
+/// Converts `value` to type `T`, rounding to the nearest integer for integer
+/// types, floating point types are converted directly
+pub(crate) fn round_as<T>(value: f64) -> T where T: AsPrimitive<f64>, f64: AsPrimitive<T> {
+    let half: T = 0.5.as_();
+
+    if half.as_() == 0.5 { value.as_() } else { value.round().as_() }
+}
+
+macro_rules! impl_as_primitive {
+    ($from: ty => $($to: ty),*) => {
+        $(
+            impl AsPrimitive<$to> for $from {
+                #[inline]
+                fn as_(self) -> $to { self as $to }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_as_primitive_all {
+    ($($from: ty),*) => {
+        $(
+            impl_as_primitive!(
+                $from => u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64
+            );
+        )*
+    };
+}
+
+impl_as_primitive_all!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_as_rounds_only_integer_types() {
+        assert_eq!(round_as::<u8>(2.5), 3);
+        assert_eq!(round_as::<i16>(-1.4), -1);
+        assert_eq!(round_as::<u8>(300.0), 255);
+        assert_eq!(round_as::<f32>(0.1), 0.1f32);
+        assert_eq!(round_as::<f64>(2.25), 2.25);
+    }
+}
@@ -0,0 +1,263 @@
+use std::ops::Mul;
+use crate::Vec2d;
+use super::{Affine2d, linalg::least_squares};
+#[cfg(feature = "serde")]
+use serde_derive::{Serialize, Deserialize};
+
+/// Projective transformation of 2D points represented by 3x3 matrix,
+/// point `(x, y)` is mapped into `(x'/w, y'/w)` where 
+/// `(x', y', w) = matrix*(x, y, 1)`
+///
+/// Transformations are composed by multiplication, `a*b` first applies
+/// `b` and then `a`, or equivalently `b.then(a)`
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Homography {
+    /// Rows of the transformation matrix
+    pub matrix: [[f64; 3]; 3],
+}
+
+impl Homography {
+    /// Create transformation from matrix rows
+    pub fn new(matrix: [[f64; 3]; 3]) -> Self {
+        Self { matrix }
+    }
+
+    /// Transformation that keeps all points in place
+    pub fn identity() -> Self {
+        Self::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Homography mapping four `src` points onto four `dst` points 
+    /// using direct linear transformation
+    ///
+    /// Returns `None` if points are degenerate (e.g. three of them 
+    /// are collinear)
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::{Vec2d, geometry::Homography};
+    /// let src = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| Vec2d::new(x, y));
+    /// let dst = [(0.0, 0.0), (2.0, 0.0), (1.5, 1.0), (0.5, 1.0)].map(|(x, y)| Vec2d::new(x, y));
+    /// let homography = Homography::from_four_points(src, dst).unwrap();
+    ///
+    /// for (s, d) in src.iter().zip(dst.iter()) {
+    ///     assert!((homography.transform_point(*s).unwrap() - *d).length() < 1e-9);
+    /// }
+    /// ```
+    pub fn from_four_points(src: [Vec2d<f64>; 4], dst: [Vec2d<f64>; 4]) -> Option<Self> {
+        let pairs: Vec<_> = src.iter().copied().zip(dst.iter().copied()).collect();
+        Self::from_correspondences(&pairs)
+    }
+
+    /// Homography mapping `src` points onto `dst` points in pairs
+    /// `(src, dst)`, in least squares sense when more than four 
+    /// pairs are provided
+    ///
+    /// Points are normalized before estimation (Hartley normalization),
+    /// returns `None` for less than four pairs or degenerate points
+    pub fn from_correspondences(pairs: &[(Vec2d<f64>, Vec2d<f64>)]) -> Option<Self> {
+        if pairs.len() < 4 {
+            return None;
+        }
+
+        let src_norm = normalization(pairs.iter().map(|pair| pair.0))?;
+        let dst_norm = normalization(pairs.iter().map(|pair| pair.1))?;
+        let mut a = Vec::with_capacity(pairs.len()*16);
+        let mut b = Vec::with_capacity(pairs.len()*2);
+
+        for (src, dst) in pairs {
+            let s = src_norm.transform_point(*src);
+            let d = dst_norm.transform_point(*dst);
+            a.extend_from_slice(&[s.x, s.y, 1.0, 0.0, 0.0, 0.0, -s.x*d.x, -s.y*d.x]);
+            a.extend_from_slice(&[0.0, 0.0, 0.0, s.x, s.y, 1.0, -s.x*d.y, -s.y*d.y]);
+            b.push(d.x);
+            b.push(d.y);
+        }
+
+        let h = least_squares(&a, &b, 8)?;
+
+        let normalized = Self::new([
+            [h[0], h[1], h[2]], 
+            [h[3], h[4], h[5]], 
+            [h[6], h[7], 1.0]
+        ]);
+
+        let result = Homography::from(dst_norm).inverse()?*normalized*Homography::from(src_norm);
+        result.normalized()
+    }
+
+    /// Transformation applying `self` first and then `next`
+    pub fn then(&self, next: Homography) -> Self {
+        next*(*self)
+    }
+
+    /// Same transformation with matrix scaled so that its bottom
+    /// right element equals one, or `None` if the element is zero
+    pub fn normalized(&self) -> Option<Self> {
+        let scale = self.matrix[2][2];
+
+        if scale == 0.0 || !scale.is_finite() {
+            return None;
+        }
+
+        Some(Self::new(self.matrix.map(|row| row.map(|value| value/scale))))
+    }
+
+    /// Determinant of the transformation matrix
+    pub fn determinant(&self) -> f64 {
+        let m = &self.matrix;
+        m[0][0]*(m[1][1]*m[2][2] - m[1][2]*m[2][1])
+            - m[0][1]*(m[1][0]*m[2][2] - m[1][2]*m[2][0])
+            + m[0][2]*(m[1][0]*m[2][1] - m[1][1]*m[2][0])
+    }
+
+    /// Inverse transformation, or `None` if transformation is singular
+    /// ```
+    /// use nanocv::{Vec2d, geometry::Homography};
+    /// let h = Homography::new([[1.0, 0.2, 3.0], [0.1, 2.0, -1.0], [0.001, 0.002, 1.0]]);
+    /// let point = Vec2d::new(10.0, 20.0);
+    /// let back = h.inverse().unwrap().transform_point(h.transform_point(point).unwrap()).unwrap();
+    /// assert!((back - point).length() < 1e-9);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let m = &self.matrix;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0]*m[r1][c1] - m[r0][c1]*m[r1][c0]
+        };
+
+        Some(Self::new([
+            [cofactor(1, 2, 1, 2)/det, -cofactor(0, 2, 1, 2)/det, cofactor(0, 1, 1, 2)/det],
+            [-cofactor(1, 2, 0, 2)/det, cofactor(0, 2, 0, 2)/det, -cofactor(0, 1, 0, 2)/det],
+            [cofactor(1, 2, 0, 1)/det, -cofactor(0, 2, 0, 1)/det, cofactor(0, 1, 0, 1)/det],
+        ]))
+    }
+
+    /// Project point using the transformation including perspective 
+    /// divide, returns `None` for points mapped to infinity
+    pub fn transform_point(&self, point: Vec2d<f64>) -> Option<Vec2d<f64>> {
+        let m = &self.matrix;
+        let w = m[2][0]*point.x + m[2][1]*point.y + m[2][2];
+
+        if w == 0.0 {
+            return None;
+        }
+
+        Some(Vec2d::new(
+            (m[0][0]*point.x + m[0][1]*point.y + m[0][2])/w,
+            (m[1][0]*point.x + m[1][1]*point.y + m[1][2])/w,
+        ))
+    }
+}
+
+impl Default for Homography {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl From<Affine2d> for Homography {
+    fn from(affine: Affine2d) -> Self {
+        let [first, second] = affine.matrix;
+        Self::new([first, second, [0.0, 0.0, 1.0]])
+    }
+}
+
+impl Mul<Homography> for Homography {
+    type Output = Homography;
+
+    /// Composition of transformations, `rhs` is applied first
+    fn mul(self, rhs: Homography) -> Homography {
+        let mut matrix = [[0.0; 3]; 3];
+
+        for row in 0..3 {
+            for column in 0..3 {
+                matrix[row][column] = (0..3)
+                    .map(|k| self.matrix[row][k]*rhs.matrix[k][column])
+                    .sum();
+            }
+        }
+
+        Homography::new(matrix)
+    }
+}
+
+/// Similarity transform moving points centroid to origin and scaling
+/// their average distance from origin to `sqrt(2)`
+fn normalization(points: impl Iterator<Item=Vec2d<f64>> + Clone) -> Option<Affine2d> {
+    let count = points.clone().count() as f64;
+    let centroid = points.clone().fold(Vec2d::new(0.0, 0.0), |acc, p| acc + p)/count;
+    let distance = points.map(|p| (p - centroid).length()).sum::<f64>()/count;
+
+    if distance == 0.0 || !distance.is_finite() {
+        return None;
+    }
+
+    let scale = std::f64::consts::SQRT_2/distance;
+    Some(Affine2d::translation(-centroid).then(Affine2d::scale(scale, scale)))
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coords: &[(f64, f64)]) -> Vec<Vec2d<f64>> {
+        coords.iter().map(|&(x, y)| Vec2d::new(x, y)).collect()
+    }
+
+    #[test]
+    fn affine_conversion_projects_same_points() {
+        let affine = Affine2d::rotation(0.5).then(Affine2d::translation(Vec2d::new(1.0, 2.0)));
+        let homography = Homography::from(affine);
+        let point = Vec2d::new(3.0, -4.0);
+        let diff = homography.transform_point(point).unwrap() - affine.transform_point(point);
+        assert!(diff.length() < 1e-12);
+    }
+
+    #[test]
+    fn least_squares_estimate_of_exact_correspondences() {
+        let truth = Homography::new([[1.1, 0.1, 5.0], [-0.2, 0.9, 3.0], [0.0005, 0.001, 1.0]]);
+        let src = points(&[(0.0, 0.0), (100.0, 0.0), (100.0, 80.0), (0.0, 80.0), (50.0, 40.0), (20.0, 70.0)]);
+
+        let pairs: Vec<_> = src.iter()
+            .map(|&p| (p, truth.transform_point(p).unwrap()))
+            .collect();
+
+        let estimate = Homography::from_correspondences(&pairs).unwrap();
+
+        for row in 0..3 {
+            for column in 0..3 {
+                assert!((estimate.matrix[row][column] - truth.matrix[row][column]).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn collinear_points_are_degenerate() {
+        let src = points(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+        let dst = points(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        let pairs: Vec<_> = src.into_iter().zip(dst).collect();
+        assert_eq!(Homography::from_correspondences(&pairs), None);
+    }
+
+    #[test]
+    fn composition_with_inverse_is_identity() {
+        let h = Homography::new([[2.0, 0.1, 1.0], [0.3, 1.0, 4.0], [0.01, 0.0, 1.0]]);
+        let identity = (h*h.inverse().unwrap()).normalized().unwrap();
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let expected = if row == column { 1.0 } else { 0.0 };
+                assert!((identity.matrix[row][column] - expected).abs() < 1e-12);
+            }
+        }
+    }
+}
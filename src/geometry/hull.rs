@@ -0,0 +1,101 @@
+use crate::Vec2d;
+
+/// Convex hull of a set of integer points (Andrew's monotone chain)
+/// 
+/// Returns hull vertices without duplicates and without collinear points 
+/// on hull edges, starting at the point with the lowest `x` (and lowest `y`
+/// among those) and ordered counter-clockwise in a coordinate system with 
+/// `y` axis pointing up (clockwise in image coordinates). Sets with fewer
+/// than three distinct non-collinear points produce a degenerate hull 
+/// of one or two vertices (empty for empty input).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{Vec2d, geometry::convex_hull};
+/// let points = [
+///     Vec2d::new(0, 0), Vec2d::new(4, 0), Vec2d::new(2, 1), 
+///     Vec2d::new(4, 4), Vec2d::new(0, 4), Vec2d::new(2, 4),
+/// ];
+/// assert_eq!(convex_hull(&points), vec![
+///     Vec2d::new(0, 0), Vec2d::new(4, 0), Vec2d::new(4, 4), Vec2d::new(0, 4),
+/// ]);
+/// ```
+pub fn convex_hull(points: &[Vec2d<isize>]) -> Vec<Vec2d<isize>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|point| (point.x, point.y));
+    sorted.dedup();
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut hull: Vec<Vec2d<isize>> = Vec::with_capacity(2*sorted.len());
+
+    // Lower hull left to right, then upper hull right to left, 
+    // the first point of each chain is the last point of the other
+    push_chain(&mut hull, sorted.iter());
+    push_chain(&mut hull, sorted.iter().rev());
+
+    // All points collinear, both chains contain just the end points
+    if hull.len() == 2 && hull[0] == hull[1] {
+        hull.pop();
+    }
+
+    hull
+}
+
+/// Appends a half of the hull built from points sorted along the chain
+fn push_chain<'a>(hull: &mut Vec<Vec2d<isize>>, points: impl Iterator<Item=&'a Vec2d<isize>>) {
+    let start = hull.len();
+
+    for &point in points {
+        while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0 {
+            hull.pop();
+        }
+
+        hull.push(point);
+    }
+
+    hull.pop();
+}
+
+/// Cross product of vectors `a - origin` and `b - origin`, positive 
+/// when `origin`, `a`, `b` make a counter-clockwise turn (`y` axis up)
+pub(crate) fn cross(origin: Vec2d<isize>, a: Vec2d<isize>, b: Vec2d<isize>) -> isize {
+    (a.x - origin.x)*(b.y - origin.y) - (a.y - origin.y)*(b.x - origin.x)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collinear_points_give_segment() {
+        let points = [Vec2d::new(2, 2), Vec2d::new(0, 0), Vec2d::new(1, 1), Vec2d::new(3, 3)];
+        assert_eq!(convex_hull(&points), vec![Vec2d::new(0, 0), Vec2d::new(3, 3)]);
+    }
+
+    #[test]
+    fn duplicate_points_give_single_vertex() {
+        let points = [Vec2d::new(1, 5), Vec2d::new(1, 5)];
+        assert_eq!(convex_hull(&points), vec![Vec2d::new(1, 5)]);
+        assert!(convex_hull(&[]).is_empty());
+    }
+
+    #[test]
+    fn hull_contains_all_points() {
+        let points: Vec<_> = (0..50isize)
+            .map(|i| Vec2d::new((i*37) % 23 - 11, (i*53) % 19 - 9))
+            .collect();
+
+        let hull = convex_hull(&points);
+        assert!(hull.len() >= 3);
+
+        for index in 0..hull.len() {
+            let (a, b) = (hull[index], hull[(index + 1) % hull.len()]);
+            assert!(points.iter().all(|&point| cross(a, b, point) >= 0));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Small dense linear algebra helpers used by geometric estimators
+
+/// Solves `a*x = b` for square matrix `a` of size `n x n` stored 
+/// in row-major order, using Gaussian elimination with partial pivoting
+///
+/// Returns `None` for singular (or numerically almost singular) matrices
+pub(crate) fn solve(mut a: Vec<f64>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    assert_eq!(a.len(), n*n, "Matrix must have {} elements", n*n);
+
+    let scale = a.iter().fold(0.0f64, |acc, value| acc.max(value.abs()));
+
+    if scale == 0.0 || !scale.is_finite() {
+        return None;
+    }
+
+    for column in 0..n {
+        let pivot = (column..n)
+            .max_by(|&i, &j| a[i*n + column].abs().total_cmp(&a[j*n + column].abs()))?;
+
+        if a[pivot*n + column].abs() <= scale*1e-12 {
+            return None;
+        }
+
+        if pivot != column {
+            for k in 0..n {
+                a.swap(pivot*n + k, column*n + k);
+            }
+            b.swap(pivot, column);
+        }
+
+        for row in (column + 1)..n {
+            let factor = a[row*n + column]/a[column*n + column];
+
+            for k in column..n {
+                a[row*n + k] -= factor*a[column*n + k];
+            }
+            b[row] -= factor*b[column];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+
+    for row in (0..n).rev() {
+        let sum: f64 = ((row + 1)..n).map(|k| a[row*n + k]*x[k]).sum();
+        x[row] = (b[row] - sum)/a[row*n + row];
+    }
+
+    Some(x)
+}
+
+/// Solves overdetermined system `a*x = b` in least squares sense using
+/// normal equations, `a` has `b.len()` rows and `columns` columns
+pub(crate) fn least_squares(a: &[f64], b: &[f64], columns: usize) -> Option<Vec<f64>> {
+    let rows = b.len();
+    let mut ata = vec![0.0; columns*columns];
+    let mut atb = vec![0.0; columns];
+
+    for row in 0..rows {
+        let line = &a[row*columns..(row + 1)*columns];
+
+        for i in 0..columns {
+            atb[i] += line[i]*b[row];
+
+            for j in 0..columns {
+                ata[i*columns + j] += line[i]*line[j];
+            }
+        }
+    }
+
+    solve(ata, atb)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_2x2_with_pivoting() {
+        let x = solve(vec![0.0, 1.0, 2.0, 0.0], vec![3.0, 4.0]).unwrap();
+        assert_eq!(x, vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn solve_singular_matrix() {
+        assert_eq!(solve(vec![1.0, 2.0, 2.0, 4.0], vec![1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn least_squares_line_fit() {
+        // y = 2x + 1 sampled at x = 0, 1, 2
+        let a = [0.0, 1.0, 1.0, 1.0, 2.0, 1.0];
+        let x = least_squares(&a, &[1.0, 3.0, 5.0], 2).unwrap();
+        assert!((x[0] - 2.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12);
+    }
+}
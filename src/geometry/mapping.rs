@@ -5,7 +5,27 @@ use crate::{Vec2d, Range2d, ImgRange};
 /// src and dst must have same exactly same width and height, 
 /// shift is difference of src and dst starting points, and
 /// src lies within input image and dst list within output image
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+/// 
+/// Mapping implements the clipping used by all filters processing
+/// an input range into an output range, and can be used to implement
+/// custom filters with the same behavior
+/// 
+/// # Example
+/// ```
+/// use nanocv::{ImgRange, Vec2d, Range2d, geometry::ImageMapping};
+/// 
+/// let mapping = ImageMapping::new(
+///     ImgRange::new(0..4, 0..4),  // input range
+///     ImgRange::new(2..6, 1..5),  // output range
+///     ImgRange::new(0..4, 0..4),  // input image
+///     ImgRange::new(0..5, 0..5),  // output image
+/// );
+/// 
+/// assert_eq!(mapping.src, Range2d::new(0..3, 0..4));
+/// assert_eq!(mapping.dst, Range2d::new(2..5, 1..5));
+/// assert_eq!(mapping.src_to_dst(Vec2d::new(1, 1)), Some(Vec2d::new(3, 2)));
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub struct ImageMapping {
     /// Area in source image
     pub src: Range2d<usize>,
@@ -43,4 +63,56 @@ impl ImageMapping {
 
         Self { src, dst, shift }
     }
+
+    /// Mapping in the opposite direction, from dst to src
+    /// ```
+    /// use nanocv::{ImgRange, Vec2d, geometry::ImageMapping};
+    /// let range = ImgRange::new(0..2, 0..2);
+    /// let mapping = ImageMapping::new(range, range + Vec2d::new(1, 0), range, range.inflate(2));
+    /// let inverse = mapping.inverse();
+    /// assert_eq!(inverse.src, mapping.dst);
+    /// assert_eq!(inverse.dst_to_src(Vec2d::new(0, 0)), Some(Vec2d::new(1, 0)));
+    /// ```
+    pub fn inverse(&self) -> Self {
+        Self { src: self.dst, dst: self.src, shift: -self.shift }
+    }
+
+    /// Mapping applying `self` first and then `next`, mapping only 
+    /// the area where dst of `self` overlaps with src of `next`
+    /// ```
+    /// use nanocv::{ImgRange, Vec2d, Range2d, geometry::ImageMapping};
+    /// let image = ImgRange::new(0..4, 0..4);
+    /// let first = ImageMapping::new(image, image + Vec2d::new(1, 0), image, image);
+    /// let second = ImageMapping::new(image, image + Vec2d::new(0, 2), image, image);
+    /// let both = first.compose(second);
+    /// assert_eq!(both.shift, Vec2d::new(1, 2));
+    /// assert_eq!(both.src, Range2d::new(0..3, 0..2));
+    /// assert_eq!(both.dst, Range2d::new(1..4, 2..4));
+    /// ```
+    pub fn compose(&self, next: ImageMapping) -> Self {
+        let middle = self.dst.cast::<isize>().intersect(next.src.cast::<isize>());
+        let (src, dst) = (middle - self.shift, middle + next.shift);
+
+        Self {
+            src: Range2d::<usize>::from(src),
+            dst: Range2d::<usize>::from(dst),
+            shift: self.shift + next.shift,
+        }
+    }
+
+    /// Position in dst image corresponding to `point` in src image,
+    /// `None` if `point` lies outside mapped area
+    pub fn src_to_dst(&self, point: Vec2d<usize>) -> Option<Vec2d<usize>> {
+        if self.src.contains(point) {
+            Some((point.cast::<isize>() + self.shift).cast())
+        } else {
+            None
+        }
+    }
+
+    /// Position in src image corresponding to `point` in dst image,
+    /// `None` if `point` lies outside mapped area
+    pub fn dst_to_src(&self, point: Vec2d<usize>) -> Option<Vec2d<usize>> {
+        self.inverse().src_to_dst(point)
+    }
 }
\ No newline at end of file
@@ -4,8 +4,28 @@ mod vec2d;
 mod range;
 mod range2d;
 mod mapping;
+mod cast;
+mod affine;
+mod homography;
+mod linalg;
+mod point;
+mod segment;
+mod polygon;
+mod hull;
+mod polyline;
 
 pub use range::Range;
-pub use range2d::{Range2d, ImgRange};
+pub use range2d::{Range2d, Range2dIter, ImgRange};
 pub use vec2d::Vec2d;
-pub use mapping::ImageMapping;
\ No newline at end of file
+pub use mapping::ImageMapping;
+pub use cast::AsPrimitive;
+pub(crate) use cast::round_as;
+pub use affine::Affine2d;
+pub use homography::Homography;
+pub use point::Point;
+pub use segment::LineSegment;
+pub use polygon::Polygon;
+pub use hull::convex_hull;
+pub(crate) use hull::cross;
+pub(crate) use linalg::solve;
+pub use polyline::{simplify_polyline, resample_polyline};
\ No newline at end of file
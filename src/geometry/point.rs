@@ -0,0 +1,17 @@
+use super::Vec2d;
+
+/// Position in continuous (subpixel) image coordinates
+/// 
+/// `Point` is just an alias of `Vec2d<f64>`, so all vector operations
+/// and float helpers like `distance` or `midpoint` are available and no 
+/// conversions between points and vectors are needed
+/// 
+/// # Example
+/// ```
+/// use nanocv::{Vec2d, geometry::Point};
+/// let a: Point = Point::new(1.0, 1.0);
+/// let b: Vec2d<f64> = Vec2d::new(4.0, 5.0);
+/// assert_eq!(a.distance(b), 5.0);
+/// assert_eq!(a.midpoint(b), Point::new(2.5, 3.0));
+/// ```
+pub type Point = Vec2d<f64>;
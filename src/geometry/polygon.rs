@@ -0,0 +1,189 @@
+use crate::{Range, Range2d};
+use super::Point;
+
+/// Closed polygon defined by a list of vertices, the last vertex 
+/// is connected to the first one
+/// 
+/// # Example
+/// ```
+/// use nanocv::geometry::{Polygon, Point};
+/// let square = Polygon::new(vec![
+///     Point::new(0.0, 0.0), Point::new(2.0, 0.0), 
+///     Point::new(2.0, 2.0), Point::new(0.0, 2.0),
+/// ]);
+/// assert_eq!(square.area(), 4.0);
+/// assert_eq!(square.perimeter(), 8.0);
+/// assert_eq!(square.centroid(), Some(Point::new(1.0, 1.0)));
+/// assert!(square.contains(Point::new(1.0, 1.5)));
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Polygon {
+    pub vertices: Vec<Point>,
+}
+
+impl Polygon {
+    /// Create polygon from vertices
+    pub fn new(vertices: Vec<Point>) -> Self {
+        Self { vertices }
+    }
+
+    /// Iterator over polygon edges as pairs of vertices
+    fn edges(&self) -> impl Iterator<Item=(Point, Point)> + '_ {
+        let count = self.vertices.len();
+        (0..count).map(move |index| (self.vertices[index], self.vertices[(index + 1) % count]))
+    }
+
+    /// Signed polygon area, positive for vertices ordered 
+    /// counter-clockwise in a coordinate system with `y` axis pointing up
+    /// (clockwise in image coordinates with `y` pointing down)
+    pub fn signed_area(&self) -> f64 {
+        self.edges().map(|(a, b)| a.x*b.y - b.x*a.y).sum::<f64>()/2.0
+    }
+
+    /// Area enclosed by the polygon (for non self-intersecting polygons)
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Total length of polygon edges
+    pub fn perimeter(&self) -> f64 {
+        self.edges().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    /// Center of mass of the polygon area, `None` for polygons
+    /// with zero area
+    pub fn centroid(&self) -> Option<Point> {
+        let area = self.signed_area();
+
+        if area == 0.0 {
+            return None;
+        }
+
+        let sum = self.edges().fold(Point::new(0.0, 0.0), |acc, (a, b)| {
+            acc + (a + b)*(a.x*b.y - b.x*a.y)
+        });
+
+        Some(sum/(6.0*area))
+    }
+
+    /// Tests whether `point` lies inside the polygon using the even-odd rule
+    pub fn contains(&self, point: Point) -> bool {
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            if (a.y > point.y) != (b.y > point.y) {
+                let x = a.x + (point.y - a.y)*(b.x - a.x)/(b.y - a.y);
+
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+
+        inside
+    }
+
+    /// Smallest range containing all polygon vertices, 
+    /// `None` for polygons without vertices
+    pub fn bounding_range(&self) -> Option<Range2d<f64>> {
+        let first = *self.vertices.first()?;
+        let extend = |range: Range<f64>, value: f64| {
+            Range::new(range.start.min(value)..range.end.max(value))
+        };
+
+        Some(self.vertices.iter().fold(
+            Range2d::new(first.x..first.x, first.y..first.y),
+            |range, vertex| Range2d { x: extend(range.x, vertex.x), y: extend(range.y, vertex.y) }
+        ))
+    }
+
+    /// Part of the polygon lying within `range` (Sutherland-Hodgman 
+    /// algorithm), result is exact for convex polygons, concave polygons 
+    /// may produce degenerate zero-area edges along range borders
+    /// ```
+    /// use nanocv::{Range2d, geometry::{Polygon, Point}};
+    /// let triangle = Polygon::new(vec![
+    ///     Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 4.0),
+    /// ]);
+    /// let clipped = triangle.clip(Range2d::new(0.0..2.0, 0.0..2.0));
+    /// assert_eq!(clipped.area(), 4.0);
+    /// ```
+    pub fn clip(&self, range: Range2d<f64>) -> Polygon {
+        // Clipping lines as (vertical line, coordinate, direction of inside)
+        let lines = [
+            (true, range.x.start, 1.0),
+            (true, range.x.end, -1.0),
+            (false, range.y.start, 1.0),
+            (false, range.y.end, -1.0),
+        ];
+
+        let mut polygon = self.clone();
+
+        for (vertical, bound, sign) in lines {
+            let coordinate = |p: Point| if vertical { p.x } else { p.y };
+            let inside = |p: Point| (coordinate(p) - bound)*sign >= 0.0;
+            let mut output = Vec::with_capacity(polygon.vertices.len() + 1);
+
+            for (a, b) in polygon.edges() {
+                let intersection = || {
+                    let t = (bound - coordinate(a))/(coordinate(b) - coordinate(a));
+                    a.lerp(b, t)
+                };
+
+                match (inside(a), inside(b)) {
+                    (true, true) => output.push(b),
+                    (true, false) => output.push(intersection()),
+                    (false, true) => {
+                        output.push(intersection());
+                        output.push(b);
+                    },
+                    (false, false) => {},
+                }
+            }
+
+            polygon = Polygon::new(output);
+        }
+
+        polygon
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Polygon {
+        Polygon::new(vec![Point::new(0.0, 0.0), Point::new(3.0, 0.0), Point::new(0.0, 3.0)])
+    }
+
+    #[test]
+    fn triangle_centroid() {
+        assert_eq!(triangle().centroid(), Some(Point::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn point_outside_triangle() {
+        assert!(!triangle().contains(Point::new(2.0, 2.0)));
+        assert!(triangle().contains(Point::new(0.5, 0.5)));
+    }
+
+    #[test]
+    fn clip_polygon_outside_range_is_empty() {
+        let clipped = triangle().clip(Range2d::new(5.0..6.0, 5.0..6.0));
+        assert!(clipped.vertices.is_empty());
+        assert_eq!(clipped.bounding_range(), None);
+    }
+
+    #[test]
+    fn bounding_range_of_triangle() {
+        assert_eq!(triangle().bounding_range(), Some(Range2d::new(0.0..3.0, 0.0..3.0)));
+    }
+
+    #[test]
+    fn empty_polygon_has_zero_area() {
+        assert_eq!(Polygon::default().area(), 0.0);
+        assert_eq!(Polygon::default().centroid(), None);
+    }
+}
@@ -0,0 +1,146 @@
+use super::{LineSegment, Point};
+
+/// Simplifies an open polyline by the Douglas-Peucker algorithm
+/// 
+/// Keeps the first and the last point and recursively keeps the point 
+/// farthest from the segment connecting already kept points while its 
+/// distance exceeds `epsilon`, so the result deviates from the original 
+/// polyline by at most `epsilon`. Closed contours should be split into two 
+/// open polylines (or passed with the first point repeated at the end).
+/// 
+/// # Example
+/// ```
+/// use nanocv::geometry::{simplify_polyline, Point};
+/// let points = [
+///     Point::new(0.0, 0.0), Point::new(1.0, 0.1), Point::new(2.0, -0.1),
+///     Point::new(3.0, 0.0), Point::new(3.0, 3.0),
+/// ];
+/// assert_eq!(simplify_polyline(&points, 0.5), vec![
+///     Point::new(0.0, 0.0), Point::new(3.0, 0.0), Point::new(3.0, 3.0),
+/// ]);
+/// ```
+pub fn simplify_polyline(points: &[Point], epsilon: f64) -> Vec<Point> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    let mut stack = vec![(0, points.len() - 1)];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    while let Some((first, last)) = stack.pop() {
+        let segment = LineSegment::new(points[first], points[last]);
+
+        let farthest = ((first + 1)..last)
+            .map(|index| (index, segment.distance_to(points[index])))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((index, distance)) = farthest {
+            if distance > epsilon {
+                keep[index] = true;
+                stack.push((first, index));
+                stack.push((index, last));
+            }
+        }
+    }
+
+    points.iter().zip(keep).filter(|(_, keep)| *keep).map(|(&point, _)| point).collect()
+}
+
+/// Resamples a polyline to `count` points equally spaced along its arc 
+/// length, the first and the last point are kept
+/// 
+/// Returns an empty vector for zero `count` or empty polyline, polyline
+/// of zero length produces `count` copies of its first point. For
+/// a single requested point only the first point is returned.
+/// 
+/// # Example
+/// ```
+/// use nanocv::geometry::{resample_polyline, Point};
+/// let points = [Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(4.0, 2.0)];
+/// assert_eq!(resample_polyline(&points, 4), vec![
+///     Point::new(0.0, 0.0), Point::new(2.0, 0.0), 
+///     Point::new(4.0, 0.0), Point::new(4.0, 2.0),
+/// ]);
+/// ```
+pub fn resample_polyline(points: &[Point], count: usize) -> Vec<Point> {
+    let first = match points.first() {
+        Some(&first) if count > 0 => first,
+        _ => return Vec::new(),
+    };
+
+    let total: f64 = points.windows(2).map(|pair| pair[0].distance(pair[1])).sum();
+
+    if count == 1 || total == 0.0 {
+        return vec![first; count];
+    }
+
+    let spacing = total/(count - 1) as f64;
+    let mut result = Vec::with_capacity(count);
+    let mut segments = points.windows(2).map(|pair| LineSegment::new(pair[0], pair[1]));
+    let mut current = segments.next().unwrap();
+    // Arc length at the start of the current segment
+    let mut start = 0.0;
+
+    for index in 0..(count - 1) {
+        let position = index as f64*spacing;
+
+        while start + current.length() < position {
+            match segments.next() {
+                Some(next) => {
+                    start += current.length();
+                    current = next;
+                }
+                None => break,
+            }
+        }
+
+        let length = current.length();
+        let t = if length > 0.0 { ((position - start)/length).clamp(0.0, 1.0) } else { 0.0 };
+        result.push(current.point_at(t));
+    }
+
+    result.push(*points.last().unwrap());
+    result
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simplified_circle_stays_within_epsilon() {
+        let points: Vec<_> = (0..=100)
+            .map(|index| {
+                let angle = index as f64*std::f64::consts::PI/100.0;
+                Point::new(10.0*angle.cos(), 10.0*angle.sin())
+            })
+            .collect();
+
+        let simplified = simplify_polyline(&points, 0.1);
+        assert!(simplified.len() > 3 && simplified.len() < 20, "{}", simplified.len());
+
+        for &point in &points {
+            let distance = simplified.windows(2)
+                .map(|pair| LineSegment::new(pair[0], pair[1]).distance_to(point))
+                .fold(f64::INFINITY, f64::min);
+            assert!(distance <= 0.1 + 1e-12);
+        }
+    }
+
+    #[test]
+    fn resampling_keeps_spacing() {
+        let points = [Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 0.0), Point::new(1.0, 5.0)];
+        let resampled = resample_polyline(&points, 7);
+        assert_eq!(resampled.len(), 7);
+
+        for pair in resampled.windows(2) {
+            assert!((pair[0].distance(pair[1]) - 1.0).abs() < 1e-12);
+        }
+
+        assert_eq!(resample_polyline(&points[..1], 3), vec![Point::new(0.0, 0.0); 3]);
+    }
+}
@@ -1,4 +1,5 @@
 use std::{cmp::{min, max}, ops::{Add, Sub}};
+use super::AsPrimitive;
 
 /// A half-open range bounded from start (inclusive) to end (exclusive).
 /// 
@@ -55,6 +56,29 @@ impl<T: Copy> Range<T> {
     pub fn to_range(&self) -> std::ops::Range<T> {
         self.start..self.end
     }
+
+    /// Convert range bounds into another numeric type, 
+    /// with semantics of the `as` operator
+    /// ```
+    /// use nanocv::Range;
+    /// assert_eq!(Range::new(1isize..4).cast::<f32>(), Range::new(1.0..4.0));
+    /// ```
+    pub fn cast<U>(&self) -> Range<U> where T: AsPrimitive<U> {
+        Range { start: self.start.as_(), end: self.end.as_() }
+    }
+}
+
+impl<T: PartialOrd> Range<T> {
+    /// Tests whether `value` lies within the range
+    /// ```
+    /// use nanocv::Range;
+    /// let range = Range::new(1..3);
+    /// assert!(range.contains(1));
+    /// assert!(!range.contains(3));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
 }
 
 impl<T: Sub<Output=T> + Copy> Range<T> {
@@ -107,6 +131,63 @@ impl<T: Ord + Copy> Range<T> {
     pub fn intersect(&self, other: Range<T>) -> Self {
         Self::new(max(self.start, other.start)..min(self.end, other.end))
     }
+
+    /// Smallest range containing both ranges, empty ranges are ignored
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::Range;
+    /// assert_eq!(Range::new(1..3).union(Range::new(5..6)), Range::new(1..6));
+    /// assert_eq!(Range::new(1..3).union(Range::new(5..5)), Range::new(1..3));
+    /// ```
+    pub fn union(&self, other: Range<T>) -> Self {
+        if other.is_empty() {
+            *self
+        } else if self.is_empty() {
+            other
+        } else {
+            Self::new(min(self.start, other.start)..max(self.end, other.end))
+        }
+    }
+
+    /// Part of the range lying within `other`, unlike `intersect`,
+    /// the result bounds always lie within `other` even if ranges 
+    /// are disjoint
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::Range;
+    /// assert_eq!(Range::new(1..3).clamp_to(Range::new(2..4)), Range::new(2..3));
+    /// assert_eq!(Range::new(5..7).clamp_to(Range::new(2..4)), Range::new(4..4));
+    /// ```
+    pub fn clamp_to(&self, other: Range<T>) -> Self {
+        let start = min(max(self.start, other.start), other.end);
+        Self::new(start..min(max(self.end, start), other.end))
+    }
+}
+
+impl<T: PartialOrd> Range<T> {
+    /// Tests whether the range contains no elements
+    /// ```
+    /// use nanocv::Range;
+    /// assert!(Range::new(2..2).is_empty());
+    /// assert!(!Range::new(2..3).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+}
+
+impl<T: Add<T, Output=T> + Sub<T, Output=T> + Copy> Range<T> {
+    /// Range extended by `margin` at both sides, negative margin
+    /// shrinks the range
+    /// ```
+    /// use nanocv::Range;
+    /// assert_eq!(Range::new(2..4).inflate(1), Range::new(1..5));
+    /// ```
+    pub fn inflate(&self, margin: T) -> Self {
+        Self::new((self.start - margin)..(self.end + margin))
+    }
 }
 
 impl<T: Add<T, Output=T> + Copy> Add<T> for Range<T> {
@@ -141,4 +222,14 @@ mod tests {
     fn test_range_sub() {
         assert_eq!(Range::new(1..3) - 1, Range::new(0..2));
     }
+
+    #[test]
+    fn test_range_union_of_empty_ranges_is_empty() {
+        assert!(Range::new(1..1).union(Range::new(3..3)).is_empty());
+    }
+
+    #[test]
+    fn test_range_clamp_before_other() {
+        assert_eq!(Range::new(-5..-3).clamp_to(Range::new(0..4)), Range::new(0..0));
+    }
 }
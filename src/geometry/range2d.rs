@@ -1,7 +1,7 @@
 
-use std::ops::{Add, Sub};
-use super::Range;
-use crate::Vec2d;
+use std::ops::{Add, Sub, Mul};
+use super::{Range, AsPrimitive};
+use crate::{Vec2d, ImgSize};
 
 /// A two directional (half-open) range describing an area within an image.
 /// 
@@ -66,6 +66,17 @@ impl<T: Copy> Range2d<T> {
     pub fn end(&self) -> Vec2d<T> {
         Vec2d::new(self.x.end, self.y.end)
     }    
+
+    /// Convert range bounds into another numeric type, 
+    /// with semantics of the `as` operator
+    /// ```
+    /// use nanocv::Range2d;
+    /// let range = Range2d::<usize>::new(0..2, 1..4);
+    /// assert_eq!(range.cast::<i32>(), Range2d::new(0..2, 1..4));
+    /// ```
+    pub fn cast<U>(&self) -> Range2d<U> where T: AsPrimitive<U> {
+        Range2d { x: self.x.cast(), y: self.y.cast() }
+    }
 }
 
 impl<T: Sub<Output=T> + Copy> Range2d<T> {
@@ -107,6 +118,186 @@ impl<T: Ord + Copy> Range2d<T> {
             y: self.y.intersect(other.y),
         }
     }
+
+    /// Smallest 2D range containing both ranges, empty ranges are ignored
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::Range2d;
+    /// assert_eq!(
+    ///     Range2d::new(0..2, 1..3).union(Range2d::new(1..3, 2..5)),
+    ///     Range2d::new(0..3, 1..5)
+    /// );
+    /// ```
+    pub fn union(&self, other: Range2d<T>) -> Self {
+        if other.is_empty() {
+            *self
+        } else if self.is_empty() {
+            other
+        } else {
+            Self { x: self.x.union(other.x), y: self.y.union(other.y) }
+        }
+    }
+
+    /// Part of the 2D range lying within `other`, unlike `intersect`,
+    /// the result bounds always lie within `other` even if ranges 
+    /// are disjoint, which makes the result safe for indexing
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::Range2d;
+    /// assert_eq!(
+    ///     Range2d::new(-1..2, 3..5).clamp_to(Range2d::new(0..4, 0..4)),
+    ///     Range2d::new(0..2, 3..4)
+    /// );
+    /// ```
+    pub fn clamp_to(&self, other: Range2d<T>) -> Self {
+        Self { x: self.x.clamp_to(other.x), y: self.y.clamp_to(other.y) }
+    }
+}
+
+impl<T: PartialOrd> Range2d<T> {
+    /// Tests whether the 2D range contains no elements
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::Range2d;
+    /// assert!(Range2d::new(0..2, 1..1).is_empty());
+    /// assert!(!Range2d::new(0..2, 1..2).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty() || self.y.is_empty()
+    }
+}
+
+impl<T: Add<T, Output=T> + Sub<T, Output=T> + Copy> Range2d<T> {
+    /// 2D range extended by `margin` at all sides, negative margin
+    /// shrinks the range
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::Range2d;
+    /// assert_eq!(Range2d::new(1..2, 3..5).inflate(1), Range2d::new(0..3, 2..6));
+    /// ```
+    pub fn inflate(&self, margin: T) -> Self {
+        Self { x: self.x.inflate(margin), y: self.y.inflate(margin) }
+    }
+}
+
+impl<T: Sub<Output=T> + Mul<Output=T> + Copy> Range2d<T> {
+    /// Number of elements in a (not inverted) 2D range
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::Range2d;
+    /// assert_eq!(Range2d::new(0..2, 1..4).area(), 6);
+    /// ```
+    pub fn area(&self) -> T {
+        self.width()*self.height()
+    }
+}
+
+impl<T: PartialOrd + Copy> Range2d<T> {
+    /// Tests whether `point` lies within the range
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::{Range2d, Vec2d};
+    /// let range = Range2d::new(0..2, 1..4);
+    /// assert!(range.contains(Vec2d::new(1, 3)));
+    /// assert!(!range.contains(Vec2d::new(2, 3)));
+    /// ```
+    pub fn contains(&self, point: Vec2d<T>) -> bool {
+        self.x.contains(point.x) && self.y.contains(point.y)
+    }
+}
+
+impl<T: Copy> Range2d<T> where std::ops::Range<T>: Iterator<Item=T> {
+    /// Iterator over all coordinates within the range in row-major order
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::{Range2d, Vec2d};
+    /// let points: Vec<_> = Range2d::new(0..2, 1..3).iter().collect();
+    /// 
+    /// assert_eq!(points, vec![
+    ///     Vec2d::new(0, 1), Vec2d::new(1, 1),
+    ///     Vec2d::new(0, 2), Vec2d::new(1, 2),
+    /// ]);
+    /// ```
+    pub fn iter(&self) -> Range2dIter<T> {
+        Range2dIter { x: self.x, y: self.y.to_range(), line: None }
+    }
+}
+
+/// Iterator over coordinates of a `Range2d` in row-major order
+/// 
+/// Created by `Range2d::iter`
+#[derive(Clone, Debug)]
+pub struct Range2dIter<T> {
+    x: Range<T>,
+    y: std::ops::Range<T>,
+    line: Option<(T, std::ops::Range<T>)>,
+}
+
+impl<T: Copy> Iterator for Range2dIter<T> where std::ops::Range<T>: Iterator<Item=T> {
+    type Item = Vec2d<T>;
+
+    fn next(&mut self) -> Option<Vec2d<T>> {
+        loop {
+            if let Some((y, columns)) = &mut self.line {
+                if let Some(x) = columns.next() {
+                    return Some(Vec2d::new(x, *y));
+                }
+            }
+
+            let y = self.y.next()?;
+            self.line = Some((y, self.x.to_range()));
+        }
+    }
+}
+
+impl Range2d<isize> {
+    /// Range covering a whole image of given `size`
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::{ImgRange, ImgSize};
+    /// assert_eq!(ImgRange::from_size(ImgSize::new(3, 2)), ImgRange::new(0..3, 0..2));
+    /// ```
+    pub fn from_size(size: ImgSize) -> Self {
+        Self::new(0..size.x as isize, 0..size.y as isize)
+    }
+
+    /// Range of given `size` centered at `center`, for even sizes, 
+    /// center lies right/below the middle of the range
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::{ImgRange, ImgSize, Vec2d};
+    /// assert_eq!(
+    ///     ImgRange::centered(Vec2d::new(5, 5), ImgSize::new(3, 4)), 
+    ///     ImgRange::new(4..7, 3..7)
+    /// );
+    /// ```
+    pub fn centered(center: Vec2d<isize>, size: ImgSize) -> Self {
+        let size = size.cast::<isize>();
+        let start = center - size/2;
+        let end = start + size;
+        Self::new(start.x..end.x, start.y..end.y)
+    }
+
+    /// Square window containing all pixels not further than `radius` 
+    /// from `center` in both directions, window size is `2*radius + 1`
+    ///
+    /// # Example
+    /// ```
+    /// use nanocv::{ImgRange, Vec2d};
+    /// assert_eq!(ImgRange::window(Vec2d::new(0, 3), 1), ImgRange::new(-1..2, 2..5));
+    /// ```
+    pub fn window(center: Vec2d<isize>, radius: usize) -> Self {
+        Self::centered(center, ImgSize::new(2*radius + 1, 2*radius + 1))
+    }
 }
 
 impl From<Range2d<isize>> for Range2d<usize> {
@@ -160,4 +351,17 @@ mod tests {
             Range2d::new(-1..1, 1..4)
         );
     }
+
+    #[test]
+    fn test_range2d_iter_empty_width() {
+        assert_eq!(Range2d::new(1..1, 0..3).iter().count(), 0);
+    }
+
+    #[test]
+    fn test_range2d_iter_negative_coordinates() {
+        assert_eq!(
+            Range2d::<isize>::new(-1..0, -2..0).iter().collect::<Vec<_>>(),
+            vec![Vec2d::new(-1, -2), Vec2d::new(-1, -1)]
+        );
+    }
 }
@@ -0,0 +1,129 @@
+use crate::Range2d;
+use super::Point;
+
+/// Straight line segment between two points
+/// 
+/// # Example
+/// ```
+/// use nanocv::geometry::{LineSegment, Point};
+/// let segment = LineSegment::new(Point::new(0.0, 0.0), Point::new(3.0, 4.0));
+/// assert_eq!(segment.length(), 5.0);
+/// assert_eq!(segment.midpoint(), Point::new(1.5, 2.0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LineSegment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl LineSegment {
+    /// Create segment from `start` to `end`
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    /// Vector from start to end point
+    pub fn vector(&self) -> Point {
+        self.end - self.start
+    }
+
+    /// Segment length
+    pub fn length(&self) -> f64 {
+        self.vector().length()
+    }
+
+    /// Point in the middle of the segment
+    pub fn midpoint(&self) -> Point {
+        self.start.midpoint(self.end)
+    }
+
+    /// Point at parameter `t`, `0` for start point and `1` for end point
+    pub fn point_at(&self, t: f64) -> Point {
+        self.start.lerp(self.end, t)
+    }
+
+    /// Distance from `point` to the nearest point of the segment
+    /// ```
+    /// use nanocv::geometry::{LineSegment, Point};
+    /// let segment = LineSegment::new(Point::new(0.0, 0.0), Point::new(2.0, 0.0));
+    /// assert_eq!(segment.distance_to(Point::new(1.0, 3.0)), 3.0);
+    /// assert_eq!(segment.distance_to(Point::new(5.0, 4.0)), 5.0);
+    /// ```
+    pub fn distance_to(&self, point: Point) -> f64 {
+        let vector = self.vector();
+        let length_squared = vector.length_squared();
+
+        if length_squared == 0.0 {
+            return self.start.distance(point);
+        }
+
+        let t = ((point - self.start)*vector/length_squared).clamp(0.0, 1.0);
+        self.point_at(t).distance(point)
+    }
+
+    /// Part of the segment lying within `range`, `None` if the segment
+    /// lies completely outside (Liang-Barsky algorithm)
+    /// ```
+    /// use nanocv::{Range2d, geometry::{LineSegment, Point}};
+    /// let segment = LineSegment::new(Point::new(-1.0, 1.0), Point::new(5.0, 1.0));
+    /// assert_eq!(
+    ///     segment.clip(Range2d::new(0.0..4.0, 0.0..4.0)), 
+    ///     Some(LineSegment::new(Point::new(0.0, 1.0), Point::new(4.0, 1.0)))
+    /// );
+    /// ```
+    pub fn clip(&self, range: Range2d<f64>) -> Option<LineSegment> {
+        let vector = self.vector();
+        let (mut t0, mut t1) = (0.0f64, 1.0f64);
+
+        let bounds = [
+            (-vector.x, self.start.x - range.x.start),
+            (vector.x, range.x.end - self.start.x),
+            (-vector.y, self.start.y - range.y.start),
+            (vector.y, range.y.end - self.start.y),
+        ];
+
+        for (p, q) in bounds {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let t = q/p;
+
+                if p < 0.0 {
+                    t0 = t0.max(t);
+                } else {
+                    t1 = t1.min(t);
+                }
+            }
+        }
+
+        if t0 > t1 {
+            None
+        } else {
+            Some(LineSegment::new(self.point_at(t0), self.point_at(t1)))
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_segment_outside_range() {
+        let segment = LineSegment::new(Point::new(-2.0, -1.0), Point::new(-1.0, 5.0));
+        assert_eq!(segment.clip(Range2d::new(0.0..4.0, 0.0..4.0)), None);
+    }
+
+    #[test]
+    fn clip_diagonal_segment() {
+        let segment = LineSegment::new(Point::new(-1.0, -1.0), Point::new(3.0, 3.0));
+        assert_eq!(
+            segment.clip(Range2d::new(0.0..2.0, 0.0..4.0)), 
+            Some(LineSegment::new(Point::new(0.0, 0.0), Point::new(2.0, 2.0)))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::{fmt::{Formatter, Debug, Error}, ops::{Add, Sub, Mul, Div, Neg}};
 #[cfg(feature = "serde")]
 use serde_derive::{Serialize, Deserialize};
+use super::AsPrimitive;
 
 /// General purpose two dimensional vector
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
@@ -66,6 +67,19 @@ impl<T: Copy> Vec2d<T> {
     }
 }
 
+impl<T: Copy> Vec2d<T> {
+    /// Convert vector elements into another numeric type, 
+    /// with semantics of the `as` operator
+    /// ```
+    /// use nanocv::Vec2d;
+    /// assert_eq!(Vec2d::new(1.7f32, -2.2).cast::<isize>(), Vec2d::new(1, -2));
+    /// assert_eq!(Vec2d::new(3usize, 4).cast::<f64>(), Vec2d::new(3.0, 4.0));
+    /// ```
+    pub fn cast<U>(self) -> Vec2d<U> where T: AsPrimitive<U> {
+        Vec2d { x: self.x.as_(), y: self.y.as_() }
+    }
+}
+
 impl<T: Mul<T, Output=T>> Vec2d<T> {
     /// Product of vector elements
     /// ```
@@ -77,6 +91,106 @@ impl<T: Mul<T, Output=T>> Vec2d<T> {
     }
 }
 
+macro_rules! impl_float_vec2d {
+    ($type: ty) => {
+        impl Vec2d<$type> {
+            /// Squared euclidean length of the vector
+            /// ```
+            /// use nanocv::Vec2d;
+            /// assert_eq!(Vec2d::<f64>::new(3.0, 4.0).length_squared(), 25.0);
+            /// ```
+            pub fn length_squared(self) -> $type {
+                self*self
+            }
+
+            /// Euclidean length of the vector
+            /// ```
+            /// use nanocv::Vec2d;
+            /// assert_eq!(Vec2d::<f64>::new(3.0, 4.0).length(), 5.0);
+            /// ```
+            pub fn length(self) -> $type {
+                self.length_squared().sqrt()
+            }
+
+            /// Vector of unit length with the same direction, 
+            /// zero vector stays zero
+            /// ```
+            /// use nanocv::Vec2d;
+            /// let vector = Vec2d::<f64>::new(3.0, 4.0);
+            /// assert_eq!(vector.normalized(), Vec2d::new(0.6, 0.8));
+            /// ```
+            pub fn normalized(self) -> Self {
+                let length = self.length();
+                if length == 0.0 { self } else { self/length }
+            }
+
+            /// Vector rotated counter-clockwise by `angle` (in radians)
+            /// ```
+            /// use nanocv::Vec2d;
+            /// let rotated = Vec2d::<f64>::new(1.0, 0.0).rotated(std::f64::consts::FRAC_PI_2);
+            /// assert!((rotated - Vec2d::new(0.0, 1.0)).length() < 1e-6);
+            /// ```
+            pub fn rotated(self, angle: $type) -> Self {
+                let (sin, cos) = angle.sin_cos();
+                Vec2d::new(self.x*cos - self.y*sin, self.x*sin + self.y*cos)
+            }
+
+            /// Angle of the vector from the x axis (in radians), in `[-pi, pi]`
+            /// ```
+            /// use nanocv::Vec2d;
+            /// assert_eq!(Vec2d::<f64>::new(0.0, 2.0).angle(), std::f64::consts::FRAC_PI_2);
+            /// ```
+            pub fn angle(self) -> $type {
+                self.y.atan2(self.x)
+            }
+
+            /// Linear interpolation between `self` (for `t = 0`) 
+            /// and `other` (for `t = 1`)
+            /// ```
+            /// use nanocv::Vec2d;
+            /// let a = Vec2d::<f64>::new(0.0, 2.0);
+            /// assert_eq!(a.lerp(Vec2d::new(2.0, 4.0), 0.5), Vec2d::new(1.0, 3.0));
+            /// ```
+            pub fn lerp(self, other: Self, t: $type) -> Self {
+                self + (other - self)*t
+            }
+
+            /// Perpendicular vector, the vector rotated 
+            /// counter-clockwise by right angle
+            /// ```
+            /// use nanocv::Vec2d;
+            /// let vector = Vec2d::<f64>::new(1.0, 2.0);
+            /// assert_eq!(vector.perp(), Vec2d::new(-2.0, 1.0));
+            /// ```
+            pub fn perp(self) -> Self {
+                Vec2d::new(-self.y, self.x)
+            }
+
+            /// Euclidean distance between two points
+            /// ```
+            /// use nanocv::Vec2d;
+            /// assert_eq!(Vec2d::<f64>::new(1.0, 1.0).distance(Vec2d::new(4.0, 5.0)), 5.0);
+            /// ```
+            pub fn distance(self, other: Self) -> $type {
+                (other - self).length()
+            }
+
+            /// Point in the middle between two points
+            /// ```
+            /// use nanocv::Vec2d;
+            /// let a = Vec2d::<f64>::new(1.0, 1.0);
+            /// assert_eq!(a.midpoint(Vec2d::new(2.0, 3.0)), Vec2d::new(1.5, 2.0));
+            /// ```
+            pub fn midpoint(self, other: Self) -> Self {
+                self.lerp(other, 0.5)
+            }
+        }
+    };
+}
+
+impl_float_vec2d!(f32);
+impl_float_vec2d!(f64);
+
 impl<T: Default> Default for Vec2d<T> {
     fn default() -> Self {
         Self { x: T::default(), y: T::default() }
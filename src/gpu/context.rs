@@ -0,0 +1,403 @@
+use std::fmt;
+use crate::{Img, ImgBuf, ImgSize, AsPrimitive};
+use crate::filter::{filter_2d_new, map_new, update, resize_nearest_new, scale_index_table};
+use super::{PointOp, device::Device, shaders};
+
+/// Backend executing the operations of a compute [Context](struct.Context.html)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Operations run as wgpu compute shaders
+    Gpu,
+    /// Operations run on the CPU using `filter` module functions
+    Cpu,
+}
+
+/// Single channel `f32` image stored in the memory of a compute device
+///
+/// Device images can only be processed by the context that created them.
+#[derive(Debug)]
+pub struct DeviceImage {
+    size: ImgSize,
+    data: DeviceData,
+}
+
+#[derive(Debug)]
+enum DeviceData {
+    Cpu(ImgBuf<f32>),
+    Gpu(wgpu::Buffer),
+}
+
+impl DeviceImage {
+    /// Image size in pixels
+    pub fn size(&self) -> ImgSize {
+        self.size
+    }
+}
+
+/// Compute context uploading images to the device, running operations
+/// and reading the results back
+///
+/// # Example
+/// ```
+/// use nanocv::{ImgBuf, ImgSize, Img, gpu::{Context, PointOp}};
+///
+/// let context = Context::new();
+/// let image = ImgBuf::<u8>::new_init(ImgSize::new(8, 6), 10);
+/// let kernel = ImgBuf::<f32>::new_init(ImgSize::new(3, 3), 1.0/9.0);
+///
+/// let uploaded = context.upload(&image);
+/// let blurred = context.filter_2d(&uploaded, &kernel);
+/// let scaled = context.map(&blurred, &[PointOp::Scale(2.0)]);
+/// let result = context.download(&context.resize(&scaled, ImgSize::new(4, 3)));
+///
+/// assert_eq!(result.size(), ImgSize::new(4, 3));
+/// assert!((result[(2, 1)] - 20.0).abs() < 1e-4);
+/// ```
+pub struct Context {
+    device: Option<Device>,
+}
+
+impl Context {
+    /// Acquires a compute device, falls back to CPU when no adapter
+    /// supporting compute shaders is available
+    ///
+    /// Adapter selection can be controlled by `WGPU_BACKEND` and
+    /// `WGPU_POWER_PREF` environment variables.
+    pub fn new() -> Self {
+        Self { device: Device::new() }
+    }
+
+    /// Context running all operations on CPU
+    pub fn cpu() -> Self {
+        Self { device: None }
+    }
+
+    /// Backend actually used to execute operations
+    pub fn backend(&self) -> Backend {
+        match self.device {
+            Some(_) => Backend::Gpu,
+            None => Backend::Cpu,
+        }
+    }
+
+    /// Name of the compute adapter, `None` for CPU backend
+    pub fn adapter(&self) -> Option<&str> {
+        self.device.as_ref().map(|device| device.name())
+    }
+
+    /// Upload image into device memory, pixels are converted to `f32`
+    pub fn upload<T: AsPrimitive<f32>>(&self, image: &dyn Img<T>) -> DeviceImage {
+        let size = image.size();
+
+        let data = match &self.device {
+            Some(device) => {
+                let mut pixels = Vec::with_capacity(size.product());
+
+                for y in 0..size.y {
+                    pixels.extend(image.line_ref(y).iter().map(|x| x.as_()));
+                }
+
+                DeviceData::Gpu(device.storage_init(&pixels))
+            },
+            None => DeviceData::Cpu(map_new(image, |x: T| x.as_())),
+        };
+
+        DeviceImage { size, data }
+    }
+
+    /// Read image back from device memory
+    pub fn download(&self, image: &DeviceImage) -> ImgBuf<f32> {
+        match (&self.device, &image.data) {
+            (Some(device), DeviceData::Gpu(buffer)) => {
+                ImgBuf::from_vec(image.size, device.read(buffer, image.size.product()))
+            },
+            (None, DeviceData::Cpu(data)) => data.clone(),
+            _ => panic!("Device image belongs to a different context"),
+        }
+    }
+
+    /// Convolve image with a 2D kernel, see
+    /// [filter_2d](../filter/fn.filter_2d.html) for border handling
+    ///
+    /// Panics if kernel is empty
+    pub fn filter_2d(&self, image: &DeviceImage, kernel: &dyn Img<f32>) -> DeviceImage {
+        let size = image.size;
+        let kernel_size = kernel.size();
+        assert!(kernel_size.x > 0 && kernel_size.y > 0, "Kernel must not be empty");
+
+        let data = match (&self.device, &image.data) {
+            (Some(device), DeviceData::Gpu(input)) => {
+                let mut weights = Vec::with_capacity(kernel_size.product());
+
+                for y in 0..kernel_size.y {
+                    weights.extend_from_slice(kernel.line_ref(y));
+                }
+
+                let params = device.uniform(&[
+                    dimension(size.x),
+                    dimension(size.y),
+                    dimension(kernel_size.x),
+                    dimension(kernel_size.y),
+                    dimension((kernel_size.x - 1)/2),
+                    dimension((kernel_size.y - 1)/2),
+                ]);
+
+                let kernel = device.storage_init(&weights);
+                let output = device.storage(size.product());
+                device.dispatch(shaders::FILTER_2D, &[&params, input, &kernel, &output], size.x, size.y);
+                DeviceData::Gpu(output)
+            },
+            (None, DeviceData::Cpu(data)) => DeviceData::Cpu(filter_2d_new(data, kernel)),
+            _ => panic!("Device image belongs to a different context"),
+        };
+
+        DeviceImage { size, data }
+    }
+
+    /// Map each pixel by point `operations` into a new device image
+    pub fn map(&self, image: &DeviceImage, operations: &[PointOp]) -> DeviceImage {
+        let size = image.size;
+
+        let data = match (&self.device, &image.data) {
+            (Some(device), DeviceData::Gpu(input)) => {
+                let output = device.storage(size.product());
+
+                if operations.is_empty() {
+                    device.copy(input, &output, size.product());
+                } else {
+                    let source = shaders::point_shader(operations, false);
+                    run_point_shader(device, &source, &[&output, input], operations, size);
+                }
+
+                DeviceData::Gpu(output)
+            },
+            (None, DeviceData::Cpu(data)) => {
+                DeviceData::Cpu(map_new(data, |x| PointOp::apply_all(operations, x)))
+            },
+            _ => panic!("Device image belongs to a different context"),
+        };
+
+        DeviceImage { size, data }
+    }
+
+    /// Update each pixel of device image in place by point `operations`
+    pub fn update(&self, image: &mut DeviceImage, operations: &[PointOp]) {
+        let size = image.size;
+
+        match (&self.device, &mut image.data) {
+            (Some(device), DeviceData::Gpu(buffer)) => {
+                if !operations.is_empty() {
+                    let source = shaders::point_shader(operations, true);
+                    run_point_shader(device, &source, &[&*buffer], operations, size);
+                }
+            },
+            (None, DeviceData::Cpu(data)) => {
+                update(data, |x| PointOp::apply_all(operations, x));
+            },
+            _ => panic!("Device image belongs to a different context"),
+        }
+    }
+
+    /// Scale image to different resolution by nearest neighbor
+    ///
+    /// Panics if the image is empty and the target size is not
+    pub fn resize(&self, image: &DeviceImage, size: ImgSize) -> DeviceImage {
+        let source = image.size;
+
+        assert!(
+            size.product() == 0 || source.product() > 0,
+            "Can not resize empty image to non-empty size"
+        );
+
+        let data = match (&self.device, &image.data) {
+            (Some(device), DeviceData::Gpu(input)) => {
+                let columns = indices(scale_index_table(source.x, size.x));
+                let lines = indices(scale_index_table(source.y, size.y));
+                let params = device.uniform(&[dimension(size.x), dimension(size.y), dimension(source.x)]);
+                let columns = device.storage_init(&columns);
+                let lines = device.storage_init(&lines);
+                let output = device.storage(size.product());
+
+                device.dispatch(
+                    shaders::RESIZE_NEAREST,
+                    &[&params, input, &columns, &lines, &output],
+                    size.x, size.y
+                );
+
+                DeviceData::Gpu(output)
+            },
+            (None, DeviceData::Cpu(data)) => DeviceData::Cpu(resize_nearest_new(data, size)),
+            _ => panic!("Device image belongs to a different context"),
+        };
+
+        DeviceImage { size, data }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("backend", &self.backend())
+            .field("adapter", &self.adapter())
+            .finish()
+    }
+}
+
+/// Run point operation shader, `buffers` are output and optional input
+fn run_point_shader(
+    device: &Device,
+    source: &str,
+    buffers: &[&wgpu::Buffer],
+    operations: &[PointOp],
+    size: ImgSize
+) {
+    let constants: Vec<f32> = operations.iter()
+        .flat_map(|operation| operation.constants())
+        .collect();
+
+    let params = device.uniform(&[dimension(size.x), dimension(size.y)]);
+    let constants_buffer = device.storage_init(&constants);
+    let mut bindings = vec![&params];
+    bindings.extend_from_slice(buffers);
+
+    // Constants binding is omitted from the shader when there are none
+    if !constants.is_empty() {
+        bindings.push(&constants_buffer);
+    }
+
+    device.dispatch(source, &bindings, size.x, size.y);
+}
+
+/// Image dimension passed to a compute shader
+fn dimension(value: usize) -> u32 {
+    // Pixel indices are computed in u32 inside shaders
+    assert!(value <= u32::MAX as usize, "Image dimension exceeds compute shader range");
+    value as u32
+}
+
+/// Index table passed to a compute shader
+fn indices(table: Vec<usize>) -> Vec<u32> {
+    table.into_iter().map(dimension).collect()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::gaussian_kernel;
+
+    fn test_image(size: ImgSize) -> ImgBuf<u8> {
+        let pixels = (0..size.product()).map(|index| (index*37 % 251) as u8).collect();
+        ImgBuf::from_vec(size, pixels)
+    }
+
+    fn assert_close(actual: &ImgBuf<f32>, expected: &ImgBuf<f32>) {
+        assert_eq!(actual.size(), expected.size());
+
+        for y in 0..expected.height() {
+            for (a, e) in actual.line_ref(y).iter().zip(expected.line_ref(y)) {
+                assert!((a - e).abs() <= 1e-3*e.abs().max(1.0), "{} != {}", a, e);
+            }
+        }
+    }
+
+    /// Run the same operations on the default and the CPU context
+    fn compare<F>(operation: F) where F: Fn(&Context) -> ImgBuf<f32> {
+        let expected = operation(&Context::cpu());
+        let actual = operation(&Context::new());
+        assert_close(&actual, &expected);
+    }
+
+    #[test]
+    fn round_trip_preserves_pixels() {
+        for context in [Context::new(), Context::cpu()].iter() {
+            let image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![1, 2, 3, 4, 5, 6]);
+            let mut uploaded = context.upload(&image);
+            context.update(&mut uploaded, &[PointOp::Offset(1.0)]);
+            let result = context.download(&uploaded);
+            assert_eq!(result.into_vec(), vec![2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        }
+    }
+
+    #[test]
+    fn filter_2d_matches_cpu() {
+        let kernel = ImgBuf::<f32>::from_vec(ImgSize::new(4, 3), vec![
+            1.0, -2.0, 0.0, 0.5,
+            0.0, 3.0, 0.25, 0.0,
+            -1.0, 0.0, 2.0, 1.5,
+        ]);
+
+        for &size in [ImgSize::new(13, 9), ImgSize::new(1, 5), ImgSize::new(0, 4)].iter() {
+            compare(|context| {
+                let image = context.upload(&test_image(size));
+                context.download(&context.filter_2d(&image, &kernel))
+            });
+        }
+
+        let weights = gaussian_kernel(2.0);
+        let mut kernel = ImgBuf::<f32>::new(ImgSize::new(weights.len(), weights.len()));
+
+        for y in 0..weights.len() {
+            for x in 0..weights.len() {
+                kernel[(x, y)] = weights[x]*weights[y];
+            }
+        }
+
+        compare(|context| {
+            let image = context.upload(&test_image(ImgSize::new(20, 17)));
+            context.download(&context.filter_2d(&image, &kernel))
+        });
+    }
+
+    #[test]
+    fn point_operations_match_cpu() {
+        let operations = [
+            PointOp::Offset(-100.0),
+            PointOp::Abs,
+            PointOp::Scale(0.5),
+            PointOp::Clamp(5.0, 40.0),
+            PointOp::Threshold(20.0, 255.0),
+        ];
+
+        for count in 0..=operations.len() {
+            compare(|context| {
+                let image = context.upload(&test_image(ImgSize::new(11, 7)));
+                context.download(&context.map(&image, &operations[..count]))
+            });
+
+            compare(|context| {
+                let mut image = context.upload(&test_image(ImgSize::new(11, 7)));
+                context.update(&mut image, &operations[..count]);
+                context.download(&image)
+            });
+        }
+    }
+
+    #[test]
+    fn map_keeps_source_image() {
+        let context = Context::new();
+        let image = context.upload(&test_image(ImgSize::new(4, 3)));
+        let _ = context.map(&image, &[PointOp::Scale(2.0)]);
+        let expected = map_new(&test_image(ImgSize::new(4, 3)), |x: u8| x as f32);
+        assert_close(&context.download(&image), &expected);
+    }
+
+    #[test]
+    fn resize_matches_cpu() {
+        let sizes = [ImgSize::new(7, 5), ImgSize::new(19, 13), ImgSize::new(1, 1), ImgSize::new(0, 3)];
+
+        for &size in sizes.iter() {
+            compare(|context| {
+                let image = context.upload(&test_image(ImgSize::new(10, 8)));
+                context.download(&context.resize(&image, size))
+            });
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::{collections::HashMap, convert::TryFrom, sync::{Mutex, mpsc}};
+use wgpu::util::DeviceExt;
+
+/// Workgroup size of all compute shaders in both dimensions
+pub(crate) const WORKGROUP_SIZE: u32 = 8;
+
+/// Compute device acquired through wgpu together with a cache
+/// of compiled pipelines indexed by shader source
+pub(crate) struct Device {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    name: String,
+    pipelines: Mutex<HashMap<String, wgpu::ComputePipeline>>,
+}
+
+impl Device {
+    /// Request adapter and device, returns `None` when there is no adapter
+    /// capable of running compute shaders
+    pub fn new() -> Option<Self> {
+        let instance = wgpu::Instance::new(
+            wgpu::InstanceDescriptor::new_without_display_handle_from_env()
+        );
+
+        let adapter = pollster::block_on(
+            instance.request_adapter(&wgpu::RequestAdapterOptions::default())
+        ).ok()?;
+
+        let capabilities = adapter.get_downlevel_capabilities();
+
+        if !capabilities.flags.contains(wgpu::DownlevelFlags::COMPUTE_SHADERS) {
+            return None;
+        }
+
+        let (device, queue) = pollster::block_on(
+            adapter.request_device(&wgpu::DeviceDescriptor {
+                label: Some("nanocv"),
+                required_limits: adapter.limits(),
+                ..Default::default()
+            })
+        ).ok()?;
+
+        Some(Self {
+            device,
+            queue,
+            name: adapter.get_info().name,
+            pipelines: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Adapter name reported by the driver
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Create storage buffer initialized by `values`
+    pub fn storage_init<T: Bytes>(&self, values: &[T]) -> wgpu::Buffer {
+        let mut contents: Vec<u8> = values.iter().flat_map(|x| x.bytes()).collect();
+        // Zero sized bindings are not allowed
+        contents.resize(contents.len().max(4), 0);
+
+        self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &contents,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+        })
+    }
+
+    /// Create uninitialized storage buffer for `length` values of `f32`
+    pub fn storage(&self, length: usize) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: buffer_size(length).max(4),
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::COPY_SRC
+                | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Create uniform buffer with shader parameters, padded to 16 bytes
+    pub fn uniform(&self, values: &[u32]) -> wgpu::Buffer {
+        let mut contents: Vec<u8> = values.iter().flat_map(|x| x.bytes()).collect();
+        contents.resize((contents.len().div_ceil(16)*16).max(16), 0);
+
+        self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &contents,
+            usage: wgpu::BufferUsages::UNIFORM,
+        })
+    }
+
+    /// Read `length` values of `f32` from storage buffer
+    pub fn read(&self, buffer: &wgpu::Buffer, length: usize) -> Vec<f32> {
+        if length == 0 {
+            return Vec::new();
+        }
+
+        let size = buffer_size(length);
+
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+
+        staging.map_async(wgpu::MapMode::Read, .., move |result| {
+            let _ = sender.send(result);
+        });
+
+        self.device.poll(wgpu::PollType::wait_indefinitely())
+            .expect("Compute device lost while reading buffer");
+
+        receiver.recv()
+            .expect("Buffer mapping was not completed")
+            .expect("Buffer mapping failed");
+
+        let values = {
+            let view = staging.get_mapped_range(..).expect("Buffer is not mapped");
+            view.chunks_exact(4)
+                .map(|bytes| f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .collect()
+        };
+
+        staging.unmap();
+        values
+    }
+
+    /// Copy content of buffer `source` into buffer `destination`
+    pub fn copy(&self, source: &wgpu::Buffer, destination: &wgpu::Buffer, length: usize) {
+        if length == 0 {
+            return;
+        }
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        encoder.copy_buffer_to_buffer(source, 0, destination, 0, buffer_size(length));
+        self.queue.submit([encoder.finish()]);
+    }
+
+    /// Run compute shader `source` over a grid of `width`x`height` invocations,
+    /// `buffers` are bound to group 0 in the order of binding indices
+    pub fn dispatch(&self, source: &str, buffers: &[&wgpu::Buffer], width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let pipeline = self.pipeline(source);
+
+        let entries: Vec<wgpu::BindGroupEntry> = buffers.iter().enumerate()
+            .map(|(index, buffer)| wgpu::BindGroupEntry {
+                binding: index as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(workgroups(width), workgroups(height), 1);
+        }
+
+        self.queue.submit([encoder.finish()]);
+    }
+
+    /// Get compiled pipeline for shader source from cache or compile a new one
+    fn pipeline(&self, source: &str) -> wgpu::ComputePipeline {
+        let mut pipelines = self.pipelines.lock().expect("Pipeline cache poisoned");
+
+        if let Some(pipeline) = pipelines.get(source) {
+            return pipeline.clone();
+        }
+
+        let module = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        pipelines.insert(source.to_string(), pipeline.clone());
+        pipeline
+    }
+}
+
+/// Values stored into device buffers
+pub(crate) trait Bytes: Copy {
+    fn bytes(self) -> [u8; 4];
+}
+
+impl Bytes for f32 {
+    fn bytes(self) -> [u8; 4] {
+        self.to_ne_bytes()
+    }
+}
+
+impl Bytes for u32 {
+    fn bytes(self) -> [u8; 4] {
+        self.to_ne_bytes()
+    }
+}
+
+/// Size in bytes of buffer holding `length` values of 4 bytes
+fn buffer_size(length: usize) -> u64 {
+    (length as u64).checked_mul(4).expect("Buffer size overflows")
+}
+
+/// Number of workgroups covering `size` invocations
+fn workgroups(size: usize) -> u32 {
+    let count = (size as u64).div_ceil(WORKGROUP_SIZE as u64);
+    u32::try_from(count).expect("Image too large for compute dispatch")
+}
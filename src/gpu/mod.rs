@@ -0,0 +1,25 @@
+//! Compute backend for offloading heavy filtering (enabled by `gpu` feature)
+//!
+//! The [Context](struct.Context.html) owns the compute device acquired
+//! through wgpu, images are uploaded into [DeviceImage](struct.DeviceImage.html)
+//! buffers, processed by compute shaders and read back by
+//! [download](struct.Context.html#method.download).
+//! 
+//! When no adapter supporting compute shaders is available, the context
+//! falls back to the CPU implementation of each operation, so that the same
+//! code runs everywhere. Check [Context::backend](struct.Context.html#method.backend)
+//! to find out which backend is actually used.
+//! 
+//! Available types:
+//!  * [Context](struct.Context.html) - compute device and operations
+//!  * [DeviceImage](struct.DeviceImage.html) - image uploaded to the device
+//!  * [Backend](enum.Backend.html) - backend executing the operations
+//!  * [PointOp](enum.PointOp.html) - per pixel operation for map and update
+
+mod context;
+mod device;
+mod point;
+mod shaders;
+
+pub use context::{Context, DeviceImage, Backend};
+pub use point::PointOp;
@@ -0,0 +1,70 @@
+/// Point operation applied to each pixel by
+/// [Context::map](struct.Context.html#method.map) and
+/// [Context::update](struct.Context.html#method.update)
+///
+/// Arbitrary closures can not run in compute shaders, so pixel mappings
+/// are composed of these operations, applied in the given order.
+///
+/// # Example
+/// ```
+/// use nanocv::gpu::PointOp;
+/// let operations = [PointOp::Offset(-10.0), PointOp::Scale(0.5), PointOp::Abs];
+/// assert_eq!(PointOp::apply_all(&operations, 4.0), 3.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PointOp {
+    /// Multiply pixel value by a constant
+    Scale(f32),
+    /// Add a constant to pixel value
+    Offset(f32),
+    /// Absolute value of pixel
+    Abs,
+    /// Clamp pixel value into range `min..=max`
+    Clamp(f32, f32),
+    /// Set pixels greater than `level` to `max_value` and other pixels to zero,
+    /// the same as [threshold_new](../filter/fn.threshold_new.html)
+    Threshold(f32, f32),
+}
+
+impl PointOp {
+    /// Apply operation to a single value on CPU
+    pub fn apply(&self, x: f32) -> f32 {
+        match *self {
+            PointOp::Scale(factor) => x*factor,
+            PointOp::Offset(offset) => x + offset,
+            PointOp::Abs => x.abs(),
+            PointOp::Clamp(min, max) => x.max(min).min(max),
+            PointOp::Threshold(level, max_value) => if x > level { max_value } else { 0.0 },
+        }
+    }
+
+    /// Apply sequence of operations to a single value on CPU
+    pub fn apply_all(operations: &[PointOp], x: f32) -> f32 {
+        operations.iter().fold(x, |value, operation| operation.apply(value))
+    }
+
+    /// Constants of operation passed to the compute shader
+    pub(crate) fn constants(&self) -> Vec<f32> {
+        match *self {
+            PointOp::Scale(factor) => vec![factor],
+            PointOp::Offset(offset) => vec![offset],
+            PointOp::Abs => vec![],
+            PointOp::Clamp(min, max) => vec![min, max],
+            PointOp::Threshold(level, max_value) => vec![level, max_value],
+        }
+    }
+
+    /// WGSL expression of operation applied to `x`, operation constants
+    /// start at index `first` of constants buffer
+    pub(crate) fn expression(&self, first: usize) -> String {
+        let constant = |index: usize| format!("constants[{}]", first + index);
+
+        match *self {
+            PointOp::Scale(_) => format!("x*{}", constant(0)),
+            PointOp::Offset(_) => format!("x + {}", constant(0)),
+            PointOp::Abs => String::from("abs(x)"),
+            PointOp::Clamp(_, _) => format!("clamp(x, {}, {})", constant(0), constant(1)),
+            PointOp::Threshold(_, _) => format!("select(0.0, {}, x > {})", constant(1), constant(0)),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use super::PointOp;
+
+/// Convolution by 2D kernel with replicated border pixels, matches
+/// [filter_2d](../filter/fn.filter_2d.html) summation order
+pub(crate) const FILTER_2D: &str = "
+struct Params {
+    width: u32,
+    height: u32,
+    kernel_width: u32,
+    kernel_height: u32,
+    anchor_x: u32,
+    anchor_y: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> input: array<f32>;
+@group(0) @binding(2) var<storage, read> kernel: array<f32>;
+@group(0) @binding(3) var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.width || id.y >= params.height) {
+        return;
+    }
+
+    var sum = 0.0;
+
+    for (var ky = 0u; ky < params.kernel_height; ky++) {
+        let line = min(max(id.y + ky, params.anchor_y) - params.anchor_y, params.height - 1u);
+
+        for (var kx = 0u; kx < params.kernel_width; kx++) {
+            let weight = kernel[ky*params.kernel_width + kx];
+
+            if (weight != 0.0) {
+                let column = min(max(id.x + kx, params.anchor_x) - params.anchor_x, params.width - 1u);
+                sum += weight*input[line*params.width + column];
+            }
+        }
+    }
+
+    output[id.y*params.width + id.x] = sum;
+}
+";
+
+/// Nearest neighbor resize using source index tables computed on host
+pub(crate) const RESIZE_NEAREST: &str = "
+struct Params {
+    width: u32,
+    height: u32,
+    source_width: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> input: array<f32>;
+@group(0) @binding(2) var<storage, read> columns: array<u32>;
+@group(0) @binding(3) var<storage, read> lines: array<u32>;
+@group(0) @binding(4) var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(8, 8)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.width || id.y >= params.height) {
+        return;
+    }
+
+    output[id.y*params.width + id.x] = input[lines[id.y]*params.source_width + columns[id.x]];
+}
+";
+
+/// Generate shader applying point `operations` to each pixel, constants of
+/// operations are bound as a storage buffer so that the shader only depends
+/// on the kinds of operations and can be reused from the pipeline cache
+///
+/// Bindings are parameters, output, input (unless `in_place`) and constants
+/// (when any operation has a constant)
+pub(crate) fn point_shader(operations: &[PointOp], in_place: bool) -> String {
+    let mut bindings = vec![
+        "var<uniform> params: Params",
+        "var<storage, read_write> output: array<f32>",
+    ];
+
+    if !in_place {
+        bindings.push("var<storage, read> input: array<f32>");
+    }
+
+    if operations.iter().any(|operation| !operation.constants().is_empty()) {
+        bindings.push("var<storage, read> constants: array<f32>");
+    }
+
+    let mut shader = String::from("struct Params {\n    width: u32,\n    height: u32,\n}\n\n");
+
+    for (index, binding) in bindings.iter().enumerate() {
+        shader += &format!("@group(0) @binding({}) {};\n", index, binding);
+    }
+
+    shader += "\n@compute @workgroup_size(8, 8)\n";
+    shader += "fn main(@builtin(global_invocation_id) id: vec3<u32>) {\n";
+    shader += "    if (id.x >= params.width || id.y >= params.height) {\n        return;\n    }\n\n";
+    shader += "    let index = id.y*params.width + id.x;\n";
+    shader += if in_place { "    var x = output[index];\n" } else { "    var x = input[index];\n" };
+
+    let mut constant = 0;
+
+    for operation in operations {
+        shader += &format!("    x = {};\n", operation.expression(constant));
+        constant += operation.constants().len();
+    }
+
+    shader += "    output[index] = x;\n}\n";
+    shader
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_shader_binds_only_used_buffers() {
+        let shader = point_shader(&[PointOp::Abs], true);
+        assert!(!shader.contains("input"));
+        assert!(!shader.contains("constants"));
+
+        let shader = point_shader(&[PointOp::Scale(2.0), PointOp::Clamp(0.0, 1.0)], false);
+        assert!(shader.contains("@binding(2) var<storage, read> input"));
+        assert!(shader.contains("@binding(3) var<storage, read> constants"));
+        assert!(shader.contains("x = x*constants[0];"));
+        assert!(shader.contains("x = clamp(x, constants[1], constants[2]);"));
+    }
+}
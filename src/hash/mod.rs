@@ -0,0 +1,19 @@
+//! Perceptual image hashes for detection of duplicate and similar images
+//! 
+//! All hashes are 64-bit values computed from grayscale images, similar 
+//! images produce hashes differing in few bits, dissimilar images differ 
+//! in about half of the bits. Bits are ordered row by row from the most 
+//! significant bit. Color images should be converted to grayscale first.
+//! 
+//! Available functions:
+//!  * [average_hash](fn.average_hash.html) - comparison of 8x8 thumbnail
+//!    pixels with their mean
+//!  * [difference_hash](fn.difference_hash.html) - horizontal gradient 
+//!    signs of 9x8 thumbnail
+//!  * [phash](fn.phash.html) - low frequency DCT coefficients compared 
+//!    with their median, most robust to scaling, blur and compression
+//!  * [hash_distance](fn.hash_distance.html) - Hamming distance of hashes
+
+mod perceptual;
+
+pub use perceptual::{average_hash, difference_hash, phash, hash_distance};
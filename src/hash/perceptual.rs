@@ -0,0 +1,186 @@
+use crate::{AsPrimitive, Img, ImgSize};
+
+/// Size of the reduced image used by [phash](fn.phash.html)
+const DCT_SIZE: usize = 32;
+
+/// Average hash, each bit tells whether a pixel of the image reduced 
+/// to 8x8 pixels (by area averaging) is brighter than the mean
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, hash::{average_hash, hash_distance}};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(16, 16), (0..256).map(|v| (v % 16) as u8).collect());
+/// let brighter = ImgBuf::<u8>::from_vec(ImgSize::new(16, 16), (0..256).map(|v| (v % 16 + 50) as u8).collect());
+/// 
+/// // left half dark, right half bright in every line
+/// assert_eq!(average_hash(&image), 0x0f0f_0f0f_0f0f_0f0f);
+/// assert_eq!(hash_distance(average_hash(&image), average_hash(&brighter)), 0);
+/// ```
+pub fn average_hash<T: AsPrimitive<f64>>(image: &dyn Img<T>) -> u64 {
+    let pixels = shrink(image, ImgSize::new(8, 8));
+    let mean = pixels.iter().sum::<f64>()/pixels.len() as f64;
+    bits(pixels.iter().map(|&value| value > mean))
+}
+
+/// Difference hash, each bit tells whether brightness increases between
+/// horizontally adjacent pixels of the image reduced to 9x8 pixels
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, hash::difference_hash};
+/// let ramp = ImgBuf::<u8>::from_vec(ImgSize::new(18, 8), (0..144).map(|v| (v % 18) as u8).collect());
+/// assert_eq!(difference_hash(&ramp), u64::MAX);
+/// ```
+pub fn difference_hash<T: AsPrimitive<f64>>(image: &dyn Img<T>) -> u64 {
+    let pixels = shrink(image, ImgSize::new(9, 8));
+
+    bits((0..8).flat_map(|y| {
+        let line = &pixels[y*9..(y + 1)*9];
+        (0..8).map(move |x| line[x + 1] > line[x])
+    }))
+}
+
+/// Perceptual hash based on discrete cosine transform
+/// 
+/// Image is reduced to 32x32 pixels, transformed by 2D DCT and each bit 
+/// tells whether one of the 8x8 lowest frequency coefficients is greater
+/// than their median (computed without the DC coefficient).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, hash::{phash, hash_distance}};
+/// let image = |scale: usize| {
+///     let size = ImgSize::new(64*scale, 48*scale);
+///     let mut image = ImgBuf::<f32>::new(size);
+/// 
+///     for (position, pixel) in image.enumerate_pixels_mut() {
+///         let (x, y) = (position.x/scale, position.y/scale);
+///         *pixel = ((x*x + 3*y) % 97) as f32 + if x > 30 { 100.0 } else { 0.0 };
+///     }
+/// 
+///     image
+/// };
+/// 
+/// // the same image in two resolutions has nearly identical hashes
+/// assert!(hash_distance(phash(&image(1)), phash(&image(3))) <= 4);
+/// ```
+pub fn phash<T: AsPrimitive<f64>>(image: &dyn Img<T>) -> u64 {
+    let pixels = shrink(image, ImgSize::new(DCT_SIZE, DCT_SIZE));
+
+    // Only the lowest 8x8 coefficients are needed
+    let basis: Vec<f64> = (0..8)
+        .flat_map(|k| (0..DCT_SIZE).map(move |n| {
+            (std::f64::consts::PI*(2*n + 1) as f64*k as f64/(2*DCT_SIZE) as f64).cos()
+        }))
+        .collect();
+
+    let mut rows = vec![0.0; 8*DCT_SIZE];
+
+    for y in 0..DCT_SIZE {
+        for k in 0..8 {
+            rows[y*8 + k] = (0..DCT_SIZE).map(|n| pixels[y*DCT_SIZE + n]*basis[k*DCT_SIZE + n]).sum();
+        }
+    }
+
+    let mut coefficients = vec![0.0; 64];
+
+    for ky in 0..8 {
+        for kx in 0..8 {
+            coefficients[ky*8 + kx] = (0..DCT_SIZE).map(|n| rows[n*8 + kx]*basis[ky*DCT_SIZE + n]).sum();
+        }
+    }
+
+    let mut sorted = coefficients[1..].to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len()/2];
+    bits(coefficients.iter().map(|&value| value > median))
+}
+
+/// Number of differing bits of two hashes
+pub fn hash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Packs 64 bits into a hash, the first bit is the most significant
+fn bits<I: Iterator<Item=bool>>(values: I) -> u64 {
+    values.take(64).fold(0u64, |hash, bit| (hash << 1) | bit as u64)
+}
+
+/// Reduces image to `size` by averaging pixels of each output cell,
+/// returns pixels row by row
+fn shrink<T: AsPrimitive<f64>>(image: &dyn Img<T>, size: ImgSize) -> Vec<f64> {
+    let source = image.size();
+    let mut output = vec![0.0; size.product()];
+
+    if source.x == 0 || source.y == 0 {
+        return output;
+    }
+
+    // Span of source pixels covered by output cell, at least one pixel
+    let span = |index: usize, count: usize, total: usize| {
+        let start = (index*total/count).min(total - 1);
+        start..((index + 1)*total/count).max(start + 1)
+    };
+
+    for y in 0..size.y {
+        let rows = span(y, size.y, source.y);
+
+        for x in 0..size.x {
+            let columns = span(x, size.x, source.x);
+            let mut sum = 0.0;
+
+            for row in rows.clone() {
+                sum += image.line_ref(row)[columns.clone()].iter().map(|value| value.as_()).sum::<f64>();
+            }
+
+            output[y*size.x + x] = sum/(rows.len()*columns.len()) as f64;
+        }
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgBuf, ImgLinesMut, random::Random};
+    use super::*;
+
+    fn noise(seed: u64) -> ImgBuf<f32> {
+        let mut random = Random::new(seed);
+        let size = ImgSize::new(40, 40);
+        ImgBuf::from_vec(size, (0..size.product()).map(|_| random.uniform() as f32).collect())
+    }
+
+    #[test]
+    fn different_images_have_distant_hashes() {
+        let (a, b) = (noise(1), noise(2));
+
+        for hash in &[average_hash::<f32>, difference_hash::<f32>, phash::<f32>] {
+            assert_eq!(hash_distance(hash(&a), hash(&a)), 0);
+            assert!(hash_distance(hash(&a), hash(&b)) > 16);
+        }
+    }
+
+    #[test]
+    fn small_image_is_upscaled() {
+        let mut image = ImgBuf::<u8>::new(ImgSize::new(2, 1));
+        image[(1, 0)] = 10;
+        assert_eq!(average_hash(&image), 0x0f0f_0f0f_0f0f_0f0f);
+        assert_eq!(shrink(&ImgBuf::<u8>::new(ImgSize::new(0, 0)), ImgSize::new(2, 2)), vec![0.0; 4]);
+    }
+
+    #[test]
+    fn noise_keeps_phash_close() {
+        let mut image = noise(3);
+        let original = phash(&image);
+        let mut random = Random::new(4);
+
+        for pixel in image.enumerate_pixels_mut().map(|(_, pixel)| pixel) {
+            *pixel += 0.05*random.normal() as f32;
+        }
+
+        assert!(hash_distance(original, phash(&image)) <= 8);
+    }
+}
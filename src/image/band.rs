@@ -0,0 +1,105 @@
+use crate::ImgSize;
+use super::traits::{Img, ImgMut, ImgLinesMut};
+
+/// Mutable view of a horizontal band of image lines, created by
+/// [ImgLinesMut::split_rows_mut](trait.ImgLinesMut.html#method.split_rows_mut)
+/// 
+/// Bands of one image never overlap, so they can be processed
+/// independently, for example by different threads
+#[derive(Debug)]
+pub struct RowBand<'a, T> {
+    /// Pixel data of band lines starting with the first pixel
+    data: &'a mut [T],
+    size: ImgSize,
+    stride: usize,
+    offset: usize,
+}
+
+impl<'a, T> RowBand<'a, T> {
+    pub(crate) fn new(data: &'a mut [T], size: ImgSize, stride: usize, offset: usize) -> Self {
+        Self { data, size, stride, offset }
+    }
+
+    /// Range of band `line` pixels in band data
+    fn line(&self, line: usize) -> std::ops::Range<usize> {
+        assert!(line < self.size.y, "Image line does not exist");
+        let start = line*self.stride;
+        start..start + self.size.x
+    }
+
+    /// Index of the band first line in the source image
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, T> Img<T> for RowBand<'a, T> {
+    fn size(&self) -> ImgSize {
+        self.size
+    }
+
+    fn line_ref(&self, line: usize) -> &[T] {
+        &self.data[self.line(line)]
+    }
+}
+
+impl<'a, T> ImgMut<T> for RowBand<'a, T> {
+    fn line_mut(&mut self, line: usize) -> &mut [T] {
+        let range = self.line(line);
+        &mut self.data[range]
+    }
+}
+
+impl<'a, T> ImgLinesMut<T> for RowBand<'a, T> {
+    fn strided_mut(&mut self) -> (&mut [T], usize) {
+        (self.data, self.stride)
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{Img, ImgMut, ImgLinesMut, ImgBuf, ImgSize, ImgRange};
+
+    #[test]
+    fn bands_cover_all_lines() {
+        let mut buf = ImgBuf::<u8>::new(ImgSize::new(2, 7));
+        let bands = buf.split_rows_mut(3);
+        let heights: Vec<_> = bands.iter().map(|band| band.height()).collect();
+        let offsets: Vec<_> = bands.iter().map(|band| band.offset()).collect();
+        assert_eq!(heights, vec![3, 2, 2]);
+        assert_eq!(offsets, vec![0, 3, 5]);
+        assert_eq!(buf.split_rows_mut(9).len(), 9);
+    }
+
+    #[test]
+    fn bands_of_cropped_image_skip_stride_padding() {
+        let mut buf = ImgBuf::<u8>::from_vec(ImgSize::new(4, 5), (0..20).collect());
+        buf.crop_in_place(ImgRange::new(1..3, 1..5));
+        let mut bands = buf.split_rows_mut(3);
+        assert!(bands[0].lines_mut().eq(vec![&mut [5, 6][..], &mut [9, 10][..]]));
+        assert_eq!(bands[2].line_ref(0), &[17, 18]);
+        bands[1].line_mut(0)[1] = 0;
+        assert_eq!(buf.line_ref(2), &[13, 0]);
+    }
+
+    #[test]
+    fn bands_are_processed_in_parallel() {
+        let mut buf = ImgBuf::<usize>::new(ImgSize::new(3, 10));
+
+        std::thread::scope(|scope| {
+            for mut band in buf.split_rows_mut(4) {
+                scope.spawn(move || {
+                    let offset = band.offset();
+
+                    for (position, pixel) in band.enumerate_pixels_mut() {
+                        *pixel = position.y + offset;
+                    }
+                });
+            }
+        });
+
+        assert!((0..10).all(|y| buf.line_ref(y) == [y, y, y]));
+    }
+}
@@ -1,5 +1,7 @@
-use super::{Img, ImgMut, ImgSize, dimensions::ImgBufLayout};
-use std::fmt::{Formatter, Debug, Error};
+use crate::ImgRange;
+use super::{Img, ImgMut, ImgLinesMut, ImgSize, dimensions::{ImgBufLayout, LayoutError}};
+use super::{iter::clip_range, view::ImgView};
+use std::{fmt::{Formatter, Debug, Error}, hash::{Hash, Hasher}, ops::{Index, IndexMut}};
 #[cfg(feature = "serde")]
 use serde_derive::{Serialize, Deserialize};
 
@@ -8,14 +10,72 @@ use serde_derive::{Serialize, Deserialize};
 ///
 /// Basic buffer implementation does not have any requirements for pixel type
 /// `T`, but most functions require `T` to implement `Copy`
-#[derive(Clone, Eq, PartialEq, Hash)]
+/// 
+/// Equality and hashing consider only image size and image pixels, stride
+/// padding, offset and alignment of lines are ignored, so that buffers with
+/// different memory layouts holding the same image are equal
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedImgBuf<T>"))]
 pub struct ImgBuf<T> {
     dimensions: ImgBufLayout,
+    /// Index of the first image pixel in `pixels`
+    offset: usize,
+    /// Byte alignment of image lines kept by `clone`
+    alignment: usize,
     /// Image pixels stored in a continuous block of memory
     pixels: Vec<T>
 }
 
+/// Deserialized content of `ImgBuf` validated before use, offset and 
+/// alignment are missing in data serialized before they were introduced
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SerializedImgBuf<T> {
+    dimensions: ImgBufLayout,
+    #[serde(default)]
+    offset: usize,
+    #[serde(default = "default_alignment")]
+    alignment: usize,
+    pixels: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+fn default_alignment() -> usize {
+    1
+}
+
+#[cfg(feature = "serde")]
+impl<T> std::convert::TryFrom<SerializedImgBuf<T>> for ImgBuf<T> {
+    type Error = String;
+
+    fn try_from(data: SerializedImgBuf<T>) -> Result<Self, String> {
+        let SerializedImgBuf { dimensions, offset, alignment, pixels } = data;
+        let ImgBufLayout { size, stride } = dimensions;
+
+        if !alignment.is_power_of_two() {
+            return Err(format!("Line alignment {} is not a power of two.", alignment));
+        }
+
+        if stride < size.x {
+            return Err(LayoutError::InvalidStride { layout: dimensions }.to_string());
+        }
+
+        // Last line of cropped image does not need to be followed by full stride
+        let end = match size.y {
+            0 => Some(offset),
+            height => (height - 1)
+                .checked_mul(stride)
+                .and_then(|length| length.checked_add(size.x))
+                .and_then(|length| length.checked_add(offset)),
+        };
+
+        match end {
+            Some(end) if end <= pixels.len() => Ok(Self { dimensions, offset, alignment, pixels }),
+            _ => Err(LayoutError::InvalidDataLength { layout: dimensions, length: pixels.len() }.to_string()),
+        }
+    }
+}
+
 impl<T> Img<T> for ImgBuf<T> {
     fn size(&self) -> ImgSize { self.dimensions.size }
     fn line_ref(&self, line: usize) -> &[T] { &self.pixels[self.line(line)] }
@@ -28,9 +88,79 @@ impl<T> ImgMut<T> for ImgBuf<T> {
     }
 }
 
+impl<T> ImgLinesMut<T> for ImgBuf<T> {
+    fn strided_mut(&mut self) -> (&mut [T], usize) {
+        (&mut self.pixels[self.offset..], self.dimensions.stride)
+    }
+}
+
+impl<T: Clone> Clone for ImgBuf<T> {
+    fn clone(&self) -> Self {
+        let pixels = self.pixels.clone();
+
+        let start = &self.pixels[self.offset..];
+
+        if self.alignment <= 1 || start.is_empty() || 
+            aligned_offset(pixels.as_ptr(), self.alignment) == Some(self.offset) 
+        {
+            return Self { pixels, ..*self };
+        }
+
+        // Cloned allocation lost the alignment of image lines
+        let padding = self.alignment/gcd(self.alignment, std::mem::size_of::<T>());
+        let mut aligned = Vec::with_capacity(self.pixels.len() + padding);
+        let offset = aligned_offset(aligned.as_ptr(), self.alignment).expect("Buffer cannot be aligned");
+        aligned.extend(std::iter::repeat(start[0].clone()).take(offset));
+        aligned.extend_from_slice(start);
+
+        Self { dimensions: self.dimensions, offset, alignment: self.alignment, pixels: aligned }
+    }
+}
+
+impl<T: PartialEq> PartialEq for ImgBuf<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size() == other.size() && 
+            (0..self.height()).all(|line| self.line_ref(line) == other.line_ref(line))
+    }
+}
+
+impl<T: Eq> Eq for ImgBuf<T> {}
+
+impl<T: Hash> Hash for ImgBuf<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.size().hash(state);
+
+        for line in 0..self.height() {
+            self.line_ref(line).hash(state);
+        }
+    }
+}
+
+/// Access pixel at `(x, y)` position
+/// ```
+/// use nanocv::{ImgBuf, ImgSize};
+/// let mut buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+/// assert_eq!(buf[(1, 0)], 2);
+/// buf[(1, 0)] = 5;
+/// assert_eq!(buf[(1, 0)], 5);
+/// ```
+impl<T> Index<(usize, usize)> for ImgBuf<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        &self.line_ref(y)[x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for ImgBuf<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        &mut self.line_mut(y)[x]
+    }
+}
+
 impl<T> ImgBuf<T> {
     fn line(&self, line: usize) -> std::ops::Range<usize> {
-        let start = line*self.dimensions.stride;
+        let start = self.offset + line*self.dimensions.stride;
         (start)..(start + self.dimensions.size.x)
     }
 
@@ -48,13 +178,112 @@ impl<T> ImgBuf<T> {
     }
 
     /// Consumes image and returns underlying vector of pixel data
+    /// 
+    /// Lines of images cropped by [crop_in_place](#method.crop_in_place)
+    /// are moved to the beginning of the vector, when the original stride
+    /// cannot be kept, lines are stored without padding
     /// ```
     /// use nanocv::{ImgBuf, ImgSize};
     /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
     /// assert_eq!(buf.into_vec(), vec![1, 2, 3, 4]);
     /// ```
     pub fn into_vec(self) -> Vec<T> {
-        self.pixels
+        self.into_raw_parts().1
+    }
+
+    /// Consumes image and returns its memory layout together with 
+    /// underlying vector of pixel data, the image can be reconstructed
+    /// by [from_raw_parts](#method.from_raw_parts)
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize, ImgBufLayout};
+    /// let layout = ImgBufLayout { size: ImgSize::new(1, 2), stride: 2 };
+    /// let buf = ImgBuf::<u8>::from_vec_stride(layout, vec![1, 2, 3, 4]);
+    /// let (layout, pixels) = buf.into_raw_parts();
+    /// assert_eq!(layout.stride, 2);
+    /// assert_eq!(pixels, vec![1, 2, 3, 4]);
+    /// let buf = ImgBuf::from_raw_parts(layout, pixels).unwrap();
+    /// assert_eq!(buf.line_ref(1), &[3]);
+    /// ```
+    pub fn into_raw_parts(self) -> (ImgBufLayout, Vec<T>) {
+        let ImgBufLayout { size, stride } = self.dimensions;
+        let (offset, mut pixels) = (self.offset, self.pixels);
+
+        let length = self.dimensions.checked_data_length().unwrap_or(usize::MAX);
+
+        if pixels.len().saturating_sub(offset) >= length {
+            pixels.drain(..offset);
+            pixels.truncate(length);
+            return (self.dimensions, pixels);
+        }
+
+        // Last line of cropped image is not followed by full stride
+        let pixels = pixels
+            .into_iter()
+            .skip(offset)
+            .enumerate()
+            .filter(|(index, _)| index % stride < size.x && index/stride < size.y)
+            .map(|(_, pixel)| pixel)
+            .collect();
+
+        (ImgBufLayout { size, stride: size.x }, pixels)
+    }
+
+    /// Crops image to `range` without moving or reallocating pixel data,
+    /// only image size and position of the first pixel are changed while
+    /// the stride is kept, parts of `range` outside image are ignored
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize, ImgRange};
+    /// let mut buf = ImgBuf::<u8>::from_vec(ImgSize::new(3, 3), (1..10).collect());
+    /// buf.crop_in_place(ImgRange::new(1..5, 1..3));
+    /// assert_eq!(buf.size(), ImgSize::new(2, 2));
+    /// assert_eq!(buf.dimensions().stride, 3);
+    /// assert_eq!(buf.line_ref(0), &[5, 6]);
+    /// assert_eq!(buf.line_ref(1), &[8, 9]);
+    /// ```
+    pub fn crop_in_place(&mut self, range: ImgRange) {
+        let range = clip_range(range, self.range());
+        let size = ImgSize::new(range.width(), range.height());
+
+        if size.x == 0 || size.y == 0 {
+            self.offset = 0;
+        } else {
+            self.offset += range.y.start*self.dimensions.stride + range.x.start;
+        }
+
+        if range.x.start > 0 {
+            self.alignment = 1;
+        }
+
+        self.dimensions.size = size;
+    }
+
+    /// Read-only view of image pixels within `range`, parts of `range`
+    /// outside image are ignored, see [crop_in_place](#method.crop_in_place)
+    /// for destructive variant
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize, ImgRange};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![1, 2, 3, 4, 5, 6]);
+    /// let view = buf.view_cropped(ImgRange::new(-1..2, 1..2));
+    /// assert_eq!(view.size(), ImgSize::new(2, 1));
+    /// assert_eq!(view.line_ref(0), &[4, 5]);
+    /// ```
+    pub fn view_cropped(&self, range: ImgRange) -> ImgView<'_, T> {
+        ImgView::new(self, range)
+    }
+
+    /// Reconstruct image from memory layout and pixel data returned by
+    /// [into_raw_parts](#method.into_raw_parts), pixel data are not copied
+    /// 
+    /// Returns error if data vector length does not correspond to `layout`
+    /// ```
+    /// use nanocv::{ImgBuf, ImgSize, ImgBufLayout};
+    /// let layout = ImgBufLayout { size: ImgSize::new(2, 2), stride: 3 };
+    /// assert!(ImgBuf::<u8>::from_raw_parts(layout, vec![0; 6]).is_ok());
+    /// assert!(ImgBuf::<u8>::from_raw_parts(layout, vec![0; 4]).is_err());
+    /// ```
+    pub fn from_raw_parts(layout: ImgBufLayout, pixels: Vec<T>) -> Result<Self, LayoutError> {
+        layout.check_data_size(pixels.len())?;
+        Ok(Self { dimensions: layout, offset: 0, alignment: 1, pixels })
     }
 }
 
@@ -77,7 +306,7 @@ impl<T: Copy> ImgBuf<T> {
     /// ```
     pub fn from_vec_stride(dimensions: ImgBufLayout, pixels: Vec<T>) -> Self {
         dimensions.assert_data_size_correct(pixels.len());
-        Self { dimensions, pixels }
+        Self { dimensions, offset: 0, alignment: 1, pixels }
     }
 
     /// Create image buffer of given size initialized with provided data
@@ -94,6 +323,37 @@ impl<T: Copy> ImgBuf<T> {
         Self::from_vec_stride(ImgBufLayout { size, stride: size.x}, data)
     }
 
+    /// Create image buffer of given size and row stride initialized
+    /// with provided data, returns error instead of panicking if 
+    /// data vector length does not correspond to `dimensions`
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize, ImgBufLayout};
+    /// let layout = ImgBufLayout { size: ImgSize::new(1, 2), stride: 2 };
+    /// let buf = ImgBuf::<u8>::try_from_vec_stride(layout, vec![1, 2, 3, 4]).unwrap();
+    /// assert_eq!(buf.line_ref(1), &[3]);
+    /// assert!(ImgBuf::<u8>::try_from_vec_stride(layout, vec![1, 2, 3]).is_err());
+    /// ```
+    pub fn try_from_vec_stride(
+        dimensions: ImgBufLayout, 
+        pixels: Vec<T>
+    ) -> Result<Self, LayoutError> {
+        dimensions.check_data_size(pixels.len())?;
+        Ok(Self { dimensions, offset: 0, alignment: 1, pixels })
+    }
+
+    /// Create image buffer of given size initialized with provided data,
+    /// returns error instead of panicking if data vector length does not
+    /// correspond to image `size`
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<u8>::try_from_vec(ImgSize::new(2, 1), vec![1, 2]).unwrap();
+    /// assert_eq!(buf.line_ref(0), &[1, 2]);
+    /// assert!(ImgBuf::<u8>::try_from_vec(ImgSize::new(2, 2), vec![1, 2]).is_err());
+    /// ```
+    pub fn try_from_vec(size: ImgSize, data: Vec<T>) -> Result<Self, LayoutError> {
+        Self::try_from_vec_stride(ImgBufLayout { size, stride: size.x}, data)
+    }
+
     /// Create image buffer with pixels initialized to the `init` value
     /// ```
     /// use nanocv::{ImgBuf, Img, ImgSize};
@@ -104,6 +364,44 @@ impl<T: Copy> ImgBuf<T> {
     pub fn new_init(size: ImgSize, init: T) -> Self {
         Self::from_vec(size, vec![init; size.product()])
     }
+
+    /// Transforms pixels by `operator` into pixels of type `U` reusing
+    /// the memory of this buffer, no new buffer is allocated
+    /// 
+    /// Types `T` and `U` must have the same size and alignment, which is
+    /// checked at compile time, stride padding is transformed too
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<u16>::from_vec(ImgSize::new(3, 1), vec![0, 32768, 65535]);
+    /// let shifted = buf.map_in_place(|x| (x as i32 - 32768) as i16);
+    /// assert_eq!(shifted.line_ref(0), &[-32768, 0, 32767]);
+    /// ```
+    /// 
+    /// Pixel types of different sizes are rejected
+    /// ```compile_fail
+    /// use nanocv::{ImgBuf, ImgSize};
+    /// let buf = ImgBuf::<u8>::new(ImgSize::new(3, 1));
+    /// let wide = buf.map_in_place(|x| x as u16);
+    /// ```
+    pub fn map_in_place<U: Copy, F: FnMut(T) -> U>(self, mut operator: F) -> ImgBuf<U> {
+        let () = SameLayout::<T, U>::ASSERT;
+
+        let mut pixels = std::mem::ManuallyDrop::new(self.pixels);
+        let (data, length, capacity) = (pixels.as_mut_ptr(), pixels.len(), pixels.capacity());
+
+        // Each element is read as T and overwritten by U of the same layout, 
+        // the vector is leaked without being dropped if operator panics
+        let pixels = unsafe {
+            for index in 0..length {
+                let pixel = data.add(index);
+                (pixel as *mut U).write(operator(pixel.read()));
+            }
+
+            Vec::from_raw_parts(data as *mut U, length, capacity)
+        };
+
+        ImgBuf { dimensions: self.dimensions, offset: self.offset, alignment: self.alignment, pixels }
+    }
 }
 
 impl<T: Copy + Default> ImgBuf<T> {
@@ -130,11 +428,128 @@ impl<T: Copy + Default> ImgBuf<T> {
     pub fn new_like(other: &Self) -> Self {
         Self::new(other.size())
     }
+
+    /// Create image buffer with pixels initialized to default value of type `T`,
+    /// with every image line starting at address aligned to `alignment` bytes
+    /// 
+    /// Image stride is rounded up, so that lines stay aligned, the alignment 
+    /// is kept when the buffer is cloned
+    /// 
+    /// Panics if `alignment` is not a power of two, lines of type `T` 
+    /// cannot be aligned to it or the aligned data size overflows `usize`
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<f32>::new_aligned(ImgSize::new(5, 3), 32);
+    /// assert_eq!(buf.dimensions().stride, 8);
+    /// assert!((0..3).all(|line| buf.line_ref(line).as_ptr() as usize % 32 == 0));
+    /// assert_eq!(buf.line_ref(2), &[0.0; 5]);
+    /// ```
+    pub fn new_aligned(size: ImgSize, alignment: usize) -> Self {
+        assert!(alignment.is_power_of_two(), "Alignment must be a power of two");
+        let pixel_size = std::mem::size_of::<T>();
+
+        if pixel_size == 0 {
+            return Self::new(size);
+        }
+
+        let step = alignment/gcd(alignment, pixel_size);
+        let overflow = "Aligned image data size overflows";
+        let stride = size.x.div_ceil(step).checked_mul(step).expect(overflow);
+        let length = size.y.checked_mul(stride).expect(overflow);
+        let mut pixels = Vec::with_capacity(length.checked_add(step).expect(overflow));
+
+        let offset = aligned_offset(pixels.as_ptr(), alignment)
+            .expect("Image lines cannot be aligned for pixel type");
+
+        pixels.resize(offset + length, T::default());
+        let dimensions = ImgBufLayout { size, stride };
+        Self { dimensions, offset, alignment, pixels }
+    }
+}
+
+/// Compile time check of equal size and alignment of types `T` and `U`,
+/// evaluated when `ASSERT` is used for concrete types
+struct SameLayout<T, U>(std::marker::PhantomData<(T, U)>);
+
+impl<T, U> SameLayout<T, U> {
+    const ASSERT: () = {
+        assert!(std::mem::size_of::<T>() == std::mem::size_of::<U>(), "Pixel sizes differ");
+        assert!(std::mem::align_of::<T>() == std::mem::align_of::<U>(), "Pixel alignments differ");
+    };
+}
+
+/// First index of element aligned to `alignment` bytes in data 
+/// starting at `pointer`
+fn aligned_offset<T>(pointer: *const T, alignment: usize) -> Option<usize> {
+    let pixel_size = std::mem::size_of::<T>();
+
+    if pixel_size == 0 || alignment <= 1 {
+        return Some(0);
+    }
+
+    let step = alignment/gcd(alignment, pixel_size);
+    (0..step).find(|index| (pointer as usize + index*pixel_size) % alignment == 0)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<T: bytemuck::Pod> ImgBuf<T> {
+    /// Bytes of pixel data starting at the first image pixel, lines are 
+    /// stored with stride given by [dimensions](#method.dimensions), 
+    /// enabled by `bytemuck` feature
+    /// ```
+    /// use nanocv::{ImgBuf, ImgSize};
+    /// let buf = ImgBuf::<u16>::from_vec(ImgSize::new(2, 1), vec![1, 0x0203]);
+    /// assert_eq!(buf.as_bytes(), [1u16.to_ne_bytes(), 0x0203u16.to_ne_bytes()].concat());
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.pixels[self.data_range()])
+    }
+
+    /// Mutable bytes of pixel data, see [as_bytes](#method.as_bytes)
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let range = self.data_range();
+        bytemuck::cast_slice_mut(&mut self.pixels[range])
+    }
+
+    /// Create image buffer with pixels copied from `bytes` of pixel data
+    /// with `layout`, returns error if length of `bytes` does not correspond
+    /// to `layout`, bytes do not need to be aligned for type `T`
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize, ImgBufLayout, color::Rgb};
+    /// let layout = ImgBufLayout { size: ImgSize::new(2, 1), stride: 2 };
+    /// let buf = ImgBuf::<Rgb<u8>>::from_bytes(layout, &[1, 2, 3, 4, 5, 6]).unwrap();
+    /// assert_eq!(buf.line_ref(0), &[Rgb::new(1, 2, 3), Rgb::new(4, 5, 6)]);
+    /// assert!(ImgBuf::<Rgb<u8>>::from_bytes(layout, &[1, 2, 3]).is_err());
+    /// ```
+    pub fn from_bytes(layout: ImgBufLayout, bytes: &[u8]) -> Result<Self, LayoutError> {
+        let pixel_size = std::mem::size_of::<T>();
+        let length = bytes.len()/pixel_size.max(1);
+
+        if length*pixel_size != bytes.len() {
+            return Err(LayoutError::InvalidDataLength { layout, length });
+        }
+
+        layout.check_data_size(length)?;
+        let mut pixels = vec![T::zeroed(); length];
+        bytemuck::cast_slice_mut(&mut pixels).copy_from_slice(bytes);
+        Self::from_raw_parts(layout, pixels)
+    }
+
+    /// Range of pixel data in `pixels` vector
+    fn data_range(&self) -> std::ops::Range<usize> {
+        let length = self.dimensions.checked_data_length().unwrap_or(usize::MAX);
+        let end = std::cmp::min(self.offset.saturating_add(length), self.pixels.len());
+        self.offset..end
+    }
 }
 
 impl<T: Debug> Debug for ImgBuf<T> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "Image size: {:?} [\n", self.dimensions)?;
+        writeln!(f, "Image size: {:?} [", self.dimensions)?;
 
         for line in 0..self.height() {
             let line_pixels = self.line_ref(line);
@@ -142,10 +557,131 @@ impl<T: Debug> Debug for ImgBuf<T> {
             for value in line_pixels {
                 write!(f, "{:5?}", value)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
-        write!(f, "]\n")?;
+        writeln!(f, "]")?;
         Ok(())
     }
 }
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn index_outside_line_with_stride_panics() {
+        let buf = ImgBuf::<u8>::from_vec_stride(
+            ImgBufLayout { size: ImgSize::new(1, 2), stride: 2 },
+            vec![1, 2, 3, 4]
+        );
+
+        let _ = buf[(1, 0)];
+    }
+
+    #[test]
+    fn lines_mut_skip_stride_padding() {
+        let mut buf = ImgBuf::<u8>::from_vec_stride(
+            ImgBufLayout { size: ImgSize::new(1, 2), stride: 2 },
+            vec![1, 2, 3, 4]
+        );
+
+        assert!(buf.lines_mut().eq(vec![&mut [1][..], &mut [3][..]]));
+        assert!(buf.lines_mut().rev().eq(vec![&mut [3][..], &mut [1][..]]));
+    }
+
+    #[test]
+    fn lines_mut_zero_width() {
+        let mut buf = ImgBuf::<u8>::new(ImgSize::new(0, 3));
+        assert_eq!(buf.lines_mut().count(), 3);
+        assert_eq!(buf.lines_mut().rev().count(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn aligned_buffer_size_overflow_panics() {
+        ImgBuf::<u32>::new_aligned(ImgSize::new(usize::MAX/8, 8), 64);
+    }
+
+    #[test]
+    fn aligned_buffer_keeps_alignment() {
+        let mut buf = ImgBuf::<[u8; 3]>::new_aligned(ImgSize::new(7, 4), 64);
+        assert_eq!(buf.dimensions().stride, 64);
+        buf[(6, 3)] = [1, 2, 3];
+        let mut copy = buf.clone();
+        assert_eq!(copy, buf);
+        assert!((0..4).all(|line| copy.line_ref(line).as_ptr() as usize % 64 == 0));
+        assert_eq!(copy.lines_mut().count(), 4);
+        let (layout, pixels) = buf.into_raw_parts();
+        assert_eq!((layout.stride, pixels.len()), (64, 4*64));
+        assert_eq!(pixels[3*64 + 6], [1, 2, 3]);
+    }
+
+    #[test]
+    fn empty_aligned_buffer_can_be_cloned() {
+        for &(width, height) in &[(0, 5), (5, 0), (0, 0)] {
+            let buf = ImgBuf::<f32>::new_aligned(ImgSize::new(width, height), 32);
+            assert_eq!(buf.clone(), buf);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_buffer_is_validated() {
+        use std::convert::TryFrom;
+        let dimensions = ImgBufLayout { size: ImgSize::new(2, 2), stride: 3 };
+        let data = |offset, alignment, length| SerializedImgBuf { dimensions, offset, alignment, pixels: vec![0u8; length] };
+        assert!(ImgBuf::try_from(data(0, 1, 5)).is_ok());
+        assert!(ImgBuf::try_from(data(1, 1, 5)).is_err());
+        assert!(ImgBuf::try_from(data(usize::MAX, 1, 5)).is_err());
+        assert!(ImgBuf::try_from(data(0, 3, 6)).is_err());
+    }
+
+    #[test]
+    fn cropped_buffer_returns_pixels() {
+        let mut buf = ImgBuf::<u8>::from_vec(ImgSize::new(4, 3), (0..12).collect());
+        buf.crop_in_place(ImgRange::new(1..3, 1..3));
+        assert_eq!(buf.clone(), ImgBuf::from_vec(ImgSize::new(2, 2), vec![5, 6, 9, 10]));
+        assert!(buf.lines_mut().eq(vec![&mut [5, 6][..], &mut [9, 10][..]]));
+        assert_eq!(buf.into_raw_parts(), (ImgBufLayout { size: ImgSize::new(2, 2), stride: 2 }, vec![5, 6, 9, 10]));
+
+        let mut top = ImgBuf::<u8>::from_vec(ImgSize::new(4, 3), (0..12).collect());
+        top.crop_in_place(ImgRange::new(0..2, 0..2));
+        assert_eq!(top.into_raw_parts(), (ImgBufLayout { size: ImgSize::new(2, 2), stride: 4 }, (0..8).collect()));
+
+        let mut outside = ImgBuf::<u8>::new(ImgSize::new(4, 3));
+        outside.crop_in_place(ImgRange::new(2..8, 5..8));
+        assert_eq!(outside.size(), ImgSize::new(2, 0));
+        assert!(outside.into_vec().is_empty());
+    }
+
+    #[test]
+    fn map_in_place_keeps_allocation() {
+        let mut buf = ImgBuf::<f32>::new_aligned(ImgSize::new(3, 2), 32);
+        buf[(2, 1)] = 2.0;
+        let pointer = buf.line_ref(1).as_ptr() as usize;
+        let mapped = buf.map_in_place(|x| (x*10.0) as u32);
+        assert_eq!(mapped.line_ref(1).as_ptr() as usize, pointer);
+        assert_eq!(mapped.line_ref(1), &[0, 0, 20]);
+    }
+
+    #[test]
+    fn equality_ignores_stride_padding() {
+        let padded = ImgBuf::<u8>::from_vec_stride(
+            ImgBufLayout { size: ImgSize::new(1, 2), stride: 2 },
+            vec![1, 2, 3, 4]
+        );
+
+        assert_eq!(padded, ImgBuf::from_vec(ImgSize::new(1, 2), vec![1, 3]));
+    }
+
+    #[test]
+    fn enumerate_pixels_range_outside_image_is_empty() {
+        let buf = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+        assert_eq!(buf.enumerate_pixels_range(ImgRange::new(3..5, 0..2)).count(), 0);
+        assert_eq!(buf.enumerate_pixels_range(ImgRange::new(0..2, -3..-1)).count(), 0);
+    }
+}
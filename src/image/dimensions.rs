@@ -1,4 +1,5 @@
 use super::ImgSize;
+use std::fmt::{Display, Formatter};
 #[cfg(feature = "serde")]
 use serde_derive::{Serialize, Deserialize};
 
@@ -19,21 +20,97 @@ impl ImgBufLayout {
         self.size.y*self.stride
     }
 
+    /// Number of allocated pixels, `None` if it does not fit into `usize`
+    ///
+    /// ```
+    /// use nanocv::{ImgSize, ImgBufLayout};
+    /// let layout = ImgBufLayout { size: ImgSize::new(2, 2), stride: 3 };
+    /// assert_eq!(layout.checked_data_length(), Some(6));
+    /// let layout = ImgBufLayout { size: ImgSize::new(2, usize::MAX), stride: 2 };
+    /// assert_eq!(layout.checked_data_length(), None);
+    /// ```
+    pub fn checked_data_length(&self) -> Option<usize> {
+        self.size.y.checked_mul(self.stride)
+    }
+
+    /// Checks that data vector of length `data_size` can be used 
+    /// as image pixels with this layout
+    ///
+    /// ```
+    /// use nanocv::{ImgSize, ImgBufLayout, LayoutError};
+    /// let layout = ImgBufLayout { size: ImgSize::new(2, 2), stride: 3 };
+    /// assert_eq!(layout.check_data_size(6), Ok(()));
+    /// assert_eq!(
+    ///     layout.check_data_size(5), 
+    ///     Err(LayoutError::InvalidDataLength { layout, length: 5 })
+    /// );
+    /// ```
+    pub fn check_data_size(&self, data_size: usize) -> Result<(), LayoutError> {
+        if self.stride < self.size.x {
+            return Err(LayoutError::InvalidStride { layout: *self });
+        }
+
+        match self.checked_data_length() {
+            Some(length) if length == data_size => Ok(()),
+            _ => Err(LayoutError::InvalidDataLength { layout: *self, length: data_size }),
+        }
+    }
+
     pub fn assert_data_size_correct(&self, data_size: usize) {
-        assert_eq!(
-            self.data_length(),
-            data_size,
-            "Vector of length {} cannot be used as an image {} X {} with \
-            stride {}, correct length should be {}.",
-            data_size,
-            self.size.x,
-            self.size.y,
-            self.stride,
-            self.data_length()
-        )
+        if let Err(error) = self.check_data_size(data_size) {
+            panic!("{}", error)
+        }
+    }
+}
+
+/// Error describing pixel data that cannot be used with a `ImgBufLayout`
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum LayoutError {
+    /// Pixel data length does not correspond to `stride*size.y`
+    InvalidDataLength { 
+        /// Requested data layout
+        layout: ImgBufLayout, 
+        /// Length of provided pixel data
+        length: usize 
+    },
+    /// Line stride is smaller than image width
+    InvalidStride {
+        /// Requested data layout
+        layout: ImgBufLayout
+    },
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            LayoutError::InvalidDataLength { layout, length } => {
+                write!(
+                    f,
+                    "Vector of length {} cannot be used as an image {} X {} with \
+                    stride {}, ",
+                    length,
+                    layout.size.x,
+                    layout.size.y,
+                    layout.stride,
+                )?;
+
+                match layout.checked_data_length() {
+                    Some(correct) => write!(f, "correct length should be {}.", correct),
+                    None => write!(f, "correct length overflows usize."),
+                }
+            },
+            LayoutError::InvalidStride { layout } => write!(
+                f,
+                "Stride {} is smaller than image width {}.",
+                layout.stride,
+                layout.size.x,
+            ),
+        }
     }
 }
 
+impl std::error::Error for LayoutError {}
+
 // ================================== TESTS ==================================
 
 #[cfg(test)]
@@ -52,4 +129,35 @@ mod tests {
         let dimensions = ImgBufLayout { size: ImgSize::new(2, 2), stride: 3 };
         dimensions.assert_data_size_correct(7)
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[should_panic]
+    fn assert_correct_data_size_stride_smaller_than_width() {
+        let dimensions = ImgBufLayout { size: ImgSize::new(3, 2), stride: 2 };
+        dimensions.assert_data_size_correct(4)
+    }
+
+    #[test]
+    fn check_data_size_overflow_is_error() {
+        let dimensions = ImgBufLayout { 
+            size: ImgSize::new(2, usize::MAX), 
+            stride: 2 
+        };
+
+        assert!(dimensions.check_data_size(0).is_err());
+    }
+
+    #[test]
+    fn overflowing_layout_error_can_be_formatted() {
+        let layout = ImgBufLayout { size: ImgSize::new(2, usize::MAX), stride: 2 };
+        let error = LayoutError::InvalidDataLength { layout, length: 0 };
+        assert!(format!("{}", error).ends_with("correct length overflows usize."));
+    }
+
+    #[test]
+    fn layout_error_contains_correct_length() {
+        let layout = ImgBufLayout { size: ImgSize::new(2, 2), stride: 3 };
+        let error = LayoutError::InvalidDataLength { layout, length: 5 };
+        assert!(format!("{}", error).ends_with("correct length should be 6."));
+    }
+}
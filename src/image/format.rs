@@ -0,0 +1,130 @@
+use std::fmt::{Debug, Formatter, Error};
+use crate::{ImgRange, ImgSize, AsPrimitive};
+use super::{Img, ImgBuf, iter::clip_range};
+
+/// Configurable text formatting of image pixels created by
+/// [ImgBuf::fmt_with](struct.ImgBuf.html#method.fmt_with)
+/// 
+/// Pixels not fitting into maximal size are replaced by `...`, values 
+/// are right-aligned to a common width
+pub struct ImgFormat<'a, T> {
+    image: &'a ImgBuf<T>,
+    precision: usize,
+    max_size: ImgSize,
+}
+
+impl<'a, T: Debug> Debug for ImgFormat<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let size = self.image.size();
+        let (columns, lines) = (size.x.min(self.max_size.x), size.y.min(self.max_size.y));
+        let precision = self.precision;
+
+        let values: Vec<Vec<String>> = (0..lines)
+            .map(|line| self.image.line_ref(line)[..columns]
+                .iter()
+                .map(|value| format!("{:.*?}", precision, value))
+                .collect())
+            .collect();
+
+        let width = values.iter().flatten().map(|value| value.len()).max().unwrap_or(0);
+        writeln!(f, "Image size: {:?} [", self.image.dimensions())?;
+
+        for line in values {
+            for value in line {
+                write!(f, " {:>1$}", value, width)?;
+            }
+
+            if columns < size.x {
+                write!(f, " ...")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        if lines < size.y {
+            writeln!(f, " ...")?;
+        }
+
+        writeln!(f, "]")
+    }
+}
+
+impl<T> ImgBuf<T> {
+    /// Text formatting of pixel values with floating point `precision`,
+    /// at most `max_size` pixels starting at the top left corner are shown
+    /// 
+    /// ```
+    /// use nanocv::{ImgBuf, ImgSize};
+    /// let buf = ImgBuf::<f32>::from_vec(ImgSize::new(3, 3), vec![1.0, 2.25, -10.5, 0.0, 0.0, 0.0, 7.0, 8.0, 9.0]);
+    /// let text = format!("{:?}", buf.fmt_with(1, ImgSize::new(2, 2)));
+    /// assert_eq!(text.lines().skip(1).collect::<Vec<_>>(), vec![
+    ///     " 1.0 2.2 ...",
+    ///     " 0.0 0.0 ...",
+    ///     " ...",
+    ///     "]",
+    /// ]);
+    /// ```
+    pub fn fmt_with(&self, precision: usize, max_size: ImgSize) -> ImgFormat<'_, T> {
+        ImgFormat { image: self, precision, max_size }
+    }
+
+    /// Renders pixels within `range` as text, one character per pixel
+    /// and one line of text per image line
+    /// 
+    /// Pixel values are scaled linearly from the minimal value in range
+    /// (first character of `chars`) to the maximal value (last character),
+    /// parts of `range` outside image are ignored
+    /// 
+    /// Panics if `chars` is empty
+    /// ```
+    /// use nanocv::{ImgBuf, ImgSize, ImgRange};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![0, 128, 255, 255, 0, 0]);
+    /// assert_eq!(buf.render_ascii(ImgRange::new(0..3, 0..2), " .#"), " .#\n#  \n");
+    /// ```
+    pub fn render_ascii(&self, range: ImgRange, chars: &str) -> String where T: AsPrimitive<f64> {
+        let chars: Vec<char> = chars.chars().collect();
+        assert!(!chars.is_empty(), "No characters to render image");
+        let range = clip_range(range, self.range());
+
+        let values = || (range.y.start..range.y.end)
+            .flat_map(|line| self.line_ref(line)[range.x.to_range()].iter())
+            .map(|value| value.as_());
+
+        let min = values().fold(f64::INFINITY, f64::min);
+        let max = values().fold(f64::NEG_INFINITY, f64::max);
+        let scale = if max > min { (chars.len() - 1) as f64/(max - min) } else { 0.0 };
+        let mut text = String::new();
+
+        for line in range.y.start..range.y.end {
+            for value in &self.line_ref(line)[range.x.to_range()] {
+                text.push(chars[((value.as_() - min)*scale).round() as usize]);
+            }
+
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_are_right_aligned() {
+        let buf = ImgBuf::<i16>::from_vec(ImgSize::new(2, 1), vec![5, -300]);
+        let text = format!("{:?}", buf.fmt_with(3, ImgSize::new(10, 10)));
+        assert_eq!(text.lines().nth(1), Some("    5 -300"));
+    }
+
+    #[test]
+    fn constant_image_renders_first_character() {
+        let buf = ImgBuf::<f32>::new_init(ImgSize::new(2, 1), 3.0);
+        assert_eq!(buf.render_ascii(ImgRange::new(-1..5, 0..1), "ab"), "aa\n");
+        assert_eq!(buf.render_ascii(ImgRange::new(5..6, 0..1), "ab"), "\n");
+        assert_eq!(buf.render_ascii(ImgRange::new(0..2, 3..4), "ab"), "");
+    }
+}
@@ -0,0 +1,70 @@
+use half::f16;
+use crate::{Pixel, AsPrimitive};
+
+// Half-precision (IEEE 754 binary16) floating point pixels of `half::f16`
+// type are intended for compact storage of images, values are converted 
+// into `f32` for any computation by `AsPrimitive`, so that generic functions
+// like `map`, `update`, `to_f32`, `from_f32` or convolution accumulating 
+// in `f32` accept `ImgBuf<f16>` directly
+
+impl Pixel for f16 {
+    type Component = f16;
+    const CHANNELS: usize = 1;
+
+    fn component(&self, index: usize) -> f16 {
+        assert!(index == 0, "Component index out of range");
+        *self
+    }
+
+    fn from_components<F: FnMut(usize) -> f16>(mut component: F) -> Self {
+        component(0)
+    }
+}
+
+impl AsPrimitive<f16> for f16 {
+    #[inline]
+    fn as_(self) -> f16 { self }
+}
+
+macro_rules! impl_as_primitive_half {
+    ($($type: ty),*) => {
+        $(
+            impl AsPrimitive<$type> for f16 {
+                #[inline]
+                fn as_(self) -> $type { self.to_f32() as $type }
+            }
+
+            impl AsPrimitive<f16> for $type {
+                #[inline]
+                fn as_(self) -> f16 { f16::from_f32(self as f32) }
+            }
+        )*
+    };
+}
+
+impl_as_primitive_half!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Img, ImgBuf, ImgSize, filter::{map_new, horizontal_filter, convolution_operator}};
+    use crate::convert::{to_f32, from_f32};
+
+    #[test]
+    fn images_convert_through_generic_functions() {
+        let image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 1), vec![0, 51, 255]);
+        let half: ImgBuf<f16> = map_new(&to_f32(&image, 255), f16::from_f32);
+        let back: ImgBuf<u8> = from_f32(&to_f32(&half, f16::ONE), 255, true);
+        assert_eq!(back.into_vec(), vec![0, 51, 255]);
+    }
+
+    #[test]
+    fn convolution_accumulates_in_f32() {
+        let input = ImgBuf::from_vec(ImgSize::new(3, 1), vec![f16::ONE; 3]);
+        let mut output = ImgBuf::<f32>::new(input.size());
+        horizontal_filter(&input, &mut output, &[0.25f32, 0.5, 0.25], convolution_operator);
+        assert_eq!(output.line_ref(0), &[1.0, 1.0, 1.0]);
+    }
+}
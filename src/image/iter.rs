@@ -0,0 +1,158 @@
+use std::{iter::Enumerate, slice, vec};
+use crate::{Vec2d, Range, Range2d, ImgRange};
+
+/// Iterator over image pixels and their coordinates, in row-major order
+/// 
+/// Created by `Img::enumerate_pixels` and `Img::enumerate_pixels_range`
+pub struct EnumeratePixels<'a, T> {
+    lines: Enumerate<vec::IntoIter<&'a [T]>>,
+    current: Option<(usize, Enumerate<slice::Iter<'a, T>>)>,
+    origin: Vec2d<usize>,
+}
+
+impl<'a, T> EnumeratePixels<'a, T> {
+    pub(crate) fn new(lines: Vec<&'a [T]>, origin: Vec2d<usize>) -> Self {
+        Self { lines: lines.into_iter().enumerate(), current: None, origin }
+    }
+}
+
+impl<'a, T> Iterator for EnumeratePixels<'a, T> {
+    type Item = (Vec2d<usize>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((y, pixels)) = &mut self.current {
+                if let Some((x, pixel)) = pixels.next() {
+                    return Some((self.origin + Vec2d::new(x, *y), pixel));
+                }
+            }
+
+            let (y, line) = self.lines.next()?;
+            self.current = Some((y, line.iter().enumerate()));
+        }
+    }
+}
+
+/// Iterator over mutable image pixels and their coordinates, 
+/// in row-major order
+/// 
+/// Created by `ImgLinesMut::enumerate_pixels_mut` and 
+/// `ImgLinesMut::enumerate_pixels_range_mut`
+pub struct EnumeratePixelsMut<'a, T> {
+    lines: Enumerate<LinesMut<'a, T>>,
+    current: Option<(usize, Enumerate<slice::IterMut<'a, T>>)>,
+    origin: Vec2d<usize>,
+}
+
+impl<'a, T> EnumeratePixelsMut<'a, T> {
+    pub(crate) fn new(lines: LinesMut<'a, T>, origin: Vec2d<usize>) -> Self {
+        Self { lines: lines.enumerate(), current: None, origin }
+    }
+}
+
+impl<'a, T> Iterator for EnumeratePixelsMut<'a, T> {
+    type Item = (Vec2d<usize>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((y, pixels)) = &mut self.current {
+                if let Some((x, pixel)) = pixels.next() {
+                    return Some((self.origin + Vec2d::new(x, *y), pixel));
+                }
+            }
+
+            let (y, line) = self.lines.next()?;
+            self.current = Some((y, line.iter_mut().enumerate()));
+        }
+    }
+}
+
+/// Iterator over image lines, from top to bottom
+/// 
+/// Created by `Img::lines` and `Img::lines_range`
+pub struct Lines<'a, T>(vec::IntoIter<&'a [T]>);
+
+impl<'a, T> Lines<'a, T> {
+    pub(crate) fn new(lines: Vec<&'a [T]>) -> Self {
+        Self(lines.into_iter())
+    }
+}
+
+impl<'a, T> Iterator for Lines<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> { self.0.next() }
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+}
+
+impl<'a, T> DoubleEndedIterator for Lines<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> { self.0.next_back() }
+}
+
+impl<'a, T> ExactSizeIterator for Lines<'a, T> {}
+
+/// Iterator over mutable image lines, from top to bottom
+/// 
+/// Created by `ImgLinesMut::lines_mut` and `ImgLinesMut::lines_range_mut`,
+/// lines are split off the image pixel data without any allocation
+pub struct LinesMut<'a, T> {
+    /// Pixel data starting with the first pixel of the next line
+    data: &'a mut [T],
+    stride: usize,
+    width: usize,
+    count: usize,
+}
+
+impl<'a, T> LinesMut<'a, T> {
+    /// Lines of `range` within pixel `data` of image lines with `stride`
+    pub(crate) fn new(data: &'a mut [T], stride: usize, range: Range2d<usize>) -> Self {
+        let start = (range.y.start*stride + range.x.start).min(data.len());
+        Self { data: &mut data[start..], stride, width: range.width(), count: range.height() }
+    }
+}
+
+impl<'a, T> Iterator for LinesMut<'a, T> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+
+        self.count -= 1;
+        let data = std::mem::take(&mut self.data);
+        // The last line does not need to be followed by full stride
+        let (line, rest) = data.split_at_mut(self.stride.min(data.len()));
+        self.data = rest;
+        Some(&mut line[..self.width])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { (self.count, Some(self.count)) }
+}
+
+impl<'a, T> DoubleEndedIterator for LinesMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.count == 0 {
+            return None;
+        }
+
+        self.count -= 1;
+        let data = std::mem::take(&mut self.data);
+        let (rest, line) = data.split_at_mut((self.count*self.stride).min(data.len()));
+        self.data = rest;
+        Some(&mut line[..self.width])
+    }
+}
+
+impl<'a, T> ExactSizeIterator for LinesMut<'a, T> {}
+
+/// Part of `lines` range lying within image of given `height`
+pub(crate) fn clip_lines(lines: std::ops::Range<isize>, height: usize) -> std::ops::Range<usize> {
+    let range = Range::new(lines).clamp_to(Range::new(0..height as isize));
+    (range.start as usize)..(range.end as usize)
+}
+
+/// Part of `range` lying within `image` range, safe for indexing
+pub(crate) fn clip_range(range: ImgRange, image: ImgRange) -> Range2d<usize> {
+    Range2d::<usize>::from(range.clamp_to(image))
+}
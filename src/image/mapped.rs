@@ -0,0 +1,187 @@
+use std::{fs::File, io, marker::PhantomData, os::raw::{c_int, c_void}, path::Path};
+use std::os::unix::io::AsRawFd;
+use super::{Img, ImgSize, dimensions::ImgBufLayout};
+
+const PROT_READ: c_int = 1;
+const MAP_PRIVATE: c_int = 2;
+
+extern "C" {
+    fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: isize) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+/// Pixel types that can be read directly from mapped file bytes
+/// 
+/// # Safety
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid value
+pub unsafe trait MappedPixel: Copy {}
+
+unsafe impl MappedPixel for u8 {}
+unsafe impl MappedPixel for i8 {}
+unsafe impl MappedPixel for u16 {}
+unsafe impl MappedPixel for i16 {}
+unsafe impl MappedPixel for u32 {}
+unsafe impl MappedPixel for i32 {}
+unsafe impl MappedPixel for u64 {}
+unsafe impl MappedPixel for i64 {}
+unsafe impl MappedPixel for f32 {}
+unsafe impl MappedPixel for f64 {}
+
+/// Read-only image backed by a memory-mapped file (enabled by `mmap` feature)
+/// 
+/// Pixels are stored in the file in native byte order starting at byte 
+/// `offset` with line `stride` declared by `layout`. Pages of the file
+/// are loaded by the operating system only when accessed, so very large 
+/// images can be processed region by region without reading the whole 
+/// file into memory.
+/// 
+/// The file must not be modified by other processes while it is mapped.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{Img, ImgSize, ImgBufLayout, ImgMapped};
+/// let name = format!("nanocv_mapped_doc_{}.raw", std::process::id());
+/// let path = std::env::temp_dir().join(name);
+/// std::fs::write(&path, [9, 9, 1, 2, 3, 4, 5, 6]).unwrap();
+/// 
+/// let layout = ImgBufLayout { size: ImgSize::new(2, 2), stride: 3 };
+/// let image = ImgMapped::<u8>::open(&path, layout, 2).unwrap();
+/// assert_eq!(image.line_ref(0), &[1, 2]);
+/// assert_eq!(image.line_ref(1), &[4, 5]);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ImgMapped<T> {
+    layout: ImgBufLayout,
+    map: *mut c_void,
+    map_length: usize,
+    offset: usize,
+    _pixels: PhantomData<T>,
+}
+
+unsafe impl<T: Sync> Send for ImgMapped<T> {}
+unsafe impl<T: Sync> Sync for ImgMapped<T> {}
+
+impl<T: MappedPixel> ImgMapped<T> {
+    /// Maps file at `path` with pixels described by `layout` starting
+    /// at byte `offset`
+    /// 
+    /// Returns `InvalidInput` error if the layout is invalid, the file 
+    /// is too short or `offset` is not aligned for pixel type `T`
+    pub fn open<P: AsRef<Path>>(path: P, layout: ImgBufLayout, offset: usize) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidInput, message.to_string());
+
+        if layout.stride < layout.size.x {
+            return Err(invalid("Image stride is smaller than image width"));
+        }
+
+        if offset % std::mem::align_of::<T>() != 0 {
+            return Err(invalid("Data offset is not aligned for pixel type"));
+        }
+
+        let file = File::open(path)?;
+
+        let data_length = layout.size.y.checked_mul(layout.stride)
+            .ok_or_else(|| invalid("Image data size overflows"))?;
+
+        let map_length = data_length
+            .checked_mul(std::mem::size_of::<T>())
+            .and_then(|length| length.checked_add(offset))
+            .ok_or_else(|| invalid("Image data size overflows"))?;
+
+        if (file.metadata()?.len() as u128) < map_length as u128 {
+            return Err(invalid("File is too short for declared image layout"));
+        }
+
+        let map = if data_length == 0 {
+            std::ptr::null_mut()
+        } else {
+            // Mapping starts at the beginning of the file, so that its offset
+            // is page aligned, page aligned address keeps pixels aligned too
+            let map = unsafe { 
+                mmap(std::ptr::null_mut(), map_length, PROT_READ, MAP_PRIVATE, file.as_raw_fd(), 0) 
+            };
+
+            if map as isize == -1 {
+                return Err(io::Error::last_os_error());
+            }
+
+            map
+        };
+
+        Ok(Self { layout, map, map_length, offset, _pixels: PhantomData })
+    }
+
+    /// Memory layout of mapped pixel data
+    pub fn layout(&self) -> ImgBufLayout {
+        self.layout
+    }
+}
+
+impl<T: MappedPixel> Img<T> for ImgMapped<T> {
+    fn size(&self) -> ImgSize {
+        self.layout.size
+    }
+
+    fn line_ref(&self, line: usize) -> &[T] {
+        assert!(line < self.layout.size.y, "Image line does not exist");
+
+        if self.layout.size.x == 0 {
+            return &[];
+        }
+
+        unsafe {
+            let data = (self.map as *const u8).add(self.offset) as *const T;
+            std::slice::from_raw_parts(data.add(line*self.layout.stride), self.layout.size.x)
+        }
+    }
+}
+
+impl<T> Drop for ImgMapped<T> {
+    fn drop(&mut self) {
+        if !self.map.is_null() {
+            unsafe { munmap(self.map, self.map_length); }
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImgBuf, filter::map_new};
+
+    /// Write `data` into temporary file unique for test `name` and process
+    fn temp_file(name: &str, data: &[u8]) -> std::path::PathBuf {
+        let name = format!("nanocv_mapped_{}_{}.raw", name, std::process::id());
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn mapped_pixels_can_be_filtered() {
+        let data: Vec<u8> = (0..12u16).flat_map(|x| (x*100).to_ne_bytes()).collect();
+        let path = temp_file("u16", &data);
+        let layout = ImgBufLayout { size: ImgSize::new(4, 3), stride: 4 };
+        let image = ImgMapped::<u16>::open(&path, layout, 0).unwrap();
+        let result: ImgBuf<u16> = map_new(&image, |x: u16| x/100);
+        assert_eq!(result.into_vec(), (0..12).collect::<Vec<u16>>());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn invalid_layouts_are_rejected() {
+        let path = temp_file("short", &[0; 10]);
+        let layout = ImgBufLayout { size: ImgSize::new(2, 2), stride: 2 };
+        assert!(ImgMapped::<u16>::open(&path, layout, 2).is_ok());
+        assert!(ImgMapped::<u16>::open(&path, layout, 1).is_err());
+        assert!(ImgMapped::<u16>::open(&path, layout, 4).is_err());
+        let narrow = ImgBufLayout { size: ImgSize::new(2, 2), stride: 1 };
+        assert!(ImgMapped::<u8>::open(&path, narrow, 0).is_err());
+        let huge = ImgBufLayout { size: ImgSize::new(2, usize::MAX), stride: 2 };
+        assert!(ImgMapped::<u8>::open(&path, huge, 0).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
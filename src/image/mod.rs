@@ -3,7 +3,26 @@
 mod traits;
 mod buffer;
 mod dimensions;
+mod iter;
+mod size;
+mod band;
+mod view;
+mod pixel;
+mod format;
+#[cfg(feature = "half")]
+mod half;
+#[cfg(all(feature = "mmap", unix))]
+mod mapped;
+#[cfg(all(feature = "mmap", not(unix)))]
+compile_error!("Feature `mmap` is supported on unix targets only");
 
-pub use traits::{Img, ImgMut, ImgSize};
+pub use traits::{Img, ImgMut, ImgLinesMut, ImgSize};
 pub use buffer::{ImgBuf};
-pub use dimensions::{ImgBufLayout};
\ No newline at end of file
+pub use dimensions::{ImgBufLayout, LayoutError};
+pub use iter::{EnumeratePixels, EnumeratePixelsMut, Lines, LinesMut};
+pub use band::RowBand;
+pub use view::ImgView;
+pub use pixel::Pixel;
+pub use format::ImgFormat;
+#[cfg(all(feature = "mmap", unix))]
+pub use mapped::{ImgMapped, MappedPixel};
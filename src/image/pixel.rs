@@ -0,0 +1,124 @@
+use crate::color::{Rgb, Rgba};
+
+/// Pixel consisting of one or more components of the same type
+/// 
+/// Allows a single implementation of an algorithm to process scalar
+/// as well as multi-channel images component by component. Generic
+/// functions like [map](filter/fn.map.html) and [update](filter/fn.update.html)
+/// combine with component-wise operations of this trait.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, filter::update};
+/// 
+/// fn halve<P: Pixel<Component = u8>>(image: &mut dyn ImgMut<P>) {
+///     update(image, |pixel| pixel.map_components(|x| x/2));
+/// }
+/// 
+/// let mut gray = ImgBuf::<u8>::new_init(ImgSize::new(2, 1), 10);
+/// let mut color = ImgBuf::new_init(ImgSize::new(2, 1), Rgb::new(10u8, 20, 30));
+/// halve(&mut gray);
+/// halve(&mut color);
+/// assert_eq!(gray[(0, 0)], 5);
+/// assert_eq!(color[(1, 0)], Rgb::new(5, 10, 15));
+/// ```
+pub trait Pixel: Copy {
+    /// Type of pixel components
+    type Component: Copy;
+
+    /// Number of pixel components
+    const CHANNELS: usize;
+
+    /// Component at `index`, panics if `index` is not less than `CHANNELS`
+    fn component(&self, index: usize) -> Self::Component;
+
+    /// Creates pixel with components produced by `component` for each
+    /// component index
+    fn from_components<F: FnMut(usize) -> Self::Component>(component: F) -> Self;
+
+    /// Creates pixel with all components set to `value`
+    fn splat(value: Self::Component) -> Self {
+        Self::from_components(|_| value)
+    }
+
+    /// Maps each component by `operator`
+    fn map_components<F: FnMut(Self::Component) -> Self::Component>(self, mut operator: F) -> Self {
+        Self::from_components(|index| operator(self.component(index)))
+    }
+
+    /// Combines components of two pixels by `operator`
+    fn zip_components<F>(self, other: Self, mut operator: F) -> Self 
+    where F: FnMut(Self::Component, Self::Component) -> Self::Component {
+        Self::from_components(|index| operator(self.component(index), other.component(index)))
+    }
+}
+
+macro_rules! impl_scalar_pixel {
+    ($($type: ty),*) => {
+        $(
+            impl Pixel for $type {
+                type Component = $type;
+                const CHANNELS: usize = 1;
+
+                #[inline]
+                fn component(&self, index: usize) -> $type {
+                    assert!(index == 0, "Component index out of range");
+                    *self
+                }
+
+                #[inline]
+                fn from_components<F: FnMut(usize) -> $type>(mut component: F) -> Self {
+                    component(0)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_pixel!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl<T: Copy> Pixel for Rgb<T> {
+    type Component = T;
+    const CHANNELS: usize = 3;
+
+    #[inline]
+    fn component(&self, index: usize) -> T {
+        [self.r, self.g, self.b][index]
+    }
+
+    #[inline]
+    fn from_components<F: FnMut(usize) -> T>(mut component: F) -> Self {
+        Rgb::new(component(0), component(1), component(2))
+    }
+}
+
+impl<T: Copy> Pixel for Rgba<T> {
+    type Component = T;
+    const CHANNELS: usize = 4;
+
+    #[inline]
+    fn component(&self, index: usize) -> T {
+        [self.r, self.g, self.b, self.a][index]
+    }
+
+    #[inline]
+    fn from_components<F: FnMut(usize) -> T>(mut component: F) -> Self {
+        Rgba::new(component(0), component(1), component(2), component(3))
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components_are_ordered() {
+        let pixel = Rgba::new(1u8, 2, 3, 4);
+        let components: Vec<u8> = (0..Rgba::<u8>::CHANNELS).map(|i| pixel.component(i)).collect();
+        assert_eq!(components, vec![1, 2, 3, 4]);
+        assert_eq!(pixel.zip_components(Rgba::splat(10), |a, b| b - a), Rgba::new(9, 8, 7, 6));
+        assert_eq!(2.5f32.map_components(|x| x*2.0), 5.0);
+    }
+}
@@ -0,0 +1,108 @@
+use std::cmp::{min, max};
+use super::ImgSize;
+
+impl ImgSize {
+    /// Ratio of image width and height
+    /// ```
+    /// use nanocv::ImgSize;
+    /// assert_eq!(ImgSize::new(1920, 1080).aspect_ratio(), 16.0/9.0);
+    /// ```
+    pub fn aspect_ratio(&self) -> f64 {
+        self.x as f64/self.y as f64
+    }
+
+    /// Tests whether image of this size fits within `bounds`
+    /// ```
+    /// use nanocv::ImgSize;
+    /// assert!(ImgSize::new(3, 2).fits_within(ImgSize::new(3, 4)));
+    /// assert!(!ImgSize::new(3, 2).fits_within(ImgSize::new(2, 4)));
+    /// ```
+    pub fn fits_within(&self, bounds: ImgSize) -> bool {
+        self.x <= bounds.x && self.y <= bounds.y
+    }
+
+    /// Largest size with the same aspect ratio that fits within `bounds`,
+    /// the dimension not limited by bounds is rounded to the nearest 
+    /// integer, but never to zero
+    /// 
+    /// Sizes with zero width or height produce zero size
+    /// ```
+    /// use nanocv::ImgSize;
+    /// let size = ImgSize::new(1920, 1080);
+    /// assert_eq!(size.scale_to_fit(ImgSize::new(100, 100)), ImgSize::new(100, 56));
+    /// assert_eq!(size.scale_to_fit(ImgSize::new(4000, 100)), ImgSize::new(178, 100));
+    /// ```
+    pub fn scale_to_fit(&self, bounds: ImgSize) -> ImgSize {
+        if self.x == 0 || self.y == 0 {
+            return ImgSize::default();
+        }
+
+        let (x, y) = (self.x as u128, self.y as u128);
+        let (bx, by) = (bounds.x as u128, bounds.y as u128);
+
+        if bx*y <= by*x {
+            ImgSize::new(bounds.x, min(bounds.y, max(1, scale(y, bx, x))))
+        } else {
+            ImgSize::new(min(bounds.x, max(1, scale(x, by, y))), bounds.y)
+        }
+    }
+
+    /// Smallest size with the same aspect ratio that covers the whole
+    /// `bounds`, the dimension not limited by bounds is rounded to 
+    /// the nearest integer
+    /// 
+    /// Sizes with zero width or height produce zero size
+    /// ```
+    /// use nanocv::ImgSize;
+    /// let size = ImgSize::new(1920, 1080);
+    /// assert_eq!(size.scale_to_fill(ImgSize::new(100, 100)), ImgSize::new(178, 100));
+    /// assert_eq!(size.scale_to_fill(ImgSize::new(4000, 100)), ImgSize::new(4000, 2250));
+    /// ```
+    pub fn scale_to_fill(&self, bounds: ImgSize) -> ImgSize {
+        if self.x == 0 || self.y == 0 {
+            return ImgSize::default();
+        }
+
+        let (x, y) = (self.x as u128, self.y as u128);
+        let (bx, by) = (bounds.x as u128, bounds.y as u128);
+
+        if bx*y >= by*x {
+            ImgSize::new(bounds.x, scale(y, bx, x))
+        } else {
+            ImgSize::new(scale(x, by, y), bounds.y)
+        }
+    }
+}
+
+/// Computes `value*numerator/denominator` rounded to the nearest integer,
+/// saturating at `usize::MAX`
+fn scale(value: u128, numerator: u128, denominator: u128) -> usize {
+    min((2*value*numerator + denominator)/(2*denominator), usize::MAX as u128) as usize
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_to_fit_very_wide_image_keeps_one_line() {
+        assert_eq!(
+            ImgSize::new(10000, 1).scale_to_fit(ImgSize::new(10, 10)), 
+            ImgSize::new(10, 1)
+        );
+    }
+
+    #[test]
+    fn scale_same_aspect_ratio_matches_bounds() {
+        let bounds = ImgSize::new(40, 30);
+        assert_eq!(ImgSize::new(4, 3).scale_to_fit(bounds), bounds);
+        assert_eq!(ImgSize::new(4, 3).scale_to_fill(bounds), bounds);
+    }
+
+    #[test]
+    fn scale_zero_size() {
+        assert_eq!(ImgSize::new(0, 3).scale_to_fit(ImgSize::new(4, 4)), ImgSize::new(0, 0));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{ImgRange, geometry::{Range2d, Vec2d}};
+use crate::{ImgRange, Range2d, geometry::Vec2d};
+use super::iter::{
+    EnumeratePixels, EnumeratePixelsMut, Lines, LinesMut, clip_range, clip_lines
+};
+use super::band::RowBand;
 
 /// Read-only access to image pixels, usually used as input data
 pub trait Img<T> {
@@ -20,7 +24,105 @@ pub trait Img<T> {
 
     /// Image size as a 2-dimensional range
     fn range(&self) -> ImgRange {
-        Range2d::new(0..self.width() as isize, 0..self.height() as isize)
+        ImgRange::from_size(self.size())
+    }
+
+    /// Non mutable access to image line pixel data,
+    /// returns `None` if image line does not exist
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+    /// assert_eq!(buf.try_line_ref(1), Some(&[3u8, 4][..]));
+    /// assert_eq!(buf.try_line_ref(2), None);
+    /// ```
+    fn try_line_ref(&self, line: usize) -> Option<&[T]> {
+        if line < self.height() { Some(self.line_ref(line)) } else { None }
+    }
+
+    /// Value of pixel at column `x` and line `y`,
+    /// returns `None` if pixel lies outside image
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+    /// assert_eq!(buf.get(1, 0), Some(2));
+    /// assert_eq!(buf.get(2, 0), None);
+    /// assert_eq!(buf.get(0, 2), None);
+    /// ```
+    fn get(&self, x: usize, y: usize) -> Option<T> where T: Copy {
+        self.try_line_ref(y).and_then(|line| line.get(x).copied())
+    }
+
+    /// Value of pixel at column `x` and line `y`
+    ///
+    /// Panics if pixel lies outside image
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+    /// assert_eq!(buf.pixel(0, 1), 3);
+    /// ```
+    fn pixel(&self, x: usize, y: usize) -> T where T: Copy {
+        self.line_ref(y)[x]
+    }
+
+    /// Iterator over all image pixels together with their coordinates,
+    /// in row-major order
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize, Vec2d};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+    /// let mut pixels = buf.enumerate_pixels();
+    /// assert_eq!(pixels.next(), Some((Vec2d::new(0, 0), &1)));
+    /// assert_eq!(pixels.next(), Some((Vec2d::new(1, 0), &2)));
+    /// assert_eq!(pixels.next(), Some((Vec2d::new(0, 1), &3)));
+    /// assert_eq!(pixels.next(), Some((Vec2d::new(1, 1), &4)));
+    /// assert_eq!(pixels.next(), None);
+    /// ```
+    fn enumerate_pixels(&self) -> EnumeratePixels<'_, T> {
+        self.enumerate_pixels_range(self.range())
+    }
+
+    /// Iterator over image pixels within `range` together with their 
+    /// coordinates, in row-major order, pixels outside image are skipped
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize, ImgRange, Vec2d};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+    /// let pixels: Vec<_> = buf.enumerate_pixels_range(ImgRange::new(1..5, 0..2)).collect();
+    /// assert_eq!(pixels, vec![(Vec2d::new(1, 0), &2), (Vec2d::new(1, 1), &4)]);
+    /// ```
+    fn enumerate_pixels_range(&self, range: ImgRange) -> EnumeratePixels<'_, T> {
+        let range = clip_range(range, self.range());
+
+        let lines = range.y.to_range()
+            .map(|line| &self.line_ref(line)[range.x.to_range()])
+            .collect();
+
+        EnumeratePixels::new(lines, range.start())
+    }
+
+    /// Iterator over all image lines, from top to bottom
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+    /// let sums: Vec<u8> = buf.lines().map(|line| line.iter().sum()).collect();
+    /// assert_eq!(sums, vec![3, 7]);
+    /// ```
+    fn lines(&self) -> Lines<'_, T> {
+        self.lines_range(0..self.height() as isize)
+    }
+
+    /// Iterator over image lines within vertical range `lines`, 
+    /// from top to bottom, lines outside image are skipped
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgSize};
+    /// let buf = ImgBuf::<u8>::from_vec(ImgSize::new(1, 3), vec![1, 2, 3]);
+    /// let lines: Vec<&[u8]> = buf.lines_range(1..5).collect();
+    /// assert_eq!(lines, vec![&[2], &[3]]);
+    /// ```
+    fn lines_range(&self, lines: std::ops::Range<isize>) -> Lines<'_, T> {
+        Lines::new(
+            clip_lines(lines, self.height())
+                .map(|line| self.line_ref(line))
+                .collect()
+        )
     }
 }
 
@@ -30,6 +132,159 @@ pub trait ImgMut<T>: Img<T> {
     ///
     /// Panics if image line does not exist
     fn line_mut(&mut self, line: usize) -> &mut [T];
+
+    /// Mutable access to specific image line,
+    /// returns `None` if image line does not exist
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgMut, ImgSize};
+    /// let mut buf = ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), vec![1, 2, 3, 4]);
+    /// buf.try_line_mut(0).unwrap()[0] = 5;
+    /// assert!(buf.try_line_mut(2).is_none());
+    /// assert_eq!(buf.line_ref(0), &[5, 2]);
+    /// ```
+    fn try_line_mut(&mut self, line: usize) -> Option<&mut [T]> {
+        if line < self.height() { Some(self.line_mut(line)) } else { None }
+    }
+
+    /// Set value of pixel at column `x` and line `y`
+    ///
+    /// Panics if pixel lies outside image
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgMut, ImgSize};
+    /// let mut buf = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+    /// buf.set_pixel(1, 0, 7);
+    /// assert_eq!(buf.line_ref(0), &[0, 7]);
+    /// ```
+    fn set_pixel(&mut self, x: usize, y: usize, value: T) {
+        self.line_mut(y)[x] = value;
+    }
+}
+
+/// Simultaneous mutable access to all image lines, enables mutable
+/// iterators and splitting of the image into independent bands
+/// 
+/// `ImgMut` borrows only one line at a time, so these methods are 
+/// provided by this subtrait instead, generic code using them has to
+/// require `ImgLinesMut` rather than `ImgMut`
+pub trait ImgLinesMut<T>: ImgMut<T> {
+    /// Mutable access to pixel data of all image lines stored in one 
+    /// block of memory, returns the data starting with the first pixel
+    /// of the first line together with line stride, line `y` occupies
+    /// elements `y*stride..y*stride + width` of the data
+    /// ```
+    /// use nanocv::{ImgBuf, ImgBufLayout, ImgLinesMut, ImgSize};
+    /// let layout = ImgBufLayout { size: ImgSize::new(1, 2), stride: 2 };
+    /// let mut buf = ImgBuf::<u8>::from_vec_stride(layout, vec![1, 2, 3, 4]);
+    /// assert_eq!(buf.strided_mut(), (&mut [1, 2, 3, 4][..], 2));
+    /// ```
+    fn strided_mut(&mut self) -> (&mut [T], usize);
+
+    /// Iterator over all mutable image pixels together with their 
+    /// coordinates, in row-major order
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgLinesMut, ImgSize};
+    /// let mut buf = ImgBuf::<usize>::new(ImgSize::new(2, 2));
+    /// 
+    /// for (position, pixel) in buf.enumerate_pixels_mut() {
+    ///     *pixel = position.x + 10*position.y;
+    /// }
+    /// 
+    /// assert_eq!(buf.line_ref(1), &[10, 11]);
+    /// ```
+    fn enumerate_pixels_mut(&mut self) -> EnumeratePixelsMut<'_, T> {
+        let range = self.range();
+        self.enumerate_pixels_range_mut(range)
+    }
+
+    /// Iterator over mutable image pixels within `range` together with their 
+    /// coordinates, in row-major order, pixels outside image are skipped
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgLinesMut, ImgSize, ImgRange};
+    /// let mut buf = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+    /// 
+    /// for (_, pixel) in buf.enumerate_pixels_range_mut(ImgRange::new(-1..1, 1..2)) {
+    ///     *pixel = 1;
+    /// }
+    /// 
+    /// assert_eq!(buf.line_ref(0), &[0, 0]);
+    /// assert_eq!(buf.line_ref(1), &[1, 0]);
+    /// ```
+    fn enumerate_pixels_range_mut(&mut self, range: ImgRange) -> EnumeratePixelsMut<'_, T> {
+        let range = clip_range(range, self.range());
+        let (data, stride) = self.strided_mut();
+        EnumeratePixelsMut::new(LinesMut::new(data, stride, range), range.start())
+    }
+
+    /// Iterator over all mutable image lines, from top to bottom
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgLinesMut, ImgSize};
+    /// let mut buf = ImgBuf::<u8>::new(ImgSize::new(2, 2));
+    /// 
+    /// for (index, line) in buf.lines_mut().enumerate() {
+    ///     line[0] = index as u8;
+    /// }
+    /// 
+    /// assert_eq!(buf, ImgBuf::from_vec(buf.size(), vec![0, 0, 1, 0]));
+    /// ```
+    fn lines_mut(&mut self) -> LinesMut<'_, T> {
+        self.lines_range_mut(0..self.height() as isize)
+    }
+
+    /// Iterator over mutable image lines within vertical range `lines`,
+    /// from top to bottom, lines outside image are skipped
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgLinesMut, ImgSize};
+    /// let mut buf = ImgBuf::<u8>::new(ImgSize::new(1, 3));
+    /// buf.lines_range_mut(-1..2).for_each(|line| line[0] = 1);
+    /// assert_eq!(buf, ImgBuf::from_vec(buf.size(), vec![1, 1, 0]));
+    /// ```
+    fn lines_range_mut(&mut self, lines: std::ops::Range<isize>) -> LinesMut<'_, T> {
+        let range = Range2d::new(0..self.width(), clip_lines(lines, self.height()));
+        let (data, stride) = self.strided_mut();
+        LinesMut::new(data, stride, range)
+    }
+
+    /// Splits image into `count` non-overlapping mutable bands of lines,
+    /// ordered from top to bottom, heights of bands differ by at most one
+    /// line and the first bands are the higher ones
+    /// 
+    /// Bands borrow the image mutably, so they can be sent to separate 
+    /// threads to process the image in parallel by any threading library
+    /// 
+    /// The method lives on `ImgLinesMut`, not on `ImgMut`, because all 
+    /// bands need to borrow their lines at the same time
+    /// 
+    /// Panics if `count` is zero
+    /// ```
+    /// use nanocv::{ImgBuf, Img, ImgLinesMut, ImgSize, filter::update};
+    /// let mut buf = ImgBuf::<u8>::new(ImgSize::new(2, 5));
+    /// 
+    /// std::thread::scope(|scope| {
+    ///     for mut band in buf.split_rows_mut(2) {
+    ///         scope.spawn(move || update(&mut band, |x| x + 1));
+    ///     }
+    /// });
+    /// 
+    /// assert!(buf.lines().all(|line| line == [1, 1]));
+    /// ```
+    fn split_rows_mut(&mut self, count: usize) -> Vec<RowBand<'_, T>> {
+        assert!(count > 0, "Number of bands must be positive");
+        let ImgSize { x: width, y: height } = self.size();
+        let (mut data, stride) = self.strided_mut();
+        let mut offset = 0;
+
+        (0..count)
+            .map(|index| {
+                let band_height = height/count + usize::from(index < height % count);
+                let split = (band_height*stride).min(data.len());
+                let (lines, rest) = std::mem::take(&mut data).split_at_mut(split);
+                data = rest;
+                let band = RowBand::new(lines, ImgSize::new(width, band_height), stride, offset);
+                offset += band_height;
+                band
+            })
+            .collect()
+    }
 }
 
 /// Image width and height, specifies an image data size for any
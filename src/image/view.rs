@@ -0,0 +1,28 @@
+use crate::{ImgRange, ImgSize, Range2d};
+use super::{Img, iter::clip_range};
+
+/// Read-only view of a rectangular part of another image, created 
+/// for example by [ImgBuf::view_cropped](struct.ImgBuf.html#method.view_cropped)
+pub struct ImgView<'a, T> {
+    image: &'a dyn Img<T>,
+    range: Range2d<usize>,
+}
+
+impl<'a, T> ImgView<'a, T> {
+    /// Creates view of `image` pixels within `range`, parts of `range` 
+    /// outside image are ignored
+    pub fn new(image: &'a dyn Img<T>, range: ImgRange) -> Self {
+        Self { range: clip_range(range, image.range()), image }
+    }
+}
+
+impl<'a, T> Img<T> for ImgView<'a, T> {
+    fn size(&self) -> ImgSize {
+        ImgSize::new(self.range.width(), self.range.height())
+    }
+
+    fn line_ref(&self, line: usize) -> &[T] {
+        assert!(line < self.range.height(), "Image line does not exist");
+        &self.image.line_ref(self.range.y.start + line)[self.range.x.to_range()]
+    }
+}
@@ -0,0 +1,8 @@
+//! Decoding of image data from external storage formats
+//! 
+//! Available modules:
+//!  * [raw](raw/index.html) - unpacking of raw sensor data
+//!  * [png](png/index.html) - reading and writing of PNG images
+
+pub mod raw;
+pub mod png;
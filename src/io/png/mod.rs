@@ -0,0 +1,586 @@
+//! Reading and writing of PNG images without external dependencies
+//! 
+//! Supported are non-interlaced grayscale, RGB and RGBA images with 8 or 16
+//! bits per component, images are compressed by internal DEFLATE encoder.
+//! 
+//! Available functions:
+//!  * [decode](fn.decode.html), [read](fn.read.html) - decoding of PNG data 
+//!    into [PngImage](enum.PngImage.html)
+//!  * [encode](fn.encode.html), [write](fn.write.html) - encoding of images
+//!    with [PngPixel](trait.PngPixel.html) pixels
+
+mod zlib;
+
+use std::{convert::TryFrom, fmt::{Display, Formatter}, path::Path};
+use crate::{Img, ImgMut, ImgBuf, ImgSize, color::{Rgb, Rgba}, filter::map_new};
+use zlib::{zlib_compress, zlib_decompress, MAX_RATIO};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Error of PNG decoding and encoding, converts into crate
+/// [Error](../../enum.Error.html) by `?` operator
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum PngError {
+    /// Data do not start with PNG signature
+    InvalidSignature,
+    /// Data are corrupted or truncated
+    InvalidData(&'static str),
+    /// Image uses PNG feature not supported by the decoder
+    Unsupported(&'static str),
+    /// Image dimensions exceed the PNG limit of `2^31 - 1` pixels
+    TooLarge,
+}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            PngError::InvalidSignature => write!(f, "Invalid PNG signature."),
+            PngError::InvalidData(message) => write!(f, "Invalid PNG data: {}.", message),
+            PngError::Unsupported(message) => write!(f, "Unsupported PNG image: {}.", message),
+            PngError::TooLarge => write!(f, "Image dimensions exceed PNG limits."),
+        }
+    }
+}
+
+impl std::error::Error for PngError {}
+
+/// Image decoded from PNG data with pixel type given by PNG color type
+/// and bit depth
+#[derive(Clone, PartialEq, Debug)]
+pub enum PngImage {
+    Gray8(ImgBuf<u8>),
+    Gray16(ImgBuf<u16>),
+    Rgb8(ImgBuf<Rgb<u8>>),
+    Rgb16(ImgBuf<Rgb<u16>>),
+    Rgba8(ImgBuf<Rgba<u8>>),
+    Rgba16(ImgBuf<Rgba<u16>>),
+}
+
+impl PngImage {
+    /// Image size in pixels
+    pub fn size(&self) -> ImgSize {
+        match self {
+            PngImage::Gray8(image) => image.size(),
+            PngImage::Gray16(image) => image.size(),
+            PngImage::Rgb8(image) => image.size(),
+            PngImage::Rgb16(image) => image.size(),
+            PngImage::Rgba8(image) => image.size(),
+            PngImage::Rgba16(image) => image.size(),
+        }
+    }
+
+    /// Converts image to 8-bit grayscale, colors are converted to luma
+    /// by ITU-R BT.601 weights, 16-bit values are scaled and alpha is ignored
+    pub fn into_gray8(self) -> ImgBuf<u8> {
+        let luma = |r: u32, g: u32, b: u32| (299*r + 587*g + 114*b + 500)/1000;
+        let scale = |x: u32| ((x + 128)/257) as u8;
+
+        match self {
+            PngImage::Gray8(image) => image,
+            PngImage::Gray16(image) => map_new(&image, |x: u16| scale(x as u32)),
+            PngImage::Rgb8(image) => map_new(&image, |p: Rgb<u8>| luma(p.r as u32, p.g as u32, p.b as u32) as u8),
+            PngImage::Rgb16(image) => map_new(&image, |p: Rgb<u16>| scale(luma(p.r as u32, p.g as u32, p.b as u32))),
+            PngImage::Rgba8(image) => map_new(&image, |p: Rgba<u8>| luma(p.r as u32, p.g as u32, p.b as u32) as u8),
+            PngImage::Rgba16(image) => map_new(&image, |p: Rgba<u16>| scale(luma(p.r as u32, p.g as u32, p.b as u32))),
+        }
+    }
+}
+
+/// Pixel types that can be stored in PNG images
+pub trait PngPixel: Copy {
+    /// PNG color type
+    const COLOR_TYPE: u8;
+    /// Number of bits per pixel component
+    const BIT_DEPTH: u8;
+    /// Number of bytes per pixel
+    const BYTES: usize;
+
+    /// Writes pixel as big-endian components
+    fn write_bytes(&self, output: &mut Vec<u8>);
+}
+
+impl PngPixel for u8 {
+    const COLOR_TYPE: u8 = 0;
+    const BIT_DEPTH: u8 = 8;
+    const BYTES: usize = 1;
+
+    fn write_bytes(&self, output: &mut Vec<u8>) {
+        output.push(*self);
+    }
+}
+
+impl PngPixel for u16 {
+    const COLOR_TYPE: u8 = 0;
+    const BIT_DEPTH: u8 = 16;
+    const BYTES: usize = 2;
+
+    fn write_bytes(&self, output: &mut Vec<u8>) {
+        output.extend(&self.to_be_bytes());
+    }
+}
+
+impl<T: PngPixel> PngPixel for Rgb<T> {
+    const COLOR_TYPE: u8 = 2;
+    const BIT_DEPTH: u8 = T::BIT_DEPTH;
+    const BYTES: usize = 3*T::BYTES;
+
+    fn write_bytes(&self, output: &mut Vec<u8>) {
+        self.r.write_bytes(output);
+        self.g.write_bytes(output);
+        self.b.write_bytes(output);
+    }
+}
+
+impl<T: PngPixel> PngPixel for Rgba<T> {
+    const COLOR_TYPE: u8 = 6;
+    const BIT_DEPTH: u8 = T::BIT_DEPTH;
+    const BYTES: usize = 4*T::BYTES;
+
+    fn write_bytes(&self, output: &mut Vec<u8>) {
+        self.r.write_bytes(output);
+        self.g.write_bytes(output);
+        self.b.write_bytes(output);
+        self.a.write_bytes(output);
+    }
+}
+
+/// Decodes PNG image from `data`
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, io::png::{encode, decode, PngImage}};
+/// let image = ImgBuf::from_vec(ImgSize::new(2, 1), vec![Rgb::new(1u8, 2, 3), Rgb::new(4, 5, 6)]);
+/// let data = encode(&image).unwrap();
+/// assert_eq!(decode(&data), Ok(PngImage::Rgb8(image)));
+/// ```
+pub fn decode(data: &[u8]) -> Result<PngImage, PngError> {
+    if !data.starts_with(&SIGNATURE) {
+        return Err(PngError::InvalidSignature);
+    }
+
+    let mut position = SIGNATURE.len();
+    let mut header = None;
+    let mut compressed = Vec::new();
+
+    loop {
+        let length = data.get(position..position + 4).ok_or(PngError::InvalidData("truncated chunk"))?;
+        let length = u32::from_be_bytes([length[0], length[1], length[2], length[3]]) as usize;
+        let chunk = data.get(position + 4..position + 8 + length).ok_or(PngError::InvalidData("truncated chunk"))?;
+        let crc = data.get(position + 8 + length..position + 12 + length).ok_or(PngError::InvalidData("truncated chunk"))?;
+
+        if u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]) != crc32(chunk) {
+            return Err(PngError::InvalidData("chunk checksum mismatch"));
+        }
+
+        let (kind, content) = chunk.split_at(4);
+        position += 12 + length;
+
+        match kind {
+            b"IHDR" if header.is_some() => return Err(PngError::InvalidData("repeated header")),
+            b"IHDR" => header = Some(Header::parse(content)?),
+            _ if header.is_none() => return Err(PngError::InvalidData("header is not the first chunk")),
+            b"IDAT" => compressed.extend_from_slice(content),
+            b"IEND" => break,
+            _ if kind[0] & 0x20 == 0 => return Err(PngError::Unsupported("critical chunk")),
+            _ => {}
+        }
+    }
+
+    let header = header.ok_or(PngError::InvalidData("missing header"))?;
+    let raw_length = header.raw_length().ok_or(PngError::InvalidData("image too large"))?;
+
+    if raw_length > compressed.len().saturating_mul(MAX_RATIO) {
+        return Err(PngError::InvalidData("image data too short for image size"));
+    }
+
+    let raw = zlib_decompress(&compressed, raw_length).map_err(PngError::InvalidData)?;
+    let pixels = unfilter(&raw, &header)?;
+    Ok(header.image(&pixels))
+}
+
+/// Encodes image into PNG data, returns `PngError::TooLarge` if image
+/// width or height exceeds `2^31 - 1` pixels
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, io::png::{encode, decode, PngImage}};
+/// let image = ImgBuf::<u16>::from_vec(ImgSize::new(3, 2), vec![0, 1, 2, 300, 40000, 65535]);
+/// assert_eq!(decode(&encode(&image).unwrap()), Ok(PngImage::Gray16(image)));
+/// ```
+pub fn encode<P: PngPixel>(image: &dyn Img<P>) -> Result<Vec<u8>, PngError> {
+    let size = image.size();
+    let (width, height) = (dimension(size.x)?, dimension(size.y)?);
+    let line_bytes = size.x*P::BYTES;
+    let mut raw = Vec::with_capacity((line_bytes + 1)*size.y);
+    let mut previous = vec![0u8; line_bytes];
+    let mut line = Vec::with_capacity(line_bytes);
+
+    for y in 0..size.y {
+        line.clear();
+        image.line_ref(y).iter().for_each(|pixel| pixel.write_bytes(&mut line));
+        let (filter, filtered) = best_filter(&line, &previous, P::BYTES);
+        raw.push(filter);
+        raw.extend(filtered);
+        std::mem::swap(&mut line, &mut previous);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(&width.to_be_bytes());
+    header.extend(&height.to_be_bytes());
+    header.extend(&[P::BIT_DEPTH, P::COLOR_TYPE, 0, 0, 0]);
+
+    let mut output = SIGNATURE.to_vec();
+    write_chunk(&mut output, b"IHDR", &header);
+    write_chunk(&mut output, b"IDAT", &zlib_compress(&raw));
+    write_chunk(&mut output, b"IEND", &[]);
+    Ok(output)
+}
+
+/// Image dimension stored in PNG header, limited to `2^31 - 1` by PNG specification
+fn dimension(value: usize) -> Result<u32, PngError> {
+    u32::try_from(value)
+        .ok()
+        .filter(|&value| value <= i32::MAX as u32)
+        .ok_or(PngError::TooLarge)
+}
+
+/// Reads and decodes PNG image from file at `path`, decoding errors
+/// are reported as `InvalidData` errors
+pub fn read<P: AsRef<Path>>(path: P) -> std::io::Result<PngImage> {
+    let data = std::fs::read(path)?;
+    decode(&data).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+}
+
+/// Encodes image and writes it into PNG file at `path`, encoding errors
+/// are reported as `InvalidInput` errors
+pub fn write<P: AsRef<Path>, T: PngPixel>(path: P, image: &dyn Img<T>) -> std::io::Result<()> {
+    let data = encode(image).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+    std::fs::write(path, data)
+}
+
+/// Image header chunk content
+struct Header {
+    size: ImgSize,
+    bit_depth: u8,
+    color_type: u8,
+}
+
+impl Header {
+    fn parse(content: &[u8]) -> Result<Self, PngError> {
+        if content.len() != 13 {
+            return Err(PngError::InvalidData("invalid header length"));
+        }
+
+        let dimension = |i: usize| u32::from_be_bytes([content[i], content[i + 1], content[i + 2], content[i + 3]]) as usize;
+        let header = Self { size: ImgSize::new(dimension(0), dimension(4)), bit_depth: content[8], color_type: content[9] };
+
+        if content[10] != 0 || content[11] != 0 {
+            return Err(PngError::InvalidData("invalid compression or filter method"));
+        }
+
+        if content[12] != 0 {
+            return Err(PngError::Unsupported("interlaced image"));
+        }
+
+        if !matches!(header.color_type, 0 | 2 | 6) {
+            return Err(PngError::Unsupported("color type"));
+        }
+
+        if !matches!(header.bit_depth, 8 | 16) {
+            return Err(PngError::Unsupported("bit depth"));
+        }
+
+        Ok(header)
+    }
+
+    fn channels(&self) -> usize {
+        match self.color_type {
+            0 => 1,
+            2 => 3,
+            _ => 4,
+        }
+    }
+
+    fn pixel_bytes(&self) -> usize {
+        self.channels()*self.bit_depth as usize/8
+    }
+
+    /// Number of pixel bytes in image line, `None` on overflow
+    fn line_bytes(&self) -> Option<usize> {
+        self.size.x.checked_mul(self.pixel_bytes())
+    }
+
+    /// Length of decompressed image data including line filter types, 
+    /// `None` on overflow
+    fn raw_length(&self) -> Option<usize> {
+        self.line_bytes()?.checked_add(1)?.checked_mul(self.size.y)
+    }
+
+    /// Creates image from unfiltered pixel bytes
+    fn image(&self, pixels: &[u8]) -> PngImage {
+        let size = self.size;
+        let channels = self.channels();
+        let wide = self.bit_depth == 16;
+
+        let component = |index: usize| if wide {
+            u16::from_be_bytes([pixels[2*index], pixels[2*index + 1]])
+        } else {
+            pixels[index] as u16
+        };
+
+        fn fill<P: Copy + Default, F: Fn(usize) -> P>(size: ImgSize, pixel: F) -> ImgBuf<P> {
+            let mut image = ImgBuf::new(size);
+
+            for y in 0..size.y {
+                let line = image.line_mut(y);
+
+                for x in 0..size.x {
+                    line[x] = pixel(y*size.x + x);
+                }
+            }
+
+            image
+        }
+
+        let rgb = |i: usize| Rgb::new(component(channels*i), component(channels*i + 1), component(channels*i + 2));
+        let alpha = |i: usize| component(channels*i + 3);
+
+        match (self.color_type, wide) {
+            (0, false) => PngImage::Gray8(fill(size, |i| component(i) as u8)),
+            (0, true) => PngImage::Gray16(fill(size, component)),
+            (2, false) => PngImage::Rgb8(fill(size, |i| { let p = rgb(i); Rgb::new(p.r as u8, p.g as u8, p.b as u8) })),
+            (2, true) => PngImage::Rgb16(fill(size, rgb)),
+            (_, false) => PngImage::Rgba8(fill(size, |i| { 
+                let p = rgb(i); 
+                Rgba::new(p.r as u8, p.g as u8, p.b as u8, alpha(i) as u8) 
+            })),
+            (_, true) => PngImage::Rgba16(fill(size, |i| { let p = rgb(i); Rgba::new(p.r, p.g, p.b, alpha(i)) })),
+        }
+    }
+}
+
+/// Reverses PNG line filters, returns pixel bytes without filter types
+fn unfilter(raw: &[u8], header: &Header) -> Result<Vec<u8>, PngError> {
+    let bpp = header.pixel_bytes();
+    let too_large = PngError::InvalidData("image too large");
+    let line_bytes = header.line_bytes().ok_or(too_large)?;
+
+    if Some(raw.len()) != header.raw_length() {
+        return Err(PngError::InvalidData("invalid image data length"));
+    }
+
+    let mut pixels = vec![0u8; line_bytes*header.size.y];
+
+    for y in 0..header.size.y {
+        let filter = raw[y*(line_bytes + 1)];
+        let src = &raw[y*(line_bytes + 1) + 1..(y + 1)*(line_bytes + 1)];
+        let (done, rest) = pixels.split_at_mut(y*line_bytes);
+        let previous = if y > 0 { &done[(y - 1)*line_bytes..] } else { &[][..] };
+        let line = &mut rest[..line_bytes];
+
+        for i in 0..line_bytes {
+            let a = if i >= bpp { line[i - bpp] } else { 0 };
+            let b = previous.get(i).copied().unwrap_or(0);
+            let c = if i >= bpp { previous.get(i - bpp).copied().unwrap_or(0) } else { 0 };
+
+            let predictor = match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16)/2) as u8,
+                4 => paeth(a, b, c),
+                _ => return Err(PngError::InvalidData("invalid filter type")),
+            };
+
+            line[i] = src[i].wrapping_add(predictor);
+        }
+    }
+
+    Ok(pixels)
+}
+
+/// Selects line filter with the minimal sum of absolute filtered values
+fn best_filter(line: &[u8], previous: &[u8], bpp: usize) -> (u8, Vec<u8>) {
+    (0..5u8)
+        .map(|filter| {
+            let filtered: Vec<u8> = (0..line.len())
+                .map(|i| {
+                    let a = if i >= bpp { line[i - bpp] } else { 0 };
+                    let c = if i >= bpp { previous[i - bpp] } else { 0 };
+                    let b = previous[i];
+
+                    let predictor = match filter {
+                        0 => 0,
+                        1 => a,
+                        2 => b,
+                        3 => ((a as u16 + b as u16)/2) as u8,
+                        _ => paeth(a, b, c),
+                    };
+
+                    line[i].wrapping_sub(predictor)
+                })
+                .collect();
+
+            (filter, filtered)
+        })
+        .min_by_key(|(_, filtered)| filtered.iter().map(|&x| (x as i8).unsigned_abs() as u64).sum::<u64>())
+        .unwrap()
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = ((p - a as i16).abs(), (p - b as i16).abs(), (p - c as i16).abs());
+
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+fn write_chunk(output: &mut Vec<u8>, kind: &[u8; 4], content: &[u8]) {
+    output.extend(&(content.len() as u32).to_be_bytes());
+    let start = output.len();
+    output.extend(kind);
+    output.extend(content);
+    let crc = crc32(&output[start..]);
+    output.extend(&crc.to_be_bytes());
+}
+
+/// CRC-32 checksum used by PNG chunks
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| CRC_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8))
+}
+
+/// CRC-32 checksums of all byte values
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = if value & 1 != 0 { 0xedb8_8320 ^ (value >> 1) } else { value >> 1 };
+            bit += 1;
+        }
+
+        table[index] = value;
+        index += 1;
+    }
+
+    table
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_example_raster() {
+        let data = std::fs::read("examples/raster.png").unwrap();
+
+        match decode(&data).unwrap() {
+            PngImage::Rgba8(image) => {
+                assert_eq!(image.size(), ImgSize::new(387, 278));
+                let encoded = encode(&image).unwrap();
+                assert_eq!(decode(&encoded), Ok(PngImage::Rgba8(image)));
+            }
+            _ => panic!("Unexpected pixel type"),
+        }
+    }
+
+    #[test]
+    fn all_pixel_types_round_trip() {
+        let size = ImgSize::new(5, 4);
+        let gray = ImgBuf::<u8>::from_vec(size, (0..20).map(|x| x*13).collect());
+        let rgb = map_new(&gray, |x: u8| Rgb::new(x as u16*257, 1000, 65535 - x as u16));
+        let rgba = map_new(&gray, |x: u8| Rgba::new(x, 255 - x, x/2, 128));
+        assert_eq!(decode(&encode(&gray).unwrap()), Ok(PngImage::Gray8(gray.clone())));
+        assert_eq!(decode(&encode(&rgb).unwrap()), Ok(PngImage::Rgb16(rgb)));
+        assert_eq!(decode(&encode(&rgba).unwrap()), Ok(PngImage::Rgba8(rgba)));
+        assert_eq!(decode(&encode(&gray).unwrap()).unwrap().into_gray8(), gray);
+    }
+
+    #[test]
+    fn corrupted_data_are_rejected() {
+        let mut data = encode(&ImgBuf::<u8>::new(ImgSize::new(4, 4))).unwrap();
+        assert_eq!(decode(&data[1..]), Err(PngError::InvalidSignature));
+        assert!(decode(&data[..data.len() - 5]).is_err());
+        data[20] ^= 1;
+        assert_eq!(decode(&data), Err(PngError::InvalidData("chunk checksum mismatch")));
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    }
+
+    /// PNG data with given header content and single IDAT chunk
+    fn png_data(header: &[u8], compressed: &[u8]) -> Vec<u8> {
+        let mut data = SIGNATURE.to_vec();
+        write_chunk(&mut data, b"IHDR", header);
+        write_chunk(&mut data, b"IDAT", compressed);
+        write_chunk(&mut data, b"IEND", &[]);
+        data
+    }
+
+    #[test]
+    fn oversized_images_are_rejected() {
+        let empty = zlib_compress(&[]);
+        let huge = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 16, 6, 0, 0, 0];
+        assert!(decode(&png_data(&huge, &empty)).is_err());
+        let large = [0, 0, 0x10, 0, 0, 0, 0x10, 0, 8, 0, 0, 0, 0];
+        assert!(decode(&png_data(&large, &empty)).is_err());
+    }
+
+    #[test]
+    fn chunks_out_of_order_are_rejected() {
+        let header = [0, 0, 0, 4, 0, 0, 0, 1, 8, 0, 0, 0, 0];
+        let compressed = zlib_compress(&[0; 5]);
+        let mut data = SIGNATURE.to_vec();
+        write_chunk(&mut data, b"IDAT", &compressed);
+        write_chunk(&mut data, b"IHDR", &header);
+        write_chunk(&mut data, b"IEND", &[]);
+        assert_eq!(decode(&data), Err(PngError::InvalidData("header is not the first chunk")));
+
+        let mut data = SIGNATURE.to_vec();
+        write_chunk(&mut data, b"IHDR", &header);
+        write_chunk(&mut data, b"IHDR", &header);
+        write_chunk(&mut data, b"IDAT", &compressed);
+        write_chunk(&mut data, b"IEND", &[]);
+        assert_eq!(decode(&data), Err(PngError::InvalidData("repeated header")));
+    }
+
+    /// Image of arbitrary size without any lines
+    struct EmptyImage(ImgSize);
+
+    impl Img<u8> for EmptyImage {
+        fn size(&self) -> ImgSize {
+            self.0
+        }
+
+        fn line_ref(&self, _line: usize) -> &[u8] {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn oversized_images_are_not_encoded() {
+        assert_eq!(encode(&EmptyImage(ImgSize::new(1 << 31, 0))), Err(PngError::TooLarge));
+        assert_eq!(encode(&EmptyImage(ImgSize::new(0, usize::MAX))), Err(PngError::TooLarge));
+    }
+
+    #[test]
+    fn decompressed_data_are_limited() {
+        let header = [0, 0, 0, 4, 0, 0, 0, 1, 8, 0, 0, 0, 0];
+        assert!(decode(&png_data(&header, &zlib_compress(&[0; 5]))).is_ok());
+        assert_eq!(
+            decode(&png_data(&header, &zlib_compress(&[0; 2000]))),
+            Err(PngError::InvalidData("Decompressed data exceed expected length"))
+        );
+    }
+}
@@ -0,0 +1,475 @@
+//! Internal zlib (RFC 1950) and DEFLATE (RFC 1951) implementation
+
+/// Base lengths of length symbols `257..=285` and numbers of extra bits
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0
+];
+
+/// Base distances of distance symbols and numbers of extra bits
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 
+    1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13
+];
+
+/// Order of code length code lengths in dynamic block header
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+const WINDOW: usize = 32768;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 64;
+
+/// Upper bound of DEFLATE compression ratio, single bit codes of 
+/// 258 byte matches expand every compressed byte to at most 1032 bytes
+pub(crate) const MAX_RATIO: usize = 1032;
+
+/// Error of decompression caused by corrupted data
+pub(crate) type InflateError = &'static str;
+
+/// Adler-32 checksum of `data`
+pub(crate) fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+
+        a %= 65521;
+        b %= 65521;
+    }
+
+    (b << 16) | a
+}
+
+/// Decompresses zlib stream, fails when decompressed data 
+/// exceed `limit` bytes
+pub(crate) fn zlib_decompress(data: &[u8], limit: usize) -> Result<Vec<u8>, InflateError> {
+    if data.len() < 6 {
+        return Err("Compressed stream is too short");
+    }
+
+    let (cmf, flg) = (data[0], data[1]);
+
+    if cmf & 0x0f != 8 || (cmf as u16*256 + flg as u16) % 31 != 0 || flg & 0x20 != 0 {
+        return Err("Invalid zlib header");
+    }
+
+    let (output, used) = inflate(&data[2..], limit)?;
+    let trailer = data.get(2 + used..2 + used + 4).ok_or("Missing zlib checksum")?;
+
+    if u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]) != adler32(&output) {
+        return Err("Invalid zlib checksum");
+    }
+
+    Ok(output)
+}
+
+/// Compresses data into zlib stream
+pub(crate) fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut output = vec![0x78, 0x01];
+    output.extend(deflate(data));
+    output.extend(&adler32(data).to_be_bytes());
+    output
+}
+
+/// Reader of bits starting from the least significant bit of each byte
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    bits: u32,
+    count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn bits(&mut self, count: u32) -> Result<u32, InflateError> {
+        while self.count < count {
+            let byte = *self.data.get(self.position).ok_or("Unexpected end of compressed data")?;
+            self.bits |= (byte as u32) << self.count;
+            self.position += 1;
+            self.count += 8;
+        }
+
+        let value = self.bits & ((1u64 << count) - 1) as u32;
+        self.bits >>= count;
+        self.count -= count;
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        self.bits = 0;
+        self.count = 0;
+    }
+}
+
+/// Canonical Huffman code given by number of codes of each length
+/// and symbols ordered by their codes
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, InflateError> {
+        let mut counts = [0u16; 16];
+        lengths.iter().for_each(|&length| counts[length as usize] += 1);
+        counts[0] = 0;
+
+        // Over-subscribed codes cannot be decoded
+        let mut left = 1i32;
+
+        for length in 1..16 {
+            left = 2*left - counts[length] as i32;
+
+            if left < 0 {
+                return Err("Invalid Huffman code");
+            }
+        }
+
+        let mut offsets = [0u16; 16];
+
+        for length in 1..15 {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+
+        let mut symbols = vec![0u16; lengths.len()];
+
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, InflateError> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+
+        for length in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[length] as i32;
+
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err("Invalid Huffman code")
+    }
+}
+
+/// Decompresses raw DEFLATE stream of at most `limit` bytes, returns
+/// decompressed data and number of consumed bytes
+fn inflate(data: &[u8], limit: usize) -> Result<(Vec<u8>, usize), InflateError> {
+    let mut reader = BitReader { data, position: 0, bits: 0, count: 0 };
+    let mut output = Vec::new();
+
+    loop {
+        let last = reader.bits(1)? == 1;
+
+        match reader.bits(2)? {
+            0 => {
+                reader.align_to_byte();
+                let header = data.get(reader.position..reader.position + 4).ok_or("Unexpected end of compressed data")?;
+                let length = u16::from_le_bytes([header[0], header[1]]);
+
+                if length != !u16::from_le_bytes([header[2], header[3]]) {
+                    return Err("Invalid stored block length");
+                }
+
+                let start = reader.position + 4;
+                let block = data.get(start..start + length as usize).ok_or("Unexpected end of compressed data")?;
+                check_limit(&output, block.len(), limit)?;
+                output.extend_from_slice(block);
+                reader.position = start + length as usize;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].iter_mut().for_each(|x| *x = 8);
+                lengths[144..256].iter_mut().for_each(|x| *x = 9);
+                lengths[256..280].iter_mut().for_each(|x| *x = 7);
+                lengths[280..].iter_mut().for_each(|x| *x = 8);
+                let literals = Huffman::new(&lengths)?;
+                let distances = Huffman::new(&[5; 30])?;
+                inflate_block(&mut reader, &mut output, &literals, &distances, limit)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut reader)?;
+                inflate_block(&mut reader, &mut output, &literals, &distances, limit)?;
+            }
+            _ => return Err("Invalid block type"),
+        }
+
+        if last {
+            return Ok((output, reader.position));
+        }
+    }
+}
+
+/// Reads Huffman codes from dynamic block header
+fn dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman), InflateError> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_count = reader.bits(4)? as usize + 4;
+
+    if literal_count > 286 || distance_count > 30 {
+        return Err("Invalid dynamic block header");
+    }
+
+    let mut code_lengths = [0u8; 19];
+
+    for &index in &CODE_LENGTH_ORDER[..code_count] {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+
+    let code = Huffman::new(&code_lengths)?;
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+
+    while lengths.len() < literal_count + distance_count {
+        let symbol = code.decode(reader)?;
+
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last().ok_or("Repeated length without previous length")?, 3 + reader.bits(2)?),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+
+        lengths.extend(std::iter::repeat(value).take(repeat as usize));
+    }
+
+    if lengths.len() != literal_count + distance_count || lengths[256] == 0 {
+        return Err("Invalid dynamic block code lengths");
+    }
+
+    Ok((Huffman::new(&lengths[..literal_count])?, Huffman::new(&lengths[literal_count..])?))
+}
+
+/// Decodes symbols of compressed block until end of block symbol
+fn inflate_block(
+    reader: &mut BitReader, 
+    output: &mut Vec<u8>, 
+    literals: &Huffman, 
+    distances: &Huffman,
+    limit: usize
+) -> Result<(), InflateError> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+
+        if symbol < 256 {
+            check_limit(output, 1, limit)?;
+            output.push(symbol as u8);
+        } else if symbol == 256 {
+            return Ok(());
+        } else {
+            let index = symbol - 257;
+
+            if index >= LENGTH_BASE.len() {
+                return Err("Invalid length symbol");
+            }
+
+            let length = LENGTH_BASE[index] as usize + reader.bits(LENGTH_EXTRA[index] as u32)? as usize;
+            let index = distances.decode(reader)? as usize;
+
+            if index >= DISTANCE_BASE.len() {
+                return Err("Invalid distance symbol");
+            }
+
+            let distance = DISTANCE_BASE[index] as usize + reader.bits(DISTANCE_EXTRA[index] as u32)? as usize;
+
+            if distance > output.len() {
+                return Err("Distance points before start of data");
+            }
+
+            check_limit(output, length, limit)?;
+            let start = output.len() - distance;
+
+            for offset in 0..length {
+                output.push(output[start + offset]);
+            }
+        }
+    }
+}
+
+/// Checks that `length` more bytes can be appended to `output`
+fn check_limit(output: &[u8], length: usize, limit: usize) -> Result<(), InflateError> {
+    if length > limit - output.len() {
+        Err("Decompressed data exceed expected length")
+    } else {
+        Ok(())
+    }
+}
+
+/// Writer of bits starting from the least significant bit of each byte
+struct BitWriter {
+    output: Vec<u8>,
+    bits: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: u32) {
+        self.bits |= (value as u64) << self.count;
+        self.count += count;
+
+        while self.count >= 8 {
+            self.output.push(self.bits as u8);
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Writes Huffman code, codes are stored from the most significant bit
+    fn write_code(&mut self, code: u32, length: u32) {
+        self.write(code.reverse_bits() >> (32 - length), length);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.output.push(self.bits as u8);
+        }
+
+        self.output
+    }
+}
+
+/// Writes literal or length symbol by fixed Huffman code
+fn write_fixed_literal(writer: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => writer.write_code(0x30 + symbol, 8),
+        144..=255 => writer.write_code(0x190 + symbol - 144, 9),
+        256..=279 => writer.write_code(symbol - 256, 7),
+        _ => writer.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+/// Compresses data into a single DEFLATE block with fixed Huffman codes,
+/// repeated strings are found by hash chains
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter { output: Vec::new(), bits: 0, count: 0 };
+    writer.write(1, 1);
+    writer.write(1, 2);
+
+    let hash = |position: usize| {
+        ((data[position] as usize) << 10 ^ (data[position + 1] as usize) << 5 ^ data[position + 2] as usize) & (WINDOW - 1)
+    };
+
+    let mut head = vec![usize::MAX; WINDOW];
+    let mut previous = vec![usize::MAX; WINDOW];
+    let insert = |position: usize, head: &mut Vec<usize>, previous: &mut Vec<usize>| {
+        if position + 2 < data.len() {
+            let key = hash(position);
+            previous[position % WINDOW] = head[key];
+            head[key] = position;
+        }
+    };
+
+    let mut position = 0;
+
+    while position < data.len() {
+        let (mut best_length, mut best_distance) = (0, 0);
+
+        if position + 2 < data.len() {
+            let mut candidate = head[hash(position)];
+            let max_length = std::cmp::min(MAX_MATCH, data.len() - position);
+
+            for _ in 0..MAX_CHAIN {
+                if candidate == usize::MAX || position - candidate > WINDOW - 1 {
+                    break;
+                }
+
+                let length = (0..max_length)
+                    .take_while(|&i| data[candidate + i] == data[position + i])
+                    .count();
+
+                if length > best_length {
+                    best_length = length;
+                    best_distance = position - candidate;
+                }
+
+                let next = previous[candidate % WINDOW];
+
+                if next == usize::MAX || next >= candidate || best_length == max_length {
+                    break;
+                }
+
+                candidate = next;
+            }
+        }
+
+        if best_length >= 3 {
+            let index = LENGTH_BASE.iter().rposition(|&base| base as usize <= best_length).unwrap();
+            write_fixed_literal(&mut writer, 257 + index as u32);
+            writer.write((best_length - LENGTH_BASE[index] as usize) as u32, LENGTH_EXTRA[index] as u32);
+
+            let index = DISTANCE_BASE.iter().rposition(|&base| base as usize <= best_distance).unwrap();
+            writer.write_code(index as u32, 5);
+            writer.write((best_distance - DISTANCE_BASE[index] as usize) as u32, DISTANCE_EXTRA[index] as u32);
+
+            for offset in 0..best_length {
+                insert(position + offset, &mut head, &mut previous);
+            }
+
+            position += best_length;
+        } else {
+            write_fixed_literal(&mut writer, data[position] as u32);
+            insert(position, &mut head, &mut previous);
+            position += 1;
+        }
+    }
+
+    write_fixed_literal(&mut writer, 256);
+    writer.finish()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Random;
+
+    #[test]
+    fn compressed_data_round_trip() {
+        let mut random = Random::new(7);
+        let noise: Vec<u8> = (0..5000).map(|_| random.index(256) as u8).collect();
+        let repeated: Vec<u8> = (0..100000).map(|x| (x % 251 + x/1000) as u8).collect();
+
+        for data in &[Vec::new(), vec![42], noise, repeated.clone()] {
+            assert_eq!(&zlib_decompress(&zlib_compress(data), data.len()).unwrap(), data);
+        }
+
+        assert!(zlib_compress(&repeated).len() < repeated.len()/10);
+    }
+
+    #[test]
+    fn decompresses_stored_and_dynamic_blocks() {
+        // Streams produced by reference zlib implementation
+        let stored = [0x78, 0x01, 0x01, 0x05, 0x00, 0xfa, 0xff, b'h', b'e', b'l', b'l', b'o', 0x06, 0x2c, 0x02, 0x15];
+        assert_eq!(zlib_decompress(&stored, 5).unwrap(), b"hello");
+        assert!(zlib_decompress(&stored, 4).is_err());
+
+        let dynamic = [
+            0x78, 0xda, 0xed, 0xca, 0xb9, 0x01, 0x00, 0x20, 0x08, 0x03, 0xc0, 0x59, 0x79, 0x0d, 
+            0x12, 0xf6, 0x6f, 0x9d, 0xc1, 0x9e, 0xab, 0x4f, 0x60, 0x73, 0x01, 0x14, 0x35, 0x19, 
+            0x84, 0xcb, 0x90, 0x62, 0xd9, 0x56, 0x4e, 0xa4, 0x9b, 0xc7, 0x69, 0x3d, 0xb2, 0x6d, 
+            0xdb, 0x4f, 0x7b, 0x80, 0x1d, 0xf1, 0x75
+        ];
+
+        let text: Vec<u8> = (0..600u32).map(|i| ((i*i*7 + i/3) % 13 + 97) as u8).collect();
+        assert_eq!(zlib_decompress(&dynamic, text.len()).unwrap(), text);
+    }
+}
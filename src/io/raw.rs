@@ -0,0 +1,165 @@
+//! Raw sensor data as produced by camera interfaces
+//! 
+//! Available functions:
+//!  * [unpack_packed](fn.unpack_packed.html) - expansion of MIPI CSI-2
+//!    packed 10, 12 and 14-bit pixels
+
+use crate::{ImgBuf, ImgMut, ImgBufLayout, LayoutError, Error};
+
+/// Expands packed raw sensor `bytes` with `bit_depth` bits per pixel into
+/// 16-bit image, pixel values keep their original range `0..2^bit_depth`
+/// 
+/// Pixels are packed by MIPI CSI-2 convention: groups of pixels start
+/// with the most significant 8 bits of each pixel followed by bytes
+/// with the remaining least significant bits, first pixel in the lowest
+/// bits. Groups are 4 pixels in 5 bytes for 10-bit data, 2 pixels in
+/// 3 bytes for 12-bit and 4 pixels in 7 bytes for 14-bit data.
+/// 
+/// # Arguments
+/// 
+/// * `bytes` - packed pixel data
+/// * `bit_depth` - number of bits per pixel, either 10, 12 or 14
+/// * `layout` - image size in pixels and line stride in bytes of packed
+///   data, stride must hold at least all pixel groups of the line
+/// 
+/// Returns `Error::UnsupportedBitDepth` for other bit depths, layout
+/// error when stride or data length are too small and `Error::Overflow`
+/// when the size of packed data does not fit into `usize`
+/// 
+/// # Example
+/// ```
+/// use nanocv::{Img, ImgSize, ImgBufLayout, io::raw::unpack_packed};
+/// // two 12-bit pixels 0xABC and 0x123
+/// let layout = ImgBufLayout { size: ImgSize::new(2, 1), stride: 3 };
+/// let image = unpack_packed(&[0xAB, 0x12, 0x3C], 12, layout).unwrap();
+/// assert_eq!(image.line_ref(0), &[0xABC, 0x123]);
+/// ```
+pub fn unpack_packed(bytes: &[u8], bit_depth: u32, layout: ImgBufLayout) -> Result<ImgBuf<u16>, Error> {
+    let (group_pixels, group_bytes) = match bit_depth {
+        10 => (4, 5),
+        12 => (2, 3),
+        14 => (4, 7),
+        _ => return Err(Error::UnsupportedBitDepth(bit_depth)),
+    };
+
+    let size = layout.size;
+    let invalid_length = LayoutError::InvalidDataLength { layout, length: bytes.len() };
+
+    let line_bytes = size.x.div_ceil(group_pixels)
+        .checked_mul(group_bytes)
+        .ok_or(Error::Overflow)?;
+
+    if layout.stride < line_bytes {
+        return Err(LayoutError::InvalidStride { layout }.into());
+    }
+
+    let required = match size.y {
+        0 => Some(0),
+        height => (height - 1)
+            .checked_mul(layout.stride)
+            .and_then(|length| length.checked_add(line_bytes)),
+    };
+
+    if bytes.len() < required.ok_or(Error::Overflow)? {
+        return Err(invalid_length.into());
+    }
+
+    if size.x == 0 {
+        return Ok(ImgBuf::new(size));
+    }
+
+    let mut image = ImgBuf::<u16>::new(size);
+    let low_bits = bit_depth - 8;
+    let mask = (1u16 << low_bits) - 1;
+    let mut group = [0u16; 4];
+
+    for y in 0..size.y {
+        let src = &bytes[y*layout.stride..y*layout.stride + line_bytes];
+        let dst = image.line_mut(y);
+
+        for (index, chunk) in src.chunks_exact(group_bytes).enumerate() {
+            // Least significant bits of the group pixels, first pixel lowest
+            let mut low = chunk[group_pixels..]
+                .iter()
+                .rev()
+                .fold(0u64, |bits, &byte| (bits << 8) | byte as u64);
+
+            for pixel in 0..group_pixels {
+                group[pixel] = ((chunk[pixel] as u16) << low_bits) | (low as u16 & mask);
+                low >>= low_bits;
+            }
+
+            let start = index*group_pixels;
+            let count = std::cmp::min(group_pixels, size.x - start);
+            dst[start..start + count].copy_from_slice(&group[..count]);
+        }
+    }
+
+    Ok(image)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Img, ImgSize};
+
+    /// Reference packing of pixels by MIPI CSI-2 convention
+    fn pack(pixels: &[u16], bit_depth: u32) -> Vec<u8> {
+        let group = if bit_depth == 12 { 2 } else { 4 };
+        let low_bits = bit_depth - 8;
+        let mut bytes = Vec::new();
+
+        for chunk in pixels.chunks(group) {
+            let mut padded = chunk.to_vec();
+            padded.resize(group, 0);
+            bytes.extend(padded.iter().map(|&x| (x >> low_bits) as u8));
+            let low = padded.iter().rev().fold(0u64, |bits, &x| (bits << low_bits) | (x as u64 & ((1 << low_bits) - 1)));
+            bytes.extend((0..(group as u32*low_bits/8)).map(|i| (low >> (8*i)) as u8));
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn all_depths_round_trip() {
+        for &depth in &[10u32, 12, 14] {
+            let pixels: Vec<u16> = (0..7u32).map(|x| (x.wrapping_mul(2654435761) >> 16) as u16 >> (16 - depth)).collect();
+            let line = pack(&pixels, depth);
+            let stride = line.len() + 3;
+            let mut bytes = line.clone();
+            bytes.resize(stride, 0xFF);
+            bytes.extend(&line);
+
+            let layout = ImgBufLayout { size: ImgSize::new(7, 2), stride };
+            let image = unpack_packed(&bytes, depth, layout).unwrap();
+            assert_eq!(image.line_ref(0), &pixels[..]);
+            assert_eq!(image.line_ref(1), &pixels[..]);
+        }
+    }
+
+    #[test]
+    fn ten_bit_layout_matches_specification() {
+        let layout = ImgBufLayout { size: ImgSize::new(4, 1), stride: 5 };
+        let image = unpack_packed(&[0xFF, 0x00, 0x80, 0x01, 0b00_01_10_11], 10, layout).unwrap();
+        assert_eq!(image.line_ref(0), &[0x3FF, 0x002, 0x201, 0x004]);
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let layout = ImgBufLayout { size: ImgSize::new(4, 2), stride: 5 };
+        assert_eq!(unpack_packed(&[0; 10], 16, layout), Err(Error::UnsupportedBitDepth(16)));
+        assert!(unpack_packed(&[0; 9], 10, layout).is_err());
+        assert!(unpack_packed(&[0; 10], 14, layout).is_err());
+        assert!(unpack_packed(&[0; 10], 10, layout).is_ok());
+    }
+
+    #[test]
+    fn overflowing_layout_is_rejected() {
+        let layout = ImgBufLayout { size: ImgSize::new(4, usize::MAX), stride: 5 };
+        assert_eq!(unpack_packed(&[0; 10], 10, layout), Err(Error::Overflow));
+        let layout = ImgBufLayout { size: ImgSize::new(usize::MAX, 1), stride: 5 };
+        assert_eq!(unpack_packed(&[0; 10], 10, layout), Err(Error::Overflow));
+    }
+}
@@ -1,11 +1,48 @@
-mod geometry;
+// Index based loops are preferred over iterators across the crate
+// as they help the compiler to autovectorize the code
+#![allow(clippy::needless_range_loop)]
+
 mod image;
+mod error;
+mod random;
 
 use geometry::{ImageMapping};
 
 // Essential types for nanocv are exported to root module of the crate
-pub use self::image::{Img, ImgMut, ImgSize, ImgBuf, ImgBufLayout};
-pub use geometry::{Range, Range2d, ImgRange, Vec2d};
+pub use self::image::{
+    Img, ImgMut, ImgLinesMut, ImgSize, ImgBuf, ImgBufLayout, LayoutError,
+    EnumeratePixels, EnumeratePixelsMut, Lines, LinesMut, RowBand, ImgView, Pixel, ImgFormat
+};
+#[cfg(all(feature = "mmap", unix))]
+pub use self::image::{ImgMapped, MappedPixel};
+pub use geometry::{Range, Range2d, Range2dIter, ImgRange, Vec2d, AsPrimitive};
+pub use error::Error;
 
 // Specific algorithms and methods are defined in respective modules
-pub mod filter;
\ No newline at end of file
+pub mod geometry;
+pub mod filter;
+pub mod stack;
+pub mod calibrate;
+pub mod segmentation;
+pub mod feature;
+pub mod register;
+pub mod color;
+pub mod synth;
+pub mod convert;
+pub mod pyramid;
+pub mod fft;
+pub mod transform;
+pub mod flow;
+pub mod stats;
+pub mod stitch;
+pub mod stereo;
+pub mod morphology;
+pub mod track;
+pub mod video;
+pub mod detect;
+pub mod hash;
+pub mod warp;
+pub mod pipeline;
+pub mod io;
+#[cfg(feature = "gpu")]
+pub mod gpu;
\ No newline at end of file
@@ -0,0 +1,95 @@
+use crate::{Img, ImgMut, ImgBuf};
+
+/// Fills holes of objects in a binary `mask`
+/// 
+/// Background (`false`) pixels 4-connected to the image border are flooded
+/// from the border, all remaining background pixels are enclosed by 
+/// objects and are set to `true` in the resulting mask. Objects are thus
+/// considered 8-connected, a hole touching an object boundary only by a 
+/// corner is still filled.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, morphology::fill_holes};
+/// let mask = ImgBuf::<bool>::from_vec(ImgSize::new(5, 4), vec![
+///     false, true, true, true, false,
+///     false, true, false, true, false,
+///     false, true, true, true, false,
+///     false, false, false, false, false,
+/// ]);
+/// let filled = fill_holes(&mask);
+/// assert_eq!(filled.line_ref(1), &[false, true, true, true, false]);
+/// assert_eq!(filled.line_ref(3), &[false; 5]);
+/// ```
+pub fn fill_holes(mask: &dyn Img<bool>) -> ImgBuf<bool> {
+    let size = mask.size();
+    let mut outside = ImgBuf::<bool>::new(size);
+    let mut stack = Vec::new();
+
+    if size.x == 0 || size.y == 0 {
+        return outside;
+    }
+
+    let seed = |x: usize, y: usize, outside: &mut ImgBuf<bool>, stack: &mut Vec<(usize, usize)>| {
+        if !mask.line_ref(y)[x] && !outside[(x, y)] {
+            outside[(x, y)] = true;
+            stack.push((x, y));
+        }
+    };
+
+    for x in 0..size.x {
+        seed(x, 0, &mut outside, &mut stack);
+        seed(x, size.y.saturating_sub(1), &mut outside, &mut stack);
+    }
+
+    for y in 0..size.y {
+        seed(0, y, &mut outside, &mut stack);
+        seed(size.x.saturating_sub(1), y, &mut outside, &mut stack);
+    }
+
+    while let Some((x, y)) = stack.pop() {
+        if x > 0 { seed(x - 1, y, &mut outside, &mut stack); }
+        if y > 0 { seed(x, y - 1, &mut outside, &mut stack); }
+        if x + 1 < size.x { seed(x + 1, y, &mut outside, &mut stack); }
+        if y + 1 < size.y { seed(x, y + 1, &mut outside, &mut stack); }
+    }
+
+    for y in 0..size.y {
+        for value in outside.line_mut(y) {
+            *value = !*value;
+        }
+    }
+
+    outside
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn hole_enclosed_by_diagonal_pixels_is_filled() {
+        let mask = ImgBuf::<bool>::from_vec(ImgSize::new(3, 3), vec![
+            false, true, false,
+            true, false, true,
+            false, true, false,
+        ]);
+        let filled = fill_holes(&mask);
+        assert_eq!(filled.line_ref(1), &[true, true, true]);
+        assert_eq!(filled.line_ref(0), &[false, true, false]);
+    }
+
+    #[test]
+    fn empty_mask_is_unchanged() {
+        for &(width, height) in &[(0, 0), (0, 3), (3, 0)] {
+            let mask = ImgBuf::<bool>::new(ImgSize::new(width, height));
+            assert_eq!(fill_holes(&mask).size(), ImgSize::new(width, height));
+        }
+
+        let mask = ImgBuf::<bool>::new(ImgSize::new(4, 3));
+        assert_eq!(fill_holes(&mask), mask);
+    }
+}
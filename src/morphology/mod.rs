@@ -0,0 +1,15 @@
+//! Morphological processing of binary masks and grayscale images
+//! 
+//! Available functions:
+//!  * [fill_holes](fn.fill_holes.html) - filling of background regions 
+//!    enclosed by objects
+//!  * [reconstruct_by_dilation](fn.reconstruct_by_dilation.html) - geodesic
+//!    reconstruction of a marker under a mask
+//!  * [reconstruct_by_erosion](fn.reconstruct_by_erosion.html) - dual
+//!    reconstruction of a marker above a mask
+
+mod holes;
+mod reconstruct;
+
+pub use holes::fill_holes;
+pub use reconstruct::{reconstruct_by_dilation, reconstruct_by_erosion};
@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+use crate::{Img, ImgBuf};
+
+/// Morphological reconstruction by dilation of `marker` under `mask`
+/// 
+/// Marker is repeatedly dilated (with 8-connected neighborhood) and 
+/// limited by the mask until stability, so the result keeps regional 
+/// structures of mask that are "seeded" by the marker. Marker values above
+/// the mask are limited to the mask first. Uses the hybrid algorithm 
+/// by L. Vincent (two raster scans followed by FIFO queue propagation),
+/// which processes most pixels only a few times.
+/// 
+/// Works for any ordered pixel type, binary masks (`bool`) reconstruct 
+/// whole mask objects touched by the marker.
+/// 
+/// # Panics
+/// 
+/// Panics when marker and mask sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, morphology::reconstruct_by_dilation};
+/// // two peaks, only the first one is marked
+/// let mask = ImgBuf::<u8>::from_vec(ImgSize::new(7, 1), vec![1, 5, 2, 0, 3, 8, 3]);
+/// let marker = ImgBuf::<u8>::from_vec(ImgSize::new(7, 1), vec![0, 4, 0, 0, 0, 0, 0]);
+/// let result = reconstruct_by_dilation(&marker, &mask);
+/// assert_eq!(result.line_ref(0), &[1, 4, 2, 0, 0, 0, 0]);
+/// ```
+pub fn reconstruct_by_dilation<T>(marker: &dyn Img<T>, mask: &dyn Img<T>) -> ImgBuf<T>
+where T: Copy + Default + PartialOrd {
+    reconstruct(marker, mask, |a, b| a < b)
+}
+
+/// Morphological reconstruction by erosion of `marker` above `mask`
+/// 
+/// Dual of [reconstruct_by_dilation](fn.reconstruct_by_dilation.html),
+/// marker is repeatedly eroded and limited from below by the mask. 
+/// Marker values below the mask are raised to the mask first. Typical use
+/// is filling of regional minima (holes) with marker equal to the mask 
+/// on image borders and maximal value elsewhere.
+/// 
+/// # Panics
+/// 
+/// Panics when marker and mask sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, morphology::reconstruct_by_erosion};
+/// // a pit surrounded by higher values
+/// let mask = ImgBuf::<u8>::from_vec(ImgSize::new(5, 1), vec![2, 6, 1, 6, 3]);
+/// let marker = ImgBuf::<u8>::from_vec(ImgSize::new(5, 1), vec![2, 9, 9, 9, 3]);
+/// let result = reconstruct_by_erosion(&marker, &mask);
+/// assert_eq!(result.line_ref(0), &[2, 6, 6, 6, 3]);
+/// ```
+pub fn reconstruct_by_erosion<T>(marker: &dyn Img<T>, mask: &dyn Img<T>) -> ImgBuf<T>
+where T: Copy + Default + PartialOrd {
+    reconstruct(marker, mask, |a, b| a > b)
+}
+
+/// Reconstruction where `below(a, b)` orders values in the direction
+/// of propagation (`a < b` for dilation, `a > b` for erosion)
+fn reconstruct<T, F>(marker: &dyn Img<T>, mask: &dyn Img<T>, below: F) -> ImgBuf<T>
+where T: Copy + Default + PartialOrd, F: Fn(T, T) -> bool {
+    let size = mask.size();
+    assert_eq!(marker.size(), size, "Marker and mask sizes differ");
+
+    let sup = |a: T, b: T| if below(a, b) { b } else { a };
+    let inf = |a: T, b: T| if below(a, b) { a } else { b };
+    let mut result = ImgBuf::<T>::new(size);
+    let mut limit = ImgBuf::<T>::new(size);
+
+    for y in 0..size.y {
+        let (src, bound) = (marker.line_ref(y), mask.line_ref(y));
+
+        for x in 0..size.x {
+            limit[(x, y)] = bound[x];
+            result[(x, y)] = inf(src[x], bound[x]);
+        }
+    }
+
+    let (width, height) = (size.x as isize, size.y as isize);
+    let inside = |x: isize, y: isize| x >= 0 && y >= 0 && x < width && y < height;
+
+    // Neighbors preceding the pixel in raster order, and following ones
+    const PREVIOUS: [(isize, isize); 4] = [(-1, -1), (0, -1), (1, -1), (-1, 0)];
+    const NEXT: [(isize, isize); 4] = [(1, 1), (0, 1), (-1, 1), (1, 0)];
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut value = result[(x as usize, y as usize)];
+
+            for &(dx, dy) in PREVIOUS.iter() {
+                if inside(x + dx, y + dy) {
+                    value = sup(value, result[((x + dx) as usize, (y + dy) as usize)]);
+                }
+            }
+
+            result[(x as usize, y as usize)] = inf(value, limit[(x as usize, y as usize)]);
+        }
+    }
+
+    let mut queue = VecDeque::new();
+
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            let mut value = result[(x as usize, y as usize)];
+
+            for &(dx, dy) in NEXT.iter() {
+                if inside(x + dx, y + dy) {
+                    value = sup(value, result[((x + dx) as usize, (y + dy) as usize)]);
+                }
+            }
+
+            let value = inf(value, limit[(x as usize, y as usize)]);
+            result[(x as usize, y as usize)] = value;
+
+            // Pixel can still propagate to a following neighbor
+            let propagates = NEXT.iter().any(|&(dx, dy)| {
+                let (nx, ny) = (x + dx, y + dy);
+
+                inside(nx, ny) && {
+                    let neighbor = result[(nx as usize, ny as usize)];
+                    below(neighbor, value) && below(neighbor, limit[(nx as usize, ny as usize)])
+                }
+            });
+
+            if propagates {
+                queue.push_back((x, y));
+            }
+        }
+    }
+
+    while let Some((x, y)) = queue.pop_front() {
+        let value = result[(x as usize, y as usize)];
+
+        for &(dx, dy) in PREVIOUS.iter().chain(NEXT.iter()) {
+            let (nx, ny) = (x + dx, y + dy);
+
+            if !inside(nx, ny) {
+                continue;
+            }
+
+            let (neighbor, bound) = (result[(nx as usize, ny as usize)], limit[(nx as usize, ny as usize)]);
+
+            if below(neighbor, value) && neighbor != bound {
+                result[(nx as usize, ny as usize)] = inf(value, bound);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    result
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgSize, random::Random};
+    use super::*;
+
+    /// Reference implementation by iterated geodesic dilation
+    fn iterated(marker: &ImgBuf<u8>, mask: &ImgBuf<u8>) -> ImgBuf<u8> {
+        let size = mask.size();
+        let mut current = marker.clone();
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                current[(x, y)] = current[(x, y)].min(mask[(x, y)]);
+            }
+        }
+
+        loop {
+            let mut next = current.clone();
+
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    let mut value = current[(x, y)];
+
+                    for ny in y.saturating_sub(1)..(y + 2).min(size.y) {
+                        for nx in x.saturating_sub(1)..(x + 2).min(size.x) {
+                            value = value.max(current[(nx, ny)]);
+                        }
+                    }
+
+                    next[(x, y)] = value.min(mask[(x, y)]);
+                }
+            }
+
+            if next == current {
+                return current;
+            }
+
+            current = next;
+        }
+    }
+
+    #[test]
+    fn matches_iterated_geodesic_dilation() {
+        let size = ImgSize::new(23, 17);
+        let mut random = Random::new(11);
+        let mask = ImgBuf::<u8>::from_vec(size, (0..size.product()).map(|_| (random.uniform()*200.0) as u8).collect());
+        let mut marker = ImgBuf::<u8>::new(size);
+        marker[(3, 4)] = 255;
+        marker[(20, 15)] = 90;
+
+        assert_eq!(reconstruct_by_dilation(&marker, &mask), iterated(&marker, &mask));
+    }
+
+    #[test]
+    fn binary_reconstruction_keeps_marked_objects() {
+        let mask = ImgBuf::<bool>::from_vec(ImgSize::new(6, 2), vec![
+            true, true, false, false, true, true,
+            false, true, false, true, true, false,
+        ]);
+        let mut marker = ImgBuf::<bool>::new(mask.size());
+        marker[(0, 0)] = true;
+
+        let result = reconstruct_by_dilation(&marker, &mask);
+        assert_eq!(result.line_ref(0), &[true, true, false, false, false, false]);
+        assert_eq!(result.line_ref(1), &[false, true, false, false, false, false]);
+    }
+}
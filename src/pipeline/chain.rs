@@ -0,0 +1,286 @@
+use crate::{Img, ImgMut, ImgLinesMut, ImgBuf, ImgSize, AsPrimitive};
+use crate::filter::{
+    gaussian_kernel, horizontal_filter, vertical_filter, convolution_operator, update
+};
+
+/// Single operation of a pipeline
+enum Stage {
+    Blur(Vec<f32>),
+    Sobel,
+    Threshold(f32),
+    Dilate(usize),
+    Erode(usize),
+    Map(Box<dyn Fn(f32) -> f32>),
+}
+
+/// Chain of image operations built once and executed repeatedly
+/// 
+/// Operations are appended by builder methods and executed in the order
+/// of insertion by [run](#method.run). All stages work on `f32` images
+/// of the input size, intermediate buffers are allocated on the first run
+/// and reused by subsequent runs until the input size changes.
+/// 
+/// Pixels outside image are replicated from the nearest border pixels
+/// by all neighborhood operations.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, pipeline::Pipeline};
+/// 
+/// let mut pipeline = Pipeline::new()
+///     .gaussian_blur(1.0)
+///     .sobel()
+///     .threshold(10.0)
+///     .dilate(1);
+/// 
+/// let mut frame = ImgBuf::<u8>::new(ImgSize::new(20, 10));
+/// for y in 0..10 {
+///     for x in 10..20 {
+///         frame[(x, y)] = 200;
+///     }
+/// }
+/// 
+/// let edges = pipeline.run(&frame);
+/// assert_eq!(edges[(10, 5)], 1.0);
+/// assert_eq!(edges[(2, 5)], 0.0);
+/// assert_eq!(edges[(18, 5)], 0.0);
+/// ```
+pub struct Pipeline {
+    stages: Vec<Stage>,
+    current: ImgBuf<f32>,
+    next: ImgBuf<f32>,
+    temp: ImgBuf<f32>,
+}
+
+impl Pipeline {
+    /// Creates empty pipeline passing the input through unchanged
+    pub fn new() -> Self {
+        let empty = ImgSize::new(0, 0);
+
+        Self {
+            stages: Vec::new(),
+            current: ImgBuf::new(empty),
+            next: ImgBuf::new(empty),
+            temp: ImgBuf::new(empty),
+        }
+    }
+
+    /// Appends Gaussian smoothing with standard deviation `sigma`, 
+    /// see [gaussian_blur](../filter/fn.gaussian_blur.html)
+    pub fn gaussian_blur(self, sigma: f32) -> Self {
+        self.stage(Stage::Blur(gaussian_kernel(sigma)))
+    }
+
+    /// Appends gradient magnitude computed by 3x3 Sobel operators
+    pub fn sobel(self) -> Self {
+        self.stage(Stage::Sobel)
+    }
+
+    /// Appends binary threshold, values greater than `threshold` 
+    /// become `1.0`, other values become `0.0`
+    pub fn threshold(self, threshold: f32) -> Self {
+        self.stage(Stage::Threshold(threshold))
+    }
+
+    /// Appends grayscale dilation (maximum) by square structuring element
+    /// of size `2*radius + 1`
+    pub fn dilate(self, radius: usize) -> Self {
+        self.stage(Stage::Dilate(radius))
+    }
+
+    /// Appends grayscale erosion (minimum) by square structuring element
+    /// of size `2*radius + 1`
+    pub fn erode(self, radius: usize) -> Self {
+        self.stage(Stage::Erode(radius))
+    }
+
+    /// Appends morphological opening (erosion followed by dilation)
+    pub fn open(self, radius: usize) -> Self {
+        self.erode(radius).dilate(radius)
+    }
+
+    /// Appends morphological closing (dilation followed by erosion)
+    pub fn close(self, radius: usize) -> Self {
+        self.dilate(radius).erode(radius)
+    }
+
+    /// Appends pixel-wise mapping by `operator`
+    pub fn map<F: Fn(f32) -> f32 + 'static>(self, operator: F) -> Self {
+        self.stage(Stage::Map(Box::new(operator)))
+    }
+
+    /// Number of operations in the pipeline
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Checks whether the pipeline contains no operations
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Executes all operations on `input` and returns reference 
+    /// to the result, valid until the next run
+    pub fn run<T: AsPrimitive<f32>>(&mut self, input: &dyn Img<T>) -> &ImgBuf<f32> {
+        self.allocate(input.size());
+
+        for y in 0..input.height() {
+            let src = input.line_ref(y);
+            let dst = self.current.line_mut(y);
+
+            for x in 0..src.len() {
+                dst[x] = src[x].as_();
+            }
+        }
+
+        for index in 0..self.stages.len() {
+            match &self.stages[index] {
+                Stage::Blur(kernel) => {
+                    fill(&mut self.temp, 0.0);
+                    fill(&mut self.next, 0.0);
+                    horizontal_filter(&self.current, &mut self.temp, kernel, convolution_operator);
+                    vertical_filter(&self.temp, &mut self.next, kernel, convolution_operator);
+                    std::mem::swap(&mut self.current, &mut self.next);
+                }
+                Stage::Sobel => {
+                    sobel(&self.current, &mut self.next);
+                    std::mem::swap(&mut self.current, &mut self.next);
+                }
+                Stage::Threshold(threshold) => {
+                    let threshold = *threshold;
+                    update(&mut self.current, |x| if x > threshold { 1.0 } else { 0.0 });
+                }
+                Stage::Dilate(radius) => {
+                    let radius = *radius;
+                    self.rank_filter(radius, f32::NEG_INFINITY, f32::max);
+                }
+                Stage::Erode(radius) => {
+                    let radius = *radius;
+                    self.rank_filter(radius, f32::INFINITY, f32::min);
+                }
+                Stage::Map(operator) => {
+                    update(&mut self.current, operator);
+                }
+            }
+        }
+
+        &self.current
+    }
+
+    fn stage(mut self, stage: Stage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Reallocates buffers in case the input size changed
+    fn allocate(&mut self, size: ImgSize) {
+        if self.current.size() != size {
+            self.current = ImgBuf::new(size);
+            self.next = ImgBuf::new(size);
+            self.temp = ImgBuf::new(size);
+        }
+    }
+
+    /// Separable minimum or maximum filter over a square window
+    fn rank_filter(&mut self, radius: usize, init: f32, combine: fn(f32, f32) -> f32) {
+        let window = vec![(); 2*radius + 1];
+
+        let operator = |src: &[f32], dst: &mut [f32], _: ()| {
+            for i in 0..std::cmp::min(src.len(), dst.len()) {
+                dst[i] = combine(dst[i], src[i]);
+            }
+        };
+
+        fill(&mut self.temp, init);
+        fill(&mut self.next, init);
+        horizontal_filter(&self.current, &mut self.temp, &window, operator);
+        vertical_filter(&self.temp, &mut self.next, &window, operator);
+        std::mem::swap(&mut self.current, &mut self.next);
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn fill(image: &mut ImgBuf<f32>, value: f32) {
+    for line in image.lines_mut() {
+        for pixel in line.iter_mut() {
+            *pixel = value;
+        }
+    }
+}
+
+/// Gradient magnitude by 3x3 Sobel operators with replicated borders
+fn sobel(input: &ImgBuf<f32>, output: &mut ImgBuf<f32>) {
+    let (width, height) = (input.width(), input.height());
+
+    for y in 0..height {
+        let top = input.line_ref(y.saturating_sub(1));
+        let middle = input.line_ref(y);
+        let bottom = input.line_ref(std::cmp::min(y + 1, height - 1));
+        let dst = output.line_mut(y);
+
+        for x in 0..width {
+            let (l, r) = (x.saturating_sub(1), std::cmp::min(x + 1, width - 1));
+            let gx = (top[r] + 2.0*middle[r] + bottom[r]) - (top[l] + 2.0*middle[l] + bottom[l]);
+            let gy = (bottom[l] + 2.0*bottom[x] + bottom[r]) - (top[l] + 2.0*top[x] + top[r]);
+            dst[x] = (gx*gx + gy*gy).sqrt();
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::gaussian_blur_new;
+
+    #[test]
+    fn blur_matches_filter_module() {
+        let mut input = ImgBuf::<u8>::new(ImgSize::new(11, 7));
+        input[(5, 3)] = 100;
+        input[(0, 0)] = 50;
+        let expected = gaussian_blur_new(&input, 1.5);
+        let mut pipeline = Pipeline::new().gaussian_blur(1.5);
+        let result = pipeline.run(&input);
+
+        for (a, b) in result.lines().flatten().zip(expected.lines().flatten()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn morphology_uses_square_window() {
+        let mut input = ImgBuf::<u8>::new(ImgSize::new(9, 9));
+        input[(4, 4)] = 1;
+        let mut pipeline = Pipeline::new().dilate(2);
+        let dilated = pipeline.run(&input).clone();
+        let count = dilated.lines().flatten().filter(|&&x| x == 1.0).count();
+        assert_eq!(count, 25);
+        assert_eq!(dilated[(2, 6)], 1.0);
+        assert_eq!(dilated[(1, 4)], 0.0);
+
+        let mut opening = Pipeline::new().open(1);
+        assert!(opening.run(&input).lines().flatten().all(|&x| x == 0.0));
+    }
+
+    #[test]
+    fn buffers_are_reused_between_runs() {
+        let input = ImgBuf::<u8>::new_init(ImgSize::new(6, 4), 3);
+        let mut pipeline = Pipeline::new().gaussian_blur(1.0).map(|x| x*2.0);
+        // blur swaps buffers once, so the result alternates between two of them
+        let first = pipeline.run(&input).line_ref(0).as_ptr();
+        let second = pipeline.run(&input).line_ref(0).as_ptr();
+        let third = pipeline.run(&input).line_ref(0).as_ptr();
+        assert_ne!(first, second);
+        assert_eq!(first, third);
+        assert!(pipeline.run(&input).lines().flatten().all(|&x| (x - 6.0).abs() < 1e-4));
+
+        let larger = ImgBuf::<u8>::new(ImgSize::new(8, 4));
+        assert_eq!(pipeline.run(&larger).size(), ImgSize::new(8, 4));
+    }
+}
@@ -0,0 +1,10 @@
+//! Reusable chains of filters executed repeatedly on image sequences
+//! 
+//! Available types:
+//!  * [Pipeline](struct.Pipeline.html) - sequence of operations built 
+//!    once and executed on every frame without reallocating intermediate
+//!    buffers
+
+mod chain;
+
+pub use chain::Pipeline;
@@ -0,0 +1,237 @@
+use crate::{
+    Img, ImgMut, ImgLinesMut, ImgBuf, ImgSize, 
+    filter::{horizontal_filter, vertical_filter, convolution_operator}
+};
+
+/// Binomial smoothing kernel used for pyramid construction
+const KERNEL: [f32; 5] = [1.0/16.0, 4.0/16.0, 6.0/16.0, 4.0/16.0, 1.0/16.0];
+
+/// Blurs image by 5x5 binomial kernel and subsamples it to half the size,
+/// odd sizes are rounded up
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, pyramid::reduce};
+/// let image = ImgBuf::<f32>::new_init(ImgSize::new(5, 4), 2.0);
+/// let smaller = reduce(&image);
+/// assert_eq!(smaller, ImgBuf::<f32>::new_init(ImgSize::new(3, 2), 2.0));
+/// ```
+pub fn reduce(image: &dyn Img<f32>) -> ImgBuf<f32> {
+    let size = image.size();
+    let mut horizontal = ImgBuf::<f32>::new(size);
+    let mut blurred = ImgBuf::<f32>::new(size);
+    horizontal_filter(image, &mut horizontal, &KERNEL, convolution_operator);
+    vertical_filter(&horizontal, &mut blurred, &KERNEL, convolution_operator);
+
+    let mut output = ImgBuf::<f32>::new(half_size(size));
+
+    for y in 0..output.height() {
+        let src = blurred.line_ref(2*y);
+        let dst = output.line_mut(y);
+
+        for x in 0..dst.len() {
+            dst[x] = src[2*x];
+        }
+    }
+
+    output
+}
+
+/// Upsamples image to `size` (at most twice the image size plus one 
+/// in each direction) by interpolating with the binomial kernel, 
+/// inverse operation to [reduce](fn.reduce.html)
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, pyramid::expand};
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(2, 1), vec![0.0, 8.0]);
+/// let larger = expand(&image, ImgSize::new(4, 2));
+/// assert_eq!(larger.line_ref(1), &[1.0, 4.0, 7.0, 8.0]);
+/// ```
+pub fn expand(image: &dyn Img<f32>, size: ImgSize) -> ImgBuf<f32> {
+    let source = image.size();
+    let mut horizontal = ImgBuf::<f32>::new(ImgSize::new(size.x, source.y));
+
+    for y in 0..source.y {
+        expand_line(image.line_ref(y), horizontal.line_mut(y));
+    }
+
+    let mut output = ImgBuf::<f32>::new(size);
+    let mut column = vec![0.0; source.y];
+    let mut expanded = vec![0.0; size.y];
+
+    for x in 0..size.x {
+        for y in 0..source.y {
+            column[y] = horizontal.line_ref(y)[x];
+        }
+
+        expand_line(&column, &mut expanded);
+
+        for y in 0..size.y {
+            output.line_mut(y)[x] = expanded[y];
+        }
+    }
+
+    output
+}
+
+/// Builds Gaussian pyramid with at most `levels` levels, the first level
+/// is a copy of the original image, construction stops earlier when the 
+/// image gets reduced to a single pixel
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, pyramid::gaussian};
+/// let image = ImgBuf::<f32>::new(ImgSize::new(8, 5));
+/// let sizes: Vec<_> = gaussian(&image, 10).iter().map(|level| level.size()).collect();
+/// assert_eq!(sizes, vec![
+///     ImgSize::new(8, 5), ImgSize::new(4, 3), ImgSize::new(2, 2), ImgSize::new(1, 1)
+/// ]);
+/// ```
+pub fn gaussian(image: &dyn Img<f32>, levels: usize) -> Vec<ImgBuf<f32>> {
+    let mut pyramid: Vec<ImgBuf<f32>> = Vec::with_capacity(levels);
+
+    for level in 0..levels {
+        let next = match pyramid.last() {
+            None => copy(image),
+            Some(previous) if previous.size().x > 1 || previous.size().y > 1 => reduce(previous),
+            Some(_) => break,
+        };
+
+        if level == 0 && next.size().product() == 0 {
+            break;
+        }
+
+        pyramid.push(next);
+    }
+
+    pyramid
+}
+
+/// Builds Laplacian pyramid with at most `levels` levels, every level
+/// except the last one contains difference between the respective Gaussian
+/// pyramid level and expanded next level, the last level is the coarsest
+/// Gaussian pyramid level
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, pyramid::{laplacian, collapse}};
+/// let image = ImgBuf::<f32>::from_vec(ImgSize::new(3, 3), (0..9).map(|v| v as f32).collect());
+/// let pyramid = laplacian(&image, 2);
+/// assert_eq!(pyramid.len(), 2);
+/// let restored = collapse(&pyramid);
+/// assert!(restored.lines().zip(image.lines())
+///     .all(|(a, b)| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5)));
+/// ```
+pub fn laplacian(image: &dyn Img<f32>, levels: usize) -> Vec<ImgBuf<f32>> {
+    let mut pyramid = gaussian(image, levels);
+
+    for level in 0..pyramid.len().saturating_sub(1) {
+        let expanded = expand(&pyramid[level + 1], pyramid[level].size());
+
+        for (line, detail) in pyramid[level].lines_mut().zip(expanded.lines()) {
+            for x in 0..line.len() {
+                line[x] -= detail[x];
+            }
+        }
+    }
+
+    pyramid
+}
+
+/// Reconstructs image from Laplacian pyramid built by 
+/// [laplacian](fn.laplacian.html), empty pyramid produces empty image
+pub fn collapse(pyramid: &[ImgBuf<f32>]) -> ImgBuf<f32> {
+    let mut levels = pyramid.iter().rev();
+
+    let mut image = match levels.next() {
+        Some(coarsest) => coarsest.clone(),
+        None => return ImgBuf::new(ImgSize::new(0, 0)),
+    };
+
+    for level in levels {
+        image = expand(&image, level.size());
+
+        for (line, detail) in image.lines_mut().zip(level.lines()) {
+            for x in 0..line.len() {
+                line[x] += detail[x];
+            }
+        }
+    }
+
+    image
+}
+
+/// Size of the next coarser pyramid level
+fn half_size(size: ImgSize) -> ImgSize {
+    ImgSize::new(size.x.div_ceil(2), size.y.div_ceil(2))
+}
+
+/// Upsamples `src` into `dst` by interpolation with binomial kernel,
+/// destination pixel `i` corresponds to source position `i/2`, positions
+/// outside source are replicated from border pixels
+fn expand_line(src: &[f32], dst: &mut [f32]) {
+    if src.is_empty() {
+        return;
+    }
+
+    let last = src.len() as isize - 1;
+
+    for i in 0..dst.len() {
+        let mut sum = 0.0;
+
+        // taps with the same parity as the destination index hit source pixels
+        for tap in -2isize..=2 {
+            let position = i as isize + tap;
+
+            if position % 2 == 0 {
+                let source = (position/2).clamp(0, last) as usize;
+                sum += 2.0*KERNEL[(tap + 2) as usize]*src[source];
+            }
+        }
+
+        dst[i] = sum;
+    }
+}
+
+/// Copy of image into a new buffer
+fn copy(image: &dyn Img<f32>) -> ImgBuf<f32> {
+    let mut output = ImgBuf::<f32>::new(image.size());
+
+    for (dst, src) in output.lines_mut().zip(image.lines()) {
+        dst.copy_from_slice(src);
+    }
+
+    output
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odd_sized_image_is_restored_from_laplacian_pyramid() {
+        let values = (0..(13*7)).map(|v| ((v*37) % 11) as f32).collect();
+        let image = ImgBuf::<f32>::from_vec(ImgSize::new(13, 7), values);
+        let restored = collapse(&laplacian(&image, 4));
+
+        for (a, b) in restored.lines().zip(image.lines()) {
+            assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-4));
+        }
+    }
+
+    #[test]
+    fn expand_keeps_constant_image() {
+        let image = ImgBuf::<f32>::new_init(ImgSize::new(3, 2), 5.0);
+        assert_eq!(expand(&image, ImgSize::new(6, 3)), ImgBuf::<f32>::new_init(ImgSize::new(6, 3), 5.0));
+    }
+
+    #[test]
+    fn empty_image_has_empty_pyramid() {
+        let image = ImgBuf::<f32>::new(ImgSize::new(0, 0));
+        assert!(gaussian(&image, 3).is_empty());
+        assert_eq!(collapse(&laplacian(&image, 3)).size(), ImgSize::new(0, 0));
+    }
+}
@@ -0,0 +1,22 @@
+//! Multi-resolution image pyramids
+//! 
+//! Gaussian pyramid is a sequence of images, each one blurred and 
+//! subsampled to half the size of the previous one, Laplacian pyramid
+//! stores the differences between successive Gaussian pyramid levels 
+//! (band-pass images) and the coarsest level, so the original image can
+//! be reconstructed exactly using [collapse](fn.collapse.html).
+//! 
+//! Pyramid levels are ordered from the finest (original size) 
+//! to the coarsest.
+//! 
+//! Available functions:
+//!  * [reduce](fn.reduce.html), [expand](fn.expand.html) - single
+//!    level downsampling and upsampling
+//!  * [gaussian](fn.gaussian.html), [laplacian](fn.laplacian.html) -
+//!    pyramid construction
+//!  * [collapse](fn.collapse.html) - reconstruction of image from 
+//!    Laplacian pyramid
+
+mod levels;
+
+pub use levels::{reduce, expand, gaussian, laplacian, collapse};
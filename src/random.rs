@@ -0,0 +1,92 @@
+//! Small deterministic pseudo random number generator, so algorithms
+//! requiring randomness produce reproducible results for a given seed
+//! without depending on external crates
+
+/// SplitMix64 pseudo random number generator
+#[derive(Clone, Debug)]
+pub(crate) struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Create generator initialized by `seed`
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next uniformly distributed 64 bit value
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    /// Uniformly distributed value in `[0, 1)` range
+    pub(crate) fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64/(1u64 << 53) as f64
+    }
+
+    /// Uniformly distributed index in `0..count` range
+    pub(crate) fn index(&mut self, count: usize) -> usize {
+        ((self.uniform()*count as f64) as usize).min(count.saturating_sub(1))
+    }
+
+    /// Normally distributed value with zero mean and unit variance
+    /// (Box-Muller transform)
+    pub(crate) fn normal(&mut self) -> f64 {
+        let radius = (-2.0*(1.0 - self.uniform()).ln()).sqrt();
+        let angle = 2.0*std::f64::consts::PI*self.uniform();
+        radius*angle.cos()
+    }
+
+    /// Poisson distributed value with given `mean`, normal approximation
+    /// is used for large means
+    pub(crate) fn poisson(&mut self, mean: f64) -> f64 {
+        if mean <= 0.0 {
+            return 0.0;
+        }
+
+        if mean > 30.0 {
+            return (mean + mean.sqrt()*self.normal()).round().max(0.0);
+        }
+
+        // Knuth's multiplication method
+        let limit = (-mean).exp();
+        let (mut count, mut product) = (0.0, self.uniform());
+
+        while product > limit {
+            count += 1.0;
+            product *= self.uniform();
+        }
+
+        count
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_produces_same_sequence() {
+        let (mut a, mut b) = (Random::new(42), Random::new(42));
+        assert!((0..10).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Random::new(1).next_u64(), Random::new(2).next_u64());
+    }
+
+    #[test]
+    fn distributions_have_expected_mean() {
+        let mut random = Random::new(7);
+        let count = 20000;
+        let uniform = (0..count).map(|_| random.uniform()).sum::<f64>()/count as f64;
+        let normal = (0..count).map(|_| random.normal()).sum::<f64>()/count as f64;
+        let poisson = (0..count).map(|_| random.poisson(4.0)).sum::<f64>()/count as f64;
+        assert!((uniform - 0.5).abs() < 0.01);
+        assert!(normal.abs() < 0.03);
+        assert!((poisson - 4.0).abs() < 0.05);
+    }
+}
@@ -0,0 +1,15 @@
+//! Registration (alignment) of images of the same scene
+//! 
+//! Available functions:
+//!  * [align_by_points](fn.align_by_points.html) - estimation of rotation
+//!    and translation between two frames from detected point positions 
+//!    (e.g. stars)
+//!  * [ransac_affine](fn.ransac_affine.html), 
+//!    [ransac_homography](fn.ransac_homography.html) - robust estimation
+//!    of transformations from point correspondences containing outliers
+
+mod points;
+mod ransac;
+
+pub use points::align_by_points;
+pub use ransac::{ransac_affine, ransac_homography};
@@ -0,0 +1,143 @@
+use crate::geometry::{Affine2d, Point};
+
+/// Maximal number of points of each list used for voting, points
+/// beyond this count are only used for the final least squares fit
+const MAX_VOTING_POINTS: usize = 20;
+
+/// Estimates rotation and translation aligning two lists of points
+/// 
+/// Points are typically positions of stars detected in two frames, 
+/// their order does not need to correspond, and some points may be 
+/// missing in either list. Lists should be sorted by significance 
+/// (e.g. by star flux), as only first 20 points of each list take part
+/// in voting.
+/// 
+/// Every pair of points from `points_a` is compared with every pair from
+/// `points_b` having the same distance (within `tolerance`), each such
+/// combination proposes a rigid transformation, which gets a vote for every
+/// point from `points_a` that is mapped closer than `tolerance` to some
+/// point of `points_b`. Transformation with most votes is refined by least
+/// squares fit over all matched points.
+/// 
+/// Returns transformation mapping points of the first list onto the points
+/// of the second list, or `None` if no transformation supported by at least
+/// two point pairs exists
+/// 
+/// # Example
+/// ```
+/// use nanocv::{geometry::{Affine2d, Point}, register::align_by_points};
+/// let a = vec![
+///     Point::new(10.0, 10.0), Point::new(50.0, 20.0), 
+///     Point::new(30.0, 70.0), Point::new(80.0, 60.0)
+/// ];
+/// let truth = Affine2d::rotation(0.1).then(Affine2d::translation(Point::new(5.0, -3.0)));
+/// // second frame misses one point and contains an extra one
+/// let mut b: Vec<Point> = a[1..].iter().map(|&p| truth.transform_point(p)).collect();
+/// b.push(Point::new(0.0, 90.0));
+/// 
+/// let estimate = align_by_points(&a, &b, 0.5).unwrap();
+/// let point = Point::new(40.0, 40.0);
+/// assert!((estimate.transform_point(point) - truth.transform_point(point)).length() < 1e-6);
+/// ```
+pub fn align_by_points(points_a: &[Point], points_b: &[Point], tolerance: f64) -> Option<Affine2d> {
+    let voting_a = &points_a[..std::cmp::min(points_a.len(), MAX_VOTING_POINTS)];
+    let voting_b = &points_b[..std::cmp::min(points_b.len(), MAX_VOTING_POINTS)];
+    let mut best: Option<(usize, Affine2d)> = None;
+
+    for i in 0..voting_a.len() {
+        for j in (i + 1)..voting_a.len() {
+            let distance_a = voting_a[i].distance(voting_a[j]);
+
+            for k in 0..voting_b.len() {
+                for l in 0..voting_b.len() {
+                    if k == l || (voting_b[k].distance(voting_b[l]) - distance_a).abs() > tolerance {
+                        continue;
+                    }
+
+                    let pairs = [(voting_a[i], voting_b[k]), (voting_a[j], voting_b[l])];
+                    let candidate = fit_rigid(&pairs);
+                    let votes = matches(voting_a, voting_b, &candidate, tolerance).len();
+
+                    if best.as_ref().map(|&(most, _)| votes > most).unwrap_or(true) {
+                        best = Some((votes, candidate));
+                    }
+                }
+            }
+        }
+    }
+
+    let (_, coarse) = best.filter(|&(votes, _)| votes >= 2)?;
+    Some(fit_rigid(&matches(points_a, points_b, &coarse, tolerance)))
+}
+
+/// Pairs of points from `a` and their nearest points from `b` not further 
+/// than `tolerance` after transforming `a` by `transform`
+fn matches(a: &[Point], b: &[Point], transform: &Affine2d, tolerance: f64) -> Vec<(Point, Point)> {
+    a.iter()
+        .filter_map(|&point| {
+            let mapped = transform.transform_point(point);
+
+            b.iter()
+                .map(|&other| (mapped.distance(other), other))
+                .filter(|&(distance, _)| distance <= tolerance)
+                .min_by(|x, y| x.0.total_cmp(&y.0))
+                .map(|(_, other)| (point, other))
+        })
+        .collect()
+}
+
+/// Least squares rigid transformation (rotation and translation)
+/// mapping first points of `pairs` onto second points
+fn fit_rigid(pairs: &[(Point, Point)]) -> Affine2d {
+    let scale = 1.0/pairs.len() as f64;
+    let center_a = pairs.iter().fold(Point::new(0.0, 0.0), |sum, pair| sum + pair.0)*scale;
+    let center_b = pairs.iter().fold(Point::new(0.0, 0.0), |sum, pair| sum + pair.1)*scale;
+    let (mut cos, mut sin) = (0.0, 0.0);
+
+    for &(a, b) in pairs {
+        let (a, b) = (a - center_a, b - center_b);
+        cos += a.x*b.x + a.y*b.y;
+        sin += a.x*b.y - a.y*b.x;
+    }
+
+    let rotation = Affine2d::rotation(sin.atan2(cos));
+    let shift = center_b - rotation.transform_point(center_a);
+    rotation.then(Affine2d::translation(shift))
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noisy_points_are_aligned() {
+        let a: Vec<Point> = (0..12)
+            .map(|index| Point::new((index*37 % 100) as f64, (index*61 % 90) as f64))
+            .collect();
+
+        let truth = Affine2d::rotation_around(-0.3, Point::new(50.0, 50.0))
+            .then(Affine2d::translation(Point::new(12.0, 7.0)));
+
+        let b: Vec<Point> = a.iter().enumerate()
+            .map(|(index, &point)| {
+                let noise = if index % 2 == 0 { 0.1 } else { -0.1 };
+                truth.transform_point(point) + Point::new(noise, -noise)
+            })
+            .rev()
+            .collect();
+
+        let estimate = align_by_points(&a, &b, 1.0).unwrap();
+
+        for &point in &a {
+            assert!(estimate.transform_point(point).distance(truth.transform_point(point)) < 0.2);
+        }
+    }
+
+    #[test]
+    fn single_point_cannot_be_aligned() {
+        let points = [Point::new(1.0, 2.0)];
+        assert_eq!(align_by_points(&points, &points, 1.0), None);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{geometry::{Affine2d, Homography, Point}, random::Random};
+
+/// Seed of the random sampling, so estimation is reproducible
+const SAMPLING_SEED: u64 = 0x5A4C_0A5E;
+
+/// Robust estimation of affine transformation by RANSAC
+/// 
+/// Each of `iterations` fits affine transformation to three randomly 
+/// chosen pairs `(src, dst)` and counts inliers, pairs whose `src` point
+/// is mapped closer than `threshold` to its `dst` point. Model with the
+/// most inliers is refined by a least squares fit over all its inliers.
+/// Sampling uses a fixed seed, so results are reproducible.
+/// 
+/// Returns transformation mapping `src` points onto `dst` points together
+/// with an inlier mask (one flag per pair), or `None` when no model is 
+/// supported by at least three pairs.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{geometry::{Affine2d, Point}, register::ransac_affine};
+/// let truth = Affine2d::new([[1.1, 0.2, 5.0], [-0.1, 0.9, -3.0]]);
+/// let mut pairs: Vec<(Point, Point)> = (0..10)
+///     .map(|i| Point::new((i*13 % 50) as f64, (i*29 % 40) as f64))
+///     .map(|p| (p, truth.transform_point(p)))
+///     .collect();
+/// // two wrong matches
+/// pairs[2].1 = Point::new(100.0, 100.0);
+/// pairs[7].1 = Point::new(0.0, 80.0);
+/// 
+/// let (model, inliers) = ransac_affine(&pairs, 0.5, 100).unwrap();
+/// assert_eq!(inliers.iter().filter(|&&inlier| inlier).count(), 8);
+/// assert!(!inliers[2] && !inliers[7]);
+/// assert!((model.transform_point(Point::new(10.0, 10.0)) 
+///     - truth.transform_point(Point::new(10.0, 10.0))).length() < 1e-6);
+/// ```
+pub fn ransac_affine(
+    pairs: &[(Point, Point)], 
+    threshold: f64, 
+    iterations: usize
+) -> Option<(Affine2d, Vec<bool>)> {
+    ransac(
+        pairs, 3, threshold, iterations, 
+        Affine2d::from_correspondences, 
+        |model, point| Some(model.transform_point(point))
+    )
+}
+
+/// Robust estimation of homography by RANSAC
+/// 
+/// Works the same way as [ransac_affine](fn.ransac_affine.html) using 
+/// random samples of four pairs, returns `None` when no model is supported
+/// by at least four pairs.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{geometry::{Homography, Point}, register::ransac_homography};
+/// let truth = Homography::new([[1.0, 0.1, 4.0], [0.05, 1.2, -2.0], [0.001, 0.002, 1.0]]);
+/// let mut pairs: Vec<(Point, Point)> = (0..12)
+///     .map(|i| Point::new((i*17 % 60) as f64, (i*23 % 45) as f64))
+///     .map(|p| (p, truth.transform_point(p).unwrap()))
+///     .collect();
+/// pairs[5].1 = Point::new(-50.0, 10.0);
+/// 
+/// let (_, inliers) = ransac_homography(&pairs, 0.5, 200).unwrap();
+/// assert_eq!(inliers.iter().filter(|&&inlier| inlier).count(), 11);
+/// assert!(!inliers[5]);
+/// ```
+pub fn ransac_homography(
+    pairs: &[(Point, Point)], 
+    threshold: f64, 
+    iterations: usize
+) -> Option<(Homography, Vec<bool>)> {
+    ransac(
+        pairs, 4, threshold, iterations, 
+        Homography::from_correspondences, 
+        |model, point| model.transform_point(point)
+    )
+}
+
+fn ransac<M, F, T>(
+    pairs: &[(Point, Point)],
+    sample_size: usize,
+    threshold: f64,
+    iterations: usize,
+    fit: F,
+    transform: T,
+) -> Option<(M, Vec<bool>)>
+where F: Fn(&[(Point, Point)]) -> Option<M>, T: Fn(&M, Point) -> Option<Point> {
+    if pairs.len() < sample_size {
+        return None;
+    }
+
+    let inliers = |model: &M| -> Vec<bool> {
+        pairs.iter()
+            .map(|&(src, dst)| transform(model, src).is_some_and(|mapped| mapped.distance(dst) <= threshold))
+            .collect()
+    };
+
+    let mut random = Random::new(SAMPLING_SEED);
+    let mut best: Option<(usize, M, Vec<bool>)> = None;
+    let mut sample = Vec::with_capacity(sample_size);
+
+    for _ in 0..iterations {
+        let mut indices = Vec::with_capacity(sample_size);
+
+        while indices.len() < sample_size {
+            let index = random.index(pairs.len());
+
+            if !indices.contains(&index) {
+                indices.push(index);
+            }
+        }
+
+        sample.clear();
+        sample.extend(indices.iter().map(|&index| pairs[index]));
+
+        let model = match fit(&sample) {
+            Some(model) => model,
+            None => continue,
+        };
+
+        let mask = inliers(&model);
+        let count = mask.iter().filter(|&&inlier| inlier).count();
+
+        if best.as_ref().map_or(true, |(most, _, _)| count > *most) {
+            best = Some((count, model, mask));
+        }
+    }
+
+    let (count, model, mask) = best.filter(|(count, _, _)| *count >= sample_size)?;
+    let supporting: Vec<(Point, Point)> = pairs.iter().zip(mask.iter())
+        .filter(|(_, &inlier)| inlier)
+        .map(|(&pair, _)| pair)
+        .collect();
+
+    // Refined model is used only if it does not lose support
+    if let Some(refined) = fit(&supporting) {
+        let refined_mask = inliers(&refined);
+
+        if refined_mask.iter().filter(|&&inlier| inlier).count() >= count {
+            return Some((refined, refined_mask));
+        }
+    }
+
+    Some((model, mask))
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_few_pairs_produce_no_model() {
+        let pairs = [(Point::new(0.0, 0.0), Point::new(1.0, 1.0)); 2];
+        assert!(ransac_affine(&pairs, 1.0, 10).is_none());
+        assert!(ransac_homography(&pairs, 1.0, 10).is_none());
+    }
+
+    #[test]
+    fn noisy_inliers_are_kept() {
+        let truth = Affine2d::rotation(0.2).then(Affine2d::translation(Point::new(-4.0, 9.0)));
+        let pairs: Vec<(Point, Point)> = (0..30)
+            .map(|i| {
+                let point = Point::new((i*37 % 100) as f64, (i*53 % 80) as f64);
+                let noise = if i % 2 == 0 { 0.2 } else { -0.2 };
+                let dst = if i % 5 == 4 { Point::new(noise*100.0, 50.0) } else { truth.transform_point(point) };
+                (point, dst + Point::new(noise, noise))
+            })
+            .collect();
+
+        let (model, inliers) = ransac_affine(&pairs, 1.0, 200).unwrap();
+
+        for (index, &inlier) in inliers.iter().enumerate() {
+            assert_eq!(inlier, index % 5 != 4, "pair {}", index);
+        }
+
+        let point = Point::new(50.0, 40.0);
+        assert!(model.transform_point(point).distance(truth.transform_point(point)) < 0.2);
+    }
+}
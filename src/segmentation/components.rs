@@ -0,0 +1,174 @@
+use crate::{Img, ImgMut, ImgBuf};
+
+/// Pixel neighborhood used to decide whether two pixels are connected
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Connectivity {
+    /// Pixels sharing an edge are connected
+    Four,
+    /// Pixels sharing an edge or a corner are connected
+    Eight,
+}
+
+/// Labels connected components of `true` pixels in `mask`
+/// 
+/// Returns label map with the same size as mask and the number of
+/// components found. Background pixels get label `0`, components are
+/// labeled `1..=count` in the order of their first pixel (row-major).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, segmentation::{label_components, Connectivity}};
+/// let mask = ImgBuf::<bool>::from_vec(ImgSize::new(4, 2), vec![
+///     true, false, false, true,
+///     false, true, false, true,
+/// ]);
+/// let (labels, count) = label_components(&mask, Connectivity::Eight);
+/// assert_eq!(count, 2);
+/// assert_eq!(labels.line_ref(0), &[1, 0, 0, 2]);
+/// assert_eq!(labels.line_ref(1), &[0, 1, 0, 2]);
+/// 
+/// let (_, count) = label_components(&mask, Connectivity::Four);
+/// assert_eq!(count, 3);
+/// ```
+pub fn label_components(mask: &dyn Img<bool>, connectivity: Connectivity) -> (ImgBuf<u32>, u32) {
+    let size = mask.size();
+    let mut labels = ImgBuf::<u32>::new(size);
+    let mut parents: Vec<u32> = vec![0];
+
+    for y in 0..size.y {
+        let line = mask.line_ref(y);
+
+        for x in 0..size.x {
+            if !line[x] {
+                continue;
+            }
+
+            let mut label = 0;
+
+            for (nx, ny) in previous_neighbors(x, y, size.x, connectivity) {
+                let neighbor = labels[(nx, ny)];
+
+                if neighbor == 0 {
+                    continue;
+                }
+
+                if label == 0 {
+                    label = find(&mut parents, neighbor);
+                } else {
+                    label = union(&mut parents, label, neighbor);
+                }
+            }
+
+            if label == 0 {
+                label = parents.len() as u32;
+                parents.push(label);
+            }
+
+            labels[(x, y)] = label;
+        }
+    }
+
+    // Assign consecutive labels to union-find roots
+    let mut remap = vec![0u32; parents.len()];
+    let mut count = 0;
+
+    for label in 1..parents.len() {
+        let root = find(&mut parents, label as u32) as usize;
+
+        if remap[root] == 0 {
+            count += 1;
+            remap[root] = count;
+        }
+
+        remap[label] = remap[root];
+    }
+
+    for y in 0..size.y {
+        let line = labels.line_mut(y);
+
+        for x in 0..line.len() {
+            line[x] = remap[line[x] as usize];
+        }
+    }
+
+    (labels, count)
+}
+
+/// Already visited neighbors of pixel `(x, y)` in row-major scan order
+fn previous_neighbors(
+    x: usize, 
+    y: usize, 
+    width: usize, 
+    connectivity: Connectivity
+) -> impl Iterator<Item=(usize, usize)> {
+    let mut neighbors = [None; 4];
+
+    if x > 0 {
+        neighbors[0] = Some((x - 1, y));
+    }
+
+    if y > 0 {
+        neighbors[1] = Some((x, y - 1));
+
+        if connectivity == Connectivity::Eight {
+            if x > 0 {
+                neighbors[2] = Some((x - 1, y - 1));
+            }
+
+            if x + 1 < width {
+                neighbors[3] = Some((x + 1, y - 1));
+            }
+        }
+    }
+
+    IntoIterator::into_iter(neighbors).flatten()
+}
+
+/// Finds root of `label`, compressing the path on the way
+fn find(parents: &mut [u32], mut label: u32) -> u32 {
+    while parents[label as usize] != label {
+        let parent = parents[label as usize];
+        parents[label as usize] = parents[parent as usize];
+        label = parent;
+    }
+
+    label
+}
+
+/// Merges sets of labels `a` and `b`, returns the common root
+fn union(parents: &mut [u32], a: u32, b: u32) -> u32 {
+    let (a, b) = (find(parents, a), find(parents, b));
+    let (root, child) = if a < b { (a, b) } else { (b, a) };
+    parents[child as usize] = root;
+    root
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn u_shape_is_merged_into_single_component() {
+        let mask = ImgBuf::<bool>::from_vec(ImgSize::new(3, 3), vec![
+            true, false, true,
+            true, false, true,
+            true, true, true,
+        ]);
+
+        let (labels, count) = label_components(&mask, Connectivity::Four);
+        assert_eq!(count, 1);
+        assert!(labels.line_ref(0).iter().all(|&label| label != 2));
+        assert_eq!(labels.line_ref(2), &[1, 1, 1]);
+    }
+
+    #[test]
+    fn empty_mask_has_no_components() {
+        let mask = ImgBuf::<bool>::new(ImgSize::new(3, 2));
+        let (labels, count) = label_components(&mask, Connectivity::Eight);
+        assert_eq!(count, 0);
+        assert!(labels.lines().all(|line| line.iter().all(|&label| label == 0)));
+    }
+}
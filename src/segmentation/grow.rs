@@ -0,0 +1,103 @@
+use std::collections::VecDeque;
+use crate::{Img, ImgBuf, Vec2d, AsPrimitive};
+
+/// Region growing segmentation from multiple seed points
+/// 
+/// Seed `seeds[i]` starts region with label `i + 1`. All regions grow
+/// simultaneously in breadth-first order, an unlabeled 4-connected 
+/// neighbor is added to a region when its value differs from the current
+/// region mean by at most `tolerance`. Region mean is updated after every 
+/// added pixel, so the decision depends on the whole region rather than
+/// on a single (possibly noisy) seed pixel value as in a flood fill.
+/// 
+/// Pixels not reached by any region keep label `0`. Seeds outside of the
+/// image or on a pixel already claimed by a previous seed are ignored.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, segmentation::region_grow};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(7, 1), vec![
+///     10, 12, 14, 90, 92, 91, 200,
+/// ]);
+/// let seeds = [Vec2d::new(0, 0), Vec2d::new(4, 0)];
+/// let labels = region_grow(&image, &seeds, 5.0);
+/// assert_eq!(labels.line_ref(0), &[1, 1, 1, 2, 2, 2, 0]);
+/// ```
+pub fn region_grow<T>(image: &dyn Img<T>, seeds: &[Vec2d<usize>], tolerance: f64) -> ImgBuf<u32>
+where T: AsPrimitive<f64> {
+    let size = image.size();
+    let mut labels = ImgBuf::<u32>::new(size);
+    let mut sums = vec![0.0; seeds.len()];
+    let mut counts = vec![0usize; seeds.len()];
+    let mut queue = VecDeque::new();
+    let value = |x: usize, y: usize| -> f64 { image.line_ref(y)[x].as_() };
+
+    for (index, seed) in seeds.iter().enumerate() {
+        if seed.x >= size.x || seed.y >= size.y || labels[(seed.x, seed.y)] != 0 {
+            continue;
+        }
+
+        labels[(seed.x, seed.y)] = index as u32 + 1;
+        sums[index] = value(seed.x, seed.y);
+        counts[index] = 1;
+        queue.push_back((seed.x, seed.y));
+    }
+
+    while let Some((x, y)) = queue.pop_front() {
+        let label = labels[(x, y)];
+        let region = label as usize - 1;
+        let mut neighbors = Vec::with_capacity(4);
+
+        if x > 0 { neighbors.push((x - 1, y)); }
+        if y > 0 { neighbors.push((x, y - 1)); }
+        if x + 1 < size.x { neighbors.push((x + 1, y)); }
+        if y + 1 < size.y { neighbors.push((x, y + 1)); }
+
+        for (nx, ny) in neighbors {
+            if labels[(nx, ny)] != 0 {
+                continue;
+            }
+
+            let neighbor = value(nx, ny);
+
+            if (neighbor - sums[region]/counts[region] as f64).abs() <= tolerance {
+                labels[(nx, ny)] = label;
+                sums[region] += neighbor;
+                counts[region] += 1;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    labels
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn pixels_are_compared_with_region_mean() {
+        let image = ImgBuf::<f32>::from_vec(ImgSize::new(6, 1), vec![
+            10.0, 13.0, 11.0, 14.0, 12.0, 20.0,
+        ]);
+        // 14 differs from the seed by 4, but from the region mean only by 2.67
+        let labels = region_grow(&image, &[Vec2d::new(0, 0)], 3.0);
+        assert_eq!(labels.line_ref(0), &[1, 1, 1, 1, 1, 0]);
+
+        let labels = region_grow(&image, &[Vec2d::new(0, 0)], 2.5);
+        assert_eq!(labels.line_ref(0), &[1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn invalid_and_duplicate_seeds_are_ignored() {
+        let image = ImgBuf::<u8>::new(ImgSize::new(3, 2));
+        let seeds = [Vec2d::new(5, 0), Vec2d::new(1, 1), Vec2d::new(1, 1)];
+        let labels = region_grow(&image, &seeds, 0.0);
+        assert_eq!(labels.line_ref(0), &[2, 2, 2]);
+        assert_eq!(labels.line_ref(1), &[2, 2, 2]);
+    }
+}
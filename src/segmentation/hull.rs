@@ -0,0 +1,88 @@
+use crate::{Img, ImgBuf, Vec2d, geometry::{convex_hull, cross}};
+
+/// Convex hull of the region with `label` in the label map rasterized 
+/// back into a mask of the same size as `labels`
+/// 
+/// Hull is computed from centers of region pixels, pixels with centers 
+/// inside or on the border of the hull are set. Mask is empty when the 
+/// label does not occur in the label map.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, segmentation::region_convex_hull};
+/// // L-shaped region is completed to a triangle
+/// let labels = ImgBuf::<u32>::from_vec(ImgSize::new(3, 3), vec![
+///     1, 0, 0,
+///     1, 0, 0,
+///     1, 1, 1,
+/// ]);
+/// let hull = region_convex_hull(&labels, 1);
+/// assert_eq!(hull.line_ref(0), &[true, false, false]);
+/// assert_eq!(hull.line_ref(1), &[true, true, false]);
+/// assert_eq!(hull.line_ref(2), &[true, true, true]);
+/// ```
+pub fn region_convex_hull(labels: &dyn Img<u32>, label: u32) -> ImgBuf<bool> {
+    let size = labels.size();
+    let mut points = Vec::new();
+
+    for y in 0..size.y {
+        for (x, &value) in labels.line_ref(y).iter().enumerate() {
+            if value == label {
+                points.push(Vec2d::new(x as isize, y as isize));
+            }
+        }
+    }
+
+    let hull = convex_hull(&points);
+    let mut mask = ImgBuf::<bool>::new(size);
+
+    if hull.is_empty() {
+        return mask;
+    }
+
+    let (min, max) = hull.iter().fold((hull[0], hull[0]), |(min, max), point| (
+        Vec2d::new(min.x.min(point.x), min.y.min(point.y)),
+        Vec2d::new(max.x.max(point.x), max.y.max(point.y)),
+    ));
+
+    for y in min.y..=max.y {
+        for x in min.x..=max.x {
+            mask[(x as usize, y as usize)] = inside(&hull, Vec2d::new(x, y));
+        }
+    }
+
+    mask
+}
+
+/// Tests whether `point` lies inside or on the border of the hull,
+/// `point` is expected within the hull bounding box
+fn inside(hull: &[Vec2d<isize>], point: Vec2d<isize>) -> bool {
+    match hull.len() {
+        1 => true,
+        // Degenerate hull is a segment, bounding box check is already done
+        2 => cross(hull[0], hull[1], point) == 0,
+        count => (0..count).all(|index| cross(hull[index], hull[(index + 1) % count], point) >= 0),
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn diagonal_segment_hull() {
+        let mut labels = ImgBuf::<u32>::new(ImgSize::new(4, 4));
+        labels[(0, 0)] = 2;
+        labels[(3, 3)] = 2;
+        labels[(0, 3)] = 1;
+
+        let hull = region_convex_hull(&labels, 2);
+        let count = hull.lines().flat_map(|line| line.iter()).filter(|&&value| value).count();
+        assert_eq!(count, 4);
+        assert!(hull[(1, 1)] && hull[(2, 2)] && !hull[(0, 3)]);
+        assert_eq!(region_convex_hull(&labels, 7), ImgBuf::new(ImgSize::new(4, 4)));
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{Img, ImgBuf, AsPrimitive, color::Rgb, random::Random};
+
+/// K-means clustering of grayscale pixel intensities
+/// 
+/// Splits pixels into `k` clusters by their intensity, cluster centers are
+/// initialized using k-means++ seeding driven by `seed` and refined by at
+/// most `max_iters` Lloyd iterations (or until labels stop changing).
+/// 
+/// Returns label map and cluster centers sorted in ascending order, pixel
+/// with label `l` belongs to the cluster with center `centers[l - 1]`.
+/// When the image contains fewer distinct values than `k`, some clusters
+/// stay empty.
+/// 
+/// # Panics
+/// 
+/// Panics when `k` is zero
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, segmentation::kmeans};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(6, 1), vec![
+///     10, 200, 12, 198, 11, 202,
+/// ]);
+/// let (labels, centers) = kmeans(&image, 2, 10, 0);
+/// assert_eq!(labels.line_ref(0), &[1, 2, 1, 2, 1, 2]);
+/// assert_eq!(centers, vec![11.0, 200.0]);
+/// ```
+pub fn kmeans<T>(image: &dyn Img<T>, k: usize, max_iters: usize, seed: u64) -> (ImgBuf<u32>, Vec<f64>)
+where T: AsPrimitive<f64> {
+    let (labels, centers) = cluster(image, k, max_iters, seed, |pixel| [pixel.as_(), 0.0, 0.0]);
+    (labels, centers.iter().map(|center| center[0]).collect())
+}
+
+/// K-means clustering of RGB pixel colors
+/// 
+/// Works the same way as [kmeans](fn.kmeans.html) using euclidean distance
+/// in RGB space, cluster centers are sorted in lexicographic `(r, g, b)`
+/// order. Useful for color quantization and palette extraction.
+/// 
+/// # Panics
+/// 
+/// Panics when `k` is zero
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, segmentation::kmeans_rgb};
+/// let image = ImgBuf::<Rgb<u8>>::from_vec(ImgSize::new(4, 1), vec![
+///     Rgb::new(250, 0, 0), Rgb::new(0, 0, 250), 
+///     Rgb::new(0, 0, 254), Rgb::new(254, 0, 0),
+/// ]);
+/// let (labels, palette) = kmeans_rgb(&image, 2, 10, 0);
+/// assert_eq!(labels.line_ref(0), &[2, 1, 1, 2]);
+/// assert_eq!(palette, vec![Rgb::new(0.0, 0.0, 252.0), Rgb::new(252.0, 0.0, 0.0)]);
+/// ```
+pub fn kmeans_rgb<T>(
+    image: &dyn Img<Rgb<T>>, 
+    k: usize, 
+    max_iters: usize, 
+    seed: u64
+) -> (ImgBuf<u32>, Vec<Rgb<f64>>)
+where T: AsPrimitive<f64> {
+    let (labels, centers) = cluster(image, k, max_iters, seed, |pixel| {
+        [pixel.r.as_(), pixel.g.as_(), pixel.b.as_()]
+    });
+
+    (labels, centers.into_iter().map(Rgb::from).collect())
+}
+
+type Point = [f64; 3];
+
+fn cluster<T, F>(
+    image: &dyn Img<T>, 
+    k: usize, 
+    max_iters: usize, 
+    seed: u64, 
+    point: F
+) -> (ImgBuf<u32>, Vec<Point>)
+where F: Fn(&T) -> Point {
+    assert!(k > 0, "Number of clusters must not be zero");
+
+    let size = image.size();
+    let points: Vec<Point> = (0..size.y)
+        .flat_map(|y| image.line_ref(y).iter().map(&point))
+        .collect();
+
+    if points.is_empty() {
+        return (ImgBuf::new(size), vec![[0.0; 3]; k]);
+    }
+
+    let mut centers = initial_centers(&points, k, seed);
+    let mut assignment = vec![usize::MAX; points.len()];
+
+    for _ in 0..max_iters.max(1) {
+        let mut changed = false;
+
+        for (index, point) in points.iter().enumerate() {
+            let nearest = nearest_center(&centers, point).0;
+
+            if assignment[index] != nearest {
+                assignment[index] = nearest;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![[0.0; 3]; k];
+        let mut counts = vec![0usize; k];
+
+        for (point, &label) in points.iter().zip(assignment.iter()) {
+            counts[label] += 1;
+
+            for dim in 0..3 {
+                sums[label][dim] += point[dim];
+            }
+        }
+
+        for label in 0..k {
+            // Empty clusters keep their previous center
+            if counts[label] > 0 {
+                for dim in 0..3 {
+                    centers[label][dim] = sums[label][dim]/counts[label] as f64;
+                }
+            }
+        }
+    }
+
+    // Sort clusters so the result does not depend on random initialization order
+    let mut order: Vec<usize> = (0..k).collect();
+    order.sort_by(|&a, &b| {
+        centers[a].iter().zip(centers[b].iter())
+            .map(|(a, b)| a.total_cmp(b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut remap = vec![0u32; k];
+
+    for (position, &label) in order.iter().enumerate() {
+        remap[label] = position as u32 + 1;
+    }
+
+    let labels = ImgBuf::from_vec(size, assignment.iter().map(|&label| remap[label]).collect());
+    (labels, order.iter().map(|&label| centers[label]).collect())
+}
+
+/// k-means++ seeding, each next center is chosen with probability 
+/// proportional to squared distance to the nearest already chosen center
+fn initial_centers(points: &[Point], k: usize, seed: u64) -> Vec<Point> {
+    let mut random = Random::new(seed);
+    let mut centers = vec![points[random.index(points.len())]];
+    let mut distances: Vec<f64> = points.iter().map(|point| distance2(point, &centers[0])).collect();
+
+    while centers.len() < k {
+        let total: f64 = distances.iter().sum();
+
+        let chosen = if total > 0.0 {
+            let mut target = random.uniform()*total;
+            let mut chosen = points.len() - 1;
+
+            for (index, &distance) in distances.iter().enumerate() {
+                if target < distance {
+                    chosen = index;
+                    break;
+                }
+
+                target -= distance;
+            }
+
+            chosen
+        } else {
+            random.index(points.len())
+        };
+
+        centers.push(points[chosen]);
+
+        for (distance, point) in distances.iter_mut().zip(points.iter()) {
+            *distance = distance.min(distance2(point, &points[chosen]));
+        }
+    }
+
+    centers
+}
+
+fn nearest_center(centers: &[Point], point: &Point) -> (usize, f64) {
+    let mut best = (0, distance2(&centers[0], point));
+
+    for (index, center) in centers.iter().enumerate().skip(1) {
+        let distance = distance2(center, point);
+
+        if distance < best.1 {
+            best = (index, distance);
+        }
+    }
+
+    best
+}
+
+fn distance2(a: &Point, b: &Point) -> f64 {
+    (0..3).map(|dim| (a[dim] - b[dim]).powi(2)).sum()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn three_intensity_levels_are_found() {
+        let size = ImgSize::new(30, 4);
+        let mut image = ImgBuf::<u8>::new(size);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                image[(x, y)] = [20, 120, 220][x/10] + (y as u8 % 2);
+            }
+        }
+
+        for seed in 0..5 {
+            let (labels, centers) = kmeans(&image, 3, 20, seed);
+            assert_eq!(centers, vec![20.5, 120.5, 220.5]);
+            assert_eq!(labels[(0, 3)], 1);
+            assert_eq!(labels[(15, 0)], 2);
+            assert_eq!(labels[(29, 1)], 3);
+        }
+    }
+
+    #[test]
+    fn too_many_clusters_leave_some_empty() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(3, 3), 7);
+        let (labels, centers) = kmeans(&image, 3, 10, 1);
+        assert_eq!(centers, vec![7.0, 7.0, 7.0]);
+        assert!(labels.lines().all(|line| line.iter().all(|&label| label == labels[(0, 0)])));
+    }
+}
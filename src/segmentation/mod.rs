@@ -0,0 +1,36 @@
+//! Partitioning of images into labeled regions
+//! 
+//! Label maps are represented as `ImgBuf<u32>` images, where label `0` 
+//! denotes background and labels `1..=count` denote individual regions.
+//! 
+//! Available functions:
+//!  * [label_components](fn.label_components.html) - connected component 
+//!    labeling of binary masks
+//!  * [watershed](fn.watershed.html) - marker-controlled watershed
+//!    segmentation
+//!  * [kmeans](fn.kmeans.html), [kmeans_rgb](fn.kmeans_rgb.html) - k-means
+//!    clustering of pixel intensities or colors
+//!  * [slic](fn.slic.html) - SLIC superpixels
+//!  * [region_grow](fn.region_grow.html) - region growing from seed points
+//!  * [region_convex_hull](fn.region_convex_hull.html) - convex hull
+//!    of a labeled region rasterized into a mask
+//!  * [region_props](fn.region_props.html) - measurements of labeled
+//!    regions
+
+mod components;
+mod perimeter;
+mod watershed;
+mod kmeans;
+mod slic;
+mod grow;
+mod hull;
+mod props;
+
+pub use components::{label_components, Connectivity};
+pub(crate) use perimeter::region_perimeter;
+pub use watershed::watershed;
+pub use kmeans::{kmeans, kmeans_rgb};
+pub use slic::slic;
+pub use grow::region_grow;
+pub use hull::region_convex_hull;
+pub use props::{region_props, RegionProps};
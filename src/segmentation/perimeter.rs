@@ -0,0 +1,55 @@
+use crate::ImgRange;
+
+/// Length of contour passing through centers of border pixels of the
+/// region given by `inside` predicate, within `range` containing the whole
+/// region
+/// 
+/// Contour is approximated by marching squares, which is exact for 
+/// horizontal, vertical and diagonal edges and overestimates the length 
+/// of other slanted edges by up to 8 percent
+pub(crate) fn region_perimeter<F: Fn(isize, isize) -> bool>(inside: F, range: ImgRange) -> f64 {
+    let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+    let mut length = 0.0;
+
+    for y in (range.y.start - 1)..range.y.end {
+        for x in (range.x.start - 1)..range.x.end {
+            let corners = [
+                inside(x, y), inside(x + 1, y), 
+                inside(x, y + 1), inside(x + 1, y + 1)
+            ];
+
+            length += match corners.iter().filter(|&&corner| corner).count() {
+                1 | 3 => diagonal,
+                2 if corners[0] == corners[3] => 2.0*diagonal,
+                2 => 1.0,
+                _ => 0.0,
+            };
+        }
+    }
+
+    length
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Range2d, Vec2d};
+
+    #[test]
+    fn square_perimeter() {
+        let range = Range2d::new(0..4, 0..4);
+        let perimeter = region_perimeter(|x, y| range.contains(Vec2d::new(x, y)), range);
+        assert!((perimeter - (12.0 + 4.0*std::f64::consts::FRAC_1_SQRT_2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn disk_perimeter_is_close_to_circumference() {
+        let radius = 20.0;
+        let range = Range2d::new(-21..22, -21..22);
+        let perimeter = region_perimeter(|x, y| ((x*x + y*y) as f64).sqrt() <= radius, range);
+        let expected = 2.0*std::f64::consts::PI*radius;
+        assert!((perimeter - expected).abs()/expected < 0.08, "{}", perimeter);
+    }
+}
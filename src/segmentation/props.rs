@@ -0,0 +1,136 @@
+use crate::{Img, ImgRange, Range2d, Vec2d, stats::compute_moments};
+use super::region_perimeter;
+
+/// Measurements of a labeled region computed by 
+/// [region_props](fn.region_props.html)
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionProps {
+    /// Region label
+    pub label: u32,
+    /// Bounding range of region pixels
+    pub range: ImgRange,
+    /// Number of region pixels
+    pub area: usize,
+    /// Centroid of region pixels
+    pub centroid: Vec2d<f64>,
+    /// Mean of intensity image over region pixels, `None` when 
+    /// no intensity image is given
+    pub mean_intensity: Option<f64>,
+    /// Length of region contour passing through border pixel centers
+    pub perimeter: f64,
+    /// Eccentricity of the ellipse with the same second order moments,
+    /// `0` for circular shapes, approaching `1` for elongated shapes
+    pub eccentricity: f64,
+}
+
+/// Measures regions of a label map
+/// 
+/// Returns properties of all nonzero labels occurring in `labels`, ordered
+/// by label. Mean intensity is computed from `intensity` image of the same 
+/// size when given.
+/// 
+/// # Panics
+/// 
+/// Panics when intensity image size differs from the label map size
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, segmentation::region_props};
+/// let labels = ImgBuf::<u32>::from_vec(ImgSize::new(4, 2), vec![
+///     1, 1, 0, 2,
+///     1, 1, 0, 2,
+/// ]);
+/// let intensity = ImgBuf::<f32>::from_vec(ImgSize::new(4, 2), vec![
+///     1.0, 3.0, 0.0, 5.0,
+///     1.0, 3.0, 0.0, 7.0,
+/// ]);
+/// let props = region_props(&labels, Some(&intensity));
+/// assert_eq!(props.len(), 2);
+/// assert_eq!(props[0].area, 4);
+/// assert_eq!(props[0].centroid, Vec2d::new(0.5, 0.5));
+/// assert_eq!(props[0].mean_intensity, Some(2.0));
+/// assert_eq!(props[1].range, Range2d::new(3..4, 0..2));
+/// assert_eq!(props[1].mean_intensity, Some(6.0));
+/// ```
+pub fn region_props(labels: &dyn Img<u32>, intensity: Option<&dyn Img<f32>>) -> Vec<RegionProps> {
+    let size = labels.size();
+
+    if let Some(intensity) = intensity {
+        assert_eq!(intensity.size(), size, "Intensity image and label map sizes differ");
+    }
+
+    let count = labels.lines().flat_map(|line| line.iter()).copied().max().unwrap_or(0) as usize;
+    let mut ranges = vec![Range2d::new(0..0, 0..0); count];
+    let mut areas = vec![0usize; count];
+    let mut sums = vec![0.0f64; count];
+
+    for y in 0..size.y {
+        for (x, &label) in labels.line_ref(y).iter().enumerate() {
+            if label == 0 {
+                continue;
+            }
+
+            let index = label as usize - 1;
+            let (px, py) = (x as isize, y as isize);
+            let pixel = Range2d::new(px..(px + 1), py..(py + 1));
+            ranges[index] = if areas[index] == 0 { pixel } else { ranges[index].union(pixel) };
+            areas[index] += 1;
+
+            if let Some(intensity) = intensity {
+                sums[index] += intensity.line_ref(y)[x] as f64;
+            }
+        }
+    }
+
+    let size = size.cast::<isize>();
+
+    (0..count)
+        .filter(|&index| areas[index] > 0)
+        .map(|index| {
+            let label = index as u32 + 1;
+            let range = ranges[index];
+            let area = areas[index];
+
+            let inside = |x: isize, y: isize| {
+                x >= 0 && y >= 0 && x < size.x && y < size.y && labels.line_ref(y as usize)[x as usize] == label
+            };
+
+            let moments = compute_moments(range, |x, y| if labels.line_ref(y)[x] == label { 1.0 } else { 0.0 });
+
+            RegionProps {
+                label,
+                range,
+                area,
+                centroid: moments.centroid().unwrap_or_default(),
+                mean_intensity: intensity.map(|_| sums[index]/area as f64),
+                perimeter: region_perimeter(inside, range),
+                eccentricity: moments.eccentricity(),
+            }
+        })
+        .collect()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgBuf, ImgSize};
+    use super::*;
+
+    #[test]
+    fn missing_labels_are_skipped() {
+        let mut labels = ImgBuf::<u32>::new(ImgSize::new(10, 5));
+
+        for x in 1..9 {
+            labels[(x, 2)] = 3;
+        }
+
+        let props = region_props(&labels, None);
+        assert_eq!(props.len(), 1);
+        assert_eq!(props[0].label, 3);
+        assert_eq!(props[0].mean_intensity, None);
+        assert_eq!(props[0].centroid, Vec2d::new(4.5, 2.0));
+        assert!((props[0].eccentricity - 1.0).abs() < 1e-12);
+        assert!((props[0].perimeter - (14.0 + 2.0*std::f64::consts::SQRT_2)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{Img, ImgBuf, AsPrimitive, filter::update};
+
+/// Number of assignment and update iterations
+const ITERATIONS: usize = 10;
+
+/// SLIC superpixel segmentation of a grayscale image
+/// 
+/// Image is covered by a regular grid of cluster centers spaced by 
+/// `region_size` pixels (moved to the lowest gradient position in their 
+/// 3x3 neighborhood), pixels are then iteratively assigned to the nearest
+/// center within `2*region_size` window using combined distance
+/// `sqrt(d_intensity² + (d_spatial/region_size)²*compactness²)`. Higher
+/// `compactness` produces more regular superpixels, lower values make them
+/// adhere more to image edges.
+/// 
+/// Connectivity is enforced at the end, disconnected fragments smaller
+/// than a quarter of the nominal superpixel area are merged into an
+/// adjacent superpixel. Returns label map with labels `1..=count` and the 
+/// number of superpixels.
+/// 
+/// # Panics
+/// 
+/// Panics when `region_size` is zero
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, segmentation::slic};
+/// let mut image = ImgBuf::<u8>::new(ImgSize::new(20, 20));
+/// 
+/// // bright right half of the image
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = if position.x >= 10 { 200 } else { 0 };
+/// }
+/// 
+/// let (labels, count) = slic(&image, 10, 10.0);
+/// assert_eq!(count, 4);
+/// // no superpixel crosses the edge
+/// assert_ne!(labels[(9, 5)], labels[(10, 5)]);
+/// ```
+pub fn slic<T>(image: &dyn Img<T>, region_size: usize, compactness: f64) -> (ImgBuf<u32>, u32) 
+where T: AsPrimitive<f64> {
+    assert!(region_size > 0, "Region size must not be zero");
+
+    let size = image.size();
+    let mut values = ImgBuf::<f64>::new(size);
+
+    for y in 0..size.y {
+        let line = image.line_ref(y);
+
+        for x in 0..size.x {
+            values[(x, y)] = line[x].as_();
+        }
+    }
+
+    if size.x == 0 || size.y == 0 {
+        return (ImgBuf::new(size), 0);
+    }
+
+    let mut centers = initial_centers(&values, region_size);
+    let mut labels = ImgBuf::<usize>::new_init(size, usize::MAX);
+    let mut distances = ImgBuf::<f64>::new(size);
+    let spatial_weight = (compactness/region_size as f64).powi(2);
+
+    for _ in 0..ITERATIONS {
+        update(&mut distances, |_| f64::INFINITY);
+
+        for (index, center) in centers.iter().enumerate() {
+            let (cx, cy) = (center.x.round() as isize, center.y.round() as isize);
+            let reach = 2*region_size as isize;
+            let x_range = (cx - reach).max(0) as usize..((cx + reach + 1).max(0) as usize).min(size.x);
+            let y_range = (cy - reach).max(0) as usize..((cy + reach + 1).max(0) as usize).min(size.y);
+
+            for y in y_range {
+                for x in x_range.clone() {
+                    let color = (values[(x, y)] - center.value).powi(2);
+                    let spatial = (x as f64 - center.x).powi(2) + (y as f64 - center.y).powi(2);
+                    let distance = color + spatial*spatial_weight;
+
+                    if distance < distances[(x, y)] {
+                        distances[(x, y)] = distance;
+                        labels[(x, y)] = index;
+                    }
+                }
+            }
+        }
+
+        let mut sums = vec![Center::default(); centers.len()];
+        let mut counts = vec![0usize; centers.len()];
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let label = labels[(x, y)];
+
+                if label < centers.len() {
+                    sums[label].x += x as f64;
+                    sums[label].y += y as f64;
+                    sums[label].value += values[(x, y)];
+                    counts[label] += 1;
+                }
+            }
+        }
+
+        for (center, (sum, &count)) in centers.iter_mut().zip(sums.iter().zip(counts.iter())) {
+            if count > 0 {
+                let count = count as f64;
+                *center = Center { x: sum.x/count, y: sum.y/count, value: sum.value/count };
+            }
+        }
+    }
+
+    enforce_connectivity(&labels, (region_size*region_size/4).max(1))
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Center {
+    x: f64,
+    y: f64,
+    value: f64,
+}
+
+/// Regular grid of centers, each moved to the lowest gradient position
+/// within its 3x3 neighborhood to avoid seeding on edges
+fn initial_centers(values: &ImgBuf<f64>, step: usize) -> Vec<Center> {
+    let size = values.size();
+    let mut centers = Vec::new();
+
+    let gradient = |x: usize, y: usize| {
+        let (left, right) = (x.saturating_sub(1), (x + 1).min(size.x - 1));
+        let (top, bottom) = (y.saturating_sub(1), (y + 1).min(size.y - 1));
+        (values[(right, y)] - values[(left, y)]).powi(2) + (values[(x, bottom)] - values[(x, top)]).powi(2)
+    };
+
+    let mut y = step/2;
+
+    while y < size.y {
+        let mut x = step/2;
+
+        while x < size.x {
+            let mut best = (x, y);
+
+            for ny in y.saturating_sub(1)..(y + 2).min(size.y) {
+                for nx in x.saturating_sub(1)..(x + 2).min(size.x) {
+                    if gradient(nx, ny) < gradient(best.0, best.1) {
+                        best = (nx, ny);
+                    }
+                }
+            }
+
+            centers.push(Center { x: best.0 as f64, y: best.1 as f64, value: values[best] });
+            x += step;
+        }
+
+        y += step;
+    }
+
+    centers
+}
+
+/// Relabel 4-connected fragments of each cluster, fragments smaller than
+/// `min_area` are merged into a previously labeled adjacent superpixel
+fn enforce_connectivity(labels: &ImgBuf<usize>, min_area: usize) -> (ImgBuf<u32>, u32) {
+    let size = labels.size();
+    let mut result = ImgBuf::<u32>::new(size);
+    let mut count = 0;
+    let mut fragment = Vec::new();
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            if result[(x, y)] != 0 {
+                continue;
+            }
+
+            let label = labels[(x, y)];
+            let mut adjacent = 0;
+            count += 1;
+            result[(x, y)] = count;
+            fragment.clear();
+            fragment.push((x, y));
+            let mut next = 0;
+
+            while next < fragment.len() {
+                let (fx, fy) = fragment[next];
+                next += 1;
+
+                for (nx, ny) in four_neighbors(fx, fy, size.x, size.y) {
+                    let neighbor = result[(nx, ny)];
+
+                    if neighbor == 0 && labels[(nx, ny)] == label {
+                        result[(nx, ny)] = count;
+                        fragment.push((nx, ny));
+                    } else if neighbor != 0 && neighbor != count {
+                        adjacent = neighbor;
+                    }
+                }
+            }
+
+            if fragment.len() < min_area && adjacent != 0 {
+                for &position in fragment.iter() {
+                    result[position] = adjacent;
+                }
+
+                count -= 1;
+            }
+        }
+    }
+
+    (result, count)
+}
+
+fn four_neighbors(x: usize, y: usize, width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+    let left = if x > 0 { Some((x - 1, y)) } else { None };
+    let top = if y > 0 { Some((x, y - 1)) } else { None };
+    let right = if x + 1 < width { Some((x + 1, y)) } else { None };
+    let bottom = if y + 1 < height { Some((x, y + 1)) } else { None };
+    IntoIterator::into_iter([left, top, right, bottom]).flatten()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgSize, ImgLinesMut, filter::map_new, segmentation::{label_components, Connectivity}};
+    use super::*;
+
+    #[test]
+    fn flat_image_produces_regular_grid() {
+        let image = ImgBuf::<u8>::new(ImgSize::new(30, 20));
+        let (labels, count) = slic(&image, 10, 10.0);
+        assert_eq!(count, 6);
+        assert_eq!(labels[(0, 0)], 1);
+        assert_ne!(labels[(5, 5)], labels[(15, 5)]);
+        assert_ne!(labels[(5, 5)], labels[(5, 15)]);
+    }
+
+    #[test]
+    fn superpixels_are_connected_and_consecutive() {
+        let size = ImgSize::new(40, 30);
+        let mut image = ImgBuf::<f32>::new(size);
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            let (x, y) = (position.x as f32, position.y as f32);
+            *pixel = 100.0*((x*0.37).sin()*(y*0.23).cos()) + if x > 17.0 { 80.0 } else { 0.0 };
+        }
+
+        let (labels, count) = slic(&image, 8, 5.0);
+        assert!(count > 1);
+
+        // every label forms exactly one 4-connected component
+        for label in 1..=count {
+            let mask = map_new(&labels, |value| value == label);
+            let (_, components) = label_components(&mask, Connectivity::Four);
+            assert_eq!(components, 1, "label {}", label);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use crate::{Img, ImgBuf, AsPrimitive};
+
+/// Marker-controlled watershed segmentation
+/// 
+/// Floods `gradient` image from the `markers` label map, pixels with
+/// non-zero marker labels are seeds of the individual regions. Unlabeled
+/// pixels are processed in the order of increasing gradient value using
+/// a priority queue, each of them gets the label of the already labeled
+/// 4-connected neighbor it was reached from. Pixels with equal gradient
+/// are processed in the order they were reached, so plateaus are split
+/// evenly between competing regions.
+/// 
+/// All pixels reachable from any marker are labeled, no watershed lines
+/// are produced. Pixels not reachable from a marker (only possible when
+/// there are no markers at all) keep label `0`.
+/// 
+/// # Panics
+/// 
+/// Panics when marker image size differs from the gradient image size
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, segmentation::watershed};
+/// // two basins separated by a ridge in the middle
+/// let gradient = ImgBuf::<u8>::from_vec(ImgSize::new(7, 1), vec![
+///     0, 1, 2, 9, 2, 1, 0,
+/// ]);
+/// let markers = ImgBuf::<u32>::from_vec(ImgSize::new(7, 1), vec![
+///     1, 0, 0, 0, 0, 0, 2,
+/// ]);
+/// let labels = watershed(&gradient, &markers);
+/// assert_eq!(labels.line_ref(0)[..3], [1, 1, 1]);
+/// assert_eq!(labels.line_ref(0)[4..], [2, 2, 2]);
+/// ```
+pub fn watershed<T>(gradient: &dyn Img<T>, markers: &dyn Img<u32>) -> ImgBuf<u32>
+where T: AsPrimitive<f64> {
+    let size = gradient.size();
+    assert_eq!(markers.size(), size, "Marker image size differs from gradient image");
+
+    let mut labels = ImgBuf::<u32>::new(size);
+    let mut queued = ImgBuf::<bool>::new(size);
+    let mut queue = BinaryHeap::new();
+    let mut order = 0u64;
+
+    for y in 0..size.y {
+        let line = markers.line_ref(y);
+
+        for x in 0..size.x {
+            if line[x] > 0 {
+                labels[(x, y)] = line[x];
+                queued[(x, y)] = true;
+            }
+        }
+    }
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            if labels[(x, y)] > 0 {
+                push_neighbors(gradient, &mut queued, &mut queue, &mut order, x, y);
+            }
+        }
+    }
+
+    while let Some(entry) = queue.pop() {
+        labels[(entry.x, entry.y)] = labels[(entry.from_x, entry.from_y)];
+        push_neighbors(gradient, &mut queued, &mut queue, &mut order, entry.x, entry.y);
+    }
+
+    labels
+}
+
+/// Pixel waiting in the flooding queue
+struct Entry {
+    value: f64,
+    order: u64,
+    x: usize,
+    y: usize,
+    from_x: usize,
+    from_y: usize,
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed to make the max-heap pop the lowest (and oldest) entry first
+        other.value.total_cmp(&self.value).then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+fn push_neighbors<T>(
+    gradient: &dyn Img<T>,
+    queued: &mut ImgBuf<bool>,
+    queue: &mut BinaryHeap<Entry>,
+    order: &mut u64,
+    x: usize,
+    y: usize,
+)
+where T: AsPrimitive<f64> {
+    let size = gradient.size();
+    let mut neighbors = Vec::with_capacity(4);
+
+    if x > 0 { neighbors.push((x - 1, y)); }
+    if y > 0 { neighbors.push((x, y - 1)); }
+    if x + 1 < size.x { neighbors.push((x + 1, y)); }
+    if y + 1 < size.y { neighbors.push((x, y + 1)); }
+
+    for (nx, ny) in neighbors {
+        if queued[(nx, ny)] {
+            continue;
+        }
+
+        queued[(nx, ny)] = true;
+        let value = gradient.line_ref(ny)[nx].as_();
+        queue.push(Entry { value, order: *order, x: nx, y: ny, from_x: x, from_y: y });
+        *order += 1;
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn touching_disks_are_separated_along_the_ridge() {
+        let size = ImgSize::new(20, 10);
+        let mut gradient = ImgBuf::<f32>::new(size);
+        let mut markers = ImgBuf::<u32>::new(size);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                // distance to the nearer of two centers forms a ridge at x = 10
+                let left = ((x as f32 - 5.0).powi(2) + (y as f32 - 5.0).powi(2)).sqrt();
+                let right = ((x as f32 - 14.0).powi(2) + (y as f32 - 5.0).powi(2)).sqrt();
+                gradient[(x, y)] = left.min(right);
+            }
+        }
+
+        markers[(5, 5)] = 1;
+        markers[(14, 5)] = 7;
+        let labels = watershed(&gradient, &markers);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let expected = if x < 10 { 1 } else { 7 };
+                assert_eq!(labels[(x, y)], expected, "pixel ({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn without_markers_nothing_is_labeled() {
+        let gradient = ImgBuf::<u8>::new(ImgSize::new(3, 3));
+        let markers = ImgBuf::<u32>::new(ImgSize::new(3, 3));
+        let labels = watershed(&gradient, &markers);
+        assert!(labels.lines().all(|line| line.iter().all(|&label| label == 0)));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{Img, ImgMut, ImgLinesMut, ImgBuf, ImgSize, color::Rgb, pyramid};
+
+/// Standard deviation of the well-exposedness weight around mid-gray
+const EXPOSURE_SIGMA: f32 = 0.2;
+/// Small weight added to all pixels, so pixels with all weights zero 
+/// are averaged
+const EPSILON: f32 = 1e-12;
+
+/// Fuses differently exposed grayscale images of the same scene 
+/// into a single well exposed image (Mertens exposure fusion)
+/// 
+/// Every pixel of each frame gets weight given by product of local 
+/// contrast (absolute response of Laplacian filter) and well-exposedness
+/// (closeness to mid-gray), frames are then blended using weights in
+/// multiple scales via Laplacian pyramids, which avoids seams. 
+/// 
+/// Panics if `frames` is empty or frame sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, stack::exposure_fusion};
+/// let dark = ImgBuf::<u8>::new_init(ImgSize::new(8, 8), 20);
+/// let bright = ImgBuf::<u8>::new_init(ImgSize::new(8, 8), 240);
+/// let normal = ImgBuf::<u8>::new_init(ImgSize::new(8, 8), 120);
+/// let fused = exposure_fusion(&[&dark, &bright, &normal]);
+/// assert!(fused.lines().all(|line| line.iter().all(|&v| v > 100 && v < 140)));
+/// ```
+pub fn exposure_fusion(frames: &[&dyn Img<u8>]) -> ImgBuf<u8> {
+    let planes: Vec<Vec<ImgBuf<f32>>> = frames.iter()
+        .map(|frame| vec![to_plane(*frame, |value| value as f32/255.0)])
+        .collect();
+
+    let weights: Vec<ImgBuf<f32>> = planes.iter()
+        .map(|channels| weight(&channels[0], None))
+        .collect();
+
+    let fused = fuse(&planes, weights);
+    generate(fused[0].size(), |x, y| to_u8(fused[0][(x, y)]))
+}
+
+/// Fuses differently exposed color images of the same scene 
+/// into a single well exposed image (Mertens exposure fusion)
+/// 
+/// Same as [exposure_fusion](fn.exposure_fusion.html), pixel weights
+/// are in addition multiplied by color saturation (standard deviation
+/// of color components) and well-exposedness is evaluated per component.
+/// 
+/// Panics if `frames` is empty or frame sizes differ
+pub fn exposure_fusion_rgb(frames: &[&dyn Img<Rgb<u8>>]) -> ImgBuf<Rgb<u8>> {
+    let planes: Vec<Vec<ImgBuf<f32>>> = frames.iter()
+        .map(|frame| vec![
+            to_plane(*frame, |pixel| pixel.r as f32/255.0),
+            to_plane(*frame, |pixel| pixel.g as f32/255.0),
+            to_plane(*frame, |pixel| pixel.b as f32/255.0),
+        ])
+        .collect();
+
+    let weights: Vec<ImgBuf<f32>> = planes.iter()
+        .map(|channels| {
+            let gray = generate(channels[0].size(), |x, y| {
+                (channels[0][(x, y)] + channels[1][(x, y)] + channels[2][(x, y)])/3.0
+            });
+
+            weight(&gray, Some(channels))
+        })
+        .collect();
+
+    let fused = fuse(&planes, weights);
+
+    generate(fused[0].size(), |x, y| Rgb::new(
+        to_u8(fused[0][(x, y)]), to_u8(fused[1][(x, y)]), to_u8(fused[2][(x, y)])
+    ))
+}
+
+/// Fusion weight of each pixel computed from grayscale image and 
+/// optional color channels
+fn weight(gray: &ImgBuf<f32>, channels: Option<&[ImgBuf<f32>]>) -> ImgBuf<f32> {
+    let size = gray.size();
+    let exposedness = |value: f32| (-(value - 0.5).powi(2)/(2.0*EXPOSURE_SIGMA*EXPOSURE_SIGMA)).exp();
+
+    generate(size, |x, y| {
+        let contrast = laplacian(gray, x, y).abs();
+
+        let (saturation, exposure) = match channels {
+            Some(channels) => {
+                let values: Vec<f32> = channels.iter().map(|channel| channel[(x, y)]).collect();
+                let mean = values.iter().sum::<f32>()/3.0;
+                let deviation = (values.iter().map(|v| (v - mean).powi(2)).sum::<f32>()/3.0).sqrt();
+                (deviation, values.iter().map(|&v| exposedness(v)).product())
+            },
+            None => (1.0, exposedness(gray[(x, y)])),
+        };
+
+        contrast*saturation*exposure + EPSILON
+    })
+}
+
+/// Blends `planes` (frames of channels) using normalized `weights` 
+/// of the frames via Laplacian pyramids
+fn fuse(planes: &[Vec<ImgBuf<f32>>], mut weights: Vec<ImgBuf<f32>>) -> Vec<ImgBuf<f32>> {
+    let size = planes.first().expect("At least one frame is required")[0].size();
+
+    for frame in planes {
+        assert_eq!(frame[0].size(), size, "All frames must be of the same size");
+    }
+
+    normalize(&mut weights);
+
+    let shorter = std::cmp::min(size.x, size.y);
+    let levels = std::cmp::max(1, (usize::BITS - shorter.leading_zeros()) as usize);
+    let weight_pyramids: Vec<_> = weights.iter().map(|w| pyramid::gaussian(w, levels)).collect();
+
+    (0..planes[0].len())
+        .map(|channel| {
+            let mut fused: Option<Vec<ImgBuf<f32>>> = None;
+
+            for (frame, weight_pyramid) in planes.iter().zip(&weight_pyramids) {
+                let mut detail = pyramid::laplacian(&frame[channel], levels);
+
+                for (level, weight) in detail.iter_mut().zip(weight_pyramid) {
+                    multiply(level, weight);
+                }
+
+                fused = Some(match fused {
+                    None => detail,
+                    Some(mut sum) => {
+                        sum.iter_mut().zip(&detail).for_each(|(a, b)| accumulate(a, b));
+                        sum
+                    }
+                });
+            }
+
+            pyramid::collapse(&fused.unwrap_or_default())
+        })
+        .collect()
+}
+
+/// Normalizes weights so they sum to one for every pixel
+fn normalize(weights: &mut [ImgBuf<f32>]) {
+    let size = weights[0].size();
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let sum: f32 = weights.iter().map(|weight| weight[(x, y)]).sum();
+
+            for weight in weights.iter_mut() {
+                weight[(x, y)] /= sum;
+            }
+        }
+    }
+}
+
+/// Response of 3x3 Laplacian filter at `(x, y)`, border pixels are replicated
+fn laplacian(image: &ImgBuf<f32>, x: usize, y: usize) -> f32 {
+    let size = image.size();
+    let left = image[(x.saturating_sub(1), y)];
+    let right = image[(std::cmp::min(x + 1, size.x - 1), y)];
+    let top = image[(x, y.saturating_sub(1))];
+    let bottom = image[(x, std::cmp::min(y + 1, size.y - 1))];
+    left + right + top + bottom - 4.0*image[(x, y)]
+}
+
+/// Multiplies `image` pixels by `weight` pixels
+fn multiply(image: &mut ImgBuf<f32>, weight: &ImgBuf<f32>) {
+    for (line, weights) in image.lines_mut().zip(weight.lines()) {
+        for x in 0..line.len() {
+            line[x] *= weights[x];
+        }
+    }
+}
+
+/// Adds `other` image pixels to `image`
+fn accumulate(image: &mut ImgBuf<f32>, other: &ImgBuf<f32>) {
+    for (line, values) in image.lines_mut().zip(other.lines()) {
+        for x in 0..line.len() {
+            line[x] += values[x];
+        }
+    }
+}
+
+/// Extracts a floating point plane from image
+fn to_plane<T: Copy, F: Fn(T) -> f32>(image: &dyn Img<T>, function: F) -> ImgBuf<f32> {
+    generate(image.size(), |x, y| function(image.line_ref(y)[x]))
+}
+
+/// Creates image of `size` evaluating `function` for every pixel position
+fn generate<T: Copy + Default, F: Fn(usize, usize) -> T>(size: ImgSize, function: F) -> ImgBuf<T> {
+    let mut output = ImgBuf::<T>::new(size);
+
+    for y in 0..size.y {
+        let line = output.line_mut(y);
+
+        for x in 0..size.x {
+            line[x] = function(x, y);
+        }
+    }
+
+    output
+}
+
+/// Converts value in `[0, 1]` range to rounded and clipped 8-bit value
+fn to_u8(value: f32) -> u8 {
+    (value*255.0).round().clamp(0.0, 255.0) as u8
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_exposed_detail_is_preferred() {
+        let size = ImgSize::new(16, 16);
+        let overexposed = ImgBuf::<u8>::new_init(size, 255);
+        let mut detailed = ImgBuf::<u8>::new_init(size, 140);
+
+        for (position, pixel) in detailed.enumerate_pixels_mut() {
+            if (position.x + position.y) % 2 == 0 {
+                *pixel = 100;
+            }
+        }
+
+        let fused = exposure_fusion(&[&overexposed, &detailed]);
+        let error: i32 = fused.lines().zip(detailed.lines())
+            .flat_map(|(a, b)| a.iter().zip(b).map(|(&x, &y)| (x as i32 - y as i32).abs()))
+            .max()
+            .unwrap();
+
+        assert!(error <= 1, "error = {}", error);
+    }
+
+    #[test]
+    fn single_color_frame_is_preserved() {
+        let frame = ImgBuf::<Rgb<u8>>::new_init(ImgSize::new(5, 3), Rgb::new(200, 100, 50));
+        assert_eq!(exposure_fusion_rgb(&[&frame]), frame);
+    }
+}
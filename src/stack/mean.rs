@@ -0,0 +1,128 @@
+use crate::{Img, ImgMut, ImgBuf, ImgSize, AsPrimitive, stats::{mean_and_std_dev, sigma_clip}};
+
+/// Computes per-pixel average of all `frames`
+/// 
+/// Panics if `frames` is empty or frame sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, stack::mean};
+/// let first = ImgBuf::<f32>::new_init(ImgSize::new(2, 2), 1.0);
+/// let second = ImgBuf::<f32>::new_init(ImgSize::new(2, 2), 3.0);
+/// let result = mean(&[&first, &second]);
+/// assert_eq!(result[(1, 1)], 2.0);
+/// ```
+pub fn mean(frames: &[&dyn Img<f32>]) -> ImgBuf<f32> {
+    mean_as_f32(frames)
+}
+
+/// Computes per-pixel average of all `frames` of any pixel type 
+/// convertible to `f32`, see [mean](fn.mean.html)
+pub(crate) fn mean_as_f32<T: AsPrimitive<f32>>(frames: &[&dyn Img<T>]) -> ImgBuf<f32> {
+    let size = check_frames(frames);
+    let mut output = ImgBuf::<f32>::new(size);
+    let scale = 1.0/frames.len() as f32;
+
+    for line in 0..size.y {
+        let dst = output.line_mut(line);
+
+        for frame in frames {
+            let src = frame.line_ref(line);
+
+            for x in 0..dst.len() {
+                dst[x] += src[x].as_();
+            }
+        }
+
+        for x in 0..dst.len() {
+            dst[x] *= scale;
+        }
+    }
+
+    output
+}
+
+/// Computes per-pixel average of all `frames` with outliers rejected
+/// 
+/// For every pixel, values further than `kappa` standard deviations 
+/// from the median of the pixel values are iteratively rejected 
+/// (at most `iterations` times) and the remaining values are averaged.
+/// Typical values are `kappa` between 2.0 and 3.0 and a few iterations,
+/// at least 3 frames are needed for the rejection to be meaningful.
+/// 
+/// Panics if `frames` is empty or frame sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, stack::sigma_clip_mean};
+/// let frames: Vec<_> = [10.0, 11.0, 9.0, 10.0, 10.0, 500.0].iter()
+///     .map(|&value| ImgBuf::<f32>::new_init(ImgSize::new(1, 1), value))
+///     .collect();
+/// let refs: Vec<&dyn Img<f32>> = frames.iter().map(|f| f as &dyn Img<f32>).collect();
+/// let result = sigma_clip_mean(&refs, 2.0, 3);
+/// assert_eq!(result[(0, 0)], 10.0);
+/// ```
+pub fn sigma_clip_mean(
+    frames: &[&dyn Img<f32>], 
+    kappa: f32, 
+    iterations: usize
+) -> ImgBuf<f32> {
+    let size = check_frames(frames);
+    let mut output = ImgBuf::<f32>::new(size);
+    let mut values = vec![0.0; frames.len()];
+
+    for line in 0..size.y {
+        let lines: Vec<&[f32]> = frames.iter().map(|frame| frame.line_ref(line)).collect();
+        let dst = output.line_mut(line);
+
+        for x in 0..dst.len() {
+            for index in 0..lines.len() {
+                values[index] = lines[index][x];
+            }
+
+            let count = sigma_clip(&mut values, kappa, iterations);
+            dst[x] = mean_and_std_dev(&values[..count]).map(|(mean, _)| mean).unwrap_or(0.0);
+        }
+    }
+
+    output
+}
+
+/// Checks that there is at least one frame and all frames have the same size
+fn check_frames<T>(frames: &[&dyn Img<T>]) -> ImgSize {
+    let size = frames.first().expect("At least one frame is required").size();
+
+    for frame in frames {
+        assert_eq!(frame.size(), size, "All frames must be of the same size");
+    }
+
+    size
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sigma_clip_mean_rejects_single_pixel_outlier() {
+        let size = ImgSize::new(3, 2);
+        let mut frames: Vec<_> = (0..5).map(|_| ImgBuf::<f32>::new_init(size, 4.0)).collect();
+        frames[2][(1, 1)] = 1000.0;
+
+        let refs: Vec<&dyn Img<f32>> = frames.iter().map(|f| f as &dyn Img<f32>).collect();
+        let result = sigma_clip_mean(&refs, 1.5, 2);
+
+        assert!(result.lines().all(|line| line.iter().all(|&v| v == 4.0)));
+        assert_eq!(mean(&refs)[(1, 1)], 203.2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn frames_of_different_size_panic() {
+        let first = ImgBuf::<f32>::new(ImgSize::new(3, 2));
+        let second = ImgBuf::<f32>::new(ImgSize::new(2, 3));
+        mean(&[&first, &second]);
+    }
+}
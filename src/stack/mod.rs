@@ -0,0 +1,19 @@
+//! Combining multiple frames of the same scene into a single image
+//! 
+//! Stacking reduces noise of the resulting image, all frames are expected
+//! to be aligned and of the same size.
+//! 
+//! Available functions:
+//!  * [mean](fn.mean.html) - plain per-pixel average of all frames
+//!  * [sigma_clip_mean](fn.sigma_clip_mean.html) - per-pixel average with
+//!    outliers (cosmic rays, satellite trails, hot pixels) rejected
+//!  * [exposure_fusion](fn.exposure_fusion.html), 
+//!    [exposure_fusion_rgb](fn.exposure_fusion_rgb.html) - blending 
+//!    of differently exposed frames into a single well exposed image
+
+mod mean;
+mod fusion;
+
+pub use mean::{mean, sigma_clip_mean};
+pub(crate) use mean::mean_as_f32;
+pub use fusion::{exposure_fusion, exposure_fusion_rgb};
\ No newline at end of file
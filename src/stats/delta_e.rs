@@ -0,0 +1,174 @@
+use crate::{Img, ImgBuf, color::{Lab, Rgb}};
+use super::robust::quantile;
+
+/// Summary of a color difference map computed by 
+/// [delta_e2000](fn.delta_e2000.html)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DeltaEStats {
+    /// Mean color difference
+    pub mean: f32,
+    /// Median color difference
+    pub median: f32,
+    /// 95th percentile of color differences
+    pub p95: f32,
+    /// Maximal color difference
+    pub max: f32,
+}
+
+/// Per-pixel CIEDE2000 color difference of two 8-bit sRGB images
+/// 
+/// Pixels are converted to CIE L\*a\*b\* (D65) and compared by 
+/// [ciede2000](fn.ciede2000.html), returns the difference map and its 
+/// summary statistics. Differences below 1 are generally not perceptible,
+/// differences around 2 are perceptible on close observation.
+/// 
+/// # Panics
+/// 
+/// Panics when image sizes differ
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, color::Rgb, stats::delta_e2000};
+/// let a = ImgBuf::new_init(ImgSize::new(4, 4), Rgb::new(120u8, 60, 30));
+/// let mut b = a.clone();
+/// b[(0, 0)] = Rgb::new(122, 60, 30);
+/// 
+/// let (map, stats) = delta_e2000(&a, &b);
+/// assert!(map[(0, 0)] > 0.5 && map[(0, 0)] < 1.5);
+/// assert_eq!(map[(1, 0)], 0.0);
+/// assert_eq!(stats.max, map[(0, 0)]);
+/// assert_eq!(stats.median, 0.0);
+/// ```
+pub fn delta_e2000(a: &dyn Img<Rgb<u8>>, b: &dyn Img<Rgb<u8>>) -> (ImgBuf<f32>, DeltaEStats) {
+    let size = a.size();
+    assert_eq!(b.size(), size, "Image sizes differ");
+
+    let lab = |pixel: Rgb<u8>| Lab::from_srgb(Rgb::new(
+        pixel.r as f64/255.0, pixel.g as f64/255.0, pixel.b as f64/255.0
+    ));
+
+    let mut map = ImgBuf::<f32>::new(size);
+    let mut values = Vec::with_capacity(size.product());
+
+    for y in 0..size.y {
+        let (first, second) = (a.line_ref(y), b.line_ref(y));
+
+        for x in 0..size.x {
+            let difference = if first[x] == second[x] { 
+                0.0 
+            } else { 
+                ciede2000(lab(first[x]), lab(second[x])) as f32 
+            };
+
+            map[(x, y)] = difference;
+            values.push(difference);
+        }
+    }
+
+    let stats = if values.is_empty() {
+        DeltaEStats::default()
+    } else {
+        DeltaEStats {
+            mean: (values.iter().map(|&v| v as f64).sum::<f64>()/values.len() as f64) as f32,
+            max: values.iter().fold(0.0f32, |a, &b| a.max(b)),
+            median: quantile(&mut values, 0.5).unwrap_or(0.0),
+            p95: quantile(&mut values, 0.95).unwrap_or(0.0),
+        }
+    };
+
+    (map, stats)
+}
+
+/// CIEDE2000 color difference of two L\*a\*b\* colors (with unit 
+/// parametric weighting factors)
+/// 
+/// # Example
+/// ```
+/// use nanocv::{color::Lab, stats::ciede2000};
+/// let difference = ciede2000(Lab::new(50.0, 2.6772, -79.7751), Lab::new(50.0, 0.0, -82.7485));
+/// assert!((difference - 2.0425).abs() < 1e-4);
+/// ```
+pub fn ciede2000(first: Lab, second: Lab) -> f64 {
+    use std::f64::consts::PI;
+
+    let degrees = |radians: f64| radians*180.0/PI;
+    let radians = |degrees: f64| degrees*PI/180.0;
+
+    let chroma = |lab: Lab| (lab.a*lab.a + lab.b*lab.b).sqrt();
+    let mean_chroma = (chroma(first) + chroma(second))/2.0;
+    let g = 0.5*(1.0 - (mean_chroma.powi(7)/(mean_chroma.powi(7) + 25f64.powi(7))).sqrt());
+
+    // Chroma and hue (in degrees) with the a* axis rescaled
+    let adjusted = |lab: Lab| {
+        let a = (1.0 + g)*lab.a;
+        let c = (a*a + lab.b*lab.b).sqrt();
+        let h = if a == 0.0 && lab.b == 0.0 { 0.0 } else { degrees(lab.b.atan2(a)).rem_euclid(360.0) };
+        (c, h)
+    };
+
+    let ((c1, h1), (c2, h2)) = (adjusted(first), adjusted(second));
+    let delta_l = second.l - first.l;
+    let delta_c = c2 - c1;
+
+    let delta_h_angle = if c1*c2 == 0.0 {
+        0.0
+    } else if (h2 - h1).abs() <= 180.0 {
+        h2 - h1
+    } else if h2 - h1 > 180.0 {
+        h2 - h1 - 360.0
+    } else {
+        h2 - h1 + 360.0
+    };
+
+    let delta_h = 2.0*(c1*c2).sqrt()*radians(delta_h_angle/2.0).sin();
+    let mean_l = (first.l + second.l)/2.0;
+    let mean_c = (c1 + c2)/2.0;
+
+    let mean_h = if c1*c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0 {
+        (h1 + h2)/2.0
+    } else if h1 + h2 < 360.0 {
+        (h1 + h2 + 360.0)/2.0
+    } else {
+        (h1 + h2 - 360.0)/2.0
+    };
+
+    let t = 1.0 - 0.17*radians(mean_h - 30.0).cos() + 0.24*radians(2.0*mean_h).cos()
+        + 0.32*radians(3.0*mean_h + 6.0).cos() - 0.20*radians(4.0*mean_h - 63.0).cos();
+
+    let delta_theta = 30.0*(-((mean_h - 275.0)/25.0).powi(2)).exp();
+    let rc = 2.0*(mean_c.powi(7)/(mean_c.powi(7) + 25f64.powi(7))).sqrt();
+    let sl = 1.0 + 0.015*(mean_l - 50.0).powi(2)/(20.0 + (mean_l - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045*mean_c;
+    let sh = 1.0 + 0.015*mean_c*t;
+    let rt = -radians(2.0*delta_theta).sin()*rc;
+
+    let (l, c, h) = (delta_l/sl, delta_c/sc, delta_h/sh);
+    (l*l + c*c + h*h + rt*c*h).sqrt()
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_color_pairs() {
+        // Test data published by Sharma, Wu and Dalal (2005)
+        let pairs = [
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            ((50.0, -0.001, 2.49), (50.0, 0.0011, -2.49), 4.7461),
+            ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+            ((2.0776, 0.0795, -1.1350), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+
+        for &((l1, a1, b1), (l2, a2, b2), expected) in &pairs {
+            let (first, second) = (Lab::new(l1, a1, b1), Lab::new(l2, a2, b2));
+            assert!((ciede2000(first, second) - expected).abs() < 1e-4);
+            assert!((ciede2000(second, first) - expected).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{Img, ImgRange, Vec2d, Range2d};
+
+/// Normalized symmetric gray-level co-occurrence matrix
+/// 
+/// Element `(i, j)` is the probability that a pixel with quantized level 
+/// `i` and a pixel displaced by the offset have level `j` (or vice versa).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Glcm {
+    /// Number of gray levels
+    pub levels: usize,
+    /// Probabilities in row-major order, `levels*levels` elements
+    pub matrix: Vec<f64>,
+}
+
+/// Computes gray-level co-occurrence matrix of the whole `image`
+/// 
+/// Pixel values are quantized into `levels` levels as `value*levels/256`,
+/// every pixel `p` with `p + offset` lying inside the image contributes 
+/// pair `(level(p), level(p + offset))` together with the swapped pair, 
+/// so the matrix is symmetric. Counts are normalized to sum to one, image
+/// without any pair produces zero matrix.
+/// 
+/// # Panics
+/// 
+/// Panics when `levels` is zero or greater than 256
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, stats::glcm};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(4, 2), vec![
+///     0, 0, 255, 255,
+///     0, 0, 255, 255,
+/// ]);
+/// let matrix = glcm(&image, Vec2d::new(1, 0), 2);
+/// assert_eq!(matrix.matrix, vec![1.0/3.0, 1.0/6.0, 1.0/6.0, 1.0/3.0]);
+/// assert_eq!(matrix.contrast(), 1.0/3.0);
+/// ```
+pub fn glcm(image: &dyn Img<u8>, offset: Vec2d<isize>, levels: usize) -> Glcm {
+    glcm_range(image, image.range(), offset, levels)
+}
+
+/// Computes gray-level co-occurrence matrix of pixels within `range`, 
+/// both pixels of every pair must lie inside the range (clipped to image
+/// bounds), see [glcm](fn.glcm.html)
+pub fn glcm_range(image: &dyn Img<u8>, range: ImgRange, offset: Vec2d<isize>, levels: usize) -> Glcm {
+    assert!((1..=256).contains(&levels), "Number of levels must be in 1..=256 range");
+
+    let range = Range2d::<usize>::from(range.clamp_to(image.range()));
+    let mut counts = vec![0u64; levels*levels];
+    let level = |value: u8| value as usize*levels/256;
+
+    for y in range.y.start..range.y.end {
+        let other_y = y as isize + offset.y;
+
+        if other_y < range.y.start as isize || other_y >= range.y.end as isize {
+            continue;
+        }
+
+        let (line, other) = (image.line_ref(y), image.line_ref(other_y as usize));
+
+        for x in range.x.start..range.x.end {
+            let other_x = x as isize + offset.x;
+
+            if other_x < range.x.start as isize || other_x >= range.x.end as isize {
+                continue;
+            }
+
+            let (i, j) = (level(line[x]), level(other[other_x as usize]));
+            counts[i*levels + j] += 1;
+            counts[j*levels + i] += 1;
+        }
+    }
+
+    let total: u64 = counts.iter().sum();
+    let scale = if total > 0 { 1.0/total as f64 } else { 0.0 };
+    Glcm { levels, matrix: counts.iter().map(|&count| count as f64*scale).collect() }
+}
+
+impl Glcm {
+    /// Probability of level pair `(i, j)`
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        self.matrix[i*self.levels + j]
+    }
+
+    /// Contrast `sum(p(i, j)*(i - j)²)`, zero for uniform images
+    pub fn contrast(&self) -> f64 {
+        self.sum(|i, j, p| p*(i - j)*(i - j))
+    }
+
+    /// Correlation of levels of paired pixels in `-1..=1` range, one for
+    /// constant images (zero variance)
+    /// ```
+    /// use nanocv::{*, stats::glcm};
+    /// let image = ImgBuf::<u8>::from_vec(ImgSize::new(4, 1), vec![0, 100, 200, 250]);
+    /// assert!(glcm(&image, Vec2d::new(1, 0), 4).correlation() > 0.0);
+    /// ```
+    pub fn correlation(&self) -> f64 {
+        // Matrix is symmetric, so both marginal distributions are the same
+        let mean = self.sum(|i, _, p| p*i);
+        let variance = self.sum(|i, _, p| p*(i - mean)*(i - mean));
+
+        if variance <= 0.0 {
+            return 1.0;
+        }
+
+        self.sum(|i, j, p| p*(i - mean)*(j - mean))/variance
+    }
+
+    /// Energy, square root of angular second moment `sum(p(i, j)²)`,
+    /// one for uniform images
+    pub fn energy(&self) -> f64 {
+        self.sum(|_, _, p| p*p).sqrt()
+    }
+
+    /// Homogeneity (inverse difference moment) `sum(p(i, j)/(1 + (i - j)²))`
+    pub fn homogeneity(&self) -> f64 {
+        self.sum(|i, j, p| p/(1.0 + (i - j)*(i - j)))
+    }
+
+    /// Entropy `-sum(p(i, j)*ln(p(i, j)))` in nats
+    pub fn entropy(&self) -> f64 {
+        -self.sum(|_, _, p| if p > 0.0 { p*p.ln() } else { 0.0 })
+    }
+
+    fn sum<F: Fn(f64, f64, f64) -> f64>(&self, term: F) -> f64 {
+        let mut sum = 0.0;
+
+        for i in 0..self.levels {
+            for j in 0..self.levels {
+                sum += term(i as f64, j as f64, self.get(i, j));
+            }
+        }
+
+        sum
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgBuf, ImgSize};
+    use super::*;
+
+    #[test]
+    fn uniform_image_has_extreme_features() {
+        let image = ImgBuf::<u8>::new_init(ImgSize::new(5, 5), 130);
+        let matrix = glcm(&image, Vec2d::new(0, 1), 8);
+        assert_eq!(matrix.get(4, 4), 1.0);
+        assert_eq!(matrix.contrast(), 0.0);
+        assert_eq!(matrix.correlation(), 1.0);
+        assert_eq!(matrix.energy(), 1.0);
+        assert_eq!(matrix.homogeneity(), 1.0);
+        assert_eq!(matrix.entropy(), 0.0);
+    }
+
+    #[test]
+    fn checkerboard_is_anticorrelated() {
+        let image = ImgBuf::<u8>::from_vec(ImgSize::new(4, 4), (0..16)
+            .map(|index| if (index%4 + index/4)%2 == 0 { 0 } else { 255 })
+            .collect());
+
+        let horizontal = glcm(&image, Vec2d::new(1, 0), 2);
+        assert_eq!(horizontal.get(0, 1), 0.5);
+        assert!((horizontal.correlation() + 1.0).abs() < 1e-12);
+        assert!((horizontal.entropy() - 2f64.ln()).abs() < 1e-12);
+
+        // diagonal neighbors have the same color
+        assert_eq!(glcm(&image, Vec2d::new(1, 1), 2).contrast(), 0.0);
+    }
+
+    #[test]
+    fn range_limits_pixel_pairs() {
+        let image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 1), vec![0, 0, 255]);
+        let matrix = glcm_range(&image, Range2d::new(0..2, 0..1), Vec2d::new(1, 0), 2);
+        assert_eq!(matrix.matrix, vec![1.0, 0.0, 0.0, 0.0]);
+
+        // offset larger than the image produces no pairs
+        assert!(glcm(&image, Vec2d::new(5, 0), 2).matrix.iter().all(|&p| p == 0.0));
+    }
+}
@@ -0,0 +1,20 @@
+//! Statistical description of images and image regions
+//! 
+//! Available functions:
+//!  * [moments](fn.moments.html), [moments_mask](fn.moments_mask.html) - 
+//!    image moments and Hu invariants
+//!  * [glcm](fn.glcm.html), [glcm_range](fn.glcm_range.html) - gray-level
+//!    co-occurrence matrix and derived texture features
+//!  * [delta_e2000](fn.delta_e2000.html), [ciede2000](fn.ciede2000.html) - 
+//!    perceptual color differences
+
+mod robust;
+mod moments;
+mod glcm;
+mod delta_e;
+
+pub use moments::{moments, moments_mask, Moments};
+pub use glcm::{glcm, glcm_range, Glcm};
+pub use delta_e::{delta_e2000, ciede2000, DeltaEStats};
+pub(crate) use moments::compute as compute_moments;
+pub(crate) use robust::{median, quantile, mad_std_dev, mean_and_std_dev, sigma_clip};
@@ -0,0 +1,208 @@
+use crate::{Img, ImgRange, Vec2d, AsPrimitive};
+
+/// Image moments up to the third order
+/// 
+/// Raw moments `mpq = sum(x^p*y^q*I(x, y))` are computed in image 
+/// coordinates, central moments `mupq` are relative to the centroid and
+/// normalized central moments `nupq = mupq/m00^(1 + (p + q)/2)` are 
+/// invariant to translation and scale.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Moments {
+    pub m00: f64, pub m10: f64, pub m01: f64,
+    pub m20: f64, pub m11: f64, pub m02: f64,
+    pub m30: f64, pub m21: f64, pub m12: f64, pub m03: f64,
+    pub mu20: f64, pub mu11: f64, pub mu02: f64,
+    pub mu30: f64, pub mu21: f64, pub mu12: f64, pub mu03: f64,
+    pub nu20: f64, pub nu11: f64, pub nu02: f64,
+    pub nu30: f64, pub nu21: f64, pub nu12: f64, pub nu03: f64,
+}
+
+/// Computes moments of grayscale image pixels within `range`,
+/// the range is clipped to image bounds
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, stats::moments};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(3, 2), vec![
+///     0, 1, 1,
+///     0, 1, 1,
+/// ]);
+/// let m = moments(&image, image.range());
+/// assert_eq!(m.m00, 4.0);
+/// assert_eq!(m.centroid(), Some(Vec2d::new(1.5, 0.5)));
+/// assert_eq!(m.mu20, 1.0);
+/// ```
+pub fn moments<T: AsPrimitive<f64>>(image: &dyn Img<T>, range: ImgRange) -> Moments {
+    compute(range.clamp_to(image.range()), |x, y| image.line_ref(y)[x].as_())
+}
+
+/// Computes moments of the shape formed by `true` pixels of `mask`
+/// within `range`, the range is clipped to image bounds
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, stats::moments_mask};
+/// let mask = ImgBuf::<bool>::from_vec(ImgSize::new(4, 1), vec![true, true, true, false]);
+/// let m = moments_mask(&mask, mask.range());
+/// assert_eq!(m.m00, 3.0);
+/// assert_eq!(m.orientation(), 0.0);
+/// assert_eq!(m.eccentricity(), 1.0);
+/// ```
+pub fn moments_mask(mask: &dyn Img<bool>, range: ImgRange) -> Moments {
+    compute(range.clamp_to(mask.range()), |x, y| if mask.line_ref(y)[x] { 1.0 } else { 0.0 })
+}
+
+impl Moments {
+    /// Intensity weighted center, `None` for zero total intensity
+    pub fn centroid(&self) -> Option<Vec2d<f64>> {
+        if self.m00 == 0.0 {
+            None
+        } else {
+            Some(Vec2d::new(self.m10/self.m00, self.m01/self.m00))
+        }
+    }
+
+    /// Angle of the major axis (in radians, in `(-π/2, π/2]` range) 
+    /// measured from the `x` axis towards the `y` axis
+    pub fn orientation(&self) -> f64 {
+        0.5*(2.0*self.mu11).atan2(self.mu20 - self.mu02)
+    }
+
+    /// Eccentricity of the ellipse with the same second order moments,
+    /// `0` for circular shapes, approaching `1` for elongated shapes
+    pub fn eccentricity(&self) -> f64 {
+        let spread = (((self.mu20 - self.mu02)/2.0).powi(2) + self.mu11*self.mu11).sqrt();
+        let major = (self.mu20 + self.mu02)/2.0 + spread;
+        let minor = (self.mu20 + self.mu02)/2.0 - spread;
+
+        if major <= 0.0 { 0.0 } else { (1.0 - minor/major).max(0.0).sqrt() }
+    }
+
+    /// Seven Hu moment invariants, invariant to translation, scale and 
+    /// rotation, the seventh one changes sign under reflection
+    /// 
+    /// # Example
+    /// ```
+    /// use nanocv::{*, stats::moments_mask};
+    /// let mut a = ImgBuf::<bool>::new(ImgSize::new(8, 8));
+    /// let mut b = ImgBuf::<bool>::new(ImgSize::new(8, 8));
+    /// 
+    /// // L shape and the same shape rotated by 90 degrees
+    /// for &(x, y) in &[(1, 1), (1, 2), (1, 3), (2, 3), (3, 3)] {
+    ///     a[(x, y)] = true;
+    ///     b[(7 - y, x)] = true;
+    /// }
+    /// 
+    /// let (ha, hb) = (moments_mask(&a, a.range()).hu(), moments_mask(&b, b.range()).hu());
+    /// assert!(ha.iter().zip(&hb).all(|(x, y)| (x - y).abs() < 1e-12));
+    /// ```
+    pub fn hu(&self) -> [f64; 7] {
+        let (n20, n11, n02) = (self.nu20, self.nu11, self.nu02);
+        let (n30, n21, n12, n03) = (self.nu30, self.nu21, self.nu12, self.nu03);
+        let (a, b) = (n30 + n12, n21 + n03);
+        let (c, d) = (n30 - 3.0*n12, 3.0*n21 - n03);
+
+        [
+            n20 + n02,
+            (n20 - n02).powi(2) + 4.0*n11*n11,
+            c*c + d*d,
+            a*a + b*b,
+            c*a*(a*a - 3.0*b*b) + d*b*(3.0*a*a - b*b),
+            (n20 - n02)*(a*a - b*b) + 4.0*n11*a*b,
+            d*a*(a*a - 3.0*b*b) - c*b*(3.0*a*a - b*b),
+        ]
+    }
+}
+
+/// Computes moments of `value(x, y)` over `range`, which must lie 
+/// within image bounds
+pub(crate) fn compute<F: Fn(usize, usize) -> f64>(range: ImgRange, value: F) -> Moments {
+    let mut m = Moments::default();
+
+    for y in range.y.start..range.y.end {
+        for x in range.x.start..range.x.end {
+            let v = value(x as usize, y as usize);
+
+            if v == 0.0 {
+                continue;
+            }
+
+            let (x, y) = (x as f64, y as f64);
+            m.m00 += v;
+            m.m10 += v*x;
+            m.m01 += v*y;
+            m.m20 += v*x*x;
+            m.m11 += v*x*y;
+            m.m02 += v*y*y;
+            m.m30 += v*x*x*x;
+            m.m21 += v*x*x*y;
+            m.m12 += v*x*y*y;
+            m.m03 += v*y*y*y;
+        }
+    }
+
+    let center = match m.centroid() {
+        Some(center) => center,
+        None => return m,
+    };
+
+    let (cx, cy) = (center.x, center.y);
+    m.mu20 = m.m20 - cx*m.m10;
+    m.mu11 = m.m11 - cx*m.m01;
+    m.mu02 = m.m02 - cy*m.m01;
+    m.mu30 = m.m30 - 3.0*cx*m.m20 + 2.0*cx*cx*m.m10;
+    m.mu21 = m.m21 - 2.0*cx*m.m11 - cy*m.m20 + 2.0*cx*cx*m.m01;
+    m.mu12 = m.m12 - 2.0*cy*m.m11 - cx*m.m02 + 2.0*cy*cy*m.m10;
+    m.mu03 = m.m03 - 3.0*cy*m.m02 + 2.0*cy*cy*m.m01;
+
+    let second = m.m00*m.m00;
+    let third = second*m.m00.sqrt();
+    m.nu20 = m.mu20/second;
+    m.nu11 = m.mu11/second;
+    m.nu02 = m.mu02/second;
+    m.nu30 = m.mu30/third;
+    m.nu21 = m.mu21/third;
+    m.nu12 = m.mu12/third;
+    m.nu03 = m.mu03/third;
+    m
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImgBuf, ImgSize, Range2d};
+
+    #[test]
+    fn central_moments_are_translation_invariant() {
+        let mut a = ImgBuf::<f32>::new(ImgSize::new(10, 10));
+        let mut b = a.clone();
+
+        for &(x, y, v) in &[(1, 1, 1.0), (2, 1, 3.0), (2, 3, 2.0), (4, 2, 0.5)] {
+            a[(x, y)] = v;
+            b[(x + 5, y + 6)] = v;
+        }
+
+        let (ma, mb) = (moments(&a, a.range()), moments(&b, b.range()));
+
+        for (x, y) in [(ma.mu21, mb.mu21), (ma.mu03, mb.mu03), (ma.nu12, mb.nu12)].iter() {
+            assert!((x - y).abs() < 1e-9);
+        }
+
+        assert!(ma.m10 != mb.m10);
+    }
+
+    #[test]
+    fn diagonal_line_orientation() {
+        let mut mask = ImgBuf::<bool>::new(ImgSize::new(5, 5));
+
+        for i in 0..5 {
+            mask[(i, i)] = true;
+        }
+
+        let m = moments_mask(&mask, mask.range());
+        assert!((m.orientation() - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+        assert_eq!(moments_mask(&mask, Range2d::new(10..12, 0..2)).centroid(), None);
+    }
+}
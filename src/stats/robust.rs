@@ -0,0 +1,143 @@
+/// Median of `values`, reorders the slice, `None` for empty slice
+///
+/// For even number of values, mean of two middle values is returned,
+/// NaN values are sorted to the end
+pub(crate) fn median(values: &mut [f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let even = values.len() % 2 == 0;
+    let middle = values.len()/2;
+    let (lower, upper, _) = values.select_nth_unstable_by(middle, |a, b| a.total_cmp(b));
+
+    if !even {
+        Some(*upper)
+    } else {
+        let below = lower.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        Some((below + *upper)/2.0)
+    }
+}
+
+/// Value below which `fraction` (in `[0, 1]` range) of `values` lies, 
+/// linearly interpolated between neighboring values, reorders the slice,
+/// `None` for empty slice
+pub(crate) fn quantile(values: &mut [f32], fraction: f32) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let position = fraction.clamp(0.0, 1.0) as f64*(values.len() - 1) as f64;
+    let index = position.floor() as usize;
+    let (_, lower, upper) = values.select_nth_unstable_by(index, |a, b| a.total_cmp(b));
+    let lower = *lower;
+
+    match upper.iter().copied().min_by(|a, b| a.total_cmp(b)) {
+        Some(upper) => Some(lower + (upper - lower)*(position - index as f64) as f32),
+        None => Some(lower),
+    }
+}
+
+/// Standard deviation estimated robustly using median absolute deviation,
+/// reorders the slice, zero for empty slice
+pub(crate) fn mad_std_dev(values: &mut [f32]) -> f32 {
+    /// Scale factor converting median absolute deviation to standard 
+    /// deviation of normally distributed values
+    const MAD_TO_SIGMA: f32 = 1.4826;
+
+    let center = match median(values) {
+        Some(center) => center,
+        None => return 0.0,
+    };
+
+    for value in values.iter_mut() {
+        *value = (*value - center).abs();
+    }
+
+    MAD_TO_SIGMA*median(values).unwrap_or(0.0)
+}
+
+/// Mean and standard deviation of `values`, `None` for empty slice
+pub(crate) fn mean_and_std_dev(values: &[f32]) -> Option<(f32, f32)> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let count = values.len() as f64;
+    let mean = values.iter().map(|&v| v as f64).sum::<f64>()/count;
+    let variance = values.iter().map(|&v| (v as f64 - mean).powi(2)).sum::<f64>()/count;
+    Some((mean as f32, variance.sqrt() as f32))
+}
+
+/// Iteratively rejects values further than `kappa` standard deviations
+/// from the median, at most `iterations` times, returns the number of
+/// kept values, which are moved to the beginning of the slice
+pub(crate) fn sigma_clip(values: &mut [f32], kappa: f32, iterations: usize) -> usize {
+    let mut count = values.len();
+
+    for _ in 0..iterations {
+        let kept = &mut values[..count];
+
+        let center = match median(kept) {
+            Some(center) => center,
+            None => break,
+        };
+
+        let (_, sigma) = mean_and_std_dev(kept).unwrap_or((0.0, 0.0));
+        let mut next = 0;
+
+        for index in 0..count {
+            let value = kept[index];
+
+            if (value - center).abs() <= kappa*sigma {
+                kept[next] = value;
+                next += 1;
+            }
+        }
+
+        if next == count || next == 0 {
+            break;
+        }
+
+        count = next;
+    }
+
+    count
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_of_odd_and_even_count() {
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), Some(2.5));
+        assert_eq!(median(&mut []), None);
+    }
+
+    #[test]
+    fn quantile_interpolates_between_values() {
+        assert_eq!(quantile(&mut [4.0, 1.0, 3.0, 2.0, 0.0], 0.5), Some(2.0));
+        assert_eq!(quantile(&mut [10.0, 0.0], 0.25), Some(2.5));
+        assert_eq!(quantile(&mut [3.0, 1.0, 2.0], 1.0), Some(3.0));
+        assert_eq!(quantile(&mut [], 0.5), None);
+    }
+
+    #[test]
+    fn mad_std_dev_ignores_outliers() {
+        let sigma = mad_std_dev(&mut [1.0, 2.0, 3.0, 4.0, 5.0, 1000.0]);
+        assert!((sigma - 1.5*1.4826).abs() < 1e-5);
+        assert_eq!(mad_std_dev(&mut []), 0.0);
+    }
+
+    #[test]
+    fn sigma_clip_rejects_outlier() {
+        let mut values = [10.0, 11.0, 9.0, 10.0, 10.5, 9.5, 10.0, 100.0];
+        let count = sigma_clip(&mut values, 2.0, 5);
+        assert_eq!(count, 7);
+        assert!(values[..count].iter().all(|&v| v < 20.0));
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{Img, ImgMut, ImgBuf, AsPrimitive};
+
+/// Disparity map of a rectified stereo pair by block matching
+/// 
+/// For every pixel of `left`, blocks of `block_size x block_size` pixels
+/// centered at the pixel are compared with blocks shifted by disparities
+/// `0..=max_disparity` in `right` image using sum of absolute differences,
+/// blocks are cropped at image borders and disparities are limited so the
+/// matched pixel stays inside the right image. Disparities are verified by left-right consistency check, the
+/// best match of the matched right pixel must lead back within one pixel,
+/// otherwise (typically in occluded or textureless areas) disparity is 
+/// set to `NaN`.
+/// 
+/// # Panics
+/// 
+/// Panics when image sizes differ or block size is zero
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, stereo::block_match};
+/// let pattern = |x: usize, y: usize| ((x*x*7 + y*y*3 + x*y) % 23) as u8;
+/// let mut left = ImgBuf::<u8>::new(ImgSize::new(24, 8));
+/// let mut right = ImgBuf::<u8>::new(left.size());
+/// 
+/// // the scene is shifted by 3 pixels in the right image
+/// for y in 0..8 {
+///     for x in 0..24 {
+///         left[(x, y)] = pattern(x + 10, y);
+///         right[(x, y)] = pattern(x + 13, y);
+///     }
+/// }
+/// 
+/// let disparity = block_match(&left, &right, 6, 5);
+/// assert_eq!(disparity[(12, 4)], 3.0);
+/// ```
+pub fn block_match<T>(
+    left: &dyn Img<T>, 
+    right: &dyn Img<T>, 
+    max_disparity: usize, 
+    block_size: usize
+) -> ImgBuf<f32>
+where T: AsPrimitive<f32> {
+    disparity(left, right, max_disparity, block_size, false)
+}
+
+/// Disparity map of a rectified stereo pair by block matching with 
+/// sub-pixel refinement
+/// 
+/// Works the same way as [block_match](fn.block_match.html), integer 
+/// disparities are refined by fitting a parabola through matching costs
+/// of the best disparity and its two neighbors.
+/// 
+/// Panics when image sizes differ or block size is zero
+pub fn block_match_subpixel<T>(
+    left: &dyn Img<T>, 
+    right: &dyn Img<T>, 
+    max_disparity: usize, 
+    block_size: usize
+) -> ImgBuf<f32>
+where T: AsPrimitive<f32> {
+    disparity(left, right, max_disparity, block_size, true)
+}
+
+fn disparity<T>(
+    left: &dyn Img<T>, 
+    right: &dyn Img<T>, 
+    max_disparity: usize, 
+    block_size: usize,
+    subpixel: bool
+) -> ImgBuf<f32>
+where T: AsPrimitive<f32> {
+    assert_eq!(left.size(), right.size(), "Image sizes differ");
+    assert!(block_size > 0, "Block size must not be zero");
+
+    let size = left.size();
+    let radius = block_size/2;
+    let count = max_disparity + 1;
+
+    // Aggregated matching costs, cost of disparity d at pixel (x, y) 
+    // is stored at index (y*size.x + x)*count + d
+    let mut costs = vec![0.0f32; size.product()*count];
+    let mut differences = ImgBuf::<f32>::new(size);
+
+    for d in 0..count {
+        for y in 0..size.y {
+            let (src, other) = (left.line_ref(y), right.line_ref(y));
+            let dst = differences.line_mut(y);
+
+            for x in 0..size.x {
+                let shifted = x.saturating_sub(d);
+                dst[x] = (src[x].as_() - other[shifted].as_()).abs();
+            }
+        }
+
+        let sums = box_sum(&differences, radius);
+
+        for y in 0..size.y {
+            let line = sums.line_ref(y);
+
+            for x in 0..size.x {
+                costs[(y*size.x + x)*count + d] = line[x];
+            }
+        }
+    }
+
+    let cost = |x: usize, y: usize, d: usize| costs[(y*size.x + x)*count + d];
+    let mut result = ImgBuf::<f32>::new_init(size, f32::NAN);
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            // Disparities are limited so that the matched pixel lies in the right image
+            let d = lowest((0..count.min(x + 1)).map(|d| (d, cost(x, y, d))));
+
+            // Right pixel xr is seen at left pixel xr + dr with disparity dr
+            let xr = x - d;
+            let back = lowest((0..count.min(size.x - xr)).map(|dr| (dr, cost(xr + dr, y, dr))));
+
+            if (back as isize - d as isize).abs() > 1 {
+                continue;
+            }
+
+            let mut value = d as f32;
+
+            if subpixel && d > 0 && d + 1 < count.min(x + 1) {
+                let (before, center, after) = (cost(x, y, d - 1), cost(x, y, d), cost(x, y, d + 1));
+                let curvature = before - 2.0*center + after;
+
+                if curvature > 0.0 {
+                    value += (before - after)/(2.0*curvature);
+                }
+            }
+
+            result[(x, y)] = value;
+        }
+    }
+
+    result
+}
+
+/// Disparity with the lowest cost, the lower disparity wins ties
+fn lowest(costs: impl Iterator<Item = (usize, f32)>) -> usize {
+    let mut best = (0, f32::INFINITY);
+
+    for (d, cost) in costs {
+        if cost < best.1 {
+            best = (d, cost);
+        }
+    }
+
+    best.0
+}
+
+/// Sums of pixels in `(2*radius + 1)` square windows, windows are clamped
+/// to the image, so sums near borders contain fewer pixels
+fn box_sum(image: &ImgBuf<f32>, radius: usize) -> ImgBuf<f32> {
+    let size = image.size();
+    let mut horizontal = ImgBuf::<f32>::new(size);
+    let mut result = ImgBuf::<f32>::new(size);
+    let mut prefix = vec![0.0f32; size.x.max(size.y) + 1];
+
+    for y in 0..size.y {
+        let line = image.line_ref(y);
+
+        for x in 0..size.x {
+            prefix[x + 1] = prefix[x] + line[x];
+        }
+
+        let dst = horizontal.line_mut(y);
+
+        for x in 0..size.x {
+            dst[x] = prefix[(x + radius + 1).min(size.x)] - prefix[x.saturating_sub(radius)];
+        }
+    }
+
+    for x in 0..size.x {
+        for y in 0..size.y {
+            prefix[y + 1] = prefix[y] + horizontal[(x, y)];
+        }
+
+        for y in 0..size.y {
+            result[(x, y)] = prefix[(y + radius + 1).min(size.y)] - prefix[y.saturating_sub(radius)];
+        }
+    }
+
+    result
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    fn pattern(x: f32, y: f32) -> f32 {
+        100.0 + 40.0*(0.9*x).sin()*(0.7*y).cos() + 30.0*(0.31*x + 0.2*y).cos()
+    }
+
+    #[test]
+    fn subpixel_shift_is_refined() {
+        let size = ImgSize::new(40, 10);
+        let mut left = ImgBuf::<f32>::new(size);
+        let mut right = ImgBuf::<f32>::new(size);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                left[(x, y)] = pattern(x as f32, y as f32);
+                right[(x, y)] = pattern(x as f32 + 4.4, y as f32);
+            }
+        }
+
+        let coarse = block_match(&left, &right, 8, 5);
+        let fine = block_match_subpixel(&left, &right, 8, 5);
+        assert_eq!(coarse[(20, 5)], 4.0);
+        assert!((fine[(20, 5)] - 4.4).abs() < 0.2, "{}", fine[(20, 5)]);
+    }
+
+    #[test]
+    fn unmatched_pixels_are_mostly_rejected() {
+        let size = ImgSize::new(30, 6);
+        let mut left = ImgBuf::<f32>::new(size);
+        let mut right = ImgBuf::<f32>::new(size);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                left[(x, y)] = pattern(x as f32, y as f32);
+                // right image sees a flat wall where the left sees the pattern
+                right[(x, y)] = if x < 15 { 10.0 } else { pattern(x as f32 + 2.0, y as f32) };
+            }
+        }
+
+        let disparity = block_match(&left, &right, 4, 3);
+        assert_eq!(disparity[(25, 3)], 2.0);
+        let line = disparity.line_ref(3);
+        assert!(line[18..].iter().all(|&d| d == 2.0));
+        assert!(line[..15].iter().filter(|d| d.is_nan()).count() > 7);
+    }
+
+    #[test]
+    fn box_sum_is_cropped_at_borders() {
+        let image = ImgBuf::<f32>::new_init(ImgSize::new(4, 3), 1.0);
+        let sums = box_sum(&image, 1);
+        assert_eq!(sums.line_ref(0), &[4.0, 6.0, 6.0, 4.0]);
+        assert_eq!(sums.line_ref(1), &[6.0, 9.0, 9.0, 6.0]);
+    }
+}
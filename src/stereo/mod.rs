@@ -0,0 +1,16 @@
+//! Depth estimation from rectified stereo image pairs
+//! 
+//! Disparity maps store for every pixel of the left image the horizontal 
+//! distance to the corresponding pixel in the right image, so that 
+//! `right[(x - disparity, y)] ≈ left[(x, y)]`. Disparity is inversely 
+//! proportional to scene depth. Pixels without a reliable disparity are
+//! set to `NaN`.
+//! 
+//! Available functions:
+//!  * [block_match](fn.block_match.html), 
+//!    [block_match_subpixel](fn.block_match_subpixel.html) - disparity
+//!    by matching blocks along image rows
+
+mod block;
+
+pub use block::{block_match, block_match_subpixel};
@@ -0,0 +1,258 @@
+use crate::{Img, ImgMut, ImgLinesMut, ImgBuf, ImgSize, Vec2d, geometry::Homography, filter::bilinear, pyramid};
+
+/// Method used to blend overlapping frames
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendMode {
+    /// Weighted average with weights decreasing linearly towards frame 
+    /// borders, smooth but may ghost when frames are not perfectly aligned
+    Feather,
+    /// Each canvas pixel is assigned to the frame with the highest feather 
+    /// weight and the seams are hidden by multi-band blending with pyramids 
+    /// of at most `levels` levels
+    Pyramid { levels: usize },
+}
+
+/// Warps `frames` into a canvas of `output_size` and blends their overlaps
+/// 
+/// Each frame is paired with a homography mapping frame coordinates into
+/// canvas coordinates, frames are resampled using bilinear interpolation.
+/// Canvas pixels not covered by any frame are set to zero. Frames whose
+/// homography is not invertible are skipped.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::{Affine2d, Homography}, stitch::{compose, BlendMode}};
+/// let left = ImgBuf::<f32>::new_init(ImgSize::new(6, 4), 10.0);
+/// let right = ImgBuf::<f32>::new_init(ImgSize::new(6, 4), 10.0);
+/// let shift = Homography::from(Affine2d::translation(Vec2d::new(4.0, 0.0)));
+/// 
+/// let frames: [(&dyn Img<f32>, Homography); 2] = [
+///     (&left, Homography::identity()), (&right, shift)
+/// ];
+/// let canvas = compose(&frames, ImgSize::new(12, 4), BlendMode::Feather);
+/// assert_eq!(canvas.line_ref(1)[..10], [10.0; 10]);
+/// assert_eq!(canvas.line_ref(1)[10..], [0.0, 0.0]);
+/// ```
+pub fn compose(
+    frames: &[(&dyn Img<f32>, Homography)], 
+    output_size: ImgSize, 
+    blend_mode: BlendMode
+) -> ImgBuf<f32> {
+    let warped: Vec<(ImgBuf<f32>, ImgBuf<f32>)> = frames.iter()
+        .filter_map(|(frame, homography)| warp(*frame, homography, output_size))
+        .collect();
+
+    match blend_mode {
+        BlendMode::Feather => feather(&warped, output_size),
+        BlendMode::Pyramid { levels } => multi_band(&warped, output_size, levels),
+    }
+}
+
+fn feather(warped: &[(ImgBuf<f32>, ImgBuf<f32>)], size: ImgSize) -> ImgBuf<f32> {
+    let mut sum = ImgBuf::<f32>::new(size);
+    let mut total = ImgBuf::<f32>::new(size);
+
+    for (image, weights) in warped {
+        for y in 0..size.y {
+            let (src, weight) = (image.line_ref(y), weights.line_ref(y));
+            let (dst, dst_total) = (sum.line_mut(y), total.line_mut(y));
+
+            for x in 0..size.x {
+                dst[x] += weight[x]*src[x];
+                dst_total[x] += weight[x];
+            }
+        }
+    }
+
+    normalize(&mut sum, &total);
+    sum
+}
+
+fn multi_band(warped: &[(ImgBuf<f32>, ImgBuf<f32>)], size: ImgSize, levels: usize) -> ImgBuf<f32> {
+    // Winner-take-all seam masks, frame with the highest weight owns the pixel
+    let mut masks: Vec<ImgBuf<f32>> = warped.iter().map(|_| ImgBuf::new(size)).collect();
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let best = (0..warped.len())
+                .filter(|&index| warped[index].1[(x, y)] > 0.0)
+                .max_by(|&a, &b| warped[a].1[(x, y)].total_cmp(&warped[b].1[(x, y)]));
+
+            if let Some(best) = best {
+                masks[best][(x, y)] = 1.0;
+            }
+        }
+    }
+
+    let mut bands: Vec<ImgBuf<f32>> = Vec::new();
+    let mut totals: Vec<ImgBuf<f32>> = Vec::new();
+
+    for ((image, weights), mask) in warped.iter().zip(masks.iter()) {
+        let laplacian = extrapolated_laplacian(image, weights, levels);
+        let gaussian = pyramid::gaussian(mask, levels);
+
+        if bands.is_empty() {
+            bands = laplacian.iter().map(|level| ImgBuf::new(level.size())).collect();
+            totals = bands.clone();
+        }
+
+        for level in 0..bands.len() {
+            for y in 0..bands[level].size().y {
+                let (src, weight) = (laplacian[level].line_ref(y), gaussian[level].line_ref(y));
+                let (dst, total) = (bands[level].line_mut(y), totals[level].line_mut(y));
+
+                for x in 0..dst.len() {
+                    dst[x] += weight[x]*src[x];
+                    total[x] += weight[x];
+                }
+            }
+        }
+    }
+
+    if bands.is_empty() {
+        return ImgBuf::new(size);
+    }
+
+    for (band, total) in bands.iter_mut().zip(totals.iter()) {
+        normalize(band, total);
+    }
+
+    let mut result = pyramid::collapse(&bands);
+
+    // Uncovered pixels stay empty
+    for y in 0..size.y {
+        for x in 0..size.x {
+            if warped.iter().all(|(_, weights)| weights[(x, y)] <= 0.0) {
+                result[(x, y)] = 0.0;
+            }
+        }
+    }
+
+    result
+}
+
+/// Laplacian pyramid of a partially covered image, each Gaussian level is
+/// normalized by the blurred coverage, so the missing pixels (zeros) do 
+/// not darken coarse levels near frame borders
+fn extrapolated_laplacian(image: &ImgBuf<f32>, weights: &ImgBuf<f32>, levels: usize) -> Vec<ImgBuf<f32>> {
+    let mut coverage = weights.clone();
+
+    for line in coverage.lines_mut() {
+        for value in line.iter_mut() {
+            *value = if *value > 0.0 { 1.0 } else { 0.0 };
+        }
+    }
+
+    let coverage = pyramid::gaussian(&coverage, levels);
+    let mut pyramid = pyramid::gaussian(image, levels);
+
+    for (level, total) in pyramid.iter_mut().zip(coverage.iter()) {
+        normalize(level, total);
+    }
+
+    for level in 0..pyramid.len().saturating_sub(1) {
+        let expanded = pyramid::expand(&pyramid[level + 1], pyramid[level].size());
+
+        for (line, detail) in pyramid[level].lines_mut().zip(expanded.lines()) {
+            for x in 0..line.len() {
+                line[x] -= detail[x];
+            }
+        }
+    }
+
+    pyramid
+}
+
+fn normalize(image: &mut ImgBuf<f32>, total: &ImgBuf<f32>) {
+    for (line, total) in image.lines_mut().zip(total.lines()) {
+        for x in 0..line.len() {
+            line[x] = if total[x] > 0.0 { line[x]/total[x] } else { 0.0 };
+        }
+    }
+}
+
+/// Resamples `frame` into canvas coordinates, returns the warped image 
+/// together with feather weights (zero outside of the frame)
+fn warp(frame: &dyn Img<f32>, homography: &Homography, size: ImgSize) -> Option<(ImgBuf<f32>, ImgBuf<f32>)> {
+    let inverse = homography.inverse()?;
+    let frame_size = frame.size();
+    let (width, height) = (frame_size.x as f64, frame_size.y as f64);
+    let mut image = ImgBuf::<f32>::new(size);
+    let mut weights = ImgBuf::<f32>::new(size);
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let point = match inverse.transform_point(Vec2d::new(x as f64, y as f64)) {
+                Some(point) => point,
+                None => continue,
+            };
+
+            // Distance to the nearest frame border, pixel centers are at 
+            // integer coordinates, so the frame spans [-0.5, size - 0.5]
+            let border = (point.x + 0.5).min(width - 0.5 - point.x)
+                .min(point.y + 0.5).min(height - 0.5 - point.y);
+
+            if let (true, Some(value)) = (border > 0.0, bilinear(frame, point.cast::<f32>())) {
+                image[(x, y)] = value as f32;
+                weights[(x, y)] = border as f32;
+            }
+        }
+    }
+
+    Some((image, weights))
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Affine2d;
+    use super::*;
+
+    fn scene(x: f64, y: f64) -> f32 {
+        (50.0 + 0.5*x + 0.3*y) as f32
+    }
+
+    fn frames() -> (ImgBuf<f32>, ImgBuf<f32>, Homography) {
+        let size = ImgSize::new(20, 12);
+        let mut left = ImgBuf::<f32>::new(size);
+        let mut right = ImgBuf::<f32>::new(size);
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                left[(x, y)] = scene(x as f64, y as f64);
+                right[(x, y)] = scene(x as f64 + 12.0, y as f64);
+            }
+        }
+
+        let shift = Homography::from(Affine2d::translation(Vec2d::new(12.0, 0.0)));
+        (left, right, shift)
+    }
+
+    #[test]
+    fn overlapping_frames_reproduce_the_scene() {
+        let (left, right, shift) = frames();
+        let frames: [(&dyn Img<f32>, Homography); 2] = [(&left, Homography::identity()), (&right, shift)];
+
+        // pyramid levels are slightly biased near frame borders
+        for &(mode, tolerance) in [(BlendMode::Feather, 0.01), (BlendMode::Pyramid { levels: 3 }, 0.5)].iter() {
+            let canvas = compose(&frames, ImgSize::new(32, 12), mode);
+
+            for y in 0..12 {
+                for x in 0..32 {
+                    let error = (canvas[(x, y)] - scene(x as f64, y as f64)).abs();
+                    assert!(error < tolerance, "{:?} pixel ({}, {}) differs by {}", mode, x, y, error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn singular_homography_skips_frame() {
+        let (left, _, _) = frames();
+        let singular = Homography::new([[0.0; 3]; 3]);
+        let frames: [(&dyn Img<f32>, Homography); 1] = [(&left, singular)];
+        let canvas = compose(&frames, ImgSize::new(4, 4), BlendMode::Pyramid { levels: 2 });
+        assert!(canvas.lines().all(|line| line.iter().all(|&value| value == 0.0)));
+    }
+}
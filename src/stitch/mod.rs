@@ -0,0 +1,13 @@
+//! Composition of multiple overlapping frames into a single panorama
+//! 
+//! Frames are placed into a shared canvas by homographies mapping frame
+//! coordinates to canvas coordinates, typically estimated by feature
+//! matching and [register](../register/index.html) functions.
+//! 
+//! Available functions:
+//!  * [compose](fn.compose.html) - warp frames into a canvas and blend
+//!    their overlaps
+
+mod compose;
+
+pub use compose::{compose, BlendMode};
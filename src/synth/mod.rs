@@ -0,0 +1,24 @@
+//! Generators of synthetic image data for testing and benchmarking
+//! 
+//! All random generators are deterministic, the same `seed` always
+//! produces the same result.
+//! 
+//! Available functions:
+//!  * noise: [add_gaussian_noise](fn.add_gaussian_noise.html), 
+//!    [add_poisson_noise](fn.add_poisson_noise.html),
+//!    [add_salt_pepper](fn.add_salt_pepper.html)
+//!  * test patterns: [gradient](fn.gradient.html), 
+//!    [checkerboard](fn.checkerboard.html),
+//!    [concentric_circles](fn.concentric_circles.html),
+//!    [siemens_star](fn.siemens_star.html),
+//!    [gaussian_spots](fn.gaussian_spots.html)
+//! 
+//! Test patterns are generated for any pixel type, pixel values range
+//! from zero to `max` given by the caller, which allows to generate
+//! patterns of arbitrary bit depth, e.g. `4095` for 12-bit data
+
+mod noise;
+mod patterns;
+
+pub use noise::{add_gaussian_noise, add_poisson_noise, add_salt_pepper};
+pub use patterns::{gradient, checkerboard, concentric_circles, siemens_star, gaussian_spots};
\ No newline at end of file
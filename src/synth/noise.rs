@@ -0,0 +1,105 @@
+use crate::{ImgMut, AsPrimitive, geometry::round_as, random::Random};
+
+/// Adds normally distributed noise with zero mean and standard
+/// deviation `sigma` to all image pixels
+/// 
+/// Results are rounded for integer pixel types, values out of the pixel
+/// type range saturate.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::add_gaussian_noise};
+/// let mut image = ImgBuf::<f32>::new(ImgSize::new(100, 100));
+/// add_gaussian_noise(&mut image, 2.0, 42);
+/// let mean = image.lines().flat_map(|line| line.iter()).sum::<f32>()/10000.0;
+/// assert!(mean.abs() < 0.1);
+/// ```
+pub fn add_gaussian_noise<T>(image: &mut dyn ImgMut<T>, sigma: f64, seed: u64) 
+where T: AsPrimitive<f64>, f64: AsPrimitive<T> {
+    let mut random = Random::new(seed);
+    update_pixels(image, |value| value + sigma*random.normal());
+}
+
+/// Replaces every pixel value by a random value drawn from Poisson
+/// distribution with mean equal to the original pixel value, simulating
+/// photon shot noise, pixel values are interpreted as photon counts
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::add_poisson_noise};
+/// let mut image = ImgBuf::<u16>::new_init(ImgSize::new(100, 100), 100);
+/// add_poisson_noise(&mut image, 1);
+/// let mean = image.lines().flat_map(|line| line.iter()).map(|&v| v as f64).sum::<f64>()/10000.0;
+/// assert!((mean - 100.0).abs() < 0.5);
+/// ```
+pub fn add_poisson_noise<T>(image: &mut dyn ImgMut<T>, seed: u64) 
+where T: AsPrimitive<f64>, f64: AsPrimitive<T> {
+    let mut random = Random::new(seed);
+    update_pixels(image, |value| random.poisson(value));
+}
+
+/// Replaces randomly chosen pixels by `pepper` or `salt` values, each
+/// pixel is replaced with `probability`, salt and pepper are equally likely
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::add_salt_pepper};
+/// let mut image = ImgBuf::<u8>::new_init(ImgSize::new(100, 100), 128);
+/// add_salt_pepper(&mut image, 0.1, 0, 255, 3);
+/// let noisy = image.lines().flat_map(|line| line.iter()).filter(|&&v| v != 128).count();
+/// assert!(noisy > 800 && noisy < 1200);
+/// ```
+pub fn add_salt_pepper<T: Copy>(
+    image: &mut dyn ImgMut<T>, 
+    probability: f64, 
+    pepper: T, 
+    salt: T, 
+    seed: u64
+) {
+    let mut random = Random::new(seed);
+
+    for y in 0..image.height() {
+        for pixel in image.line_mut(y).iter_mut() {
+            let sample = random.uniform();
+
+            if sample < probability {
+                *pixel = if sample < probability/2.0 { pepper } else { salt };
+            }
+        }
+    }
+}
+
+/// Updates all image pixels using `function` evaluated in `f64`
+fn update_pixels<T, F>(image: &mut dyn ImgMut<T>, mut function: F)
+where T: AsPrimitive<f64>, f64: AsPrimitive<T>, F: FnMut(f64) -> f64 {
+    for y in 0..image.height() {
+        for pixel in image.line_mut(y).iter_mut() {
+            *pixel = round_as(function(pixel.as_()));
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Img, ImgBuf, ImgSize};
+
+    #[test]
+    fn noise_is_deterministic_and_saturates() {
+        let mut a = ImgBuf::<u8>::new_init(ImgSize::new(20, 20), 250);
+        let mut b = a.clone();
+        add_gaussian_noise(&mut a, 10.0, 5);
+        add_gaussian_noise(&mut b, 10.0, 5);
+        assert_eq!(a, b);
+        assert!(a.lines().flat_map(|line| line.iter()).any(|&v| v == 255));
+    }
+
+    #[test]
+    fn zero_probability_keeps_image() {
+        let mut image = ImgBuf::<u8>::new_init(ImgSize::new(5, 5), 10);
+        add_salt_pepper(&mut image, 0.0, 0, 255, 1);
+        assert_eq!(image, ImgBuf::<u8>::new_init(ImgSize::new(5, 5), 10));
+    }
+}
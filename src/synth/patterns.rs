@@ -0,0 +1,151 @@
+use crate::{ImgMut, ImgBuf, ImgSize, AsPrimitive, geometry::{round_as, Point}};
+
+/// Linear gradient rising from `0` to `max` in the direction of `angle` 
+/// (in radians, zero angle rises from left to right)
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::gradient};
+/// let image = gradient::<u8>(ImgSize::new(3, 2), 0.0, 255);
+/// assert_eq!(image.line_ref(1), &[0, 128, 255]);
+/// ```
+pub fn gradient<T>(size: ImgSize, angle: f64, max: T) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let direction = Point::new(angle.cos(), angle.sin());
+    let corners = [(0, 0), (1, 0), (0, 1), (1, 1)];
+
+    let projections: Vec<f64> = corners.iter()
+        .map(|&(x, y)| Point::new((x*size.x.saturating_sub(1)) as f64, (y*size.y.saturating_sub(1)) as f64))
+        .map(|corner| corner*direction)
+        .collect();
+
+    let low = projections.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = projections.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = if high > low { high - low } else { 1.0 };
+
+    generate(size, max, |x, y| (Point::new(x, y)*direction - low)/span)
+}
+
+/// Checkerboard of squares with side of `cell` pixels, top left square 
+/// is black (zero)
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::checkerboard};
+/// let image = checkerboard::<u16>(ImgSize::new(4, 2), 2, 1000);
+/// assert_eq!(image.line_ref(0), &[0, 0, 1000, 1000]);
+/// ```
+pub fn checkerboard<T>(size: ImgSize, cell: usize, max: T) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    assert!(cell > 0, "Checkerboard cell size must not be zero");
+
+    generate(size, max, |x, y| {
+        ((x as usize/cell + y as usize/cell) % 2) as f64
+    })
+}
+
+/// Concentric sinusoidal rings around the image center with given
+/// `period` (distance between rings) in pixels, maximal value at center
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::concentric_circles};
+/// let image = concentric_circles::<f32>(ImgSize::new(9, 9), 8.0, 1.0);
+/// assert_eq!(image[(4, 4)], 1.0);
+/// assert!(image[(0, 4)] < 1e-6);
+/// ```
+pub fn concentric_circles<T>(size: ImgSize, period: f64, max: T) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let center = image_center(size);
+
+    generate(size, max, |x, y| {
+        let radius = Point::new(x, y).distance(center);
+        0.5 + 0.5*(2.0*std::f64::consts::PI*radius/period).cos()
+    })
+}
+
+/// Siemens star with `spokes` bright and `spokes` dark wedges 
+/// around the image center, useful to measure resolution
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::siemens_star};
+/// let image = siemens_star::<u8>(ImgSize::new(11, 11), 4, 255);
+/// assert_eq!(image[(10, 6)], 255);
+/// assert_eq!(image[(10, 4)], 0);
+/// ```
+pub fn siemens_star<T>(size: ImgSize, spokes: usize, max: T) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let center = image_center(size);
+
+    generate(size, max, |x, y| {
+        let angle = (y - center.y).atan2(x - center.x);
+        if (spokes as f64*angle).sin() > 0.0 { 1.0 } else { 0.0 }
+    })
+}
+
+/// Gaussian spots with standard deviation `sigma` at `centers`, 
+/// reaching `max` at spot centers, sum of overlapping spots is clipped
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, synth::gaussian_spots};
+/// let image = gaussian_spots::<u16>(ImgSize::new(16, 8), &[Point::new(4.0, 4.0)], 1.5, 60000);
+/// assert_eq!(image[(4, 4)], 60000);
+/// assert_eq!(image[(15, 0)], 0);
+/// ```
+pub fn gaussian_spots<T>(size: ImgSize, centers: &[Point], sigma: f64, max: T) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    generate(size, max, |x, y| {
+        centers.iter()
+            .map(|&center| (Point::new(x, y) - center).length_squared())
+            .map(|distance| (-distance/(2.0*sigma*sigma)).exp())
+            .sum::<f64>()
+    })
+}
+
+/// Center of the image in pixel coordinates
+fn image_center(size: ImgSize) -> Point {
+    Point::new(size.x.saturating_sub(1) as f64/2.0, size.y.saturating_sub(1) as f64/2.0)
+}
+
+/// Creates image by evaluating `function` returning values in `[0, 1]`
+/// range (larger values are clipped) for every pixel and scaling them to `max`
+fn generate<T, F>(size: ImgSize, max: T, function: F) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T>, F: Fn(f64, f64) -> f64 {
+    let mut image = ImgBuf::<T>::new(size);
+    let scale = max.as_();
+
+    for y in 0..size.y {
+        let line = image.line_mut(y);
+
+        for x in 0..size.x {
+            line[x] = round_as(function(x as f64, y as f64).clamp(0.0, 1.0)*scale);
+        }
+    }
+
+    image
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Img;
+
+    #[test]
+    fn vertical_gradient_rises_downwards() {
+        let image = gradient::<u8>(ImgSize::new(2, 3), std::f64::consts::FRAC_PI_2, 100);
+        assert_eq!(image.line_ref(0), &[0, 0]);
+        assert_eq!(image.line_ref(2), &[100, 100]);
+    }
+
+    #[test]
+    fn overlapping_spots_are_clipped() {
+        let centers = [Point::new(2.0, 2.0), Point::new(2.0, 2.0)];
+        let image = gaussian_spots::<f32>(ImgSize::new(5, 5), &centers, 1.0, 1.0);
+        assert_eq!(image[(2, 2)], 1.0);
+        assert!((image[(0, 2)] - 2.0*(-2.0f32).exp()).abs() < 1e-6);
+    }
+}
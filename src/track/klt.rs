@@ -0,0 +1,273 @@
+use crate::{
+    AsPrimitive, Img, ImgBuf, Vec2d, 
+    feature::gradients, filter::{bilinear, map_new}, geometry::Point, pyramid::gaussian,
+};
+
+/// Parameters of [KltTracker](struct.KltTracker.html)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KltParams {
+    /// Half size of the tracked window, window has `2*window + 1` pixels
+    pub window: usize,
+    /// Number of pyramid levels, more levels allow larger motions
+    pub levels: usize,
+    /// Maximal number of Lucas-Kanade iterations at each pyramid level
+    pub max_iterations: usize,
+    /// Minimal position update (in pixels) to continue iterating
+    pub epsilon: f64,
+    /// Maximal distance (in pixels) between the original point and the
+    /// point tracked forward and then back, points with larger 
+    /// forward-backward error are reported as lost
+    pub max_error: f64,
+}
+
+impl Default for KltParams {
+    /// 15x15 window, 3 pyramid levels, 1 pixel forward-backward error
+    fn default() -> Self {
+        Self { window: 7, levels: 3, max_iterations: 20, epsilon: 0.01, max_error: 1.0 }
+    }
+}
+
+/// Pyramid level of a frame with its derivatives
+struct Level {
+    image: ImgBuf<f32>,
+    gx: ImgBuf<f32>,
+    gy: ImgBuf<f32>,
+}
+
+/// Pyramidal Lucas-Kanade (KLT) tracker of point sets
+/// 
+/// Tracker keeps the image pyramid of the previous frame, each call 
+/// of [track](#method.track) estimates new positions of points given 
+/// in the previous frame coordinates and replaces the stored frame 
+/// by the new one. Every point is tracked coarse-to-fine through pyramid
+/// levels, then tracked back from the new frame to the previous one, 
+/// points whose backward track does not return close to the original 
+/// position (occlusions, points leaving the frame, untextured windows) 
+/// are reported as lost (`None`).
+/// 
+/// Points are usually initialized by 
+/// [good_features_to_track](../feature/fn.good_features_to_track.html).
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, track::{KltTracker, KltParams}};
+/// 
+/// // smooth texture moving by (2.5, -1.5) pixels per frame
+/// let frame = |shift: Vec2d<f64>| {
+///     let mut image = ImgBuf::<f32>::new(ImgSize::new(64, 64));
+/// 
+///     for (position, pixel) in image.enumerate_pixels_mut() {
+///         let p = position.cast::<f64>() - shift;
+///         *pixel = (100.0*((p.x*0.3).sin()*(p.y*0.25).cos() + 1.0)) as f32;
+///     }
+/// 
+///     image
+/// };
+/// 
+/// let mut tracker = KltTracker::new(KltParams::default());
+/// tracker.track(&frame(Vec2d::new(0.0, 0.0)), &[]);
+/// let tracked = tracker.track(&frame(Vec2d::new(2.5, -1.5)), &[Point::new(30.0, 30.0)]);
+/// assert!((tracked[0].unwrap() - Point::new(32.5, 28.5)).length() < 0.1);
+/// ```
+pub struct KltTracker {
+    params: KltParams,
+    previous: Vec<Level>,
+}
+
+impl KltTracker {
+    /// Creates tracker without any stored frame
+    pub fn new(params: KltParams) -> Self {
+        Self { params, previous: Vec::new() }
+    }
+
+    /// Tracks `points` from the previous frame to `frame` and stores 
+    /// `frame` as the previous frame for the next call
+    /// 
+    /// Returns new point positions in the same order as `points`, `None`
+    /// for lost points. When there is no previous frame (first call or 
+    /// after [reset](#method.reset)), or the frame size has changed,
+    /// points are returned unchanged.
+    pub fn track<T: AsPrimitive<f32>>(&mut self, frame: &dyn Img<T>, points: &[Point]) -> Vec<Option<Point>> {
+        let current = pyramid(frame, self.params.levels);
+        let comparable = self.previous.first().map(|level| level.image.size()) == Some(frame.size());
+
+        let tracked = if comparable {
+            points.iter().map(|&point| {
+                let forward = track_point(&self.previous, &current, point, &self.params)?;
+                let backward = track_point(&current, &self.previous, forward, &self.params)?;
+
+                if backward.distance(point) <= self.params.max_error {
+                    Some(forward)
+                } else {
+                    None
+                }
+            }).collect()
+        } else {
+            points.iter().map(|&point| Some(point)).collect()
+        };
+
+        self.previous = current;
+        tracked
+    }
+
+    /// Forgets the stored previous frame
+    pub fn reset(&mut self) {
+        self.previous.clear();
+    }
+
+    /// Tracker parameters
+    pub fn params(&self) -> &KltParams {
+        &self.params
+    }
+}
+
+/// Gaussian pyramid of the frame with derivatives of each level
+fn pyramid<T: AsPrimitive<f32>>(frame: &dyn Img<T>, levels: usize) -> Vec<Level> {
+    let image = map_new(frame, |value: T| value.as_());
+
+    gaussian(&image, levels.max(1))
+        .into_iter()
+        .map(|image| {
+            let (gx, gy) = gradients(&image);
+            Level { image, gx, gy }
+        })
+        .collect()
+}
+
+/// Tracks a single point from `from` pyramid to `to` pyramid, `None` when
+/// the point leaves the frame or its window has no texture
+fn track_point(from: &[Level], to: &[Level], point: Point, params: &KltParams) -> Option<Point> {
+    let levels = from.len().min(to.len());
+    let radius = params.window as isize;
+    let mut guess = Vec2d::new(0.0, 0.0);
+
+    for level in (0..levels).rev() {
+        let (source, target) = (&from[level], &to[level]);
+        let scale = (1u64 << level) as f64;
+        let center = point/scale;
+
+        // Window samples (offset, intensity, gradient) in the source frame
+        let mut samples = Vec::with_capacity((2*radius as usize + 1).pow(2));
+        let (mut a, mut b, mut c) = (0.0, 0.0, 0.0);
+
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let offset = Vec2d::new(dx as f64, dy as f64);
+                let position = (center + offset).cast::<f32>();
+
+                let sample = (
+                    bilinear(&source.image, position), 
+                    bilinear(&source.gx, position), 
+                    bilinear(&source.gy, position)
+                );
+
+                if let (Some(value), Some(x), Some(y)) = sample {
+                    a += x*x;
+                    b += x*y;
+                    c += y*y;
+                    samples.push((offset, value, Vec2d::new(x, y)));
+                }
+            }
+        }
+
+        let determinant = a*c - b*b;
+        let min_eigenvalue = (a + c)/2.0 - (((a - c)/2.0).powi(2) + b*b).sqrt();
+
+        if samples.is_empty() || min_eigenvalue <= 1e-6*samples.len() as f64 || determinant <= 0.0 {
+            return None;
+        }
+
+        let mut motion = Vec2d::new(0.0, 0.0);
+
+        for _ in 0..params.max_iterations.max(1) {
+            let mut mismatch = Vec2d::new(0.0, 0.0);
+
+            for &(offset, value, gradient) in &samples {
+                let position = (center + guess + motion + offset).cast::<f32>();
+
+                if let Some(moved) = bilinear(&target.image, position) {
+                    mismatch = mismatch + gradient*(value - moved);
+                }
+            }
+
+            let step = Vec2d::new(c*mismatch.x - b*mismatch.y, a*mismatch.y - b*mismatch.x)/determinant;
+            motion = motion + step;
+
+            if step.length() < params.epsilon {
+                break;
+            }
+        }
+
+        guess = if level > 0 { (guess + motion)*2.0 } else { guess + motion };
+    }
+
+    let tracked = point + guess;
+    let size = to.first()?.image.size();
+    let inside = tracked.x >= 0.0 && tracked.y >= 0.0 
+        && tracked.x <= (size.x - 1) as f64 && tracked.y <= (size.y - 1) as f64;
+
+    if inside { Some(tracked) } else { None }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgLinesMut, ImgSize, feature::good_features_to_track};
+    use super::*;
+
+    /// Random blobs texture shifted by `shift`
+    fn frame(size: ImgSize, shift: Vec2d<f64>) -> ImgBuf<f32> {
+        let mut random = crate::random::Random::new(3);
+        let blobs: Vec<_> = (0..60)
+            .map(|_| Point::new(random.uniform()*size.x as f64, random.uniform()*size.y as f64))
+            .collect();
+
+        let mut image = ImgBuf::<f32>::new(size);
+
+        for (position, pixel) in image.enumerate_pixels_mut() {
+            let p = position.cast::<f64>() - shift;
+            let value: f64 = blobs.iter().map(|blob| (-blob.distance(p).powi(2)/18.0).exp()).sum();
+            *pixel = (200.0*value) as f32;
+        }
+
+        image
+    }
+
+    #[test]
+    fn large_motion_is_tracked_through_pyramid() {
+        let size = ImgSize::new(96, 96);
+        let shift = Vec2d::new(7.3, -5.6);
+        let first = frame(size, Vec2d::new(0.0, 0.0));
+        let points: Vec<_> = good_features_to_track(&first, 20, 0.05, 8.0)
+            .into_iter()
+            .filter(|p| p.x > 15.0 && p.y > 15.0 && p.x < 80.0 && p.y < 80.0)
+            .collect();
+
+        assert!(points.len() >= 5);
+
+        let mut tracker = KltTracker::new(KltParams::default());
+        assert_eq!(tracker.track(&first, &points), points.iter().map(|&p| Some(p)).collect::<Vec<_>>());
+
+        let tracked = tracker.track(&frame(size, shift), &points);
+
+        for (point, tracked) in points.iter().zip(tracked) {
+            let tracked = tracked.expect("point lost");
+            assert!((tracked - (*point + shift)).length() < 0.1, "{:?} {:?}", point, tracked);
+        }
+    }
+
+    #[test]
+    fn flat_and_leaving_points_are_lost() {
+        let size = ImgSize::new(64, 64);
+        let mut tracker = KltTracker::new(KltParams::default());
+        tracker.track(&ImgBuf::<u8>::new_init(size, 50), &[]);
+        let tracked = tracker.track(&ImgBuf::<u8>::new_init(size, 50), &[Point::new(30.0, 30.0)]);
+        assert_eq!(tracked, vec![None]);
+
+        tracker.reset();
+        tracker.track(&frame(size, Vec2d::new(0.0, 0.0)), &[]);
+        let tracked = tracker.track(&frame(size, Vec2d::new(-6.0, 0.0)), &[Point::new(2.0, 30.0)]);
+        assert_eq!(tracked, vec![None]);
+    }
+}
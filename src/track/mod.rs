@@ -0,0 +1,13 @@
+//! Tracking of image features across video frames
+//! 
+//! Available types and functions:
+//!  * [KltTracker](struct.KltTracker.html) - pyramidal Lucas-Kanade 
+//!    tracker of point sets
+//!  * [mean_shift](fn.mean_shift.html), [cam_shift](fn.cam_shift.html) - 
+//!    tracking of windows in probability maps
+
+mod klt;
+mod shift;
+
+pub use klt::{KltTracker, KltParams};
+pub use shift::{mean_shift, cam_shift};
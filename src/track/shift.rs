@@ -0,0 +1,162 @@
+use crate::{AsPrimitive, Img, ImgRange, Range2d, Vec2d, feature::TermCriteria, stats::moments};
+
+/// Margin (in pixels) around the converged CAMShift window used to 
+/// measure the distribution, so the window can grow
+const MARGIN: isize = 10;
+
+/// Moves `window` to the local mode of probability map `prob_map` by the
+/// mean-shift algorithm
+/// 
+/// In each iteration the window (keeping its size) is centered on the
+/// centroid of probability values within the window, until the window 
+/// moves by less than `criteria.epsilon` pixels or after 
+/// `criteria.max_iterations` iterations. Window is kept inside the image
+/// (as far as its size allows), window with zero probability mass stays 
+/// in place. Probability maps are usually created by back-projection 
+/// of a color histogram of the tracked object.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::TermCriteria, track::mean_shift};
+/// let mut prob = ImgBuf::<f32>::new(ImgSize::new(40, 40));
+/// 
+/// for (position, pixel) in prob.enumerate_pixels_mut() {
+///     let offset = position.cast::<f64>() - Vec2d::new(25.0, 22.0);
+///     *pixel = (-offset.length_squared()/20.0).exp() as f32;
+/// }
+/// 
+/// let window = mean_shift(&prob, Range2d::new(16..27, 12..23), TermCriteria::default());
+/// assert_eq!(window, Range2d::new(20..31, 17..28));
+/// ```
+pub fn mean_shift<T: AsPrimitive<f64>>(prob_map: &dyn Img<T>, window: ImgRange, criteria: TermCriteria) -> ImgRange {
+    let bounds = prob_map.range();
+    let mut window = keep_inside(window, bounds);
+
+    for _ in 0..criteria.max_iterations.max(1) {
+        let center = match moments(prob_map, window).centroid() {
+            Some(center) => center,
+            None => break,
+        };
+
+        let current = window_center(window);
+        let shift = center - current;
+        let moved = keep_inside(window + Vec2d::new(shift.x.round() as isize, shift.y.round() as isize), bounds);
+        let done = moved == window || shift.length() < criteria.epsilon;
+        window = moved;
+
+        if done {
+            break;
+        }
+    }
+
+    window
+}
+
+/// Continuously adaptive mean-shift (CAMShift) tracking step
+/// 
+/// Runs [mean_shift](fn.mean_shift.html) and then adapts window size 
+/// and orientation to the probability distribution found around the 
+/// converged window. Returns the new window (the bounding range of the
+/// ellipse with axes of two standard deviations of the distribution in 
+/// each direction) and the orientation of the major axis in radians, 
+/// measured from the `x` axis towards the `y` axis. Returned window 
+/// is intended as the initial window for the next frame.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, feature::TermCriteria, track::cam_shift};
+/// // elongated distribution along the x axis
+/// let mut prob = ImgBuf::<f32>::new(ImgSize::new(60, 40));
+/// 
+/// for (position, pixel) in prob.enumerate_pixels_mut() {
+///     let (x, y) = (position.x as f64 - 30.0, position.y as f64 - 20.0);
+///     *pixel = (-(x*x/50.0 + y*y/8.0)).exp() as f32;
+/// }
+/// 
+/// let (window, angle) = cam_shift(&prob, Range2d::new(24..34, 14..24), TermCriteria::default());
+/// assert!(window.width() > window.height());
+/// assert!(window.contains(Vec2d::new(30, 20)));
+/// assert!(angle.abs() < 1e-6);
+/// ```
+pub fn cam_shift<T: AsPrimitive<f64>>(prob_map: &dyn Img<T>, window: ImgRange, criteria: TermCriteria) -> (ImgRange, f64) {
+    let bounds = prob_map.range();
+    let converged = mean_shift(prob_map, window, criteria);
+    let m = moments(prob_map, converged.inflate(MARGIN).clamp_to(bounds));
+
+    let center = match m.centroid() {
+        Some(center) => center,
+        None => return (converged, 0.0),
+    };
+
+    let angle = m.orientation();
+    let spread = (((m.mu20 - m.mu02)/2.0).powi(2) + m.mu11*m.mu11).sqrt();
+    let major = (((m.mu20 + m.mu02)/2.0 + spread)/m.m00).max(0.0).sqrt()*2.0;
+    let minor = (((m.mu20 + m.mu02)/2.0 - spread)/m.m00).max(0.0).sqrt()*2.0;
+
+    // Bounding box of the rotated ellipse
+    let (sin, cos) = angle.sin_cos();
+    let half_x = ((major*cos).powi(2) + (minor*sin).powi(2)).sqrt().max(0.5);
+    let half_y = ((major*sin).powi(2) + (minor*cos).powi(2)).sqrt().max(0.5);
+
+    let adapted = Range2d::new(
+        ((center.x - half_x).round() as isize)..((center.x + half_x).round() as isize + 1),
+        ((center.y - half_y).round() as isize)..((center.y + half_y).round() as isize + 1),
+    ).clamp_to(bounds);
+
+    (if adapted.is_empty() { converged } else { adapted }, angle)
+}
+
+/// Center of window pixels
+fn window_center(window: ImgRange) -> Vec2d<f64> {
+    Vec2d::new(
+        (window.x.start + window.x.end - 1) as f64/2.0, 
+        (window.y.start + window.y.end - 1) as f64/2.0
+    )
+}
+
+/// Shifts window inside `bounds`, windows larger than bounds are aligned
+/// to the bounds start
+fn keep_inside(window: ImgRange, bounds: ImgRange) -> ImgRange {
+    let shift = |start: isize, end: isize, low: isize, high: isize| {
+        if end > high { (high - end).max(low - start) } else if start < low { low - start } else { 0 }
+    };
+
+    let dx = shift(window.x.start, window.x.end, bounds.x.start, bounds.x.end);
+    let dy = shift(window.y.start, window.y.end, bounds.y.start, bounds.y.end);
+    window + Vec2d::new(dx, dy)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImgBuf, ImgLinesMut, ImgSize};
+    use super::*;
+
+    #[test]
+    fn window_stays_inside_image() {
+        let mut prob = ImgBuf::<u8>::new(ImgSize::new(20, 20));
+        prob[(0, 0)] = 255;
+
+        let window = mean_shift(&prob, Range2d::new(0..6, 0..6), TermCriteria::default());
+        assert_eq!(window, Range2d::new(0..6, 0..6));
+        assert_eq!(keep_inside(Range2d::new(17..23, -2..4), prob.range()), Range2d::new(14..20, 0..6));
+    }
+
+    #[test]
+    fn cam_shift_follows_rotated_distribution() {
+        let mut prob = ImgBuf::<f32>::new(ImgSize::new(80, 80));
+        let direction = Vec2d::<f64>::new(1.0, 1.0).normalized();
+
+        for (position, pixel) in prob.enumerate_pixels_mut() {
+            let offset = position.cast::<f64>() - Vec2d::new(45.0, 40.0);
+            let (along, across) = (offset*direction, offset*direction.perp());
+            *pixel = (-(along*along/60.0 + across*across/6.0)).exp() as f32;
+        }
+
+        let (window, angle) = cam_shift(&prob, Range2d::new(30..44, 28..42), TermCriteria::default());
+        assert!((angle - std::f64::consts::FRAC_PI_4).abs() < 1e-3, "{}", angle);
+        assert!(window.contains(Vec2d::new(45, 40)));
+        assert!((window.width() - window.height()).abs() <= 1);
+    }
+}
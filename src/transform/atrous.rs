@@ -0,0 +1,164 @@
+use crate::{
+    Img, ImgLinesMut, ImgBuf, AsPrimitive, stats::mad_std_dev,
+    filter::{horizontal_filter, vertical_filter},
+};
+
+/// B3 spline scaling function kernel
+const KERNEL: [f32; 5] = [1.0/16.0, 4.0/16.0, 6.0/16.0, 4.0/16.0, 1.0/16.0];
+
+/// Result of the à-trous wavelet decomposition, the original image 
+/// equals the sum of all detail planes and the residual
+#[derive(Clone, Debug, PartialEq)]
+pub struct WaveletPlanes {
+    /// Detail planes from the finest to the coarsest scale
+    pub details: Vec<ImgBuf<f32>>,
+    /// Smooth residual image after removing all details
+    pub residual: ImgBuf<f32>,
+}
+
+/// Decomposes image into `levels` detail planes and a residual 
+/// using the à-trous wavelet transform with B3 spline kernel
+/// 
+/// Image is repeatedly smoothed by the 5x5 B3 spline kernel, which is 
+/// dilated by inserting `2^j - 1` zeros between its elements at level `j`, 
+/// the detail plane is the difference between successive smoothed images.
+/// All planes have the same size as the original image.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, transform::{atrous_decompose, atrous_reconstruct}};
+/// let image = ImgBuf::<u8>::from_vec(ImgSize::new(4, 2), vec![1, 5, 2, 8, 0, 3, 9, 4]);
+/// let planes = atrous_decompose(&image, 3);
+/// assert_eq!(planes.details.len(), 3);
+/// let restored = atrous_reconstruct(&planes);
+/// assert!((restored[(3, 0)] - 8.0).abs() < 1e-5);
+/// ```
+pub fn atrous_decompose<T: AsPrimitive<f32>>(image: &dyn Img<T>, levels: usize) -> WaveletPlanes {
+    let size = image.size();
+    let mut current = ImgBuf::<f32>::new(size);
+
+    for (dst, src) in current.lines_mut().zip(image.lines()) {
+        for x in 0..dst.len() {
+            dst[x] = src[x].as_();
+        }
+    }
+
+    let mut details = Vec::with_capacity(levels);
+
+    for level in 0..levels {
+        let kernel = dilated_kernel(level);
+        let mut horizontal = ImgBuf::<f32>::new(size);
+        let mut smooth = ImgBuf::<f32>::new(size);
+        horizontal_filter(&current, &mut horizontal, &kernel, sparse_convolution);
+        vertical_filter(&horizontal, &mut smooth, &kernel, sparse_convolution);
+
+        for (detail, coarse) in current.lines_mut().zip(smooth.lines()) {
+            for x in 0..detail.len() {
+                detail[x] -= coarse[x];
+            }
+        }
+
+        details.push(std::mem::replace(&mut current, smooth));
+    }
+
+    WaveletPlanes { details, residual: current }
+}
+
+/// Reconstructs image as the sum of all detail planes and the residual
+pub fn atrous_reconstruct(planes: &WaveletPlanes) -> ImgBuf<f32> {
+    let mut output = planes.residual.clone();
+
+    for detail in &planes.details {
+        for (dst, src) in output.lines_mut().zip(detail.lines()) {
+            for x in 0..dst.len() {
+                dst[x] += src[x];
+            }
+        }
+    }
+
+    output
+}
+
+/// Reduces noise by soft thresholding of wavelet detail planes
+/// 
+/// Image is decomposed into `thresholds.len()` detail planes, noise 
+/// level of every plane is estimated robustly (by median absolute 
+/// deviation) and coefficients of plane `j` are shrunk towards zero 
+/// by `thresholds[j]` times the noise level. Typical thresholds are 
+/// around `3.0` for the finest planes, decreasing for coarser planes.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, synth::add_gaussian_noise, transform::wavelet_denoise};
+/// let clean = ImgBuf::<f32>::new_init(ImgSize::new(32, 32), 100.0);
+/// let mut noisy = clean.clone();
+/// add_gaussian_noise(&mut noisy, 5.0, 1);
+/// let denoised = wavelet_denoise(&noisy, &[3.0, 2.0, 1.0]);
+/// let error = |image: &ImgBuf<f32>| image.lines()
+///     .flat_map(|line| line.iter().map(|v| (v - 100.0).abs()))
+///     .sum::<f32>();
+/// assert!(error(&denoised) < error(&noisy)/2.0);
+/// ```
+pub fn wavelet_denoise<T: AsPrimitive<f32>>(image: &dyn Img<T>, thresholds: &[f32]) -> ImgBuf<f32> {
+    let mut planes = atrous_decompose(image, thresholds.len());
+
+    for (detail, &factor) in planes.details.iter_mut().zip(thresholds) {
+        let mut values: Vec<f32> = detail.lines().flat_map(|line| line.iter().copied()).collect();
+        let threshold = factor*mad_std_dev(&mut values);
+
+        for line in detail.lines_mut() {
+            for value in line.iter_mut() {
+                *value = value.signum()*(value.abs() - threshold).max(0.0);
+            }
+        }
+    }
+
+    atrous_reconstruct(&planes)
+}
+
+/// B3 spline kernel with `2^level - 1` zeros inserted between elements
+fn dilated_kernel(level: usize) -> Vec<f32> {
+    let step = 1 << level;
+    let mut kernel = vec![0.0; 4*step + 1];
+
+    for (index, &value) in KERNEL.iter().enumerate() {
+        kernel[index*step] = value;
+    }
+
+    kernel
+}
+
+/// Convolution operator skipping zero kernel elements
+fn sparse_convolution(src: &[f32], dst: &mut [f32], kernel: f32) {
+    if kernel != 0.0 {
+        for i in 0..std::cmp::min(src.len(), dst.len()) {
+            dst[i] += kernel*src[i];
+        }
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImgSize;
+
+    #[test]
+    fn constant_image_has_zero_details() {
+        let image = ImgBuf::<f32>::new_init(ImgSize::new(7, 5), 3.0);
+        let planes = atrous_decompose(&image, 4);
+
+        for detail in &planes.details {
+            assert!(detail.lines().all(|line| line.iter().all(|v| v.abs() < 1e-5)));
+        }
+
+        assert!((planes.residual[(3, 2)] - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn kernel_is_dilated() {
+        assert_eq!(dilated_kernel(0).len(), 5);
+        assert_eq!(dilated_kernel(1), vec![1.0/16.0, 0.0, 0.25, 0.0, 0.375, 0.0, 0.25, 0.0, 1.0/16.0]);
+    }
+}
@@ -0,0 +1,12 @@
+//! Multiscale image transforms
+//! 
+//! Available functions:
+//!  * [atrous_decompose](fn.atrous_decompose.html), 
+//!    [atrous_reconstruct](fn.atrous_reconstruct.html) - à-trous 
+//!    (undecimated) wavelet transform
+//!  * [wavelet_denoise](fn.wavelet_denoise.html) - noise reduction by
+//!    thresholding of wavelet detail planes
+
+mod atrous;
+
+pub use atrous::{atrous_decompose, atrous_reconstruct, wavelet_denoise, WaveletPlanes};
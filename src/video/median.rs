@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+use crate::{Img, ImgMut, ImgBuf, ImgSize};
+
+/// Per-pixel median over a sliding window of the last `window` frames
+/// 
+/// Removes transient objects (passing cars, people, birds) and temporal 
+/// noise from footage of a static camera. Values of each pixel in the 
+/// window are kept sorted, so pushing a frame costs `O(window)` per pixel 
+/// instead of sorting the whole window again. For even number of frames 
+/// the lower of the two middle values is used, so the median is always 
+/// one of the observed values (and no averaging of integer types is 
+/// needed).
+/// 
+/// # Panics
+/// 
+/// [push](#method.push) panics when the frame size differs from the size
+/// of previously pushed frames (call [reset](#method.reset) first)
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, video::TemporalMedian};
+/// let mut median = TemporalMedian::<u8>::new(3);
+/// 
+/// for &value in &[10, 250, 12, 11] {
+///     median.push(&ImgBuf::new_init(ImgSize::new(2, 2), value));
+/// }
+/// 
+/// // window contains the last three frames, the outlier is removed
+/// assert_eq!(median.len(), 3);
+/// assert_eq!(median.median(), Some(ImgBuf::new_init(ImgSize::new(2, 2), 12)));
+/// ```
+pub struct TemporalMedian<T> {
+    window: usize,
+    size: ImgSize,
+    frames: VecDeque<ImgBuf<T>>,
+    /// Sorted values of each pixel, `frames.len()` values per pixel
+    sorted: Vec<T>,
+}
+
+impl<T: Copy + Default + PartialOrd> TemporalMedian<T> {
+    /// Creates filter with a window of `window` frames (at least one)
+    pub fn new(window: usize) -> Self {
+        Self { 
+            window: window.max(1), 
+            size: ImgSize::new(0, 0), 
+            frames: VecDeque::with_capacity(window.max(1)),
+            sorted: Vec::new(),
+        }
+    }
+
+    /// Adds `frame` to the window, the oldest frame is dropped 
+    /// when the window is full
+    pub fn push(&mut self, frame: &dyn Img<T>) {
+        if self.frames.is_empty() {
+            self.size = frame.size();
+            self.sorted = Vec::with_capacity(self.size.product()*self.window);
+        }
+
+        assert_eq!(frame.size(), self.size, "Frame size differs from previous frames");
+
+        let (size, count) = (self.size, self.frames.len());
+        let oldest = if count == self.window { self.frames.pop_front() } else { None };
+        let mut incoming = ImgBuf::<T>::new(size);
+        let mut sorted = Vec::with_capacity(size.product()*(count + 1).min(self.window));
+
+        for y in 0..size.y {
+            let line = frame.line_ref(y);
+            incoming.line_mut(y).copy_from_slice(line);
+
+            for x in 0..size.x {
+                let pixel = y*size.x + x;
+                let values = &self.sorted[pixel*count..(pixel + 1)*count];
+                let removed = oldest.as_ref().map(|oldest| oldest.line_ref(y)[x]);
+                let value = line[x];
+                let mut inserted = false;
+                let mut skipped = false;
+
+                for &existing in values {
+                    if !skipped && removed.is_some_and(|removed| same(existing, removed)) {
+                        skipped = true;
+                        continue;
+                    }
+
+                    if !inserted && value < existing {
+                        sorted.push(value);
+                        inserted = true;
+                    }
+
+                    sorted.push(existing);
+                }
+
+                if !inserted {
+                    sorted.push(value);
+                }
+            }
+        }
+
+        self.sorted = sorted;
+        self.frames.push_back(incoming);
+    }
+
+    /// Median frame of the current window, `None` before the first frame
+    pub fn median(&self) -> Option<ImgBuf<T>> {
+        let count = self.frames.len();
+
+        if count == 0 {
+            return None;
+        }
+
+        let middle = (count - 1)/2;
+        let values = self.sorted.chunks_exact(count).map(|values| values[middle]).collect();
+        Some(ImgBuf::from_vec(self.size, values))
+    }
+
+    /// Number of frames currently in the window
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Tests whether no frame has been pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Drops all frames from the window
+    pub fn reset(&mut self) {
+        self.frames.clear();
+        self.sorted.clear();
+    }
+}
+
+/// Value equality treating unordered values (NaN) as equal to each other,
+/// so removed NaN values are found in the sorted window
+fn same<T: PartialOrd>(a: T, b: T) -> bool {
+    a == b || (a.partial_cmp(&a).is_none() && b.partial_cmp(&b).is_none())
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Random;
+    use super::*;
+
+    #[test]
+    fn matches_sorted_window() {
+        let size = ImgSize::new(5, 3);
+        let mut random = Random::new(5);
+        let frames: Vec<ImgBuf<u16>> = (0..12)
+            .map(|_| ImgBuf::from_vec(size, (0..size.product()).map(|_| random.index(8) as u16).collect()))
+            .collect();
+
+        let mut median = TemporalMedian::new(4);
+
+        for (index, frame) in frames.iter().enumerate() {
+            median.push(frame);
+            let window = &frames[index.saturating_sub(3)..=index];
+            let result = median.median().unwrap();
+
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    let mut values: Vec<_> = window.iter().map(|frame| frame[(x, y)]).collect();
+                    values.sort();
+                    assert_eq!(result[(x, y)], values[(values.len() - 1)/2]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reset_allows_new_frame_size() {
+        let mut median = TemporalMedian::<f32>::new(2);
+        assert_eq!(median.median(), None);
+        median.push(&ImgBuf::new(ImgSize::new(3, 3)));
+        median.reset();
+        assert!(median.is_empty());
+        median.push(&ImgBuf::new_init(ImgSize::new(1, 2), 4.0));
+        assert_eq!(median.median(), Some(ImgBuf::new_init(ImgSize::new(1, 2), 4.0)));
+    }
+}
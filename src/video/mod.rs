@@ -0,0 +1,14 @@
+//! Stateful operators processing sequences of video frames
+//! 
+//! Available types:
+//!  * [TemporalMedian](struct.TemporalMedian.html) - per-pixel median 
+//!    over a sliding window of frames
+//!  * [FrameSource](trait.FrameSource.html) - common input interface 
+//!    of frame streams, implemented by [IterSource](struct.IterSource.html)
+//!    and [DirectorySource](struct.DirectorySource.html)
+
+mod median;
+mod source;
+
+pub use median::TemporalMedian;
+pub use source::{FrameSource, IterSource, DirectorySource};
@@ -0,0 +1,162 @@
+use std::path::{Path, PathBuf};
+use crate::ImgBuf;
+
+/// Source of video frames consumed by stateful video operators
+/// 
+/// Implementors produce frames one by one until the stream ends. 
+/// Sources decoding into preallocated memory can override 
+/// [next_frame_into](#method.next_frame_into) to avoid allocating 
+/// a new buffer for every frame.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, video::{FrameSource, IterSource, TemporalMedian}};
+/// let frames = (0..5u8).map(|value| ImgBuf::new_init(ImgSize::new(4, 4), value));
+/// let mut source = IterSource::new(frames);
+/// let mut median = TemporalMedian::new(5);
+/// 
+/// while let Some(frame) = source.next_frame() {
+///     median.push(&frame);
+/// }
+/// 
+/// assert_eq!(median.median(), Some(ImgBuf::new_init(ImgSize::new(4, 4), 2)));
+/// ```
+pub trait FrameSource<T> {
+    /// Next frame of the stream, `None` at the end of the stream
+    fn next_frame(&mut self) -> Option<ImgBuf<T>>;
+
+    /// Stores the next frame into `buffer`, returns `false` (keeping 
+    /// `buffer` unchanged) at the end of the stream
+    fn next_frame_into(&mut self, buffer: &mut ImgBuf<T>) -> bool {
+        match self.next_frame() {
+            Some(frame) => {
+                *buffer = frame;
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Frame source adapter over an iterator of frames
+pub struct IterSource<I> {
+    frames: I,
+}
+
+impl<I> IterSource<I> {
+    /// Creates source producing frames of `frames` iterator
+    pub fn new<S: IntoIterator<IntoIter=I>>(frames: S) -> Self {
+        Self { frames: frames.into_iter() }
+    }
+}
+
+impl<T, I: Iterator<Item=ImgBuf<T>>> FrameSource<T> for IterSource<I> {
+    fn next_frame(&mut self) -> Option<ImgBuf<T>> {
+        self.frames.next()
+    }
+}
+
+/// Frame source reading image files of a directory in the order 
+/// of file names
+/// 
+/// Files are decoded by the `loader` function, so any image format 
+/// can be used. Files with extension different from the requested one,
+/// subdirectories and files the loader fails to decode (returns `None`)
+/// are skipped.
+/// 
+/// # Example
+/// ```no_run
+/// use nanocv::{ImgBuf, ImgSize, video::{FrameSource, DirectorySource}};
+/// 
+/// // raw 640x480 8-bit frames stored as frame_0000.raw, frame_0001.raw, ...
+/// let mut source = DirectorySource::new("frames", "raw", |path| {
+///     let data = std::fs::read(path).ok()?;
+///     let size = ImgSize::new(640, 480);
+///     (data.len() == size.product()).then(|| ImgBuf::from_vec(size, data))
+/// }).unwrap();
+/// 
+/// while let Some(frame) = source.next_frame() {
+///     // process frame
+/// }
+/// ```
+pub struct DirectorySource<F> {
+    paths: std::vec::IntoIter<PathBuf>,
+    loader: F,
+}
+
+impl<F> DirectorySource<F> {
+    /// Lists files with `extension` (compared case insensitively) 
+    /// in `directory`, fails when the directory cannot be read
+    pub fn new<P, T>(directory: P, extension: &str, loader: F) -> std::io::Result<Self> 
+    where P: AsRef<Path>, F: FnMut(&Path) -> Option<ImgBuf<T>> {
+        let mut paths = Vec::new();
+
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+
+            let matches = path.extension()
+                .and_then(|value| value.to_str())
+                .is_some_and(|value| value.eq_ignore_ascii_case(extension));
+
+            if matches && path.is_file() {
+                paths.push(path);
+            }
+        }
+
+        paths.sort();
+        Ok(Self { paths: paths.into_iter(), loader })
+    }
+
+    /// Number of files not read yet
+    pub fn remaining(&self) -> usize {
+        self.paths.len()
+    }
+}
+
+impl<T, F: FnMut(&Path) -> Option<ImgBuf<T>>> FrameSource<T> for DirectorySource<F> {
+    fn next_frame(&mut self) -> Option<ImgBuf<T>> {
+        for path in &mut self.paths {
+            if let Some(frame) = (self.loader)(&path) {
+                return Some(frame);
+            }
+        }
+
+        None
+    }
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use crate::ImgSize;
+    use super::*;
+
+    #[test]
+    fn directory_frames_are_read_in_name_order() {
+        let directory = std::env::temp_dir().join(format!("nanocv-frames-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        for (name, value) in &[("b.RAW", 2u8), ("a.raw", 1), ("c.raw", 3), ("d.txt", 4), ("e.raw", 0)] {
+            let length = if *value == 0 { 1 } else { 4 };
+            std::fs::write(directory.join(name), vec![*value; length]).unwrap();
+        }
+
+        let mut source = DirectorySource::new(&directory, "raw", |path| {
+            let data = std::fs::read(path).ok()?;
+            (data.len() == 4).then(|| ImgBuf::<u8>::from_vec(ImgSize::new(2, 2), data))
+        }).unwrap();
+
+        assert_eq!(source.remaining(), 4);
+        let mut buffer = ImgBuf::new(ImgSize::new(2, 2));
+        let mut values = Vec::new();
+
+        while source.next_frame_into(&mut buffer) {
+            values.push(buffer[(0, 0)]);
+        }
+
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(values, vec![1, 2, 3]);
+        assert_eq!(source.next_frame(), None);
+    }
+}
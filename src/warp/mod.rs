@@ -0,0 +1,21 @@
+//! Geometric warps between camera projections and coordinate systems
+//! 
+//! All warps build a coordinate map and resample the input image by 
+//! [filter::remap](../filter/fn.remap.html) with bilinear interpolation,
+//! output pixels mapped outside of the input image are set to zero.
+//! 
+//! Available functions:
+//!  * [fisheye_to_rectilinear](fn.fisheye_to_rectilinear.html) - 
+//!    perspective view of an equidistant fisheye image
+//!  * [equirect_to_perspective](fn.equirect_to_perspective.html) - 
+//!    perspective view into an equirectangular panorama
+//!  * [log_polar](fn.log_polar.html), [log_polar_inverse](fn.log_polar_inverse.html) - 
+//!    log-polar transform turning scaling and rotation into translation
+//!  * [to_polar](fn.to_polar.html), [from_polar](fn.from_polar.html) - 
+//!    unwrapping of circular areas into rectangular strips
+
+mod projection;
+mod polar;
+
+pub use projection::{fisheye_to_rectilinear, equirect_to_perspective};
+pub use polar::{log_polar, log_polar_inverse, to_polar, from_polar};
@@ -0,0 +1,206 @@
+use std::f64::consts::PI;
+use crate::{AsPrimitive, Img, ImgBuf, ImgSize, Vec2d, geometry::Point, filter::remap};
+
+/// Log-polar transform of `image` around `center`
+/// 
+/// Output has the same size as input, column `x` corresponds to the 
+/// distance `exp(x/magnitude_scale)` from the center and line `y` 
+/// to the angle `2π*y/height` (measured from the `x` axis towards the
+/// `y` axis). Scaling of the input image around the center becomes 
+/// horizontal shift and rotation becomes vertical (cyclic) shift of 
+/// the output, so scale and rotation can be estimated by translation 
+/// registration (phase correlation) of log-polar images.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, warp::log_polar};
+/// // bright pixel 8 pixels right of the center
+/// let mut image = ImgBuf::<f32>::new(ImgSize::new(32, 32));
+/// image[(24, 16)] = 1.0;
+/// 
+/// let scale = 10.0;
+/// let transformed = log_polar(&image, Point::new(16.0, 16.0), scale);
+/// let column = (8f64.ln()*scale).round() as usize;
+/// assert!(transformed[(column, 0)] > 0.5);
+/// ```
+pub fn log_polar<T>(image: &dyn Img<T>, center: Point, magnitude_scale: f64) -> ImgBuf<T> 
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let size = image.size();
+    let angle_step = 2.0*PI/size.y.max(1) as f64;
+    polar_warp(image, size, center, |x| (x/magnitude_scale).exp(), angle_step)
+}
+
+/// Inverse of [log_polar](fn.log_polar.html), maps log-polar `image` 
+/// back to cartesian image of `output_size`
+/// 
+/// Parameters `center` and `magnitude_scale` must be the same as used 
+/// for the forward transform, pixels closer to the center than one pixel
+/// (not represented in the log-polar image) are zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, warp::{log_polar, log_polar_inverse}};
+/// let mut image = ImgBuf::<f32>::new(ImgSize::new(64, 64));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     *pixel = (position.x/8 % 2) as f32;
+/// }
+/// 
+/// let center = Point::new(32.0, 32.0);
+/// let polar = log_polar(&image, center, 10.0);
+/// let restored = log_polar_inverse(&polar, center, 10.0, image.size());
+/// assert!((restored[(45, 36)] - image[(45, 36)]).abs() < 0.1);
+/// ```
+pub fn log_polar_inverse<T>(image: &dyn Img<T>, center: Point, magnitude_scale: f64, output_size: ImgSize) -> ImgBuf<T> 
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let angle_step = 2.0*PI/image.height().max(1) as f64;
+    cartesian_warp(image, output_size, center, |radius| {
+        if radius >= 1.0 { Some(magnitude_scale*radius.ln()) } else { None }
+    }, angle_step)
+}
+
+/// Unwraps circular area around `center` into a rectangular strip
+/// 
+/// Output has the same size as input, column `x` corresponds to the
+/// distance `x*max_radius/width` from the center and line `y` to the 
+/// angle `2π*y/height` (measured from the `x` axis towards the `y` axis),
+/// so circles around the center become vertical lines.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, warp::to_polar};
+/// // ring of radius 10 becomes a vertical line
+/// let mut image = ImgBuf::<f32>::new(ImgSize::new(40, 40));
+/// 
+/// for (position, pixel) in image.enumerate_pixels_mut() {
+///     let radius = (position.cast::<f64>() - Vec2d::new(20.0, 20.0)).length();
+///     *pixel = if (radius - 10.0).abs() < 1.0 { 1.0 } else { 0.0 };
+/// }
+/// 
+/// let strip = to_polar(&image, Point::new(20.0, 20.0), 20.0);
+/// assert!((0..40).all(|y| strip[(20, y)] > 0.5 && strip[(10, y)] == 0.0));
+/// ```
+pub fn to_polar<T>(image: &dyn Img<T>, center: Point, max_radius: f64) -> ImgBuf<T> 
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let size = image.size();
+    let radius_step = max_radius/size.x.max(1) as f64;
+    let angle_step = 2.0*PI/size.y.max(1) as f64;
+    polar_warp(image, size, center, |x| x*radius_step, angle_step)
+}
+
+/// Inverse of [to_polar](fn.to_polar.html), maps polar strip `image` 
+/// back to cartesian image of `output_size`
+/// 
+/// Parameters `center` and `max_radius` must be the same as used for the
+/// forward transform, pixels further than `max_radius` from the center
+/// are zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, geometry::Point, warp::from_polar};
+/// // strip with bright first half of columns becomes a disk
+/// let mut strip = ImgBuf::<u8>::new(ImgSize::new(20, 36));
+/// 
+/// for (position, pixel) in strip.enumerate_pixels_mut() {
+///     *pixel = if position.x < 10 { 200 } else { 0 };
+/// }
+/// 
+/// let image = from_polar(&strip, Point::new(15.0, 15.0), 10.0, ImgSize::new(31, 31));
+/// assert_eq!(image[(15, 15)], 200);
+/// assert_eq!(image[(18, 12)], 200);
+/// assert_eq!(image[(15, 27)], 0);
+/// ```
+pub fn from_polar<T>(image: &dyn Img<T>, center: Point, max_radius: f64, output_size: ImgSize) -> ImgBuf<T> 
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let width = image.width() as f64;
+    let angle_step = 2.0*PI/image.height().max(1) as f64;
+    cartesian_warp(image, output_size, center, |radius| {
+        if radius <= max_radius && max_radius > 0.0 { Some(radius*width/max_radius) } else { None }
+    }, angle_step)
+}
+
+/// Samples `image` along rays from `center`, output column `x` is sampled
+/// at distance `radius(x)`, line `y` at angle `y*angle_step`
+fn polar_warp<T, F>(image: &dyn Img<T>, size: ImgSize, center: Point, radius: F, angle_step: f64) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T>, F: Fn(f64) -> f64 {
+    let mut map = ImgBuf::<Vec2d<f32>>::new(size);
+
+    for y in 0..size.y {
+        let (sin, cos) = (y as f64*angle_step).sin_cos();
+
+        for x in 0..size.x {
+            let r = radius(x as f64);
+            map[(x, y)] = Vec2d::new((center.x + r*cos) as f32, (center.y + r*sin) as f32);
+        }
+    }
+
+    remap(image, &map)
+}
+
+/// Samples polar `image` for every cartesian output pixel, `column(r)` 
+/// gives polar image column for distance `r` from the center (`None` 
+/// outside of the polar image), polar lines are `angle_step` apart and
+/// wrap around
+fn cartesian_warp<T, F>(image: &dyn Img<T>, size: ImgSize, center: Point, column: F, angle_step: f64) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T>, F: Fn(f64) -> Option<f64> {
+    let height = image.height();
+    let mut map = ImgBuf::<Vec2d<f32>>::new(size);
+
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let offset = Point::new(x as f64, y as f64) - center;
+
+            map[(x, y)] = match column(offset.length()) {
+                Some(column) => {
+                    let line = offset.y.atan2(offset.x).rem_euclid(2.0*PI)/angle_step;
+                    // Angles past the last line are taken from the first line
+                    let line = if line > (height as f64 - 0.5) { line - height as f64 } else { line };
+                    Vec2d::new(column as f32, line as f32)
+                },
+                None => Vec2d::new(-1.0, -1.0),
+            };
+        }
+    }
+
+    remap(image, &map)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_becomes_vertical_shift() {
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(64, 64));
+        let mut rotated = ImgBuf::<f32>::new(ImgSize::new(64, 64));
+        image[(52, 32)] = 1.0;
+        rotated[(32, 52)] = 1.0;
+
+        let center = Point::new(32.0, 32.0);
+        let (a, b) = (log_polar(&image, center, 12.0), log_polar(&rotated, center, 12.0));
+        let column = (20f64.ln()*12.0).round() as usize;
+        assert!(a[(column, 0)] > 0.5);
+        assert!(b[(column, 16)] > 0.5);
+    }
+
+    #[test]
+    fn polar_round_trip() {
+        let mut image = ImgBuf::<f32>::new(ImgSize::new(41, 41));
+
+        for y in 0..41 {
+            for x in 0..41 {
+                image[(x, y)] = (x as f32*0.1).sin() + (y as f32*0.07).cos();
+            }
+        }
+
+        let center = Point::new(20.0, 20.0);
+        let strip = to_polar(&image, center, 20.0);
+        let restored = from_polar(&strip, center, 20.0, image.size());
+
+        for &(x, y) in &[(25, 20), (10, 14), (20, 33), (30, 29)] {
+            assert!((restored[(x, y)] - image[(x, y)]).abs() < 0.1);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::f64::consts::PI;
+use crate::{AsPrimitive, Img, ImgBuf, ImgSize, Vec2d, filter::remap};
+
+/// Map position of output pixels without any source pixel
+const OUTSIDE: Vec2d<f32> = Vec2d { x: -1.0, y: -1.0 };
+
+/// Converts fisheye image to rectilinear (perspective) projection
+/// 
+/// Input image is expected to use the equidistant fisheye projection
+/// (distance from image center proportional to the angle from the optical
+/// axis) with the image circle inscribed into the image, the circle 
+/// diameter covering field of view `fov` (in radians, e.g. `PI` for 
+/// a 180 degree lens). Output view of `output_size` keeps the scale 
+/// of the fisheye image in its center, straight lines become straight. 
+/// Output pixels looking outside of the lens field of view are zero.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, warp::fisheye_to_rectilinear};
+/// let image = ImgBuf::<u8>::new_init(ImgSize::new(101, 101), 100);
+/// let output = fisheye_to_rectilinear(&image, std::f64::consts::PI, ImgSize::new(51, 51));
+/// assert_eq!(output[(25, 25)], 100);
+/// assert_eq!(output[(0, 0)], 100);
+/// ```
+pub fn fisheye_to_rectilinear<T>(image: &dyn Img<T>, fov: f64, output_size: ImgSize) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let size = image.size();
+    let center = Vec2d::new((size.x as f64 - 1.0)/2.0, (size.y as f64 - 1.0)/2.0);
+    let output_center = Vec2d::new((output_size.x as f64 - 1.0)/2.0, (output_size.y as f64 - 1.0)/2.0);
+    // Pixels per radian of the equidistant projection, the same focal 
+    // length is used for the perspective output
+    let focal = size.x.min(size.y) as f64/fov;
+    let mut map = ImgBuf::<Vec2d<f32>>::new(output_size);
+
+    for y in 0..output_size.y {
+        for x in 0..output_size.x {
+            let offset = Vec2d::new(x as f64, y as f64) - output_center;
+            let distance = offset.length();
+            let theta = (distance/focal).atan();
+
+            map[(x, y)] = if 2.0*theta > fov {
+                OUTSIDE
+            } else if distance == 0.0 {
+                center.cast::<f32>()
+            } else {
+                (center + offset*(focal*theta/distance)).cast::<f32>()
+            };
+        }
+    }
+
+    remap(image, &map)
+}
+
+/// Renders perspective view into an equirectangular panorama
+/// 
+/// Panorama spans longitude `-PI..PI` horizontally and latitude 
+/// `-PI/2..PI/2` vertically (top to bottom). View direction is given
+/// by `yaw` (rotation around the vertical axis, positive to the right) 
+/// and `pitch` (positive up), both in radians, `fov` is the horizontal 
+/// field of view of the output view of `output_size`. Panorama is 
+/// sampled without wrapping across its left and right border.
+/// 
+/// # Example
+/// ```
+/// use nanocv::{*, warp::equirect_to_perspective};
+/// // panorama with bright column in the direction of yaw = PI/2
+/// let mut panorama = ImgBuf::<u8>::new(ImgSize::new(360, 180));
+/// 
+/// for y in 0..180 {
+///     panorama[(270, y)] = 255;
+/// }
+/// 
+/// let size = ImgSize::new(41, 41);
+/// let view = equirect_to_perspective(&panorama, std::f64::consts::FRAC_PI_2, 0.0, 1.0, size);
+/// assert!(view[(20, 20)] > 100);
+/// assert_eq!(view[(5, 20)], 0);
+/// ```
+pub fn equirect_to_perspective<T>(
+    image: &dyn Img<T>, 
+    yaw: f64, 
+    pitch: f64, 
+    fov: f64, 
+    output_size: ImgSize,
+) -> ImgBuf<T>
+where T: AsPrimitive<f64> + Default, f64: AsPrimitive<T> {
+    let size = image.size();
+    let output_center = Vec2d::new((output_size.x as f64 - 1.0)/2.0, (output_size.y as f64 - 1.0)/2.0);
+    let focal = output_size.x as f64/2.0/(fov/2.0).tan();
+    let (sin_yaw, cos_yaw) = yaw.sin_cos();
+    let (sin_pitch, cos_pitch) = pitch.sin_cos();
+    let mut map = ImgBuf::<Vec2d<f32>>::new(output_size);
+
+    for y in 0..output_size.y {
+        for x in 0..output_size.x {
+            // Ray in camera coordinates (x right, y down, z forward)
+            let (rx, ry, rz) = ((x as f64 - output_center.x)/focal, (y as f64 - output_center.y)/focal, 1.0);
+
+            // Pitch rotates around the x axis, positive pitch looks up
+            let (ry, rz) = (ry*cos_pitch - rz*sin_pitch, ry*sin_pitch + rz*cos_pitch);
+            // Yaw rotates around the vertical axis, positive yaw looks right
+            let (rx, rz) = (rx*cos_yaw + rz*sin_yaw, -rx*sin_yaw + rz*cos_yaw);
+
+            let longitude = rx.atan2(rz);
+            let latitude = (ry/(rx*rx + ry*ry + rz*rz).sqrt()).asin();
+
+            map[(x, y)] = Vec2d::new(
+                ((longitude/(2.0*PI) + 0.5)*size.x as f64 - 0.5) as f32,
+                ((latitude/PI + 0.5)*size.y as f64 - 0.5) as f32,
+            );
+        }
+    }
+
+    remap(image, &map)
+}
+
+// ================================== TESTS ==================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fisheye_corners_outside_fov_are_empty() {
+        let image = ImgBuf::<f32>::new_init(ImgSize::new(64, 64), 1.0);
+        // 90 degree lens, output corners look 60 degrees away from the axis
+        let output = fisheye_to_rectilinear(&image, PI/2.0, ImgSize::new(200, 200));
+        assert_eq!(output[(100, 100)], 1.0);
+        assert_eq!(output[(0, 0)], 0.0);
+    }
+
+    #[test]
+    fn straight_line_stays_straight() {
+        // horizontal line 40 pixels below the center of the perspective 
+        // view is curved in the equidistant fisheye image
+        let (size, fov) = (ImgSize::new(201, 201), PI);
+        let focal = 201.0/fov;
+        let mut image = ImgBuf::<f32>::new(size);
+
+        for x in 0..161 {
+            for sub in 0..20 {
+                let dx = x as f64 - 80.0 + sub as f64/20.0;
+                let distance = (dx*dx + 1600.0).sqrt();
+                let scale = focal*(distance/focal).atan()/distance;
+                let (fx, fy) = (100.0 + dx*scale, 100.0 + 40.0*scale);
+                image[(fx.round() as usize, fy.round() as usize)] = 1.0;
+            }
+        }
+
+        let output = fisheye_to_rectilinear(&image, fov, ImgSize::new(161, 161));
+
+        for x in 20..141 {
+            let column: Vec<f32> = (0..161).map(|y| output[(x, y)]).collect();
+            let brightest = (0..161).max_by(|&a, &b| column[a].total_cmp(&column[b])).unwrap();
+            assert!((brightest as isize - 120).abs() <= 1, "column {}: {}", x, brightest);
+        }
+    }
+
+    #[test]
+    fn looking_up_sees_the_top_of_panorama() {
+        let mut panorama = ImgBuf::<u8>::new(ImgSize::new(100, 50));
+
+        for x in 0..100 {
+            for y in 0..5 {
+                panorama[(x, y)] = 200;
+            }
+        }
+
+        let view = equirect_to_perspective(&panorama, 0.0, 1.4, 1.0, ImgSize::new(11, 11));
+        assert!(view[(5, 5)] > 100);
+        assert_eq!(equirect_to_perspective(&panorama, 0.0, 0.0, 1.0, ImgSize::new(11, 11))[(5, 5)], 0);
+    }
+}